@@ -197,6 +197,8 @@ pub enum ConfigMode {
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum BackupType {
     Direct,
+    /// only copy what was appended to the journals since the base backup
+    Incremental,
 }
 
 #[derive(Debug, PartialEq)]
@@ -206,6 +208,8 @@ pub struct BackupSettings {
     pub kind: BackupType,
     pub description: Option<String>,
     pub allow_dirty: bool,
+    /// the manifest of the backup that an incremental backup is based on
+    pub base: Option<String>,
 }
 
 impl BackupSettings {
@@ -215,6 +219,7 @@ impl BackupSettings {
         kind: BackupType,
         description: Option<String>,
         allow_dirty: bool,
+        base: Option<String>,
     ) -> Self {
         Self {
             to,
@@ -222,6 +227,7 @@ impl BackupSettings {
             kind,
             description,
             allow_dirty,
+            base,
         }
     }
 }
@@ -235,6 +241,8 @@ pub struct RestoreSettings {
     pub flag_allow_invalid_date: bool,
     pub flag_delete_on_restore_completion: bool,
     pub flag_skip_compatibility_check: bool,
+    /// if set, the restored data is rewound to this point
+    pub until: Option<RestorePoint>,
 }
 
 impl RestoreSettings {
@@ -255,8 +263,21 @@ impl RestoreSettings {
             flag_allow_invalid_date,
             flag_delete_on_restore_completion,
             flag_skip_compatibility_check,
+            until: None,
         }
     }
+    fn with_until(self, until: Option<RestorePoint>) -> Self {
+        Self { until, ..self }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// The point in time that a restore is rewound to
+pub enum RestorePoint {
+    /// right before the GNS transaction with this ID
+    Txn(u64),
+    /// right after this time (in nanoseconds since the epoch)
+    Time(u64),
 }
 
 /*
@@ -807,6 +828,7 @@ pub fn parse_cli_args<'a, T: ArgItem>(
                         let backup_from = subcommand.settings_mut().take_option("from")?;
                         let backup_kind = match subcommand.settings_mut().option("type")?.as_ref() {
                             "direct" => BackupType::Direct,
+                            "incremental" => BackupType::Incremental,
                             backup_scheme => {
                                 return Err(ConfigError::with_src(
                                     ConfigSource::Cli,
@@ -821,6 +843,28 @@ pub fn parse_cli_args<'a, T: ArgItem>(
                             subcommand.settings_mut().take_flag("allow-dirty")?;
                         let backup_description =
                             subcommand.settings_mut().take_option("description")?;
+                        let backup_base = subcommand.settings_mut().take_option("base")?;
+                        match (backup_kind, backup_base.is_some()) {
+                            (BackupType::Direct, false) | (BackupType::Incremental, true) => {}
+                            (BackupType::Direct, true) => {
+                                return Err(ConfigError::with_src(
+                                    ConfigSource::Cli,
+                                    ConfigErrorKind::ErrorString(
+                                        "`--base` can only be used with incremental backups".into(),
+                                    ),
+                                )
+                                .into())
+                            }
+                            (BackupType::Incremental, false) => {
+                                return Err(ConfigError::with_src(
+                                    ConfigSource::Cli,
+                                    ConfigErrorKind::ErrorString(
+                                        "incremental backups need a base backup (`--base`)".into(),
+                                    ),
+                                )
+                                .into())
+                            }
+                        }
                         subcommand.settings().ensure_empty()?;
                         CLIConfigParseReturn::Backup(BackupSettings::new(
                             backup_to,
@@ -828,6 +872,7 @@ pub fn parse_cli_args<'a, T: ArgItem>(
                             backup_kind,
                             backup_description,
                             backup_flag_allow_dirty,
+                            backup_base,
                         ))
                     }
                     "restore" => {
@@ -846,16 +891,63 @@ pub fn parse_cli_args<'a, T: ArgItem>(
                         let flag_skip_compatibility_check = subcommand
                             .settings_mut()
                             .take_flag("skip-compatibility-check")?;
+                        let restore_until = match (
+                            subcommand.settings_mut().take_option("until-txn")?,
+                            subcommand.settings_mut().take_option("until-time")?,
+                        ) {
+                            (None, None) => None,
+                            (Some(txn_id), None) => match txn_id.parse() {
+                                Ok(txn_id) => Some(RestorePoint::Txn(txn_id)),
+                                Err(_) => {
+                                    return Err(ConfigError::with_src(
+                                        ConfigSource::Cli,
+                                        ConfigErrorKind::ErrorString(format!(
+                                            "invalid transaction ID `{txn_id}`"
+                                        )),
+                                    )
+                                    .into())
+                                }
+                            },
+                            (None, Some(time)) => match chrono::DateTime::parse_from_rfc3339(&time)
+                                .ok()
+                                .and_then(|time| time.timestamp_nanos_opt())
+                                .and_then(|time| u64::try_from(time).ok())
+                            {
+                                Some(time) => Some(RestorePoint::Time(time)),
+                                None => {
+                                    return Err(ConfigError::with_src(
+                                        ConfigSource::Cli,
+                                        ConfigErrorKind::ErrorString(format!(
+                                            "invalid time `{time}` (expected an RFC 3339 timestamp like `2024-01-01T00:00:00Z`)"
+                                        )),
+                                    )
+                                    .into())
+                                }
+                            },
+                            (Some(_), Some(_)) => {
+                                return Err(ConfigError::with_src(
+                                    ConfigSource::Cli,
+                                    ConfigErrorKind::ErrorString(
+                                        "only one of `--until-txn` and `--until-time` can be used"
+                                            .into(),
+                                    ),
+                                )
+                                .into())
+                            }
+                        };
                         subcommand.settings().ensure_empty()?;
-                        CLIConfigParseReturn::Restore(RestoreSettings::new(
-                            restore_from,
-                            restore_to,
-                            flag_allow_incompatible,
-                            flag_allow_different_host,
-                            flag_allow_invalid_date,
-                            flag_delete_on_restore,
-                            flag_skip_compatibility_check,
-                        ))
+                        CLIConfigParseReturn::Restore(
+                            RestoreSettings::new(
+                                restore_from,
+                                restore_to,
+                                flag_allow_incompatible,
+                                flag_allow_different_host,
+                                flag_allow_invalid_date,
+                                flag_delete_on_restore,
+                                flag_skip_compatibility_check,
+                            )
+                            .with_until(restore_until),
+                        )
                     }
                     _ => {
                         return Err(ConfigError::with_src(
@@ -25,12 +25,13 @@
 */
 
 use crate::engine::{
-    core::system_db::SystemDatabase,
+    core::{system_db::SystemDatabase, EntityIDRef},
     data::{tag::TagClass, DictEntryGeneric},
     error::{QueryError, QueryResult},
     fractal::GlobalInstanceLike,
     net::protocol::ClientLocalState,
-    ql::dcl::{SysctlCommand, UserDecl, UserDel},
+    ql::dcl::{PrivilegeDecl, RoleDecl, RoleGrantDecl, SysctlCommand, UserDecl, UserDel},
+    storage,
 };
 
 const KEY_PASSWORD: &str = "password";
@@ -62,6 +63,13 @@ pub fn exec_ref<G: GlobalInstanceLike>(
                 Err(QueryError::SysServerError)
             }
         }
+        SysctlCommand::Grant(grant) => grant_privilege(g, grant),
+        SysctlCommand::Revoke(revoke) => revoke_privilege(g, revoke),
+        SysctlCommand::CreateRole(role) => create_role(g, role),
+        SysctlCommand::DropRole(role) => drop_role(g, role),
+        SysctlCommand::GrantRole(grant) => grant_role(g, grant),
+        SysctlCommand::RevokeRole(revoke) => revoke_role(g, revoke),
+        SysctlCommand::Backup(backup) => storage::online_backup(g, backup.path(), None),
     }
 }
 
@@ -122,3 +130,70 @@ fn drop_user(
         .sys_db()
         .drop_user(global, user_del.username())
 }
+
+fn grant_privilege(global: &impl GlobalInstanceLike, grant: PrivilegeDecl) -> QueryResult<()> {
+    let ns = global.state().namespace();
+    /*
+        we can only grant privileges on a space or model that exists. the namespace is held (in the same order
+        that a drop takes it) until the grant is committed, since otherwise the object could be dropped (and its
+        grants purged) in between and the grant would then apply to a new object with the same name
+    */
+    let spaces = ns.idx().read();
+    let models = ns.idx_models().read();
+    let exists = match grant.model() {
+        Some(model) => models.contains_key(&EntityIDRef::new(grant.space(), model)),
+        None => spaces.contains_key(grant.space()),
+    };
+    if !exists {
+        return Err(QueryError::QExecObjectNotFound);
+    }
+    ns.sys_db().grant(
+        global,
+        grant.grantee(),
+        grant.space(),
+        grant.model(),
+        grant.privilege(),
+    )
+}
+
+fn revoke_privilege(global: &impl GlobalInstanceLike, revoke: PrivilegeDecl) -> QueryResult<()> {
+    global.state().namespace().sys_db().revoke(
+        global,
+        revoke.grantee(),
+        revoke.space(),
+        revoke.model(),
+        revoke.privilege(),
+    )
+}
+
+fn create_role(global: &impl GlobalInstanceLike, role: RoleDecl) -> QueryResult<()> {
+    global
+        .state()
+        .namespace()
+        .sys_db()
+        .create_role(global, role.role())
+}
+
+fn drop_role(global: &impl GlobalInstanceLike, role: RoleDecl) -> QueryResult<()> {
+    global
+        .state()
+        .namespace()
+        .sys_db()
+        .drop_role(global, role.role())
+}
+
+fn grant_role(global: &impl GlobalInstanceLike, grant: RoleGrantDecl) -> QueryResult<()> {
+    global
+        .state()
+        .namespace()
+        .sys_db()
+        .grant_role(global, grant.username(), grant.role())
+}
+
+fn revoke_role(global: &impl GlobalInstanceLike, revoke: RoleGrantDecl) -> QueryResult<()> {
+    global
+        .state()
+        .namespace()
+        .sys_db()
+        .revoke_role(global, revoke.username(), revoke.role())
+}
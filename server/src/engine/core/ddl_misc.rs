@@ -25,10 +25,22 @@
 */
 
 use crate::engine::{
+    core::{
+        dml::encode_cell,
+        index::PrimaryIndexKind,
+        model::{Durability, ModelData},
+    },
+    data::{
+        cell::Datacell,
+        tag::{DataTag, TagSelector},
+        DictEntryGeneric, DictGeneric,
+    },
     error::{QueryError, QueryResult},
     fractal::GlobalInstanceLike,
+    idx::STIndexSeq,
+    mem::IntegerRepr,
     net::protocol::{ClientLocalState, Response, ResponseType},
-    ql::ddl::Inspect,
+    ql::ddl::{Describe, Inspect},
 };
 
 pub fn inspect(
@@ -73,10 +85,33 @@ pub fn inspect(
         Inspect::Model(m) => match g.state().namespace().idx_models().read().get(&m) {
             Some(m) => {
                 let m = m.data();
+                let mut props = vec![];
+                match m.primary_index().kind() {
+                    PrimaryIndexKind::Hashed => {}
+                    kind => props.push(format!(
+                        "\"{}\":\"{}\"",
+                        PrimaryIndexKind::PROP_KEY,
+                        kind.name_str()
+                    )),
+                }
+                if let Some(ttl) = m.ttl() {
+                    props.push(format!("\"{}\":{ttl}", ModelData::PROP_TTL));
+                }
+                match m.durability() {
+                    Durability::Async => {}
+                    durability => props.push(format!(
+                        "\"{}\":\"{}\"",
+                        Durability::PROP_KEY,
+                        durability.name_str()
+                    )),
+                }
+                let props = props.join(",");
                 format!(
-                    "{{\"decl\":\"{}\",\"rows\":{},\"properties\":{{}}}}",
-                    m.describe(),
-                    m.primary_index().count()
+                    "{{\"decl\":\"{}\",\"rows\":{},\"properties\":{{{}}}}}",
+                    // layer properties can have string literals
+                    m.describe().replace('\\', "\\\\").replace('"', "\\\""),
+                    m.primary_index().count(),
+                    props
                 )
             }
             None => return Err(QueryError::QExecObjectNotFound),
@@ -105,3 +140,113 @@ pub fn inspect(
         data: ret.into_bytes(),
     })
 }
+
+/// Describe a space or model, returning a typed row.
+///
+/// For a model, the row has the cells: `[pk, row_count, fields, properties]` where `fields` is a list
+/// of `[name, [layer, ...], nullable, is_pk]` (in declaration order) and `properties` is a list of
+/// `[key, value]` pairs.
+///
+/// For a space, the row has the cells: `[models, properties]` where `models` is a sorted list of model names
+pub fn describe(g: &impl GlobalInstanceLike, stmt: Describe) -> QueryResult<Response> {
+    let mut data = vec![];
+    let size = match stmt {
+        Describe::Model(m) => g.state().namespace().with_model(m, |mdl| {
+            encode_cell(&mut data, &Datacell::new_str(mdl.p_key().into()));
+            encode_cell(
+                &mut data,
+                &Datacell::new_uint_default(mdl.primary_index().count() as u64),
+            );
+            encode_list_header(&mut data, mdl.fields().len());
+            for (field_name, field) in mdl.fields().stseq_ord_kv() {
+                let field_name: &str = field_name;
+                encode_list_header(&mut data, 4);
+                encode_cell(&mut data, &Datacell::new_str(field_name.into()));
+                encode_list_header(&mut data, field.layers().len());
+                for layer in field.layers() {
+                    let layer_name = layer.tag().tag_selector().name_str();
+                    encode_cell(&mut data, &Datacell::new_str(layer_name.into()));
+                }
+                encode_cell(&mut data, &Datacell::new_bool(field.is_nullable()));
+                encode_cell(&mut data, &Datacell::new_bool(field_name == mdl.p_key()));
+            }
+            // the index kind and durability are listed if they aren't the default, and the ttl if it is set
+            let kind = match mdl.primary_index().kind() {
+                PrimaryIndexKind::Hashed => None,
+                kind => Some(kind),
+            };
+            let durability = match mdl.durability() {
+                Durability::Async => None,
+                durability => Some(durability),
+            };
+            encode_list_header(
+                &mut data,
+                kind.is_some() as usize
+                    + mdl.ttl().is_some() as usize
+                    + durability.is_some() as usize,
+            );
+            if let Some(kind) = kind {
+                encode_list_header(&mut data, 2);
+                encode_cell(
+                    &mut data,
+                    &Datacell::new_str(PrimaryIndexKind::PROP_KEY.into()),
+                );
+                encode_cell(&mut data, &Datacell::new_str(kind.name_str().into()));
+            }
+            if let Some(ttl) = mdl.ttl() {
+                encode_list_header(&mut data, 2);
+                encode_cell(&mut data, &Datacell::new_str(ModelData::PROP_TTL.into()));
+                encode_cell(&mut data, &Datacell::new_uint_default(ttl));
+            }
+            if let Some(durability) = durability {
+                encode_list_header(&mut data, 2);
+                encode_cell(&mut data, &Datacell::new_str(Durability::PROP_KEY.into()));
+                encode_cell(&mut data, &Datacell::new_str(durability.name_str().into()));
+            }
+            Ok(4)
+        })?,
+        Describe::Space(s) => match g.state().namespace().idx().read().get(s.as_str()) {
+            Some(space) => {
+                let mut models: Vec<&str> = space.models().iter().map(|m| m.as_ref()).collect();
+                models.sort_unstable();
+                encode_list_header(&mut data, models.len());
+                for model in models {
+                    encode_cell(&mut data, &Datacell::new_str(model.into()));
+                }
+                encode_props(&mut data, space.props());
+                2
+            }
+            None => return Err(QueryError::QExecObjectNotFound),
+        },
+    };
+    Ok(Response::Serialized {
+        ty: ResponseType::Row,
+        size,
+        data,
+    })
+}
+
+fn encode_list_header(buf: &mut Vec<u8>, len: usize) {
+    buf.push(TagSelector::List.value_u8() + 1);
+    IntegerRepr::scoped(len as u64, |b| buf.extend(b));
+    buf.push(b'\n');
+}
+
+/// Encode a property dictionary as a list of `[key, value]` pairs (nested dictionaries are encoded recursively)
+fn encode_props(buf: &mut Vec<u8>, props: &DictGeneric) {
+    let mut props: Vec<_> = props.iter().collect();
+    props.sort_unstable_by_key(|(k, _)| *k);
+    encode_list_header(buf, props.len());
+    for (key, value) in props {
+        encode_list_header(buf, 2);
+        encode_cell(buf, &Datacell::new_str(key.as_ref().into()));
+        match value {
+            DictEntryGeneric::Data(dc) => encode_cell(buf, dc),
+            DictEntryGeneric::Map(map) => encode_props(buf, map),
+            DictEntryGeneric::Producer(name) => encode_cell(
+                buf,
+                &Datacell::new_str(format!("@{name}()").into_boxed_str()),
+            ),
+        }
+    }
+}
@@ -0,0 +1,420 @@
+/*
+ * Created on Sun Oct 18 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+/*
+    batches
+    ---
+    a batch (`begin` ... `commit`) is staged on the connection and only executed on commit. all statements are
+    then run against the model while holding its secondary indexes (which serializes writers) and every change
+    is recorded along with the data needed to undo it. if any statement fails, all the changes made so far are
+    rolled back. otherwise, all the deltas are written to the model's journal as a single event, which makes the
+    batch atomic on disk as well.
+
+    scope:
+    - a batch can only change a single model. the batch is made atomic on disk by writing it to the model's
+    journal as one event, and there's no record that could tie together events in the journals of different
+    models (so a crash could leave a multi-model batch partially applied). statements that target any other
+    model are rejected when they are staged, with `QExecBatchMultipleModels`
+    - there's no isolation. the statements are only run on commit, and readers (which don't take the locks that
+    writers take) can see the changes while the batch is being applied, including changes that are then rolled
+    back because a later statement failed. the only guarantee is that either all of the changes or none of them
+    are applied, and that no other writer can change the model while the batch is applied
+*/
+
+use {
+    super::{
+        del::delete_with,
+        ins::{insert_prepared, prepare_insert, upsert_prepared, upsert_row},
+        upd::{update_with, AssignmentUndo},
+        DeltaSink, ReturnedRows,
+    },
+    crate::engine::{
+        core::{
+            index::{DcFieldIndex, PrimaryIndexKey, Row, SecondaryIndexesGuard},
+            model::{
+                copy_fields,
+                delta::{DataDelta, DataDeltaKind, DeltaState, DeltaVersion},
+                ChangeEvent, ModelData,
+            },
+            with_model_for_batch, EntityIDRef,
+        },
+        error::{QueryError, QueryResult},
+        fractal::{GlobalInstanceLike, ModelUniqueIDRef},
+        idx::MTIndex,
+        ql::{
+            ast::{traits::ASTNode, InplaceData, State},
+            dml::{del::DeleteStatement, ins::InsertStatement, upd::UpdateStatement},
+            lex::{KeywordStmt, SecureLexer},
+        },
+        storage::{safe_interfaces::TxnModelBatch, BatchStats},
+        sync::atm::{cpin, Guard},
+    },
+    std::collections::HashMap,
+};
+
+/*
+    staging
+*/
+
+/// A batch of DML statements staged on a connection until it is committed
+#[derive(Debug, PartialEq)]
+pub struct StagedBatch {
+    /// the space that was in use when the batch was started
+    space: Option<Box<str>>,
+    /// the model that all the statements target (set by the first statement)
+    model: Option<(Box<str>, Box<str>)>,
+    queries: Vec<StagedQuery>,
+}
+
+#[derive(Debug, PartialEq)]
+struct StagedQuery {
+    query: Box<[u8]>,
+    params: Box<[u8]>,
+}
+
+impl StagedBatch {
+    pub fn new(space: Option<&str>) -> Self {
+        Self {
+            space: space.map(Into::into),
+            model: None,
+            queries: Vec::new(),
+        }
+    }
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.queries.len()
+    }
+    /// Validate a DML statement and stage it. All statements in a batch must target the same model (see the
+    /// notes on scope above) and can't return any rows
+    pub fn stage<'a>(
+        &mut self,
+        state: &mut State<'a, InplaceData>,
+        stmt: KeywordStmt,
+        query: &[u8],
+        params: &[u8],
+    ) -> QueryResult<()> {
+        let (entity, returns) = match stmt {
+            KeywordStmt::Insert | KeywordStmt::Upsert => {
+                let insert = InsertStatement::parse_from_state_hardened(state)?;
+                (insert.entity(), insert.returning().is_some())
+            }
+            KeywordStmt::Update => {
+                let update = UpdateStatement::parse_from_state_hardened(state)?;
+                (update.entity(), update.returning().is_some())
+            }
+            KeywordStmt::Delete => {
+                let delete = DeleteStatement::parse_from_state_hardened(state)?;
+                (delete.entity(), delete.returning().is_some())
+            }
+            _ => return Err(QueryError::QExecBatchIllegalStatement),
+        };
+        if returns {
+            return Err(QueryError::QExecBatchIllegalStatement);
+        }
+        match &self.model {
+            Some((space, model))
+                if (space.as_ref() != entity.space()) | (model.as_ref() != entity.entity()) =>
+            {
+                return Err(QueryError::QExecBatchMultipleModels)
+            }
+            Some(_) => {}
+            None => self.model = Some((entity.space().into(), entity.entity().into())),
+        }
+        self.queries.push(StagedQuery {
+            query: query.into(),
+            params: params.into(),
+        });
+        Ok(())
+    }
+}
+
+/*
+    commit
+*/
+
+enum BatchStatement<'a> {
+    Insert(InsertStatement<'a>),
+    Upsert(InsertStatement<'a>),
+    Update(UpdateStatement<'a>),
+    Delete(DeleteStatement<'a>),
+}
+
+/// Run all the statements in the batch. Either all the changes are applied (and persisted as a single event),
+/// or none are
+pub fn commit(global: &impl GlobalInstanceLike, batch: StagedBatch) -> QueryResult<()> {
+    let Some((space_name, model_name)) = batch.model.as_ref() else {
+        // nothing was staged
+        return Ok(());
+    };
+    let tokens = batch
+        .queries
+        .iter()
+        .map(|q| SecureLexer::new_with_segments(&q.query, &q.params).lex())
+        .collect::<QueryResult<Vec<_>>>()?;
+    let mut statements = Vec::with_capacity(tokens.len());
+    for tokens in tokens.iter() {
+        let mut state = State::new_inplace(tokens);
+        state.set_space_maybe(unsafe {
+            // UNSAFE(@ohsayan): exclusively used within this scope
+            core::mem::transmute(batch.space.as_deref())
+        });
+        let stmt = match state.try_statement()? {
+            KeywordStmt::Insert => {
+                BatchStatement::Insert(InsertStatement::parse_from_state_hardened(&mut state)?)
+            }
+            KeywordStmt::Upsert => {
+                BatchStatement::Upsert(InsertStatement::parse_from_state_hardened(&mut state)?)
+            }
+            KeywordStmt::Update => {
+                BatchStatement::Update(UpdateStatement::parse_from_state_hardened(&mut state)?)
+            }
+            KeywordStmt::Delete => {
+                BatchStatement::Delete(DeleteStatement::parse_from_state_hardened(&mut state)?)
+            }
+            _ => return Err(QueryError::QExecBatchIllegalStatement),
+        };
+        statements.push(stmt);
+    }
+    with_model_for_batch(global, EntityIDRef::new(space_name, model_name), |model| {
+        let mdl = model.data();
+        // lock the secondary indexes before the primary index
+        let mut sidx = mdl.secondary_indexes().lock();
+        let _idx_latch = mdl.primary_index().acquire_shared();
+        let gate = mdl.change_feed().hold();
+        let g = cpin();
+        let mut log = BatchLog::new();
+        for stmt in statements {
+            if let Err(e) = exec_statement(mdl, &mut sidx, stmt, &mut log, &g) {
+                log.rollback(mdl, &mut sidx, &g);
+                return Err(e);
+            }
+        }
+        let r = model.driver().driver_context(
+            global,
+            ModelUniqueIDRef::new(space_name, model_name, mdl.get_uuid()),
+            |drv| drv.commit_with_ctx(TxnModelBatch::new(mdl, &log.deltas), BatchStats::new()),
+        );
+        match r {
+            Ok(()) if gate.captures() => {
+                mdl.change_feed().publish(&gate, log.changes(mdl));
+            }
+            Ok(()) => {}
+            Err(_) => log.rollback(mdl, &mut sidx, &g),
+        }
+        r
+    })
+}
+
+fn exec_statement<'a>(
+    mdl: &ModelData,
+    sidx: &mut SecondaryIndexesGuard,
+    stmt: BatchStatement<'a>,
+    log: &mut BatchLog<'a>,
+    g: &Guard,
+) -> QueryResult<()> {
+    match stmt {
+        BatchStatement::Insert(insert) => {
+            let expires_at = mdl.row_expiry(insert.ttl())?;
+            for row in insert.data() {
+                let (pk, data) = prepare_insert(mdl, row)?;
+                insert_prepared(mdl, sidx, pk, data, expires_at, log, g)?;
+            }
+        }
+        BatchStatement::Upsert(insert) => {
+            let expires_at = mdl.row_expiry(insert.ttl())?;
+            let (pk, data) = prepare_insert(mdl, upsert_row(insert)?)?;
+            upsert_prepared(mdl, sidx, pk, data, expires_at, log, g)?;
+        }
+        BatchStatement::Update(mut update) => {
+            let mut wc = mdl.resolve_where(update.clauses_mut())?;
+            update_with(
+                mdl,
+                sidx,
+                &mut wc,
+                update.expressions(),
+                log,
+                &mut ReturnedRows::new(None),
+                g,
+            )?;
+        }
+        BatchStatement::Delete(mut delete) => {
+            let mut wc = mdl.resolve_where(delete.clauses_mut())?;
+            delete_with(mdl, sidx, &mut wc, log, &mut ReturnedRows::new(None), g)?;
+        }
+    }
+    Ok(())
+}
+
+/*
+    undo log
+*/
+
+/// The data needed to undo a change (the row is in the delta)
+pub(super) enum Undo<'a> {
+    /// remove the inserted row
+    Insert,
+    /// remove the upserted row and restore the row that it replaced (if any)
+    Upsert(Option<Row>),
+    /// restore the assigned fields and the previous revision of the row
+    Update(Vec<AssignmentUndo<'a>>, DeltaVersion),
+    /// restore the deleted row with its previous revision
+    Delete(DeltaVersion),
+}
+
+/// All the changes made by a batch (or a bulk insert), in order
+pub(super) struct BatchLog<'a> {
+    deltas: Vec<DataDelta>,
+    undo: Vec<Undo<'a>>,
+}
+
+impl<'a> BatchLog<'a> {
+    pub(super) fn new() -> Self {
+        Self {
+            deltas: Vec::new(),
+            undo: Vec::new(),
+        }
+    }
+    pub(super) fn deltas(&self) -> &[DataDelta] {
+        &self.deltas
+    }
+    pub(super) fn into_deltas(self) -> Vec<DataDelta> {
+        self.deltas
+    }
+    /// Add all the changes in the other log (after the ones in this log)
+    pub(super) fn append(&mut self, other: Self) {
+        self.deltas.extend(other.deltas);
+        self.undo.extend(other.undo);
+    }
+    /// Returns the change events for all the changes (in order). This must be done while the changes are still in
+    /// place (before the locks that they were made with are released), since the rows have the data of the last
+    /// change to them and the images are worked out by walking back from there
+    pub(super) fn changes(self, mdl: &ModelData) -> Vec<ChangeEvent> {
+        let ds = mdl.delta_state();
+        // the image of a row before the (later) change that was last looked at
+        let mut images: HashMap<&PrimaryIndexKey, Option<DcFieldIndex>> = HashMap::new();
+        let mut events = Vec::with_capacity(self.deltas.len());
+        for (delta, undo) in self.deltas.iter().zip(self.undo).rev() {
+            let row = delta.row();
+            let post = match images.remove(row.d_key()) {
+                Some(image) => image,
+                None if delta.change() == DataDeltaKind::Delete => None,
+                None => Some(copy_fields(
+                    row.resolve_schema_deltas_and_freeze(ds).fields(),
+                )),
+            };
+            let pre = match undo {
+                Undo::Insert => None,
+                Undo::Upsert(old_row) => old_row.map(|old_row| {
+                    copy_fields(old_row.resolve_schema_deltas_and_freeze(ds).fields())
+                }),
+                Undo::Update(assignments, _) => post.as_ref().map(|post| {
+                    let mut pre = copy_fields(post);
+                    assignments
+                        .into_iter()
+                        .rev()
+                        .for_each(|undo| undo.apply(&mut pre));
+                    pre
+                }),
+                Undo::Delete(_) => Some(copy_fields(
+                    row.resolve_schema_deltas_and_freeze(ds).fields(),
+                )),
+            };
+            events.push(ChangeEvent::row(
+                mdl,
+                delta.data_version(),
+                delta.change(),
+                row.d_key(),
+                pre.as_ref(),
+                post.as_ref(),
+            ));
+            images.insert(row.d_key(), pre);
+        }
+        events.reverse();
+        events
+    }
+    /// Undo all the changes in reverse order
+    pub(super) fn rollback(self, mdl: &ModelData, sidx: &mut SecondaryIndexesGuard, g: &Guard) {
+        let ds = mdl.delta_state();
+        let index = mdl.primary_index().__raw_index();
+        for (delta, undo) in self.deltas.into_iter().zip(self.undo).rev() {
+            let row = delta.row();
+            match undo {
+                Undo::Insert => {
+                    unindex_row(ds, sidx, row);
+                    let _ = index.mt_delete(row.d_key(), g);
+                }
+                Undo::Upsert(old_row) => {
+                    unindex_row(ds, sidx, row);
+                    match old_row {
+                        Some(old_row) => {
+                            let values =
+                                sidx.values(old_row.resolve_schema_deltas_and_freeze(ds).fields());
+                            let _ = index.mt_upsert(old_row.clone(), g);
+                            sidx.insert_unchecked(old_row.d_key(), &values);
+                        }
+                        None => {
+                            let _ = index.mt_delete(row.d_key(), g);
+                        }
+                    }
+                }
+                Undo::Update(assignments, old_version) => {
+                    let mut row_data = row.d_data().write();
+                    let values = sidx.values(row_data.fields());
+                    sidx.remove(row.d_key(), &values);
+                    assignments
+                        .into_iter()
+                        .rev()
+                        .for_each(|undo| undo.apply(row_data.fields_mut()));
+                    row_data.set_txn_revised(old_version);
+                    let values = sidx.values(row_data.fields());
+                    sidx.insert_unchecked(row.d_key(), &values);
+                }
+                Undo::Delete(old_version) => {
+                    row.d_data().write().set_txn_revised(old_version);
+                    let values = sidx.values(row.resolve_schema_deltas_and_freeze(ds).fields());
+                    let _ = index.mt_insert(row.clone(), g);
+                    sidx.insert_unchecked(row.d_key(), &values);
+                }
+            }
+        }
+    }
+}
+
+fn unindex_row(ds: &DeltaState, sidx: &mut SecondaryIndexesGuard, row: &Row) {
+    let values = sidx.values(row.resolve_schema_deltas_and_freeze(ds).fields());
+    sidx.remove(row.d_key(), &values);
+}
+
+impl<'a> DeltaSink<'a> for BatchLog<'a> {
+    fn keeps_undo(&self) -> bool {
+        true
+    }
+    fn publish(&mut self, _: &DeltaState, delta: DataDelta, undo: Undo<'a>, _: &Guard) -> usize {
+        self.deltas.push(delta);
+        self.undo.push(undo);
+        self.deltas.len()
+    }
+}
@@ -25,45 +25,178 @@
 */
 
 use crate::engine::{
-    core::{self, dml::QueryExecMeta, model::delta::DataDeltaKind},
+    core::{
+        dml::{self, batch::Undo, DeltaSink, DirectSink, ResolvedWhere, ReturnedRows},
+        index::{expiry_now, Row, RowData, SecondaryIndexesGuard},
+        model::{
+            delta::{DataDelta, DataDeltaKind, DeltaVersion},
+            ModelData,
+        },
+    },
     error::{QueryError, QueryResult},
     fractal::GlobalInstanceLike,
-    idx::MTIndex,
     net::protocol::Response,
     ql::dml::del::DeleteStatement,
-    sync,
+    sync::{self, atm::Guard},
 };
 
 pub fn delete_resp(
     global: &impl GlobalInstanceLike,
-    delete: DeleteStatement,
+    mut delete: DeleteStatement,
 ) -> QueryResult<Response> {
-    self::delete(global, delete).map(|_| Response::Empty)
+    let mut returned = ReturnedRows::new(delete.take_returning());
+    self::delete_returning(global, delete, &mut returned)?;
+    Ok(returned.into_response(Response::Empty))
 }
 
-pub fn delete(global: &impl GlobalInstanceLike, mut delete: DeleteStatement) -> QueryResult<()> {
-    core::with_model_for_data_update(global, delete.entity(), |model| {
+#[cfg(test)]
+pub fn delete(global: &impl GlobalInstanceLike, delete: DeleteStatement) -> QueryResult<()> {
+    self::delete_returning(global, delete, &mut ReturnedRows::new(None))
+}
+
+fn delete_returning<'a>(
+    global: &impl GlobalInstanceLike,
+    mut delete: DeleteStatement<'a>,
+    returned: &mut ReturnedRows,
+) -> QueryResult<()> {
+    let (entity, durability) = (delete.entity(), delete.durability());
+    dml::with_model_for_write(global, entity, durability, |model, ctx| {
+        returned.validate(model)?;
         let g = sync::atm::cpin();
-        let delta_state = model.delta_state();
+        let mut wc = model.resolve_where(delete.clauses_mut())?;
+        // lock the secondary indexes before the primary index
+        let mut sidx = model.secondary_indexes().lock();
         let _idx_latch = model.primary_index().acquire_shared();
-        // create new version
-        let new_version = delta_state.create_new_data_delta_version();
-        match model
-            .primary_index()
-            .__raw_index()
-            .mt_delete_return_entry(&model.resolve_where(delete.clauses_mut())?, &g)
-        {
-            Some(row) => {
-                row.d_data().write().set_txn_revised(new_version);
-                let dp = delta_state.append_new_data_delta_with(
-                    DataDeltaKind::Delete,
-                    row.clone(),
-                    new_version,
-                    &g,
-                );
-                Ok(QueryExecMeta::new(dp))
+        let mut sink = ctx.sink();
+        delete_with(model, &mut sidx, &mut wc, &mut sink, returned, &g)?;
+        sink.finish(&mut sidx, &g)
+    })
+}
+
+/// Delete the rows matching the resolved `where` clause. The removed rows are added to `returned`. The caller
+/// must hold the secondary indexes and a shared latch on the primary index
+pub(super) fn delete_with<'a>(
+    model: &ModelData,
+    sidx: &mut SecondaryIndexesGuard,
+    wc: &mut ResolvedWhere,
+    sink: &mut impl DeltaSink<'a>,
+    returned: &mut ReturnedRows,
+    g: &Guard,
+) -> QueryResult<usize> {
+    let delta_state = model.delta_state();
+    if let Some(key) = wc.key() {
+        loop {
+            let Some(row) = model.primary_index().select(key.clone(), g) else {
+                return Err(QueryError::QExecDmlRowNotFound);
+            };
+            drop(row.resolve_schema_deltas_and_freeze(delta_state));
+            // the conditions are checked under the row's lock, so nothing can change the row before it's removed
+            let mut row_data_wl = row.d_data().write();
+            if !wc.row_matches(model, row.d_key(), &row_data_wl) {
+                return Err(QueryError::QExecDmlPreconditionFailed);
+            }
+            if let Some(dp) = remove_locked(model, sidx, row, &mut row_data_wl, sink, g) {
+                returned.push(model, row.d_key(), row_data_wl.fields());
+                return Ok(dp);
             }
-            None => Err(QueryError::QExecDmlRowNotFound),
+            // the row was removed or replaced while we were waiting for the lock, so we look it up again
         }
-    })
+    }
+    returned.start_scan();
+    let targets: Vec<Row> = wc
+        .scan(model, Some(sidx), g)
+        .filter(|row| {
+            wc.row_matches(
+                model,
+                row.d_key(),
+                &row.resolve_schema_deltas_and_freeze(delta_state),
+            )
+        })
+        .cloned()
+        .collect();
+    let mut dp = None;
+    for row in targets.iter() {
+        let mut row_data_wl = row.d_data().write();
+        // the row might have been changed since we scanned it
+        if !wc.row_matches(model, row.d_key(), &row_data_wl) {
+            continue;
+        }
+        if let Some(p) = remove_locked(model, sidx, row, &mut row_data_wl, sink, g) {
+            returned.push(model, row.d_key(), row_data_wl.fields());
+            dp = Some(p);
+        }
+    }
+    dp.ok_or(QueryError::QExecDmlRowNotFound)
+}
+
+/// Remove a row (whose data is locked by the caller) from the model and publish the delete. Nothing is done if the
+/// row has already been removed or replaced by another write, in which case `None` is returned
+fn remove_locked<'a>(
+    model: &ModelData,
+    sidx: &mut SecondaryIndexesGuard,
+    row: &Row,
+    row_data: &mut RowData,
+    sink: &mut impl DeltaSink<'a>,
+    g: &Guard,
+) -> Option<usize> {
+    // create new version
+    let new_version = model.delta_state().create_new_data_delta_version();
+    if !model.primary_index().remove_row(row, g) {
+        return None;
+    }
+    if !sidx.is_empty() {
+        let values = sidx.values(row_data.fields());
+        sidx.remove(row.d_key(), &values);
+    }
+    Some(publish_delete(model, row, row_data, new_version, sink, g))
+}
+
+/// Mark a removed row (whose data is locked by the caller) as deleted in the given version and publish the delta
+fn publish_delete<'a>(
+    model: &ModelData,
+    row: &Row,
+    row_data: &mut RowData,
+    new_version: DeltaVersion,
+    sink: &mut impl DeltaSink<'a>,
+    g: &Guard,
+) -> usize {
+    let old_version = row_data.get_txn_revised();
+    row_data.set_txn_revised(new_version);
+    sink.publish(
+        model.delta_state(),
+        DataDelta::new(new_version, row.clone(), DataDeltaKind::Delete),
+        Undo::Delete(old_version),
+        g,
+    )
+}
+
+/// Remove the rows in the model that have expired among the next `budget` rows (see
+/// [`PrimaryIndex::select_expired`](crate::engine::core::index::PrimaryIndex::select_expired)), publishing a delete
+/// for each of them. Returns the number of rows that were purged and whether the whole model has been visited
+pub fn purge_expired(model: &ModelData, budget: usize) -> (usize, bool) {
+    if !model.primary_index().expires_rows() {
+        return (0, true);
+    }
+    // lock the secondary indexes before the primary index
+    let mut sidx = model.secondary_indexes().lock();
+    let _idx_latch = model.primary_index().acquire_shared();
+    let g = sync::atm::cpin();
+    let delta_state = model.delta_state();
+    let mut sink = DirectSink::new(model);
+    let now = expiry_now();
+    let (targets, done) = model.primary_index().select_expired(now, budget, &g);
+    let mut purged = 0;
+    for row in targets.iter() {
+        drop(row.resolve_schema_deltas_and_freeze(delta_state));
+        let mut row_data_wl = row.d_data().write();
+        // the row might have been given a new expiry (or removed) since we selected it
+        if !row_data_wl.is_expired_at(now) {
+            continue;
+        }
+        if remove_locked(model, &mut sidx, row, &mut row_data_wl, &mut sink, &g).is_some() {
+            purged += 1;
+        }
+    }
+    sink.finish(model);
+    (purged, done)
 }
@@ -24,79 +24,266 @@
  *
 */
 
-use crate::engine::{
-    core::{
-        self,
-        dml::QueryExecMeta,
-        index::{DcFieldIndex, PrimaryIndexKey, Row},
-        model::{delta::DataDeltaKind, ModelData},
+use {
+    crate::engine::{
+        core::{
+            dml::{
+                self,
+                batch::{BatchLog, Undo},
+                DeltaSink, ReturnedRows, WriteCtx, WriteOutcome,
+            },
+            index::{DcFieldIndex, PrimaryIndexKey, Row, SecondaryIndexesGuard},
+            model::{
+                delta::{DataDelta, DataDeltaKind},
+                ModelData,
+            },
+        },
+        error::{QueryError, QueryResult},
+        fractal::GlobalInstanceLike,
+        idx::{IndexBaseSpec, MTIndex, STIndex, STIndexExt, STIndexSeq},
+        net::protocol::Response,
+        ql::dml::ins::{InsertData, InsertStatement},
+        sync::atm::{cpin, Guard},
     },
-    error::{QueryError, QueryResult},
-    fractal::GlobalInstanceLike,
-    idx::{IndexBaseSpec, MTIndex, STIndex, STIndexExt, STIndexSeq},
-    net::protocol::Response,
-    ql::dml::ins::{InsertData, InsertStatement},
-    sync::atm::cpin,
+    std::collections::HashSet,
 };
 
 pub fn insert_resp(
     global: &impl GlobalInstanceLike,
-    insert: InsertStatement,
+    mut insert: InsertStatement,
 ) -> QueryResult<Response> {
-    self::insert(global, insert).map(|_| Response::Empty)
+    let mut returned = ReturnedRows::new(insert.take_returning());
+    self::insert_returning(global, insert, &mut returned)?;
+    Ok(returned.into_response(Response::Empty))
 }
 
+#[cfg(test)]
 pub fn insert(global: &impl GlobalInstanceLike, insert: InsertStatement) -> QueryResult<()> {
-    core::with_model_for_data_update(global, insert.entity(), |mdl| {
-        let (pk, data) = prepare_insert(mdl, insert.data())?;
-        let _idx_latch = mdl.primary_index().acquire_shared();
-        let g = cpin();
-        let ds = mdl.delta_state();
-        // create new version
-        let new_version = ds.create_new_data_delta_version();
-        let row = Row::new(pk, data, ds.schema_current_version(), new_version);
-        if mdl.primary_index().__raw_index().mt_insert(row.clone(), &g) {
-            // append delta for new version
-            let dp = ds.append_new_data_delta_with(DataDeltaKind::Insert, row, new_version, &g);
-            Ok(QueryExecMeta::new(dp))
+    self::insert_returning(global, insert, &mut ReturnedRows::new(None))
+}
+
+/// Insert all the rows in the statement
+fn insert_returning(
+    global: &impl GlobalInstanceLike,
+    insert: InsertStatement,
+    returned: &mut ReturnedRows,
+) -> QueryResult<()> {
+    let (entity, ttl, durability) = (insert.entity(), insert.ttl(), insert.durability());
+    let mut rows = insert.data();
+    dml::with_model_for_write(global, entity, durability, |mdl, ctx| {
+        returned.validate(mdl)?;
+        let expires_at = mdl.row_expiry(ttl)?;
+        if rows.len() == 1 {
+            let (pk, data) = prepare_insert(mdl, rows.pop().unwrap())?;
+            returned.push(mdl, &pk, &data);
+            // lock the secondary indexes before the primary index
+            let mut sidx = mdl.secondary_indexes().lock();
+            let _idx_latch = mdl.primary_index().acquire_shared();
+            let g = cpin();
+            let mut sink = ctx.sink();
+            insert_prepared(mdl, &mut sidx, pk, data, expires_at, &mut sink, &g)?;
+            sink.finish(&mut sidx, &g)
         } else {
-            Err(QueryError::QExecDmlDuplicate)
+            insert_bulk(mdl, rows, expires_at, ctx, returned)
         }
     })
 }
 
-pub fn upsert(global: &impl GlobalInstanceLike, insert: InsertStatement) -> QueryResult<bool> {
+/// Insert all the rows or none of them. Every row is validated and checked for duplicates before the model is
+/// touched, and the deltas are only published (together) once all the rows have been inserted.
+///
+/// The only way for the insert to fail once it has started inserting rows is a concurrent insert of one of its
+/// keys (into a model without secondary indexes, since the indexes are otherwise held by one insert at a time).
+/// The rows that were already inserted are then visible to readers until they're rolled back
+fn insert_bulk<G: GlobalInstanceLike>(
+    mdl: &ModelData,
+    rows: Vec<InsertData>,
+    expires_at: u64,
+    ctx: &WriteCtx<'_, G>,
+    returned: &mut ReturnedRows,
+) -> QueryResult<WriteOutcome> {
+    let prepared = rows
+        .into_iter()
+        .map(|row| prepare_insert(mdl, row))
+        .collect::<QueryResult<Vec<_>>>()?;
+    returned.start_scan();
+    prepared
+        .iter()
+        .for_each(|(pk, data)| returned.push(mdl, pk, data));
+    // lock the secondary indexes before the primary index
+    let mut sidx = mdl.secondary_indexes().lock();
+    let _idx_latch = mdl.primary_index().acquire_shared();
+    let g = cpin();
+    let mut sink = ctx.sink();
+    let values: Vec<_> = prepared.iter().map(|(_, data)| sidx.values(data)).collect();
+    let mut keys = HashSet::with_capacity(prepared.len());
+    let no_duplicates = prepared
+        .iter()
+        .all(|(pk, _)| keys.insert(pk) && mdl.primary_index().select_key(pk, &g).is_none())
+        && sidx.can_insert_all(prepared.iter().map(|(pk, _)| pk).zip(&values));
+    if !no_duplicates {
+        return Err(QueryError::QExecDmlDuplicate);
+    }
+    let mut log = BatchLog::new();
+    for (pk, data) in prepared {
+        if let Err(e) = insert_prepared(mdl, &mut sidx, pk, data, expires_at, &mut log, &g) {
+            // a row with the same key was inserted by someone else in the meantime
+            log.rollback(mdl, &mut sidx, &g);
+            return Err(e);
+        }
+    }
+    sink.publish_log(mdl.delta_state(), log, &g);
+    sink.finish(&mut sidx, &g)
+}
+
+/// Insert a prepared row that expires at the given time (zero if it never expires). The caller must hold the
+/// secondary indexes and a shared latch on the primary index
+pub(super) fn insert_prepared<'a>(
+    mdl: &ModelData,
+    sidx: &mut SecondaryIndexesGuard,
+    pk: PrimaryIndexKey,
+    data: DcFieldIndex,
+    expires_at: u64,
+    sink: &mut impl DeltaSink<'a>,
+    g: &Guard,
+) -> QueryResult<usize> {
+    if mdl.primary_index().expires_rows()
+        && matches!(mdl.primary_index().select_key_any(&pk, g), Some((_, true)))
+    {
+        // the row with this key has expired but hasn't been purged yet, so we replace it
+        return upsert_prepared(mdl, sidx, pk, data, expires_at, sink, g).map(|(_, dp)| dp);
+    }
+    let values = sidx.values(&data);
+    if !sidx.can_insert(&pk, &values) {
+        return Err(QueryError::QExecDmlDuplicate);
+    }
+    let ds = mdl.delta_state();
+    // create new version
+    let new_version = ds.create_new_data_delta_version();
+    let row = Row::new_expiring(
+        pk,
+        data,
+        ds.schema_current_version(),
+        new_version,
+        expires_at,
+    );
+    if mdl.primary_index().__raw_index().mt_insert(row.clone(), g) {
+        sidx.insert_unchecked(row.d_key(), &values);
+        // append delta for new version
+        Ok(sink.publish(
+            ds,
+            DataDelta::new(new_version, row, DataDeltaKind::Insert),
+            Undo::Insert,
+            g,
+        ))
+    } else {
+        Err(QueryError::QExecDmlDuplicate)
+    }
+}
+
+/// Returns the row to be upserted. Unlike inserts, upserts can't have multiple rows
+pub(super) fn upsert_row(insert: InsertStatement) -> QueryResult<InsertData> {
+    let mut rows = insert.data();
+    match rows.pop() {
+        Some(row) if rows.is_empty() => Ok(row),
+        _ => Err(QueryError::QLInvalidSyntax),
+    }
+}
+
+fn upsert(
+    global: &impl GlobalInstanceLike,
+    insert: InsertStatement,
+    returned: &mut ReturnedRows,
+) -> QueryResult<bool> {
     let mut ret = false;
-    core::with_model_for_data_update(global, insert.entity(), |mdl| {
-        let (pk, data) = prepare_insert(mdl, insert.data())?;
+    let (entity, ttl, durability) = (insert.entity(), insert.ttl(), insert.durability());
+    let row = upsert_row(insert)?;
+    dml::with_model_for_write(global, entity, durability, |mdl, ctx| {
+        returned.validate(mdl)?;
+        let expires_at = mdl.row_expiry(ttl)?;
+        let (pk, data) = prepare_insert(mdl, row)?;
+        returned.push(mdl, &pk, &data);
+        // lock the secondary indexes before the primary index
+        let mut sidx = mdl.secondary_indexes().lock();
         let _idx_latch = mdl.primary_index().acquire_shared();
         let g = cpin();
-        let ds = mdl.delta_state();
-        // create new version
-        let new_version = ds.create_new_data_delta_version();
-        let row = Row::new(pk, data, ds.schema_current_version(), new_version);
-        ret = mdl.primary_index().__raw_index().mt_upsert(row.clone(), &g);
-        // append delta for new version
-        let dp = ds.append_new_data_delta_with(DataDeltaKind::Upsert, row, new_version, &g);
-        Ok(QueryExecMeta::new(dp))
+        let mut sink = ctx.sink();
+        let (inserted, _) = upsert_prepared(mdl, &mut sidx, pk, data, expires_at, &mut sink, &g)?;
+        ret = inserted;
+        sink.finish(&mut sidx, &g)
     })
     .map(|_| ret)
 }
 
+/// Upsert a prepared row that expires at the given time (zero if it never expires), returning the result of the
+/// upsert along with the delta hint. The caller must hold the secondary indexes and a shared latch on the primary index
+pub(super) fn upsert_prepared<'a>(
+    mdl: &ModelData,
+    sidx: &mut SecondaryIndexesGuard,
+    pk: PrimaryIndexKey,
+    data: DcFieldIndex,
+    expires_at: u64,
+    sink: &mut impl DeltaSink<'a>,
+    g: &Guard,
+) -> QueryResult<(bool, usize)> {
+    let values = sidx.values(&data);
+    if !sidx.can_insert(&pk, &values) {
+        return Err(QueryError::QExecDmlDuplicate);
+    }
+    let ds = mdl.delta_state();
+    let mut old_row = None;
+    // replacing a row that has expired (but hasn't been purged yet) counts as an insert
+    let mut replaces_expired = false;
+    if !sidx.is_empty() | sink.keeps_undo() | mdl.primary_index().expires_rows() {
+        // unindex the row that we're about to replace (if any)
+        if let Some((row, expired)) = mdl.primary_index().select_key_any(&pk, g) {
+            let old_values = sidx.values(row.resolve_schema_deltas_and_freeze(ds).fields());
+            sidx.remove(&pk, &old_values);
+            old_row = Some(row.clone());
+            replaces_expired = expired;
+        }
+    }
+    // create new version
+    let new_version = ds.create_new_data_delta_version();
+    let row = Row::new_expiring(
+        pk,
+        data,
+        ds.schema_current_version(),
+        new_version,
+        expires_at,
+    );
+    let ret = mdl.primary_index().__raw_index().mt_upsert(row.clone(), g) | replaces_expired;
+    sidx.insert_unchecked(row.d_key(), &values);
+    // append delta for new version
+    let dp = sink.publish(
+        ds,
+        DataDelta::new(new_version, row, DataDeltaKind::Upsert),
+        Undo::Upsert(old_row),
+        g,
+    );
+    Ok((ret, dp))
+}
+
 pub fn upsert_resp(
     global: &impl GlobalInstanceLike,
-    insert: InsertStatement,
+    mut insert: InsertStatement,
 ) -> QueryResult<Response> {
-    self::upsert(global, insert).map(Response::Bool)
+    let mut returned = ReturnedRows::new(insert.take_returning());
+    let inserted = self::upsert(global, insert, &mut returned)?;
+    Ok(returned.into_response(Response::Bool(inserted)))
 }
 
 // TODO(@ohsayan): optimize null case
-fn prepare_insert(
+pub(super) fn prepare_insert(
     model: &ModelData,
     insert: InsertData,
 ) -> QueryResult<(PrimaryIndexKey, DcFieldIndex)> {
     let fields = model.fields();
-    let mut okay = fields.len() == insert.column_count();
+    let mut okay = match &insert {
+        InsertData::Ordered(tuple) => tuple.len() == fields.len(),
+        // fields with a default can be omitted
+        InsertData::Map(map) => map.len() <= fields.len(),
+    };
     let mut prepared_data = DcFieldIndex::idx_init_cap(fields.len());
     match insert {
         InsertData::Ordered(tuple) => {
@@ -148,6 +335,27 @@ fn prepare_insert(
                 );
                 inserted += 1;
             }
+            if okay & (inserted != fields.len()) {
+                for (field_id, field) in fields.stseq_ord_kv() {
+                    if prepared_data.st_contains(field_id.as_str()) {
+                        continue;
+                    }
+                    let Some(default) = field.default() else {
+                        okay = false;
+                        break;
+                    };
+                    let mut data = default.produce();
+                    okay &= field.vt_data_fpath(&mut data);
+                    prepared_data.st_insert(
+                        unsafe {
+                            // UNSAFE(@ohsayan): as long as model lives, we're good
+                            field_id.clone()
+                        },
+                        data,
+                    );
+                    inserted += 1;
+                }
+            }
             okay &= inserted == fields.len();
         }
     }
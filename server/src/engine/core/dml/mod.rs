@@ -24,48 +24,675 @@
  *
 */
 
+mod batch;
 mod del;
 mod ins;
 mod sel;
 mod upd;
 
-use crate::{
-    engine::{
-        core::model::ModelData,
-        data::{lit::Lit, tag::DataTag},
-        error::{QueryError, QueryResult},
-        ql::dml::WhereClause,
+use {
+    crate::{
+        engine::{
+            core::{
+                self,
+                index::{
+                    DcFieldIndex, PrimaryIndexKey, PrimaryIndexKind, PrimaryIndexScan, Row,
+                    RowData, SecondaryIndexesGuard,
+                },
+                model::{
+                    delta::{DataDelta, DeltaState},
+                    Durability, FeedGate, Model, ModelData,
+                },
+                EntityIDRef,
+            },
+            data::{
+                cell::{Datacell, VirtualDatacell},
+                lit::Lit,
+                tag::{DataTag, TagClass},
+            },
+            error::{QueryError, QueryResult},
+            fractal::{GlobalInstanceLike, GroupTicket, ModelUniqueIDRef},
+            idx::{STIndex, STIndexSeq},
+            mem::IntegerRepr,
+            net::protocol::{Response, ResponseType},
+            ql::{
+                dml::{RelationalExpr, ReturningClause, WhereClause},
+                lex::Ident,
+            },
+            storage::{safe_interfaces::TxnModelBatch, BatchStats},
+            sync::atm::Guard,
+        },
+        util::compiler,
     },
-    util::compiler,
+    std::{cmp::Ordering, mem, ops::Bound, vec},
 };
 
+pub use batch::{commit as commit_batch, StagedBatch};
+pub(in crate::engine::core) use sel::encode_cell;
 #[cfg(test)]
 pub use {
     del::delete,
     ins::insert,
-    sel::{select_all, select_custom},
+    sel::{exists, select_aggregate, select_all, select_custom},
     upd::{collect_trace_path as update_flow_trace, update},
 };
 pub use {
-    del::delete_resp,
+    del::{delete_resp, purge_expired},
     ins::{insert_resp, upsert_resp},
-    sel::{select_all_resp, select_resp},
+    sel::{exists_resp, select_all_resp, select_resp},
     upd::update_resp,
 };
 
+/// Where the changes made by a DML query end up
+trait DeltaSink<'a> {
+    /// Returns true if the sink needs the data to undo the changes (lookups needed only for that can be skipped otherwise)
+    fn keeps_undo(&self) -> bool;
+    /// Publish a change, returning a delta hint
+    fn publish(
+        &mut self,
+        ds: &DeltaState,
+        delta: DataDelta,
+        undo: batch::Undo<'a>,
+        g: &Guard,
+    ) -> usize;
+}
+
+/// Changes are published to the model's delta state right away (to be picked up by the fractal manager). If the
+/// model's change feed has subscribers, the changes are also captured so that they can be published to it
+struct DirectSink<'a> {
+    gate: FeedGate<'a>,
+    feed: Option<batch::BatchLog<'a>>,
+}
+
+impl<'a> DirectSink<'a> {
+    /// Returns a sink for changes to the model, holding its change feed. The caller must hold the model's secondary
+    /// indexes and can't get any versions before this
+    fn new(mdl: &'a ModelData) -> Self {
+        let gate = mdl.change_feed().hold();
+        Self {
+            feed: gate.captures().then(batch::BatchLog::new),
+            gate,
+        }
+    }
+    /// Publish the captured changes (if any) to the model's change feed. The caller must still hold the locks that
+    /// it made the changes with
+    fn finish(self, mdl: &ModelData) {
+        if let Some(feed) = self.feed {
+            mdl.change_feed().publish(&self.gate, feed.changes(mdl));
+        }
+    }
+}
+
+impl<'a> DeltaSink<'a> for DirectSink<'a> {
+    fn keeps_undo(&self) -> bool {
+        self.feed.is_some()
+    }
+    fn publish(
+        &mut self,
+        ds: &DeltaState,
+        delta: DataDelta,
+        undo: batch::Undo<'a>,
+        g: &Guard,
+    ) -> usize {
+        if let Some(feed) = self.feed.as_mut() {
+            feed.publish(ds, delta.clone(), undo, g);
+        }
+        ds.append_new_data_delta(delta, g)
+    }
+}
+
+/// Run a DML query against a model with the given durability (or the model's own). The query makes its changes with
+/// a sink from the context (that it gets once it holds the model's secondary indexes) and finishes the sink while it
+/// still holds its locks. `group-commit` writes then wait for their changes to be written (after the locks have been
+/// released)
+fn with_model_for_write<'e, G, F>(
+    global: &G,
+    entity: EntityIDRef<'e>,
+    durability: Option<Durability>,
+    f: F,
+) -> QueryResult<()>
+where
+    G: GlobalInstanceLike,
+    F: for<'m> FnOnce(&'m ModelData, &WriteCtx<'m, G>) -> QueryResult<WriteOutcome>,
+{
+    core::with_model_for_data_update(global, entity, |model| {
+        let mdl = model.data();
+        let ctx = WriteCtx {
+            global,
+            model,
+            entity,
+            durability: durability.unwrap_or(mdl.durability()),
+        };
+        let write = || match f(mdl, &ctx)? {
+            WriteOutcome::Published(hint) => Ok(QueryExecMeta::new(hint)),
+            WriteOutcome::Written => Ok(QueryExecMeta::new(0)),
+            WriteOutcome::Grouped(ticket) => model
+                .driver()
+                .group_commit(global, ctx.mdl_id(), mdl, ticket)
+                .map(|_| QueryExecMeta::new(0)),
+        };
+        match ctx.durability {
+            Durability::Async => write(),
+            // durable writes wait on the disk, so let the runtime move this worker's other tasks elsewhere
+            Durability::GroupCommit | Durability::Sync => tokio::task::block_in_place(write),
+        }
+    })
+}
+
+/// The model (and durability) that a single DML query writes to
+struct WriteCtx<'m, G> {
+    global: &'m G,
+    model: &'m Model,
+    entity: EntityIDRef<'m>,
+    durability: Durability,
+}
+
+impl<'m, G: GlobalInstanceLike> WriteCtx<'m, G> {
+    fn mdl_id(&self) -> ModelUniqueIDRef<'m> {
+        ModelUniqueIDRef::new(
+            self.entity.space(),
+            self.entity.entity(),
+            self.model.data().get_uuid(),
+        )
+    }
+    /// Returns a sink for the query's changes, holding the model's change feed (so that the changes are captured for
+    /// the change feed if and only if it has subscribers). The caller must hold the model's secondary indexes and can't
+    /// get any versions before this
+    fn sink<'a>(&self) -> WriteSink<'a, 'm, G> {
+        let mode = match self.durability {
+            Durability::Async => WriteMode::Direct(0),
+            Durability::GroupCommit => WriteMode::GroupCommit(Vec::new()),
+            Durability::Sync => WriteMode::Sync(batch::BatchLog::new()),
+        };
+        let gate = self.model.data().change_feed().hold();
+        WriteSink {
+            ctx: WriteCtx { ..*self },
+            mode,
+            capture: gate.captures(),
+            gate,
+            feed: batch::BatchLog::new(),
+        }
+    }
+}
+
+/// Where a DML query's changes go, depending on the durability of the write
+struct WriteSink<'a, 'm, G> {
+    ctx: WriteCtx<'m, G>,
+    mode: WriteMode<'a>,
+    /// held until the changes have been published
+    gate: FeedGate<'m>,
+    /// the changes need to be published to the model's change feed
+    capture: bool,
+    /// the changes captured for the change feed (a `sync` write publishes its own log instead)
+    feed: batch::BatchLog<'a>,
+}
+
+enum WriteMode<'a> {
+    /// `async`: changes are published to the delta state right away (with the last delta hint)
+    Direct(usize),
+    /// `group-commit`: changes are collected to be written with a group commit once the locks are released
+    GroupCommit(Vec<DataDelta>),
+    /// `sync`: changes are collected to be written before the locks are released (so that they can be undone)
+    Sync(batch::BatchLog<'a>),
+}
+
+/// What's left to do once a DML query has finished its sink (and released its locks)
+enum WriteOutcome {
+    /// the changes were published to the delta state, with this delta hint
+    Published(usize),
+    /// the changes were written to the journal
+    Written,
+    /// the changes still need to be written with the commit group that they joined
+    Grouped(GroupTicket),
+}
+
+impl<'a, 'm, G: GlobalInstanceLike> WriteSink<'a, 'm, G> {
+    /// Publish all the changes of a bulk write (which the caller collected so that it could undo them)
+    fn publish_log(&mut self, ds: &DeltaState, log: batch::BatchLog<'a>, g: &Guard) {
+        let deltas = match &mut self.mode {
+            WriteMode::Sync(own) => return own.append(log),
+            _ if self.capture => {
+                let deltas = log.deltas().to_vec();
+                self.feed.append(log);
+                deltas
+            }
+            _ => log.into_deltas(),
+        };
+        match &mut self.mode {
+            WriteMode::Direct(hint) => *hint = ds.append_new_data_deltas(deltas, g),
+            WriteMode::GroupCommit(own) => own.extend(deltas),
+            WriteMode::Sync(_) => unreachable!(),
+        }
+    }
+    /// Finish the write. The caller must still hold the locks that it made the changes with, since the changes of a
+    /// `sync` write are written here and undone if they can't be
+    fn finish(self, sidx: &mut SecondaryIndexesGuard, g: &Guard) -> QueryResult<WriteOutcome> {
+        let mdl = self.ctx.model.data();
+        if self.capture {
+            mdl.change_feed()
+                .publish(&self.gate, self.feed.changes(mdl));
+        }
+        match self.mode {
+            WriteMode::Direct(hint) => Ok(WriteOutcome::Published(hint)),
+            WriteMode::GroupCommit(deltas) if deltas.is_empty() => Ok(WriteOutcome::Written),
+            // join the group while we hold the locks, so that a subscriber never misses these changes
+            WriteMode::GroupCommit(deltas) => Ok(WriteOutcome::Grouped(
+                self.ctx.model.driver().join_group(deltas),
+            )),
+            WriteMode::Sync(log) if log.deltas().is_empty() => Ok(WriteOutcome::Written),
+            WriteMode::Sync(log) => {
+                let r = self.ctx.model.driver().driver_context(
+                    self.ctx.global,
+                    self.ctx.mdl_id(),
+                    |drv| {
+                        drv.commit_with_ctx(
+                            TxnModelBatch::new(mdl, log.deltas()),
+                            BatchStats::new(),
+                        )
+                    },
+                );
+                match r {
+                    Ok(()) => {
+                        if self.capture {
+                            mdl.change_feed().publish(&self.gate, log.changes(mdl));
+                        }
+                        Ok(WriteOutcome::Written)
+                    }
+                    Err(e) => {
+                        log.rollback(mdl, sidx, g);
+                        Err(e)
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<'a, 'm, G: GlobalInstanceLike> DeltaSink<'a> for WriteSink<'a, 'm, G> {
+    fn keeps_undo(&self) -> bool {
+        self.capture | matches!(self.mode, WriteMode::Sync(_))
+    }
+    fn publish(
+        &mut self,
+        ds: &DeltaState,
+        delta: DataDelta,
+        undo: batch::Undo<'a>,
+        g: &Guard,
+    ) -> usize {
+        if let WriteMode::Sync(log) = &mut self.mode {
+            return log.publish(ds, delta, undo, g);
+        }
+        if self.capture {
+            self.feed.publish(ds, delta.clone(), undo, g);
+        }
+        match &mut self.mode {
+            WriteMode::Direct(hint) => {
+                *hint = ds.append_new_data_delta(delta, g);
+                *hint
+            }
+            WriteMode::GroupCommit(deltas) => {
+                deltas.push(delta);
+                deltas.len()
+            }
+            WriteMode::Sync(_) => unreachable!(),
+        }
+    }
+}
+
+/// A `where` clause resolved against a model
+struct ResolvedWhere<'a> {
+    /// the primary key, if the clause has an equality predicate on it
+    key: Option<Lit<'a>>,
+    /// all other predicates, which need to be evaluated against each row
+    filters: Vec<RelationalExpr<'a>>,
+    /// the primary key range that a scan needs to visit (only narrowed if the primary index is ordered)
+    pk_range: (Bound<PrimaryIndexKey>, Bound<PrimaryIndexKey>),
+    /// an equality predicate (on the field and with the value) that a secondary index can answer, in which case a
+    /// scan only needs to visit the rows that the index holds for the value
+    index_eq: Option<(Ident<'a>, Lit<'a>)>,
+}
+
+impl<'a> ResolvedWhere<'a> {
+    /// A clause that matches every row (for queries where the `where` clause is optional)
+    pub fn all() -> Self {
+        Self {
+            key: None,
+            filters: Vec::new(),
+            pk_range: (Bound::Unbounded, Bound::Unbounded),
+            index_eq: None,
+        }
+    }
+    /// Returns the primary key if this is a point query; `None` if a scan is required
+    pub fn key(&self) -> Option<Lit<'a>> {
+        self.key.clone()
+    }
+    /// Take the primary key range that a scan needs to visit. The filters still need to be checked
+    /// against every row
+    pub fn take_pk_range(&mut self) -> (Bound<PrimaryIndexKey>, Bound<PrimaryIndexKey>) {
+        mem::replace(&mut self.pk_range, (Bound::Unbounded, Bound::Unbounded))
+    }
+    /// Returns the field of the secondary index that a scan reads, if any
+    pub fn index_field(&self) -> Option<&str> {
+        self.index_eq.as_ref().map(|(field, _)| field.as_str())
+    }
+    /// Returns an iterator over the rows that a scan needs to visit: either the candidates from a secondary
+    /// index or the primary key range. The filters still need to be checked against every row. A writer passes its
+    /// index guard so that an index that it holds is read under its lock
+    pub fn scan<'g>(
+        &mut self,
+        mdl: &'g ModelData,
+        sidx: Option<&SecondaryIndexesGuard>,
+        g: &'g Guard,
+    ) -> RowScan<'g> {
+        match self.index_eq.take() {
+            Some((field, value)) => {
+                let index = mdl
+                    .secondary_indexes()
+                    .get_by_field(field.as_str())
+                    .unwrap();
+                let keys = match sidx.and_then(|sidx| sidx.lookup(index, value.clone())) {
+                    Some(keys) => keys,
+                    None => index.lookup(value),
+                };
+                RowScan::Index {
+                    mdl,
+                    keys: keys.into_iter(),
+                    g,
+                }
+            }
+            None => {
+                let (lo, hi) = self.take_pk_range();
+                RowScan::Range(Box::new(mdl.primary_index().select_range(lo, hi, g)))
+            }
+        }
+    }
+    /// Check if the row satisfies all the filters
+    pub fn row_matches(&self, mdl: &ModelData, pk: &PrimaryIndexKey, data: &RowData) -> bool {
+        self.filters.iter().all(|expr| {
+            let vdc;
+            let dc = if expr.lhs().as_str() == mdl.p_key() {
+                vdc = VirtualDatacell::new_pk(pk, mdl.p_tag());
+                &*vdc
+            } else {
+                match data.fields().st_get(expr.lhs().as_str()) {
+                    Some(dc) => dc,
+                    // an unresolved schema delta; this is a null
+                    None => return false,
+                }
+            };
+            if expr.is_contains() {
+                return !dc.is_null()
+                    && dc.list().read().iter().any(|element| {
+                        compare_cell_lit(element, &expr.rhs()) == Some(Ordering::Equal)
+                    });
+            }
+            compare_cell_lit(dc, &expr.rhs()).is_some_and(|ord| expr.eval_ordering(ord))
+        })
+    }
+}
+
 impl ModelData {
     pub(self) fn resolve_where<'a>(
         &self,
         where_clause: &mut WhereClause<'a>,
-    ) -> QueryResult<Lit<'a>> {
-        match where_clause.clauses_mut().remove(self.p_key().as_bytes()) {
+    ) -> QueryResult<ResolvedWhere<'a>> {
+        let ranges: Vec<_> = where_clause.range_clauses_mut().drain().collect();
+        let clauses = where_clause.clauses_mut();
+        if compiler::unlikely(clauses.is_empty()) {
+            return compiler::cold_rerr(QueryError::QExecDmlWhereHasUnindexedColumn);
+        }
+        let key = match clauses.get(self.p_key().as_bytes()) {
             Some(clause)
                 if clause.filter_hint_none()
                     & (clause.rhs().kind().tag_unique() == self.p_tag().tag_unique()) =>
             {
-                Ok(clause.rhs())
+                clauses
+                    .remove(self.p_key().as_bytes())
+                    .map(|clause| clause.rhs())
+            }
+            _ => None,
+        };
+        let mut filters = Vec::with_capacity(clauses.len() + ranges.len());
+        for (_, expr) in clauses.drain().chain(ranges) {
+            let Some(field) = self.fields().st_get(expr.lhs().as_str()) else {
+                return compiler::cold_rerr(QueryError::QExecUnknownField);
+            };
+            // `contains` compares the elements of a list
+            let layers = field.layers();
+            let comparable = match (expr.is_contains(), layers[0].tag().tag_class()) {
+                (true, TagClass::List) => {
+                    tag_class_comparable(layers[1].tag().tag_class(), expr.rhs().kind().tag_class())
+                }
+                (true, _) => false,
+                (false, class) => tag_class_comparable(class, expr.rhs().kind().tag_class()),
+            };
+            if !comparable {
+                return compiler::cold_rerr(QueryError::QExecDmlValidationError);
+            }
+            filters.push(expr);
+        }
+        let mut pk_range = (Bound::Unbounded, Bound::Unbounded);
+        if self.primary_index().kind() == PrimaryIndexKind::Ordered {
+            let to_key = |lit: Lit| {
+                (lit.kind().tag_unique() == self.p_tag().tag_unique())
+                    .then(|| PrimaryIndexKey::try_from_lit(lit))
+                    .flatten()
+            };
+            for expr in filters.iter().filter(|f| f.lhs().as_str() == self.p_key()) {
+                let bound = match expr.range_bound() {
+                    Some((lower, Bound::Included(lit))) => {
+                        to_key(lit).map(|k| (lower, Bound::Included(k)))
+                    }
+                    Some((lower, Bound::Excluded(lit))) => {
+                        to_key(lit).map(|k| (lower, Bound::Excluded(k)))
+                    }
+                    _ => None,
+                };
+                match bound {
+                    Some((true, bound)) => pk_range.0 = bound,
+                    Some((false, bound)) => pk_range.1 = bound,
+                    None => {}
+                }
+            }
+        }
+        // an equality predicate on an indexed field narrows down the scan (the predicate is still checked
+        // since the index is read ahead of the rows)
+        let mut index_eq = None;
+        if key.is_none() & !self.secondary_indexes().is_empty() {
+            let mut unique = false;
+            for expr in filters.iter().filter(|f| f.filter_hint_none()) {
+                let Some(index) = self.secondary_indexes().get_by_field(expr.lhs().as_str()) else {
+                    continue;
+                };
+                let field_tag = self.fields().st_get(expr.lhs().as_str()).unwrap().layers()[0]
+                    .tag()
+                    .tag_unique();
+                if (expr.rhs().kind().tag_unique() != field_tag) | (unique & !index.is_unique()) {
+                    continue;
+                }
+                unique = index.is_unique();
+                index_eq = Some((expr.lhs(), expr.rhs()));
+                if unique {
+                    break;
+                }
+            }
+        }
+        Ok(ResolvedWhere {
+            key,
+            filters,
+            pk_range,
+            index_eq,
+        })
+    }
+}
+
+/// The rows visited by a scan
+enum RowScan<'g> {
+    /// rows looked up using the candidate keys from a secondary index (rows that were removed since are skipped)
+    Index {
+        mdl: &'g ModelData,
+        keys: vec::IntoIter<PrimaryIndexKey>,
+        g: &'g Guard,
+    },
+    /// rows in a primary key range
+    Range(Box<PrimaryIndexScan<'g, 'g, 'g>>),
+}
+
+impl<'g> Iterator for RowScan<'g> {
+    type Item = &'g Row;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Index { mdl, keys, g } => {
+                let (mdl, g) = (*mdl, *g);
+                keys.find_map(|key| mdl.primary_index().select_key(&key, g))
+            }
+            Self::Range(iter) => iter.next(),
+        }
+    }
+}
+
+/// Returns true if a cell of class `a` can be compared with a literal of class `b`
+fn tag_class_comparable(a: TagClass, b: TagClass) -> bool {
+    let numeric = |c| {
+        matches!(
+            c,
+            TagClass::UnsignedInt | TagClass::SignedInt | TagClass::Float
+        )
+    };
+    ((a == b) & (a != TagClass::List)) | (numeric(a) & numeric(b))
+}
+
+/// Compare a cell with a literal. Returns `None` if the cell is null or if the values can't be ordered
+fn compare_cell_lit(dc: &Datacell, lit: &Lit) -> Option<Ordering> {
+    if dc.is_null() {
+        return None;
+    }
+    unsafe {
+        // UNSAFE(@ohsayan): +tagck on both sides
+        match (dc.kind(), lit.kind().tag_class()) {
+            (TagClass::Bool, TagClass::Bool) => Some(dc.read_bool().cmp(&lit.bool())),
+            (TagClass::UnsignedInt, TagClass::UnsignedInt) => Some(dc.read_uint().cmp(&lit.uint())),
+            (TagClass::SignedInt, TagClass::SignedInt) => Some(dc.read_sint().cmp(&lit.sint())),
+            (TagClass::UnsignedInt, TagClass::SignedInt) => {
+                Some((dc.read_uint() as i128).cmp(&(lit.sint() as i128)))
+            }
+            (TagClass::SignedInt, TagClass::UnsignedInt) => {
+                Some((dc.read_sint() as i128).cmp(&(lit.uint() as i128)))
+            }
+            (TagClass::Float, TagClass::Float) => dc.read_float().partial_cmp(&lit.float()),
+            (TagClass::Float, TagClass::UnsignedInt) => {
+                dc.read_float().partial_cmp(&(lit.uint() as f64))
+            }
+            (TagClass::Float, TagClass::SignedInt) => {
+                dc.read_float().partial_cmp(&(lit.sint() as f64))
             }
-            _ => compiler::cold_rerr(QueryError::QExecDmlWhereHasUnindexedColumn),
+            (TagClass::UnsignedInt, TagClass::Float) => {
+                (dc.read_uint() as f64).partial_cmp(&lit.float())
+            }
+            (TagClass::SignedInt, TagClass::Float) => {
+                (dc.read_sint() as f64).partial_cmp(&lit.float())
+            }
+            (TagClass::Bin, TagClass::Bin) | (TagClass::Str, TagClass::Str) => {
+                Some(dc.read_bin().cmp(lit.bin()))
+            }
+            (TagClass::Timestamp, TagClass::Timestamp) => {
+                Some(dc.read_timestamp().cmp(&lit.timestamp()))
+            }
+            (TagClass::Uuid, TagClass::Uuid) => Some(dc.read_uuid().cmp(&lit.uuid())),
+            _ => None,
+        }
+    }
+}
+
+/// The rows returned by a DML query with a `returning` clause, encoded like the rows returned by a `select`
+pub(super) struct ReturnedRows<'a> {
+    returning: Option<ReturningClause<'a>>,
+    data: Vec<u8>,
+    /// the number of cells in a row
+    col_c: usize,
+    /// the number of rows, if this is a scan (that can return any number of rows)
+    scan_row_c: Option<usize>,
+}
+
+impl<'a> ReturnedRows<'a> {
+    pub fn new(returning: Option<ReturningClause<'a>>) -> Self {
+        Self {
+            returning,
+            data: Vec::new(),
+            col_c: 0,
+            scan_row_c: None,
+        }
+    }
+    /// Check that all the returned fields exist. This must be done before any changes are made
+    pub fn validate(&mut self, mdl: &ModelData) -> QueryResult<()> {
+        let Some(returning) = self.returning.as_ref() else {
+            return Ok(());
+        };
+        if returning
+            .fields()
+            .iter()
+            .any(|f| !mdl.fields().st_contains(f.as_str()))
+        {
+            return Err(QueryError::QExecUnknownField);
+        }
+        self.col_c = if returning.is_wildcard() {
+            mdl.fields().len()
+        } else {
+            returning.fields().len()
+        };
+        Ok(())
+    }
+    /// Mark this as a scan, so that every changed row is returned in a multi-row response
+    pub fn start_scan(&mut self) {
+        if self.returning.is_some() {
+            IntegerRepr::scoped(self.col_c as u64, |repr| self.data.extend(repr));
+            self.data.push(b'\n');
+            self.scan_row_c = Some(0);
+        }
+    }
+    /// Add a row. If a field is missing (an unresolved schema delta), it is returned as a null
+    pub fn push(&mut self, mdl: &ModelData, pk: &PrimaryIndexKey, fields: &DcFieldIndex) {
+        let Some(returning) = self.returning.as_ref() else {
+            return;
+        };
+        let pkdc = VirtualDatacell::new_pk(pk, mdl.p_tag());
+        let null = Datacell::null();
+        let mut push_field = |key: &str| {
+            let dc = match fields.st_get(key) {
+                Some(dc) => dc,
+                None if key == mdl.p_key() => &*pkdc,
+                None => &null,
+            };
+            encode_cell(&mut self.data, dc);
+        };
+        if returning.is_wildcard() {
+            mdl.fields()
+                .stseq_ord_key()
+                .for_each(|key| push_field(key.as_ref()));
+        } else {
+            returning
+                .fields()
+                .iter()
+                .for_each(|key| push_field(key.as_str()));
+        }
+        if let Some(row_c) = self.scan_row_c.as_mut() {
+            *row_c += 1;
+        }
+    }
+    /// Returns the response with the returned rows, or `default` if nothing was to be returned
+    pub fn into_response(self, default: Response) -> Response {
+        match (self.returning, self.scan_row_c) {
+            (None, _) => default,
+            (Some(_), Some(row_c)) => Response::Serialized {
+                ty: ResponseType::MultiRow,
+                size: row_c,
+                data: self.data,
+            },
+            (Some(_), None) => Response::Serialized {
+                ty: ResponseType::Row,
+                size: self.col_c,
+                data: self.data,
+            },
         }
     }
 }
@@ -79,9 +706,6 @@ impl QueryExecMeta {
     pub fn new(delta_hint: usize) -> Self {
         Self { delta_hint }
     }
-    pub fn zero() -> Self {
-        Self::new(0)
-    }
     pub fn delta_hint(&self) -> usize {
         self.delta_hint
     }
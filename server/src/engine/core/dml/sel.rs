@@ -24,24 +24,34 @@
  *
 */
 
-use crate::engine::{
-    core::{
-        index::{
-            DcFieldIndex, IndexLatchHandleExclusive, PrimaryIndexKey, Row, RowData, RowDataLck,
+use {
+    crate::engine::{
+        core::{
+            dml::{ResolvedWhere, RowScan},
+            index::{DcFieldIndex, PrimaryIndexKey, RowData},
+            model::ModelData,
         },
-        model::ModelData,
+        data::{
+            cell::{Datacell, VirtualDatacell},
+            lit::Lit,
+            tag::{DataTag, TagClass, TagSelector, TagUnique},
+        },
+        error::{QueryError, QueryResult},
+        fractal::GlobalInstanceLike,
+        idx::{STIndex, STIndexSeq},
+        mem::IntegerRepr,
+        net::protocol::{Response, ResponseType},
+        ql::dml::sel::{
+            Aggregate, AggregateFn, ExistsStatement, OrderBy, SelectAllStatement, SelectStatement,
+        },
+        sync,
     },
-    data::{
-        cell::{Datacell, VirtualDatacell},
-        tag::{DataTag, TagClass},
+    std::{
+        cmp::Ordering,
+        collections::{BinaryHeap, HashSet},
+        ops::Bound,
+        str,
     },
-    error::{QueryError, QueryResult},
-    fractal::GlobalInstanceLike,
-    idx::{IndexMTRaw, MTIndexExt, STIndex, STIndexSeq},
-    mem::IntegerRepr,
-    net::protocol::{Response, ResponseType},
-    ql::dml::sel::{SelectAllStatement, SelectStatement},
-    sync,
 };
 
 pub fn select_resp(
@@ -49,15 +59,40 @@ pub fn select_resp(
     select: SelectStatement,
 ) -> QueryResult<Response> {
     let mut data = vec![];
+    if select.is_aggregate() {
+        let cells = self::select_aggregate(global, select)?;
+        cells.iter().for_each(|cell| encode_cell(&mut data, cell));
+        return Ok(Response::Serialized {
+            ty: ResponseType::Row,
+            size: cells.len(),
+            data,
+        });
+    }
     let mut i = 0usize;
-    self::select_custom(global, select, |item| {
-        encode_cell(&mut data, item);
-        i += 1;
-    })?;
-    Ok(Response::Serialized {
-        ty: ResponseType::Row,
-        size: i,
-        data,
+    let scan = self::select_custom(
+        global,
+        select,
+        &mut data,
+        |buf, col_c| {
+            IntegerRepr::scoped(col_c as u64, |repr| buf.extend(repr));
+            buf.push(b'\n');
+        },
+        |buf, item| {
+            encode_cell(buf, item);
+            i += 1;
+        },
+    )?;
+    Ok(match scan {
+        Some(row_c) => Response::Serialized {
+            ty: ResponseType::MultiRow,
+            size: row_c,
+            data,
+        },
+        None => Response::Serialized {
+            ty: ResponseType::Row,
+            size: i,
+            data,
+        },
     })
 }
 
@@ -66,7 +101,7 @@ pub fn select_all_resp(
     select: SelectAllStatement,
 ) -> QueryResult<Response> {
     let mut ret_buf = Vec::new();
-    let i = self::select_all(
+    let (i, cursor) = self::select_all(
         global,
         select,
         &mut ret_buf,
@@ -76,30 +111,96 @@ pub fn select_all_resp(
         },
         |buf, data, _| encode_cell(buf, data),
     )?;
+    let ty = match cursor {
+        Some(cursor) => {
+            // the cursor trails the rows
+            encode_cell(&mut ret_buf, &cursor);
+            ResponseType::MultiRowCursor
+        }
+        None => ResponseType::MultiRow,
+    };
     Ok(Response::Serialized {
-        ty: ResponseType::MultiRow,
+        ty,
         size: i,
         data: ret_buf,
     })
 }
 
+pub fn exists_resp(
+    global: &impl GlobalInstanceLike,
+    exists: ExistsStatement,
+) -> QueryResult<Response> {
+    let count = self::exists(global, &exists)?;
+    if exists.is_multi() {
+        let mut data = vec![];
+        encode_cell(&mut data, &Datacell::new_uint_default(count as u64));
+        Ok(Response::Serialized {
+            ty: ResponseType::Row,
+            size: 1,
+            data,
+        })
+    } else {
+        Ok(Response::Bool(count == 1))
+    }
+}
+
+/// Returns the number of the given keys that are present in the primary index (a key that is
+/// repeated is only counted once). The rows are only probed and never materialized
+pub fn exists(global: &impl GlobalInstanceLike, exists: &ExistsStatement) -> QueryResult<usize> {
+    global
+        .state()
+        .namespace()
+        .with_model(exists.entity(), |mdl| {
+            if (exists.key().as_str() != mdl.p_key())
+                | exists
+                    .keys()
+                    .iter()
+                    .any(|k| k.kind().tag_unique() != mdl.p_tag().tag_unique())
+            {
+                return Err(QueryError::QExecDmlWhereHasUnindexedColumn);
+            }
+            let g = sync::atm::cpin();
+            let keys: HashSet<_> = exists
+                .keys()
+                .iter()
+                .filter_map(|k| PrimaryIndexKey::try_from_lit(k.clone()))
+                .collect();
+            Ok(keys
+                .iter()
+                .filter(|k| mdl.primary_index().select_key(k, &g).is_some())
+                .count())
+        })
+}
+
+/// Scan (at most `limit`) rows from the model. For a paginated scan, this also returns the cursor to resume
+/// from (which is null once the scan is complete)
 pub fn select_all<Fm, F, T>(
     global: &impl GlobalInstanceLike,
     select: SelectAllStatement,
     serialize_target: &mut T,
     mut f_mdl: Fm,
     mut f: F,
-) -> QueryResult<usize>
+) -> QueryResult<(usize, Option<Datacell>)>
 where
     Fm: FnMut(&mut T, &ModelData, usize),
     F: FnMut(&mut T, &Datacell, usize),
 {
     global.state().namespace().with_model(select.entity, |mdl| {
         let g = sync::atm::cpin();
+        let limit = select.limit as usize;
+        let after = match select.after {
+            Some(cursor) => Some(cursor_decode(mdl, cursor)?),
+            None => None,
+        };
+        if let Some(order_by) = select.order_by {
+            let i = select_all_ordered(mdl, &select, order_by, &g, serialize_target, f_mdl, f)?;
+            return Ok((i, None));
+        }
         let mut i = 0;
+        let mut last = None;
         if select.wildcard {
             f_mdl(serialize_target, mdl, mdl.fields().len());
-            for (key, data) in RowIteratorAll::new(&g, mdl, select.limit as usize) {
+            for (key, data) in RowIteratorAll::new_after(&g, mdl, limit, after.flatten()) {
                 let vdc = VirtualDatacell::new_pk(key, mdl.p_tag());
                 for key in mdl.fields().stseq_ord_key() {
                     let r = if key.as_str() == mdl.p_key() {
@@ -109,6 +210,7 @@ where
                     };
                     f(serialize_target, r, mdl.fields().len());
                 }
+                last = Some(key);
                 i += 1;
             }
         } else {
@@ -122,7 +224,7 @@ where
                 return Err(QueryError::QExecUnknownField);
             }
             f_mdl(serialize_target, mdl, select.fields.len());
-            for (key, data) in RowIteratorAll::new(&g, mdl, select.limit as usize) {
+            for (key, data) in RowIteratorAll::new_after(&g, mdl, limit, after.flatten()) {
                 let vdc = VirtualDatacell::new_pk(key, mdl.p_tag());
                 for key in select.fields.iter() {
                     let r = if key.as_str() == mdl.p_key() {
@@ -132,15 +234,204 @@ where
                     };
                     f(serialize_target, r, select.fields.len());
                 }
+                last = Some(key);
                 i += 1;
             }
         }
-        Ok(i)
+        let cursor = select.after.map(|cursor| match last {
+            // a full page; there might be more
+            Some(key) if i == limit => {
+                Datacell::new_bin(cursor_encode(mdl, key).into_boxed_slice())
+            }
+            // nothing was requested, so nothing moved
+            _ if limit == 0 => Datacell::new_bin(cursor.into()),
+            _ => Datacell::null(),
+        });
+        Ok((i, cursor))
     })
 }
 
-fn encode_cell(resp: &mut Vec<u8>, item: &Datacell) {
-    resp.push((item.tag().tag_selector().value_u8() + 1) * (item.is_init() as u8));
+/*
+    ordered scans
+    ---
+    the whole model is scanned but only the best `limit` rows seen so far are kept, in a heap whose top is the
+    worst of them. rows are ordered with `compare_cells` and ties are broken by scan order
+*/
+
+/// Scan all the rows and return the first `limit` rows as ordered by the given field. Only `limit` rows are
+/// held in memory at any point
+fn select_all_ordered<Fm, F, T>(
+    mdl: &ModelData,
+    select: &SelectAllStatement,
+    order_by: OrderBy,
+    g: &sync::atm::Guard,
+    serialize_target: &mut T,
+    mut f_mdl: Fm,
+    mut f: F,
+) -> QueryResult<usize>
+where
+    Fm: FnMut(&mut T, &ModelData, usize),
+    F: FnMut(&mut T, &Datacell, usize),
+{
+    // schema check
+    if select
+        .fields
+        .iter()
+        .any(|f| !mdl.fields().st_contains(f.as_str()))
+    {
+        return Err(QueryError::QExecUnknownField);
+    }
+    match mdl.fields().st_get(order_by.field().as_str()) {
+        Some(field) if is_orderable(field.layers()[0].tag().tag_class()) => {}
+        Some(_) => return Err(QueryError::QExecDmlValidationError),
+        None => return Err(QueryError::QExecUnknownField),
+    }
+    let columns: Vec<&str> = if select.wildcard {
+        mdl.fields().stseq_ord_key().map(|k| k.as_ref()).collect()
+    } else {
+        select.fields.iter().map(|f| f.as_str()).collect()
+    };
+    let limit = select.limit as usize;
+    let mut heap = BinaryHeap::new();
+    if limit != 0 {
+        for (seq, (key, data)) in RowIteratorAll::new_after(g, mdl, usize::MAX, None).enumerate() {
+            let pkdc = VirtualDatacell::new_pk(key, mdl.p_tag());
+            let read = |field: &str| {
+                if field == mdl.p_key() {
+                    (*pkdc).clone()
+                } else {
+                    data.fields()
+                        .st_get(field)
+                        .cloned()
+                        .unwrap_or_else(Datacell::null)
+                }
+            };
+            let sort_key = read(order_by.field().as_str());
+            if heap.len() == limit {
+                // this row comes after all the others (including on ties, since it was scanned later)
+                let worst: &OrderedRow = heap.peek().unwrap();
+                if worst.cmp_key(&sort_key).is_le() {
+                    continue;
+                }
+                heap.pop();
+            }
+            heap.push(OrderedRow {
+                key: sort_key,
+                seq,
+                desc: order_by.is_desc(),
+                cells: columns.iter().map(|&c| read(c)).collect(),
+            });
+        }
+    }
+    f_mdl(serialize_target, mdl, columns.len());
+    let rows = heap.into_sorted_vec();
+    for row in rows.iter() {
+        for cell in row.cells.iter() {
+            f(serialize_target, cell, columns.len());
+        }
+    }
+    Ok(rows.len())
+}
+
+/// A row in an ordered scan
+struct OrderedRow {
+    /// the value of the field that the rows are ordered by
+    key: Datacell,
+    /// the position of the row in the scan
+    seq: usize,
+    desc: bool,
+    cells: Vec<Datacell>,
+}
+
+impl OrderedRow {
+    /// Compare this row's key with another key, in the requested direction
+    fn cmp_key(&self, key: &Datacell) -> Ordering {
+        let ord = compare_cells(&self.key, key);
+        if self.desc {
+            ord.reverse()
+        } else {
+            ord
+        }
+    }
+}
+
+impl PartialEq for OrderedRow {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for OrderedRow {}
+
+impl PartialOrd for OrderedRow {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedRow {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_key(&other.key).then(self.seq.cmp(&other.seq))
+    }
+}
+
+/*
+    cursors
+    ---
+    a cursor is the model's UUID followed by the last key that was returned; the key is 8B (LE) for integer
+    keys and the raw bytes otherwise. clients must treat it as opaque. an empty cursor starts a fresh scan
+*/
+
+fn cursor_encode(mdl: &ModelData, key: &PrimaryIndexKey) -> Vec<u8> {
+    let mut cursor = mdl.get_uuid().to_le_bytes().to_vec();
+    unsafe {
+        // UNSAFE(@ohsayan): +tagck
+        match key.tag() {
+            TagUnique::UnsignedInt | TagUnique::SignedInt => {
+                cursor.extend(key.read_uint().to_le_bytes())
+            }
+            TagUnique::Bin | TagUnique::Str => cursor.extend(key.read_bin()),
+            TagUnique::Illegal => unreachable!(),
+        }
+    }
+    cursor
+}
+
+fn cursor_decode(mdl: &ModelData, cursor: &[u8]) -> QueryResult<Option<PrimaryIndexKey>> {
+    if cursor.is_empty() {
+        return Ok(None);
+    }
+    let uuid = mdl.get_uuid().to_le_bytes();
+    if (cursor.len() < uuid.len()) || (cursor[..uuid.len()] != uuid) {
+        // this cursor belongs to some other model (or is just garbage)
+        return Err(QueryError::QExecDmlValidationError);
+    }
+    let key = &cursor[uuid.len()..];
+    let key = match mdl.p_tag().tag_unique() {
+        TagUnique::UnsignedInt => <[u8; 8]>::try_from(key)
+            .ok()
+            .map(|qw| Lit::new_uint(u64::from_le_bytes(qw))),
+        TagUnique::SignedInt => <[u8; 8]>::try_from(key)
+            .ok()
+            .map(|qw| Lit::new_sint(i64::from_le_bytes(qw))),
+        TagUnique::Bin => Some(Lit::new_bin(key)),
+        TagUnique::Str => str::from_utf8(key).ok().map(Lit::new_str),
+        TagUnique::Illegal => None,
+    };
+    key.and_then(PrimaryIndexKey::try_from_lit)
+        .map(Some)
+        .ok_or(QueryError::QExecDmlValidationError)
+}
+
+pub(in crate::engine::core) fn encode_cell(resp: &mut Vec<u8>, item: &Datacell) {
+    let ty = match item.tag().tag_selector() {
+        // the wire types of these don't follow their selectors
+        TagSelector::Timestamp => ResponseType::Timestamp.value_u8(),
+        TagSelector::Uuid => ResponseType::Uuid.value_u8(),
+        TagSelector::Dict => ResponseType::Dict.value_u8(),
+        selector => selector.value_u8() + 1,
+    };
+    resp.push(ty * (item.is_init() as u8));
     if item.is_null() {
         return;
     }
@@ -151,6 +442,8 @@ fn encode_cell(resp: &mut Vec<u8>, item: &Datacell) {
             TagClass::UnsignedInt => IntegerRepr::scoped(item.read_uint(), |b| resp.extend(b)),
             TagClass::SignedInt => IntegerRepr::scoped(item.read_sint(), |b| resp.extend(b)),
             TagClass::Float => resp.extend(item.read_float().to_string().as_bytes()),
+            TagClass::Timestamp => IntegerRepr::scoped(item.read_timestamp(), |b| resp.extend(b)),
+            TagClass::Uuid => return resp.extend(item.read_uuid().to_be_bytes()),
             TagClass::Bin | TagClass::Str => {
                 let slc = item.read_bin();
                 IntegerRepr::scoped(slc.len() as u64, |b| resp.extend(b));
@@ -168,70 +461,359 @@ fn encode_cell(resp: &mut Vec<u8>, item: &Datacell) {
                 }
                 return;
             }
+            TagClass::Dict => {
+                // [len]\n([key len]\n[key][value])*, with the keys in order
+                let dict = item.read_dict().read();
+                let mut entries: Vec<_> = dict.iter().collect();
+                entries.sort_unstable_by_key(|(k, _)| *k);
+                IntegerRepr::scoped(entries.len() as u64, |b| resp.extend(b));
+                resp.push(b'\n');
+                for (key, value) in entries {
+                    IntegerRepr::scoped(key.len() as u64, |b| resp.extend(b));
+                    resp.push(b'\n');
+                    resp.extend(key.as_bytes());
+                    encode_cell(resp, value);
+                }
+                return;
+            }
         }
     }
     resp.push(b'\n');
 }
 
-pub fn select_custom<F>(
+/// Run a select query. If the where clause has an equality predicate on the primary key, the row is looked
+/// up directly and `None` is returned. Otherwise, all the rows are scanned and filtered in which case
+/// `f_cols` is called once with the number of columns and the number of matched rows is returned.
+pub fn select_custom<Fc, F, T>(
     global: &impl GlobalInstanceLike,
     mut select: SelectStatement,
+    serialize_target: &mut T,
+    f_cols: Fc,
     mut cellfn: F,
-) -> QueryResult<()>
+) -> QueryResult<Option<usize>>
 where
-    F: FnMut(&Datacell),
+    Fc: FnOnce(&mut T, usize),
+    F: FnMut(&mut T, &Datacell),
 {
     global
         .state()
         .namespace()
         .with_model(select.entity(), |mdl| {
-            let target_key = mdl.resolve_where(select.clauses_mut())?;
-            let pkdc = VirtualDatacell::new(target_key.clone(), mdl.p_tag().tag_unique());
+            let mut wc = mdl.resolve_where(select.clauses_mut())?;
+            // schema check
+            if !select.is_wildcard()
+                && select
+                    .fields()
+                    .iter()
+                    .any(|f| !mdl.fields().st_contains(f.as_str()))
+            {
+                return Err(QueryError::QExecUnknownField);
+            }
             let g = sync::atm::cpin();
-            let mut read_field = |key, fields: &DcFieldIndex| {
-                match fields.st_get(key) {
-                    Some(dc) => cellfn(dc),
-                    None if key == mdl.p_key() => cellfn(&pkdc),
-                    None => return Err(QueryError::QExecUnknownField),
-                }
-                Ok(())
-            };
-            match mdl.primary_index().select(target_key.clone(), &g) {
-                Some(row) => {
-                    let r = row.resolve_schema_deltas_and_freeze(mdl.delta_state());
+            let mut read_row =
+                |target: &mut T, pk: &PrimaryIndexKey, fields: &DcFieldIndex| -> QueryResult<()> {
+                    let pkdc = VirtualDatacell::new_pk(pk, mdl.p_tag());
+                    let mut read_field = |key| {
+                        match fields.st_get(key) {
+                            Some(dc) => cellfn(target, dc),
+                            None if key == mdl.p_key() => cellfn(target, &pkdc),
+                            None => return Err(QueryError::QExecUnknownField),
+                        }
+                        Ok(())
+                    };
                     if select.is_wildcard() {
                         for key in mdl.fields().stseq_ord_key() {
-                            read_field(key.as_ref(), r.fields())?;
+                            read_field(key.as_ref())?;
                         }
                     } else {
-                        for key in select.into_fields() {
-                            read_field(key.as_str(), r.fields())?;
+                        for key in select.fields() {
+                            read_field(key.as_str())?;
+                        }
+                    }
+                    Ok(())
+                };
+            match wc.key() {
+                Some(target_key) => match mdl.primary_index().select(target_key, &g) {
+                    Some(row) => {
+                        let r = row.resolve_schema_deltas_and_freeze(mdl.delta_state());
+                        if !wc.row_matches(mdl, row.d_key(), &r) {
+                            return Err(QueryError::QExecDmlRowNotFound);
+                        }
+                        read_row(serialize_target, row.d_key(), r.fields())?;
+                        Ok(None)
+                    }
+                    None => Err(QueryError::QExecDmlRowNotFound),
+                },
+                None => {
+                    let col_c = if select.is_wildcard() {
+                        mdl.fields().len()
+                    } else {
+                        select.fields().len()
+                    };
+                    f_cols(serialize_target, col_c);
+                    let mut i = 0;
+                    for (key, data) in RowIteratorAll::new_scan(&g, mdl, usize::MAX, &mut wc) {
+                        if wc.row_matches(mdl, key, &data) {
+                            read_row(serialize_target, key, data.fields())?;
+                            i += 1;
+                        }
+                    }
+                    Ok(Some(i))
+                }
+            }
+        })
+}
+
+/*
+    aggregates
+*/
+
+/// Run a select with aggregates, returning one cell for each aggregate (in order). Nulls are skipped and
+/// every aggregate other than `count` is null if no values were seen
+pub fn select_aggregate(
+    global: &impl GlobalInstanceLike,
+    mut select: SelectStatement,
+) -> QueryResult<Vec<Datacell>> {
+    global
+        .state()
+        .namespace()
+        .with_model(select.entity(), |mdl| {
+            let mut aggregates = select
+                .aggregates()
+                .iter()
+                .map(|agg| AggregateState::new(mdl, agg))
+                .collect::<QueryResult<Vec<_>>>()?;
+            let mut wc = if select.clauses_mut().clauses_mut().is_empty() {
+                ResolvedWhere::all()
+            } else {
+                mdl.resolve_where(select.clauses_mut())?
+            };
+            let g = sync::atm::cpin();
+            match wc.key() {
+                Some(target_key) => {
+                    if let Some(row) = mdl.primary_index().select(target_key, &g) {
+                        let r = row.resolve_schema_deltas_and_freeze(mdl.delta_state());
+                        if wc.row_matches(mdl, row.d_key(), &r) {
+                            aggregate_row(mdl, &mut aggregates, row.d_key(), &r)?;
+                        }
+                    }
+                }
+                None => {
+                    for (key, data) in RowIteratorAll::new_scan(&g, mdl, usize::MAX, &mut wc) {
+                        if wc.row_matches(mdl, key, &data) {
+                            aggregate_row(mdl, &mut aggregates, key, &data)?;
                         }
                     }
                 }
-                None => return Err(QueryError::QExecDmlRowNotFound),
             }
-            Ok(())
+            Ok(aggregates.into_iter().map(|agg| agg.acc.finish()).collect())
         })
 }
 
+fn aggregate_row(
+    mdl: &ModelData,
+    aggregates: &mut [AggregateState],
+    pk: &PrimaryIndexKey,
+    data: &RowData,
+) -> QueryResult<()> {
+    let pkdc = VirtualDatacell::new_pk(pk, mdl.p_tag());
+    for agg in aggregates {
+        match agg.field {
+            // count(*)
+            None => agg.acc.accumulate(&pkdc)?,
+            Some(field) if field == mdl.p_key() => agg.acc.accumulate(&pkdc)?,
+            Some(field) => match data.fields().st_get(field) {
+                Some(dc) if !dc.is_null() => agg.acc.accumulate(dc)?,
+                // a null or an unresolved schema delta
+                _ => {}
+            },
+        }
+    }
+    Ok(())
+}
+
+struct AggregateState<'a> {
+    /// the field (`None` for `count(*)`)
+    field: Option<&'a str>,
+    acc: Accumulator,
+}
+
+impl<'a> AggregateState<'a> {
+    fn new(mdl: &ModelData, agg: &Aggregate<'a>) -> QueryResult<Self> {
+        let class = match agg.field() {
+            Some(field) => match mdl.fields().st_get(field.as_str()) {
+                Some(f) => Some(f.layers()[0].tag().tag_class()),
+                None => return Err(QueryError::QExecUnknownField),
+            },
+            None => None,
+        };
+        let acc = match (agg.func(), class) {
+            (AggregateFn::Count, _) => Accumulator::Count(0),
+            (AggregateFn::Sum, Some(TagClass::UnsignedInt)) => Accumulator::SumUInt(None),
+            (AggregateFn::Sum, Some(TagClass::SignedInt)) => Accumulator::SumSInt(None),
+            (AggregateFn::Sum, Some(TagClass::Float)) => Accumulator::SumFloat(None),
+            (
+                AggregateFn::Avg,
+                Some(TagClass::UnsignedInt | TagClass::SignedInt | TagClass::Float),
+            ) => Accumulator::Avg(0.0, 0),
+            (AggregateFn::Min, Some(class)) if is_orderable(class) => Accumulator::Min(None),
+            (AggregateFn::Max, Some(class)) if is_orderable(class) => Accumulator::Max(None),
+            // can't sum or average non-numeric fields and can't order lists or dicts
+            _ => return Err(QueryError::QExecDmlValidationError),
+        };
+        Ok(Self {
+            field: agg.field().map(|f| f.as_str()),
+            acc,
+        })
+    }
+}
+
+/// The running value of an aggregate. `None` means that no values were seen yet
+enum Accumulator {
+    Count(u64),
+    SumUInt(Option<u64>),
+    SumSInt(Option<i64>),
+    SumFloat(Option<f64>),
+    /// the running sum and count
+    Avg(f64, u64),
+    Min(Option<Datacell>),
+    Max(Option<Datacell>),
+}
+
+impl Accumulator {
+    /// Add a (non-null) value. Integer sums that overflow are an error
+    fn accumulate(&mut self, dc: &Datacell) -> QueryResult<()> {
+        unsafe {
+            // UNSAFE(@ohsayan): the class of the field was checked when the accumulator was created
+            match self {
+                Self::Count(c) => *c += 1,
+                Self::SumUInt(sum) => {
+                    *sum = Some(
+                        sum.unwrap_or(0)
+                            .checked_add(dc.read_uint())
+                            .ok_or(QueryError::QExecDmlValidationError)?,
+                    )
+                }
+                Self::SumSInt(sum) => {
+                    *sum = Some(
+                        sum.unwrap_or(0)
+                            .checked_add(dc.read_sint())
+                            .ok_or(QueryError::QExecDmlValidationError)?,
+                    )
+                }
+                Self::SumFloat(sum) => *sum = Some(sum.unwrap_or(0.0) + dc.read_float()),
+                Self::Avg(sum, c) => {
+                    *sum += match dc.kind() {
+                        TagClass::UnsignedInt => dc.read_uint() as f64,
+                        TagClass::SignedInt => dc.read_sint() as f64,
+                        _ => dc.read_float(),
+                    };
+                    *c += 1;
+                }
+                Self::Min(min) => Self::replace_if(min, dc, Ordering::Less),
+                Self::Max(max) => Self::replace_if(max, dc, Ordering::Greater),
+            }
+        }
+        Ok(())
+    }
+    fn replace_if(current: &mut Option<Datacell>, dc: &Datacell, ord: Ordering) {
+        let replace = match current {
+            Some(current) => compare_cells(dc, current) == ord,
+            None => true,
+        };
+        if replace {
+            *current = Some(dc.clone());
+        }
+    }
+    fn finish(self) -> Datacell {
+        match self {
+            Self::Count(c) => Datacell::new_uint_default(c),
+            Self::SumUInt(sum) => sum.map_or_else(Datacell::null, Datacell::new_uint_default),
+            Self::SumSInt(sum) => sum.map_or_else(Datacell::null, Datacell::new_sint_default),
+            Self::SumFloat(sum) => sum.map_or_else(Datacell::null, Datacell::new_float_default),
+            Self::Avg(_, 0) => Datacell::null(),
+            Self::Avg(sum, c) => Datacell::new_float_default(sum / c as f64),
+            Self::Min(dc) | Self::Max(dc) => dc.unwrap_or_else(Datacell::null),
+        }
+    }
+}
+
+/// Returns true if the values of a field of this class can be ordered with [`compare_cells`]. Lists and dicts
+/// have no order, so queries that need to order them are rejected when they are validated
+fn is_orderable(class: TagClass) -> bool {
+    !matches!(class, TagClass::List | TagClass::Dict)
+}
+
+/// Compare two cells of the same field, which must be of an orderable class (see [`is_orderable`]). Nulls come
+/// before all other values, booleans are ordered with `false` first, floats use the IEEE 754 total order (so
+/// `NaN`s are ordered too), strings and binary values are compared bytewise, timestamps chronologically and
+/// UUIDs by their 128-bit value
+fn compare_cells(a: &Datacell, b: &Datacell) -> Ordering {
+    match (a.is_null(), b.is_null()) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Less,
+        (false, true) => return Ordering::Greater,
+        (false, false) => {}
+    }
+    unsafe {
+        // UNSAFE(@ohsayan): +tagck
+        match (a.kind(), b.kind()) {
+            (TagClass::Bool, TagClass::Bool) => a.read_bool().cmp(&b.read_bool()),
+            (TagClass::UnsignedInt, TagClass::UnsignedInt) => a.read_uint().cmp(&b.read_uint()),
+            (TagClass::SignedInt, TagClass::SignedInt) => a.read_sint().cmp(&b.read_sint()),
+            (TagClass::Float, TagClass::Float) => a.read_float().total_cmp(&b.read_float()),
+            (TagClass::Bin, TagClass::Bin) | (TagClass::Str, TagClass::Str) => {
+                a.read_bin().cmp(b.read_bin())
+            }
+            (TagClass::Timestamp, TagClass::Timestamp) => {
+                a.read_timestamp().cmp(&b.read_timestamp())
+            }
+            (TagClass::Uuid, TagClass::Uuid) => a.read_uuid().cmp(&b.read_uuid()),
+            (a, b) => unreachable!("can't order a {a:?} and a {b:?}"),
+        }
+    }
+}
+
 struct RowIteratorAll<'g> {
     _g: &'g sync::atm::Guard,
     mdl: &'g ModelData,
-    iter: <IndexMTRaw<Row> as MTIndexExt<Row, PrimaryIndexKey, RowDataLck>>::IterEntry<'g, 'g, 'g>,
-    _latch: IndexLatchHandleExclusive<'g>,
+    iter: RowScan<'g>,
     limit: usize,
 }
 
 impl<'g> RowIteratorAll<'g> {
-    fn new(g: &'g sync::atm::Guard, mdl: &'g ModelData, limit: usize) -> Self {
+    /// Returns an iterator over the rows that follow the cursor (or over all the rows). Writers aren't held off
+    /// while a page is read, so a page only reflects the rows as they were when the scan reached them
+    fn new_after(
+        g: &'g sync::atm::Guard,
+        mdl: &'g ModelData,
+        limit: usize,
+        after: Option<PrimaryIndexKey>,
+    ) -> Self {
         let idx = mdl.primary_index();
-        let latch = idx.acquire_exclusive();
+        let iter = match after {
+            Some(key) => idx.select_after(key, g),
+            None => idx.select_range(Bound::Unbounded, Bound::Unbounded, g),
+        };
+        Self {
+            _g: g,
+            mdl,
+            iter: RowScan::Range(Box::new(iter)),
+            limit,
+        }
+    }
+    /// Returns an iterator over the rows that the where clause can match. Like [`Self::new_after`], writers
+    /// aren't held off, so every row is only read as it was when the scan reached it
+    fn new_scan(
+        g: &'g sync::atm::Guard,
+        mdl: &'g ModelData,
+        limit: usize,
+        wc: &mut ResolvedWhere,
+    ) -> Self {
         Self {
             _g: g,
             mdl,
-            iter: idx.__raw_index().mt_iter_entry(g),
-            _latch: latch,
+            iter: wc.scan(mdl, None, g),
             limit,
         }
     }
@@ -28,11 +28,17 @@ use {
     crate::{
         engine::{
             core::{
-                self, dml::QueryExecMeta, model::delta::DataDeltaKind,
+                dml::{self, batch::Undo, DeltaSink, ResolvedWhere, ReturnedRows},
+                index::{DcFieldIndex, RowData, SecondaryIndexesGuard},
+                model::{
+                    delta::{DataDelta, DataDeltaKind},
+                    Field, ModelData,
+                },
                 query_meta::AssignmentOperator,
             },
             data::{
                 cell::Datacell,
+                dict::DataDict,
                 lit::Lit,
                 tag::{DataTag, FloatSpec, SIntSpec, TagClass, UIntSpec},
             },
@@ -40,8 +46,11 @@ use {
             fractal::GlobalInstanceLike,
             idx::STIndex,
             net::protocol::Response,
-            ql::dml::upd::{AssignmentExpression, UpdateStatement},
-            sync,
+            ql::{
+                dml::upd::{AssignmentExpression, UpdateStatement},
+                lex::Ident,
+            },
+            sync::{self, atm::Guard},
         },
         util::compiler::{self, TaggedEnum},
     },
@@ -184,12 +193,23 @@ unsafe fn dc_op_str_add(dc: &Datacell, rhs: Lit) -> (bool, Datacell) {
     (true, Datacell::new_str(str.into_boxed_str()))
 }
 
+// timestamp
+unsafe fn dc_op_timestamp_ass(_: &Datacell, rhs: Lit) -> (bool, Datacell) {
+    (true, Datacell::new_timestamp(rhs.timestamp()))
+}
+// uuid
+unsafe fn dc_op_uuid_ass(_: &Datacell, rhs: Lit) -> (bool, Datacell) {
+    (true, Datacell::new_uuid(rhs.uuid()))
+}
+
 static OPERATOR: [unsafe fn(&Datacell, Lit) -> (bool, Datacell); {
-    TagClass::MAX_DSCR as usize * AssignmentOperator::VARIANT_COUNT
+    TagClass::List.value_word() * AssignmentOperator::VARIANT_COUNT
 }] = [
     // bool
     dc_op_bool_ass,
-    // -- pad: 4
+    // -- pad: 6
+    dc_op_fail,
+    dc_op_fail,
     dc_op_fail,
     dc_op_fail,
     dc_op_fail,
@@ -200,29 +220,60 @@ static OPERATOR: [unsafe fn(&Datacell, Lit) -> (bool, Datacell); {
     dc_op_uint_sub,
     dc_op_uint_mul,
     dc_op_uint_div,
+    // -- pad: 2
+    dc_op_fail,
+    dc_op_fail,
     // sint
     dc_op_sint_ass,
     dc_op_sint_add,
     dc_op_sint_sub,
     dc_op_sint_mul,
     dc_op_sint_div,
+    // -- pad: 2
+    dc_op_fail,
+    dc_op_fail,
     // float
     dc_op_float_ass,
     dc_op_float_add,
     dc_op_float_sub,
     dc_op_float_mul,
     dc_op_float_div,
+    // -- pad: 2
+    dc_op_fail,
+    dc_op_fail,
     // bin
     dc_op_bin_ass,
     dc_op_bin_add,
-    // -- pad: 3
+    // -- pad: 5
+    dc_op_fail,
+    dc_op_fail,
     dc_op_fail,
     dc_op_fail,
     dc_op_fail,
     // str
     dc_op_str_ass,
     dc_op_str_add,
-    // -- pad: 3
+    // -- pad: 5
+    dc_op_fail,
+    dc_op_fail,
+    dc_op_fail,
+    dc_op_fail,
+    dc_op_fail,
+    // timestamp
+    dc_op_timestamp_ass,
+    // -- pad: 6
+    dc_op_fail,
+    dc_op_fail,
+    dc_op_fail,
+    dc_op_fail,
+    dc_op_fail,
+    dc_op_fail,
+    // uuid
+    dc_op_uuid_ass,
+    // -- pad: 6
+    dc_op_fail,
+    dc_op_fail,
+    dc_op_fail,
     dc_op_fail,
     dc_op_fail,
     dc_op_fail,
@@ -252,124 +303,594 @@ pub fn collect_trace_path() -> Vec<&'static str> {
 }
 pub fn update_resp(
     global: &impl GlobalInstanceLike,
-    update: UpdateStatement,
+    mut update: UpdateStatement,
 ) -> QueryResult<Response> {
-    self::update(global, update).map(|_| Response::Empty)
+    let mut returned = ReturnedRows::new(update.take_returning());
+    self::update_returning(global, update, &mut returned)?;
+    Ok(returned.into_response(Response::Empty))
 }
 
-pub fn update(global: &impl GlobalInstanceLike, mut update: UpdateStatement) -> QueryResult<()> {
-    core::with_model_for_data_update(global, update.entity(), |mdl| {
-        let mut ret = Ok(QueryExecMeta::zero());
+#[cfg(test)]
+pub fn update(global: &impl GlobalInstanceLike, update: UpdateStatement) -> QueryResult<()> {
+    self::update_returning(global, update, &mut ReturnedRows::new(None))
+}
+
+fn update_returning<'a>(
+    global: &impl GlobalInstanceLike,
+    mut update: UpdateStatement<'a>,
+    returned: &mut ReturnedRows,
+) -> QueryResult<()> {
+    let (entity, durability) = (update.entity(), update.durability());
+    dml::with_model_for_write(global, entity, durability, |mdl, ctx| {
+        returned.validate(mdl)?;
         // prepare row fetch
-        let key = mdl.resolve_where(update.clauses_mut())?;
-        // fetch row
+        let mut wc = mdl.resolve_where(update.clauses_mut())?;
         let g = sync::atm::cpin();
-        let Some(row) = mdl.primary_index().select(key, &g) else {
-            return Err(QueryError::QExecDmlRowNotFound);
+        // lock the secondary indexes that the update changes (or reads) before locking any rows
+        let mut sidx = mdl.secondary_indexes().lock_affected(|idx| {
+            wc.index_field() == Some(idx.field())
+                || update
+                    .expressions()
+                    .iter()
+                    .any(|expr| expr.lhs.as_str() == idx.field())
+        });
+        let mut sink = ctx.sink();
+        update_with(
+            mdl,
+            &mut sidx,
+            &mut wc,
+            update.expressions(),
+            &mut sink,
+            returned,
+            &g,
+        )?;
+        sink.finish(&mut sidx, &g)
+    })
+}
+
+/// Apply the assignments to all the rows matching the resolved `where` clause. Either all rows are updated,
+/// or none are. The updated rows are added to `returned`. The caller must hold (at least) the secondary indexes
+/// on the assigned fields
+pub(super) fn update_with<'a>(
+    mdl: &ModelData,
+    sidx: &mut SecondaryIndexesGuard,
+    wc: &mut ResolvedWhere,
+    expressions: &[AssignmentExpression<'a>],
+    sink: &mut impl DeltaSink<'a>,
+    returned: &mut ReturnedRows,
+    g: &Guard,
+) -> QueryResult<usize> {
+    let ds = mdl.delta_state();
+    // fetch and lock rows
+    let mut targets = Vec::new();
+    match wc.key() {
+        Some(key) => loop {
+            let Some(row) = mdl.primary_index().select(key.clone(), g) else {
+                return Err(QueryError::QExecDmlRowNotFound);
+            };
+            drop(row.resolve_schema_deltas_and_freeze(ds));
+            // the conditions are checked under the row's lock, so nothing can change the row before it's updated
+            let row_data_wl = row.d_data().write();
+            if !mdl.primary_index().holds_row(row, g) {
+                // the row was removed or replaced while we were waiting for the lock, so we look it up again
+                continue;
+            }
+            if !wc.row_matches(mdl, row.d_key(), &row_data_wl) {
+                input_trace("precondition");
+                return Err(QueryError::QExecDmlPreconditionFailed);
+            }
+            targets.push((row, row_data_wl));
+            break;
+        },
+        None => {
+            returned.start_scan();
+            // the filters are checked under a shared lock, so only the rows that match are locked for writing
+            let matched: Vec<_> = wc
+                .scan(mdl, Some(sidx), g)
+                .filter(|row| {
+                    wc.row_matches(mdl, row.d_key(), &row.resolve_schema_deltas_and_freeze(ds))
+                })
+                .collect();
+            for row in matched {
+                let row_data_wl = row.d_data().write();
+                // the row might have been changed or removed since we scanned it
+                if mdl.primary_index().holds_row(row, g)
+                    && wc.row_matches(mdl, row.d_key(), &row_data_wl)
+                {
+                    targets.push((row, row_data_wl));
+                }
+            }
+            if targets.is_empty() {
+                return Err(QueryError::QExecDmlRowNotFound);
+            }
+        }
+    }
+    // create new version
+    let new_version = ds.create_new_data_delta_version();
+    // process changes
+    let old_values: Vec<_> = targets
+        .iter()
+        .map(|(_, row_data_wl)| sidx.values(row_data_wl.fields()))
+        .collect();
+    let mut rollback_data = Vec::with_capacity(targets.len());
+    let mut ret = Ok(());
+    for (_, row_data_wl) in targets.iter_mut() {
+        let (rollback, r) = apply_assignments(mdl, row_data_wl, expressions);
+        rollback_data.push(rollback);
+        if r.is_err() {
+            ret = r;
+            break;
+        }
+    }
+    // reindex
+    if ret.is_ok()
+        && !sidx.is_empty()
+        && !sidx.replace(
+            targets
+                .iter()
+                .map(|(row, row_data_wl)| (row.d_key(), row_data_wl.fields())),
+            &old_values,
+        )
+    {
+        input_trace("sidx;duplicate");
+        ret = Err(QueryError::QExecDmlDuplicate);
+    }
+    if compiler::unlikely(ret.is_err()) {
+        input_trace("rollback");
+        rollback_data.into_iter().zip(targets.iter_mut()).for_each(
+            |(rollback, (_, row_data_wl))| {
+                rollback
+                    .into_iter()
+                    .rev()
+                    .for_each(|undo| undo.apply(row_data_wl.fields_mut()))
+            },
+        );
+        ret.map(|_| 0)
+    } else {
+        let mut dp = 0;
+        for ((row, mut row_data_wl), rollback) in targets.into_iter().zip(rollback_data) {
+            returned.push(mdl, row.d_key(), row_data_wl.fields());
+            // update revised tag
+            let old_version = row_data_wl.get_txn_revised();
+            row_data_wl.set_txn_revised(new_version);
+            // publish delta
+            dp = sink.publish(
+                ds,
+                DataDelta::new(new_version, row.clone(), DataDeltaKind::Update),
+                Undo::Update(rollback, old_version),
+                g,
+            );
+        }
+        Ok(dp)
+    }
+}
+
+/// The data needed to roll back a single assignment
+pub(super) enum AssignmentUndo<'a> {
+    /// restore the previous value of the field
+    Restore(&'a str, Datacell),
+    /// remove the element that was appended to the list
+    ListPop(&'a str),
+    /// restore the previous value of the element at the index in the list
+    ListRestore(&'a str, usize, Datacell),
+    /// insert the element that was removed at the index back into the list
+    ListInsert(&'a str, usize, Datacell),
+    /// append the elements that were truncated back to the list
+    ListExtend(&'a str, Vec<Datacell>),
+    /// restore the previous value at the path in a dict field (or remove the key if it wasn't set)
+    DictRestore(&'a str, Box<[Ident<'a>]>, Option<Datacell>),
+}
+
+impl<'a> AssignmentUndo<'a> {
+    pub(super) fn apply(self, fields: &mut DcFieldIndex) {
+        match self {
+            Self::Restore(field_id, restored_data) => {
+                fields.st_update(field_id, restored_data);
+            }
+            Self::ListPop(field_id) => {
+                if let Some(list) = fields.st_get_mut(field_id) {
+                    unsafe {
+                        // UNSAFE(@ohsayan): we only record this for lists
+                        list.read_list().write().pop();
+                    }
+                }
+            }
+            Self::ListRestore(field_id, index, restored_data) => {
+                if let Some(list) = fields.st_get_mut(field_id) {
+                    unsafe {
+                        // UNSAFE(@ohsayan): we only record this for lists
+                        list.read_list().write()[index] = restored_data;
+                    }
+                }
+            }
+            Self::ListInsert(field_id, index, restored_data) => {
+                if let Some(list) = fields.st_get_mut(field_id) {
+                    unsafe {
+                        // UNSAFE(@ohsayan): we only record this for lists
+                        list.read_list().write().insert(index, restored_data);
+                    }
+                }
+            }
+            Self::ListExtend(field_id, restored_data) => {
+                if let Some(list) = fields.st_get_mut(field_id) {
+                    unsafe {
+                        // UNSAFE(@ohsayan): we only record this for lists
+                        list.read_list().write().extend(restored_data);
+                    }
+                }
+            }
+            Self::DictRestore(field_id, path, restored_data) => {
+                if let Some(mut dc) = fields.st_get_mut(field_id) {
+                    let (key, parents) = path.split_last().unwrap();
+                    unsafe {
+                        // UNSAFE(@ohsayan): the path was walked through dicts when the assignment was applied
+                        for parent in parents {
+                            dc = dc.read_dict_mut().get_mut(parent.as_str()).unwrap();
+                        }
+                        let dict = dc.read_dict_mut();
+                        match restored_data {
+                            Some(restored_data) => {
+                                dict.insert(key.as_str().into(), restored_data);
+                            }
+                            None => {
+                                dict.remove(key.as_str());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Apply an assignment to the value at the path in a dict field. For `=`, missing dicts on the path are created
+/// (and a null field is replaced with a new dict). Returns the data needed to roll back the assignment or `None`
+/// if it is invalid, in which case nothing was changed
+fn apply_dict_assignment<'a>(
+    field: &Field,
+    field_data: &mut Datacell,
+    lhs: &'a str,
+    path: &[Ident<'a>],
+    rhs: Lit,
+    operator_fn: AssignmentOperator,
+) -> Option<AssignmentUndo<'a>> {
+    let layers = field.layers();
+    if layers[0].tag().tag_class() != TagClass::Dict {
+        return None;
+    }
+    let is_assign = operator_fn == AssignmentOperator::Assign;
+    if field_data.is_null() {
+        if !is_assign {
+            return None;
+        }
+        let mut new = new_dict_path(path, rhs);
+        return Field::rvt_data(layers, &mut new)
+            .then(|| AssignmentUndo::Restore(lhs, mem::replace(field_data, new)));
+    }
+    let mut dc = field_data;
+    let mut depth = 0;
+    loop {
+        let dict = unsafe {
+            // UNSAFE(@ohsayan): we only walk through non-null dicts
+            dc.read_dict_mut()
         };
-        // lock row
-        let mut row_data_wl = row.d_data().write();
-        // create new version
-        let ds = mdl.delta_state();
-        let new_version = ds.create_new_data_delta_version();
-        // process changes
-        let mut rollback_now = false;
-        let mut rollback_data = Vec::with_capacity(update.expressions().len());
-        let mut assn_expressions = update.into_expressions().into_iter();
-        /*
-            FIXME(@ohsayan): where's my usual magic? I'll do it once we have the SE stabilized
-        */
-        // apply changes
-        while (assn_expressions.len() != 0) & (!rollback_now) {
-            let AssignmentExpression {
+        let key = path[depth].as_str();
+        let is_leaf = depth + 1 == path.len();
+        if !is_leaf && dict.contains_key(key) {
+            let nested = dict.get_mut(key).unwrap();
+            if (nested.kind() != TagClass::Dict) | nested.is_null() {
+                return None;
+            }
+            dc = nested;
+            depth += 1;
+            continue;
+        }
+        // the layers of the values in this dict (empty if they are dynamically typed)
+        let value_layers = layers.get(depth + 1..).unwrap_or(&[]);
+        let mut new = match (dict.get(key), is_leaf) {
+            (_, false) if is_assign => new_dict_path(&path[depth + 1..], rhs),
+            (_, true) if is_assign => Datacell::from(rhs),
+            (Some(current), true)
+                if current.is_init()
+                    & (current.kind() == rhs.kind().tag_class())
+                    & (current.kind() < TagClass::List) =>
+            {
+                let (okay, new) = unsafe {
+                    // UNSAFE(@ohsayan): matched tags
+                    OPERATOR[opc(current.kind(), operator_fn)](current, rhs)
+                };
+                if !okay {
+                    return None;
+                }
+                new
+            }
+            _ => return None,
+        };
+        if !(value_layers.is_empty() || Field::rvt_data(value_layers, &mut new)) {
+            return None;
+        }
+        let len_okay = dict.contains_key(key)
+            || layers.get(depth).map_or(true, |layer| {
+                layer
+                    .props()
+                    .map_or(true, |props| props.check_len(dict.len() as u64 + 1))
+            });
+        if !len_okay {
+            return None;
+        }
+        let restored = dict.insert(key.into(), new);
+        return Some(AssignmentUndo::DictRestore(
+            lhs,
+            path[..=depth].into(),
+            restored,
+        ));
+    }
+}
+
+/// Create the nested dicts for the keys in the path, with the value at the end of it
+fn new_dict_path(path: &[Ident], value: Lit) -> Datacell {
+    path.iter().rev().fold(Datacell::from(value), |value, key| {
+        let mut dict = DataDict::new();
+        dict.insert(key.as_str().into(), value);
+        Datacell::new_dict(dict)
+    })
+}
+
+/// Apply a list operation other than an append to a non-null list field: an assignment to the element at the
+/// index (`x[i] += y`), a removal by value (`x -= y`) or by index (`remove x[i]`) or a truncation (`truncate x to n`).
+/// Returns the data needed to roll back the operation or `None` if it is invalid, in which case nothing was changed
+fn apply_list_assignment<'a>(
+    field: &Field,
+    field_data: &mut Datacell,
+    lhs: &'a str,
+    index: Option<i64>,
+    rhs: Lit,
+    operator_fn: AssignmentOperator,
+) -> Option<AssignmentUndo<'a>> {
+    let layers = field.layers();
+    if (layers[0].tag().tag_class() != TagClass::List) | field_data.is_null() {
+        return None;
+    }
+    let mut list = unsafe {
+        // UNSAFE(@ohsayan): +tagck
+        field_data.read_list().write()
+    };
+    let len_okay = |len: usize| {
+        layers[0]
+            .props()
+            .map_or(true, |props| props.check_len(len as u64))
+    };
+    match (index, operator_fn) {
+        (Some(index), operator_fn) if operator_fn < AssignmentOperator::Remove => {
+            let index = list_index(index, list.len())?;
+            let current = &list[index];
+            if (current.kind() != rhs.kind().tag_class()) | (current.kind() >= TagClass::List) {
+                return None;
+            }
+            let (okay, mut new) = unsafe {
+                // UNSAFE(@ohsayan): matched tags
+                OPERATOR[opc(current.kind(), operator_fn)](current, rhs)
+            };
+            if !(okay && Field::rvt_data(&layers[1..], &mut new)) {
+                return None;
+            }
+            input_trace("list;index");
+            Some(AssignmentUndo::ListRestore(
                 lhs,
+                index,
+                mem::replace(&mut list[index], new),
+            ))
+        }
+        (None, AssignmentOperator::SubAssign)
+            if layers[1].tag().tag_class() == rhs.kind().tag_class() =>
+        {
+            let value = Datacell::from(rhs);
+            if !len_okay(list.iter().filter(|element| **element != value).count()) {
+                return None;
+            }
+            let restored = list.clone();
+            list.retain(|element| *element != value);
+            input_trace("list;remove");
+            Some(AssignmentUndo::Restore(lhs, Datacell::new_list(restored)))
+        }
+        (None, AssignmentOperator::Remove) => {
+            let index = rhs
+                .try_uint()
+                .and_then(|index| i64::try_from(index).ok())
+                .or_else(|| rhs.try_sint())?;
+            let index = list_index(index, list.len())?;
+            if !len_okay(list.len() - 1) {
+                return None;
+            }
+            input_trace("list;remove");
+            Some(AssignmentUndo::ListInsert(lhs, index, list.remove(index)))
+        }
+        (None, AssignmentOperator::Truncate) => {
+            let len = list
+                .len()
+                .min(usize::try_from(rhs.try_uint()?).unwrap_or(usize::MAX));
+            if !len_okay(len) {
+                return None;
+            }
+            input_trace("list;truncate");
+            Some(AssignmentUndo::ListExtend(lhs, list.split_off(len)))
+        }
+        _ => None,
+    }
+}
+
+/// Resolve an index into a list of the given length, where negative indexes count from the end of the list
+fn list_index(index: i64, len: usize) -> Option<usize> {
+    let index = if index < 0 {
+        len.checked_sub(index.unsigned_abs() as usize)?
+    } else {
+        index as usize
+    };
+    (index < len).then_some(index)
+}
+
+/// Apply the assignment expressions to the given row. The data needed to roll back the
+/// (possibly partially applied) changes is always returned, alongside the result
+fn apply_assignments<'a>(
+    mdl: &ModelData,
+    row_data_wl: &mut RowData,
+    expressions: &[AssignmentExpression<'a>],
+) -> (Vec<AssignmentUndo<'a>>, QueryResult<()>) {
+    let mut ret = Ok(());
+    let mut rollback_data = Vec::with_capacity(expressions.len());
+    let mut assn_expressions = expressions.iter();
+    /*
+        FIXME(@ohsayan): where's my usual magic? I'll do it once we have the SE stabilized
+    */
+    // apply changes
+    while assn_expressions.len() != 0 {
+        let AssignmentExpression {
+            lhs,
+            path,
+            index,
+            rhs,
+            operator_fn,
+        } = unsafe {
+            // UNSAFE(@ohsayan): pre-loop cond
+            assn_expressions.next().unwrap_unchecked()
+        };
+        let operator_fn = *operator_fn;
+        let rhs = rhs.clone();
+        let field_definition;
+        let field_data;
+        match (
+            mdl.fields().st_get(lhs.as_str()),
+            row_data_wl.fields_mut().st_get_mut(lhs.as_str()),
+        ) {
+            (Some(fdef), Some(fdata)) => {
+                field_definition = fdef;
+                field_data = fdata;
+            }
+            _ => {
+                input_trace("fieldnotfound");
+                ret = Err(QueryError::QExecUnknownField);
+                break;
+            }
+        }
+        if !path.is_empty() {
+            match apply_dict_assignment(
+                field_definition,
+                field_data,
+                lhs.as_str(),
+                path,
                 rhs,
                 operator_fn,
-            } = unsafe {
-                // UNSAFE(@ohsayan): pre-loop cond
-                assn_expressions.next().unwrap_unchecked()
-            };
-            let field_definition;
-            let field_data;
-            match (
-                mdl.fields().st_get(lhs.as_str()),
-                row_data_wl.fields_mut().st_get_mut(lhs.as_str()),
             ) {
-                (Some(fdef), Some(fdata)) => {
-                    field_definition = fdef;
-                    field_data = fdata;
+                Some(undo) => {
+                    input_trace("dict;path");
+                    rollback_data.push(undo);
                 }
-                _ => {
-                    input_trace("fieldnotfound");
-                    rollback_now = true;
-                    ret = Err(QueryError::QExecUnknownField);
+                None => {
+                    input_trace("dict;badvalue");
+                    ret = Err(QueryError::QExecDmlValidationError);
                     break;
                 }
             }
-            match (
-                field_definition.layers()[0].tag().tag_class(),
-                rhs.kind().tag_class(),
+            continue;
+        }
+        let is_list = field_definition.layers()[0].tag().tag_class() == TagClass::List;
+        let is_list_op = matches!(
+            operator_fn,
+            AssignmentOperator::SubAssign
+                | AssignmentOperator::Remove
+                | AssignmentOperator::Truncate
+        );
+        if index.is_some() | (is_list & is_list_op) {
+            match apply_list_assignment(
+                field_definition,
+                field_data,
+                lhs.as_str(),
+                *index,
+                rhs,
+                operator_fn,
             ) {
-                (tag_a, tag_b)
-                    if (tag_a == tag_b) & (tag_a < TagClass::List) & field_data.is_init() =>
-                {
-                    let (okay, new) = unsafe { OPERATOR[opc(tag_a, operator_fn)](field_data, rhs) };
-                    rollback_now &= !okay;
-                    rollback_data.push((lhs.as_str(), mem::replace(field_data, new)));
-                    input_trace("sametag;nonnull");
+                Some(undo) => rollback_data.push(undo),
+                None => {
+                    input_trace("list;badvalue");
+                    ret = Err(QueryError::QExecDmlValidationError);
+                    break;
                 }
-                (tag_a, tag_b)
-                    if (tag_a == tag_b)
-                        & field_data.is_null()
-                        & (operator_fn == AssignmentOperator::Assign) =>
-                {
-                    rollback_data.push((lhs.as_str(), mem::replace(field_data, rhs.into())));
-                    input_trace("sametag;orignull");
+            }
+            continue;
+        }
+        match (
+            field_definition.layers()[0].tag().tag_class(),
+            rhs.kind().tag_class(),
+        ) {
+            (tag_a, tag_b)
+                if (tag_a == tag_b) & (tag_a < TagClass::List) & field_data.is_init() =>
+            {
+                let (okay, new) = unsafe { OPERATOR[opc(tag_a, operator_fn)](field_data, rhs) };
+                let okay = okay && field_definition.layers()[0].check_props(&new);
+                rollback_data.push(AssignmentUndo::Restore(
+                    lhs.as_str(),
+                    mem::replace(field_data, new),
+                ));
+                input_trace("sametag;nonnull");
+                if !okay {
+                    input_trace("badvalue");
+                    ret = Err(QueryError::QExecDmlValidationError);
+                    break;
                 }
-                (TagClass::List, tag_b) if operator_fn == AssignmentOperator::AddAssign => {
-                    if field_definition.layers()[1].tag().tag_class() == tag_b {
-                        unsafe {
-                            // UNSAFE(@ohsayan): matched tags
-                            let mut list = field_data.read_list().write();
-                            if list.try_reserve(1).is_ok() {
-                                input_trace("list;sametag");
-                                list.push(rhs.into());
-                            } else {
-                                rollback_now = true;
-                                ret = Err(QueryError::SysOutOfMemory);
-                                break;
-                            }
+            }
+            (tag_a, tag_b)
+                if (tag_a == tag_b)
+                    & field_data.is_null()
+                    & (operator_fn == AssignmentOperator::Assign) =>
+            {
+                let new: Datacell = rhs.into();
+                let okay = field_definition.layers()[0].check_props(&new);
+                rollback_data.push(AssignmentUndo::Restore(
+                    lhs.as_str(),
+                    mem::replace(field_data, new),
+                ));
+                input_trace("sametag;orignull");
+                if !okay {
+                    input_trace("badvalue");
+                    ret = Err(QueryError::QExecDmlValidationError);
+                    break;
+                }
+            }
+            (TagClass::List, tag_b) if operator_fn == AssignmentOperator::AddAssign => {
+                if field_definition.layers()[1].tag().tag_class() == tag_b {
+                    unsafe {
+                        // UNSAFE(@ohsayan): matched tags
+                        let mut list = field_data.read_list().write();
+                        let new: Datacell = rhs.into();
+                        let len_okay = field_definition.layers()[0]
+                            .props()
+                            .map_or(true, |props| props.check_len(list.len() as u64 + 1));
+                        if !(len_okay && field_definition.layers()[1].check_props(&new)) {
+                            input_trace("list;badvalue");
+                            ret = Err(QueryError::QExecDmlValidationError);
+                            break;
+                        }
+                        if list.try_reserve(1).is_ok() {
+                            input_trace("list;sametag");
+                            list.push(new);
+                            rollback_data.push(AssignmentUndo::ListPop(lhs.as_str()));
+                        } else {
+                            ret = Err(QueryError::SysOutOfMemory);
+                            break;
                         }
-                    } else {
-                        input_trace("list;badtag");
-                        rollback_now = true;
-                        ret = Err(QueryError::QExecDmlValidationError);
-                        break;
                     }
-                }
-                _ => {
-                    input_trace("unknown_reason;exitmainloop");
+                } else {
+                    input_trace("list;badtag");
                     ret = Err(QueryError::QExecDmlValidationError);
-                    rollback_now = true;
                     break;
                 }
             }
+            _ => {
+                input_trace("unknown_reason;exitmainloop");
+                ret = Err(QueryError::QExecDmlValidationError);
+                break;
+            }
         }
-        if compiler::unlikely(rollback_now) {
-            input_trace("rollback");
-            rollback_data
-                .into_iter()
-                .for_each(|(field_id, restored_data)| {
-                    row_data_wl.fields_mut().st_update(field_id, restored_data);
-                });
-        } else {
-            // update revised tag
-            row_data_wl.set_txn_revised(new_version);
-            // publish delta
-            let dp =
-                ds.append_new_data_delta_with(DataDeltaKind::Update, row.clone(), new_version, &g);
-            ret = Ok(QueryExecMeta::new(dp))
-        }
-        ret
-    })
+    }
+    (rollback_data, ret)
 }
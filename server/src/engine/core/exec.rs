@@ -25,14 +25,27 @@
 */
 
 use crate::engine::{
-    core::{ddl_misc, dml, model::ModelData, space::Space},
+    core::{ddl_misc, dml, model::ModelData, space::Space, system_db::Privilege, EntityIDRef},
+    data::cell::Datacell,
     error::{QueryError, QueryResult},
     fractal::{Global, GlobalInstanceLike},
     net::protocol::{ClientLocalState, Response, ResponseType, SQuery},
     ql::{
         ast::{traits::ASTNode, InplaceData, State},
-        ddl::Use,
-        lex::KeywordStmt,
+        ddl::{
+            alt::AlterModel,
+            crt::{CreateIndex, CreateModel},
+            drop::{DropIndex, DropModel},
+            Describe, Inspect, TruncateModel, Use,
+        },
+        dml::{
+            del::DeleteStatement,
+            ins::InsertStatement,
+            sel::{ExistsStatement, SelectAllStatement, SelectStatement},
+            sub::SubscribeStatement,
+            upd::UpdateStatement,
+        },
+        lex::{KeywordStmt, Token},
     },
 };
 
@@ -55,6 +68,18 @@ pub async fn dispatch_to_executor<'a>(
         core::mem::transmute(cstate.get_cs())
     });
     let stmt = state.try_statement()?;
+    if stmt.is_batch_control() {
+        return run_batch_control(global, cstate, &state, stmt).await;
+    }
+    authorize(global, cstate, &tokens)?;
+    if let Some(batch) = cstate.batch_mut() {
+        // stage the statement; it will only be executed on commit
+        batch.stage(&mut state, stmt, query.query(), query.params())?;
+        return Ok(Response::Empty);
+    }
+    if stmt == KeywordStmt::Subscribe {
+        return start_subscription(global, cstate, state).await;
+    }
     if stmt.is_blocking() {
         run_blocking_stmt(global, cstate, state, stmt).await
     } else {
@@ -62,6 +87,164 @@ pub async fn dispatch_to_executor<'a>(
     }
 }
 
+/*
+    authorization
+*/
+
+/// What a statement needs the current user to have been granted before it can run
+enum Access<'a> {
+    /// nothing (every user can run it)
+    Anyone,
+    /// only the root account can run it
+    Root,
+    /// all these privileges on the model
+    Model(EntityIDRef<'a>, &'static [Privilege]),
+    /// any privilege on the space or model, to see its definition
+    Visible(&'a str, Option<&'a str>),
+}
+
+/// Check if the current user has the privilege needed to run this statement. The root account can run anything,
+/// while every other account needs a grant (to them or to one of their roles) on the target space or model.
+///
+/// NB: the statement is parsed here to find its target, and is parsed again when it is run
+pub(super) fn authorize(
+    global: &impl GlobalInstanceLike,
+    cstate: &ClientLocalState,
+    tokens: &[Token],
+) -> QueryResult<()> {
+    if cstate.is_root() {
+        return Ok(());
+    }
+    let mut state = State::new_inplace(tokens);
+    let stmt = state.try_statement()?;
+    if !stmt.is_blocking() {
+        // NB: DDL never uses the current space
+        state.set_space_maybe(unsafe {
+            // UNSAFE(@ohsayan): the state (and the statement) don't outlive this call
+            core::mem::transmute(cstate.get_cs())
+        });
+    }
+    let sys_db = global.state().namespace().sys_db();
+    let okay = match required_access(&mut state, stmt)? {
+        Access::Anyone => true,
+        Access::Root => false,
+        Access::Model(model, privileges) => privileges.iter().all(|p| {
+            sys_db.has_privilege(cstate.username(), model.space(), Some(model.entity()), *p)
+        }),
+        Access::Visible(space, model) => sys_db.can_see(cstate.username(), space, model),
+    };
+    if okay {
+        Ok(())
+    } else {
+        Err(QueryError::SysPermissionDenied)
+    }
+}
+
+fn required_access<'a>(
+    state: &mut State<'a, InplaceData>,
+    stmt: KeywordStmt,
+) -> QueryResult<Access<'a>> {
+    let access = match stmt {
+        // sysctl enforces its own permissions, and `use` only sets the current space
+        KeywordStmt::Sysctl | KeywordStmt::Use => Access::Anyone,
+        // every statement in a batch is authorized when it is staged
+        KeywordStmt::Begin | KeywordStmt::Commit | KeywordStmt::Rollback => Access::Anyone,
+        KeywordStmt::Inspect => match Inspect::parse_from_state_hardened(state)? {
+            // NB: only root is shown the users
+            Inspect::Global => Access::Anyone,
+            Inspect::Space(space) => Access::Visible(space.as_str(), None),
+            Inspect::Model(model) => Access::Visible(model.space(), Some(model.entity())),
+        },
+        KeywordStmt::Describe => match Describe::parse_from_state_hardened(state)? {
+            Describe::Space(space) => Access::Visible(space.as_str(), None),
+            Describe::Model(model) => Access::Visible(model.space(), Some(model.entity())),
+        },
+        KeywordStmt::Select if state.cursor_rounded_eq(Token![all]) => {
+            state.cursor_ahead();
+            let select = SelectAllStatement::parse_from_state_hardened(state)?;
+            Access::Model(select.entity, &[Privilege::Select])
+        }
+        KeywordStmt::Select => {
+            let select = SelectStatement::parse_from_state_hardened(state)?;
+            Access::Model(select.entity(), &[Privilege::Select])
+        }
+        KeywordStmt::Exists => {
+            let exists = ExistsStatement::parse_from_state_hardened(state)?;
+            Access::Model(exists.entity(), &[Privilege::Select])
+        }
+        KeywordStmt::Insert => {
+            let insert = InsertStatement::parse_from_state_hardened(state)?;
+            Access::Model(insert.entity(), &[Privilege::Insert])
+        }
+        KeywordStmt::Upsert => {
+            let upsert = InsertStatement::parse_from_state_hardened(state)?;
+            Access::Model(upsert.entity(), &[Privilege::Insert, Privilege::Update])
+        }
+        KeywordStmt::Update => {
+            let update = UpdateStatement::parse_from_state_hardened(state)?;
+            Access::Model(update.entity(), &[Privilege::Update])
+        }
+        KeywordStmt::Delete => {
+            let delete = DeleteStatement::parse_from_state_hardened(state)?;
+            Access::Model(delete.entity(), &[Privilege::Delete])
+        }
+        // a subscriber sees all the data that is written to the model
+        KeywordStmt::Subscribe => {
+            let subscribe = SubscribeStatement::parse_from_state_hardened(state)?;
+            Access::Model(subscribe.entity(), &[Privilege::Select])
+        }
+        // NB: space DDL is reserved for root
+        KeywordStmt::Create => match state.current().first() {
+            Some(Token![model]) => {
+                state.cursor_ahead();
+                let create = CreateModel::parse_from_state_hardened(state)?;
+                Access::Model(create.model_name, &[Privilege::Ddl])
+            }
+            // NB: a `create index` is parsed from the `index` keyword (or the `unique` ident)
+            Some(Token![index]) => {
+                let create = CreateIndex::parse_from_state_hardened(state)?;
+                Access::Model(create.model_name, &[Privilege::Ddl])
+            }
+            Some(tok) if tok.ident_eq("unique") => {
+                let create = CreateIndex::parse_from_state_hardened(state)?;
+                Access::Model(create.model_name, &[Privilege::Ddl])
+            }
+            _ => Access::Root,
+        },
+        KeywordStmt::Alter => match state.current().first() {
+            Some(Token![model]) => {
+                state.cursor_ahead();
+                let alter = AlterModel::parse_from_state_hardened(state)?;
+                Access::Model(alter.model, &[Privilege::Ddl])
+            }
+            _ => Access::Root,
+        },
+        KeywordStmt::Drop => match state.current().first() {
+            Some(Token![model]) => {
+                state.cursor_ahead();
+                let drop = DropModel::parse_from_state_hardened(state)?;
+                Access::Model(drop.entity, &[Privilege::Ddl])
+            }
+            Some(Token![index]) => {
+                state.cursor_ahead();
+                let drop = DropIndex::parse_from_state_hardened(state)?;
+                Access::Model(drop.model, &[Privilege::Ddl])
+            }
+            _ => Access::Root,
+        },
+        // a truncate only removes data, so it needs the same privilege as a delete
+        KeywordStmt::Truncate => match state.current().first() {
+            Some(Token![model]) => {
+                state.cursor_ahead();
+                let truncate = TruncateModel::parse_from_state_hardened(state)?;
+                Access::Model(truncate.entity, &[Privilege::Delete])
+            }
+            _ => Access::Root,
+        },
+    };
+    Ok(access)
+}
+
 fn _callgs_map<A: ASTNode<'static> + core::fmt::Debug, T>(
     g: &Global,
     state: &mut State<'static, InplaceData>,
@@ -107,10 +290,7 @@ async fn run_blocking_stmt(
     mut state: State<'_, InplaceData>,
     stmt: KeywordStmt,
 ) -> Result<Response, QueryError> {
-    if !(cstate.is_root() | (stmt == KeywordStmt::Sysctl)) {
-        // all the actions here need root permission (but we do an exception for sysctl which allows status to be called by anyone)
-        return Err(QueryError::SysPermissionDenied);
-    }
+    // NB: permissions have already been checked by `authorize`
     state.ensure_minimum_for_blocking_stmt()?;
     /*
         IMPORTANT: DDL queries will NOT pick up the currently set space. instead EVERY DDL query must manually fully specify the entity that
@@ -122,6 +302,7 @@ async fn run_blocking_stmt(
     let create = stmt == KeywordStmt::Create;
     let alter = stmt == KeywordStmt::Alter;
     let drop = stmt == KeywordStmt::Drop;
+    let truncate = stmt == KeywordStmt::Truncate;
     let last_id = b.is_ident();
     let last_allow = Token![allow].eq(b);
     let last_if = Token![if].eq(b);
@@ -131,13 +312,19 @@ async fn run_blocking_stmt(
     let a_m = (alter & Token![model].eq(a) & last_id) as u8 * 5;
     let d_s = (drop & Token![space].eq(a) & (last_id | last_allow | last_if)) as u8 * 6;
     let d_m = (drop & Token![model].eq(a) & (last_id | last_allow | last_if)) as u8 * 7;
-    let fc = sysctl as u8 | c_s | c_m | a_s | a_m | d_s | d_m;
-    state.cursor_ahead_if(!sysctl);
+    // NB: a `create index` is parsed from the `index` keyword (or the `unique` ident)
+    let c_i = create
+        & ((Token![index].eq(a) & (last_id | last_if))
+            | (a.ident_eq("unique") & Token![index].eq(b)));
+    let d_i = (drop & Token![index].eq(a) & (last_id | last_if)) as u8 * 9;
+    let t_m = (truncate & Token![model].eq(a) & last_id) as u8 * 10;
+    let fc = sysctl as u8 | c_s | c_m | a_s | a_m | d_s | d_m | (c_i as u8 * 8) | d_i | t_m;
+    state.cursor_ahead_if(!(sysctl | c_i));
     static BLK_EXEC: [fn(
         Global,
         &ClientLocalState,
         &mut State<'static, InplaceData>,
-    ) -> QueryResult<Response>; 8] = [
+    ) -> QueryResult<Response>; 11] = [
         |_, _, _| Err(QueryError::QLUnknownStatement),
         blocking_exec_sysctl,
         |g, _, t| {
@@ -171,6 +358,27 @@ async fn run_blocking_stmt(
                 translate_ddl_result,
             )
         },
+        |g, _, t| {
+            _callgs_map(
+                &g,
+                t,
+                ModelData::transactional_exec_create_index,
+                translate_ddl_result,
+            )
+        },
+        |g, _, t| {
+            _callgs_map(
+                &g,
+                t,
+                ModelData::transactional_exec_drop_index,
+                translate_ddl_result,
+            )
+        },
+        |g, _, t| {
+            _callgs_map(&g, t, ModelData::transactional_exec_truncate, |_| {
+                Response::Empty
+            })
+        },
     ];
     let r = unsafe {
         // UNSAFE(@ohsayan): the only await is within this block
@@ -195,6 +403,75 @@ fn blocking_exec_sysctl(
     super::dcl::exec(g, cstate, r).map(|_| Response::Empty)
 }
 
+/*
+    batch control
+*/
+
+async fn run_batch_control(
+    global: &Global,
+    cstate: &mut ClientLocalState,
+    state: &State<'_, InplaceData>,
+    stmt: KeywordStmt,
+) -> QueryResult<Response> {
+    if !state.exhausted() {
+        return Err(QueryError::QLInvalidSyntax);
+    }
+    match (stmt, cstate.take_batch()) {
+        (KeywordStmt::Begin, None) => {
+            let batch = dml::StagedBatch::new(cstate.get_cs());
+            cstate.set_batch(batch);
+            Ok(Response::Empty)
+        }
+        (KeywordStmt::Commit, Some(batch)) => {
+            // the batch is persisted before we return, so don't block the runtime
+            let c_glob = global.clone();
+            tokio::task::spawn_blocking(move || dml::commit_batch(&c_glob, batch))
+                .await
+                .unwrap()
+                .map(|_| Response::Empty)
+        }
+        (KeywordStmt::Rollback, Some(_)) => Ok(Response::Empty),
+        (_, batch) => {
+            // put back whatever we had
+            if let Some(batch) = batch {
+                cstate.set_batch(batch);
+            }
+            Err(QueryError::QExecBatchIllegalState)
+        }
+    }
+}
+
+/*
+    subscriptions
+*/
+
+/// Start a subscription to a model's change feed. The response is a row with the version that the subscription
+/// starts from, after which the connection streams the changes
+async fn start_subscription(
+    global: &Global,
+    cstate: &mut ClientLocalState,
+    mut state: State<'_, InplaceData>,
+) -> QueryResult<Response> {
+    let stmt = SubscribeStatement::parse_from_state_hardened(&mut state)?;
+    // the pending changes might have to be written (and the journal read back), so don't block the runtime
+    let subscription = unsafe {
+        // UNSAFE(@ohsayan): the only await is within this block
+        let c_glob = global.clone();
+        let stmt: SubscribeStatement<'static> = core::mem::transmute(stmt);
+        tokio::task::spawn_blocking(move || ModelData::exec_subscribe(&c_glob, stmt))
+            .await
+            .unwrap()?
+    };
+    let mut data = Vec::new();
+    dml::encode_cell(&mut data, &Datacell::new_uint_default(subscription.start()));
+    cstate.set_subscription(subscription);
+    Ok(Response::Serialized {
+        ty: ResponseType::Row,
+        size: 1,
+        data,
+    })
+}
+
 /*
     nb exec
 */
@@ -256,13 +533,13 @@ fn run_nb(
     }] = [
         cstate_use, // use
         |g, c, s| _callgcs(g, c, s, ddl_misc::inspect),
-        |_, _, _| Err(QueryError::QLUnknownStatement), // describe
+        |g, _, s| _callgs(g, s, ddl_misc::describe),
         |g, _, s| _callgs(g, s, dml::insert_resp),
         |g, _, s| _callgs(g, s, dml::select_resp),
         |g, _, s| _callgs(g, s, dml::update_resp),
         |g, _, s| _callgs(g, s, dml::delete_resp),
         |g, _, s| _callgs(g, s, dml::upsert_resp),
-        |_, _, _| Err(QueryError::QLUnknownStatement), // exists
+        |g, _, s| _callgs(g, s, dml::exists_resp),
         |g, _, s| _callgs(g, s, dml::select_all_resp),
     ];
     {
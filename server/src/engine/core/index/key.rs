@@ -37,10 +37,11 @@ use {
         mem::{self, DwordNN, DwordQN, SpecialPaddedWord, WordIO, ZERO_BLOCK},
     },
     core::{
+        cmp::Ordering,
         fmt,
         hash::{Hash, Hasher},
         mem::ManuallyDrop,
-        slice, str,
+        ptr, slice, str,
     },
 };
 
@@ -102,6 +103,24 @@ impl PrimaryIndexKey {
     pub fn try_from_dc(dc: Datacell) -> Option<Self> {
         Self::check(&dc).then(|| unsafe { Self::new_from_dc(dc) })
     }
+    /// Create a new key from the given literal, if it is an eligible candidate key
+    pub fn try_from_lit(lit: Lit) -> Option<Self> {
+        lit.kind().tag_unique().is_unique().then(|| unsafe {
+            // UNSAFE(@ohsayan): verified tag; the datacell gets a new allocation
+            Self::new_from_dc(Datacell::from(lit))
+        })
+    }
+    /// Create a new key from a copy of the given cell, if it holds an eligible candidate key
+    pub fn try_from_dc_ref(dc: &Datacell) -> Option<Self> {
+        (dc.is_init() && Self::check(dc)).then(|| {
+            let borrowed = ManuallyDrop::new(unsafe {
+                // UNSAFE(@ohsayan): verified tag; neither the bitwise copy of the cell nor the borrowed key
+                // are ever dropped (the cell still owns the allocation)
+                Self::new_from_dc(ptr::read(dc))
+            });
+            borrowed.clone_owned()
+        })
+    }
     /// ## Safety
     ///
     /// Make sure that the [`Datacell`] is an eligible candidate key (ensuring uniqueness constraints + allocation correctness).
@@ -156,6 +175,23 @@ impl PrimaryIndexKey {
             },
         }
     }
+    /// Returns a copy of this key which (unlike [`Self::raw_clone`]) owns its own allocation
+    pub fn clone_owned(&self) -> Self {
+        match self.tag {
+            TagUnique::Bin | TagUnique::Str => {
+                let mut block =
+                    ManuallyDrop::new(self.virtual_block().to_owned().into_boxed_slice());
+                unsafe {
+                    // UNSAFE(@ohsayan): same tag; we just allocated this block
+                    Self::new_from_dual(self.tag, block.len() as u64, block.as_mut_ptr() as usize)
+                }
+            }
+            _ => unsafe {
+                // UNSAFE(@ohsayan): integer keys don't own an allocation
+                self.raw_clone()
+            },
+        }
+    }
     pub unsafe fn raw_clone(&self) -> Self {
         Self::new(self.tag, {
             let (qw, nw) = self.data.dwordqn_load_qw_nw();
@@ -223,6 +259,28 @@ impl Hash for PrimaryIndexKey {
     }
 }
 
+impl PartialOrd for PrimaryIndexKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PrimaryIndexKey {
+    /// Keys are first ordered by their tag; integers are then ordered by value while strings and binary
+    /// are ordered lexicographically by their bytes
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.tag.cmp(&other.tag).then_with(|| unsafe {
+            // UNSAFE(@ohsayan): +tagck (both tags are the same)
+            match self.tag {
+                TagUnique::UnsignedInt => self.read_uint().cmp(&other.read_uint()),
+                TagUnique::SignedInt => self.read_sint().cmp(&other.read_sint()),
+                TagUnique::Bin | TagUnique::Str => self.virtual_block().cmp(other.virtual_block()),
+                TagUnique::Illegal => unreachable!("found illegal value. check ctor."),
+            }
+        })
+    }
+}
+
 impl<'a> PartialEq<Lit<'a>> for PrimaryIndexKey {
     fn eq(&self, key: &Lit<'a>) -> bool {
         debug_assert!(key.kind().tag_unique().is_unique());
@@ -414,3 +472,18 @@ fn queue_ensure_offsets() {
     }
     assert_eq!(__orig, __reloaded);
 }
+
+#[test]
+fn check_pk_ord() {
+    let pk = |dc: Datacell| PrimaryIndexKey::try_from_dc(dc).unwrap();
+    assert!(pk(Datacell::new_uint_default(2)) < pk(Datacell::new_uint_default(10)));
+    assert!(pk(Datacell::from(-10)) < pk(Datacell::from(-2)));
+    assert!(pk(Datacell::from("apple")) < pk(Datacell::from("apples")));
+    assert!(pk(Datacell::from("b".as_bytes())) > pk(Datacell::from("abc".as_bytes())));
+    assert_eq!(
+        pk(Datacell::from("hello"))
+            .cmp(&PrimaryIndexKey::try_from_lit(Lit::new_str("hello")).unwrap()),
+        Ordering::Equal
+    );
+    assert!(PrimaryIndexKey::try_from_lit(Lit::new_bool(true)).is_none());
+}
@@ -26,43 +26,229 @@
 
 mod key;
 mod row;
+mod secondary;
 
-use crate::engine::{
-    data::lit::Lit,
-    idx::{IndexBaseSpec, IndexMTRaw, MTIndex},
-    sync::atm::Guard,
+#[cfg(debug_assertions)]
+use crate::engine::idx::DummyMetrics;
+use {
+    crate::engine::{
+        data::lit::Lit,
+        idx::{
+            meta::Comparable, AsValue, IndexBaseSpec, IndexMTOrd, IndexMTRaw, MTIndex, MTIndexExt,
+        },
+        sync::atm::Guard,
+    },
+    parking_lot::Mutex,
+    std::{
+        ops::Bound,
+        sync::atomic::{AtomicBool, Ordering},
+    },
 };
 
 pub use {
     key::PrimaryIndexKey,
-    row::{DcFieldIndex, Row, RowData},
+    row::{expiry_now, DcFieldIndex, Row, RowData},
+    secondary::{SecondaryIndex, SecondaryIndexes, SecondaryIndexesGuard},
 };
 
 pub type RowDataLck = parking_lot::RwLock<RowData>;
+pub type PrimaryIndexIterEntry<'t, 'g, 'v> =
+    <PrimaryIndexData as MTIndexExt<Row, PrimaryIndexKey, RowDataLck>>::IterEntry<'t, 'g, 'v>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// The structure used by a model's primary index
+pub enum PrimaryIndexKind {
+    /// a hash trie; iteration order is meaningless
+    #[default]
+    Hashed,
+    /// an ordered index that supports key range scans
+    Ordered,
+}
+
+impl PrimaryIndexKind {
+    pub const PROP_KEY: &'static str = "index";
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "hashed" => Some(Self::Hashed),
+            "ordered" => Some(Self::Ordered),
+            _ => None,
+        }
+    }
+    pub fn name_str(&self) -> &'static str {
+        match self {
+            Self::Hashed => "hashed",
+            Self::Ordered => "ordered",
+        }
+    }
+}
+
+/*
+    row expiry
+    ---
+    expired rows stay in the index until they're purged (by the fractal manager), but all lookups and scans skip
+    them. the clock is only read once an index holds a row that can expire
+*/
 
 #[derive(Debug)]
 pub struct PrimaryIndex {
-    data: IndexMTRaw<row::Row>,
+    data: PrimaryIndexData,
     latch: IndexLatch,
+    /// set once a row that can expire is added
+    expiring: AtomicBool,
+    /// the key of the last row that a purge visited, if it stopped before the end of the index
+    purge_cursor: Mutex<Option<PrimaryIndexKey>>,
 }
 
 impl PrimaryIndex {
-    pub fn new_empty() -> Self {
+    pub fn new_empty(kind: PrimaryIndexKind) -> Self {
         Self {
-            data: IndexMTRaw::idx_init(),
+            data: match kind {
+                PrimaryIndexKind::Hashed => PrimaryIndexData::Hashed(IndexMTRaw::idx_init()),
+                PrimaryIndexKind::Ordered => PrimaryIndexData::Ordered(IndexMTOrd::idx_init()),
+            },
             latch: IndexLatch::new(),
+            expiring: AtomicBool::new(false),
+            purge_cursor: Mutex::new(None),
+        }
+    }
+    pub fn kind(&self) -> PrimaryIndexKind {
+        match self.data {
+            PrimaryIndexData::Hashed(_) => PrimaryIndexKind::Hashed,
+            PrimaryIndexData::Ordered(_) => PrimaryIndexKind::Ordered,
         }
     }
     pub fn acquire_shared(&self) -> IndexLatchHandleShared {
         self.latch.gl_handle_shared()
     }
-    pub fn acquire_exclusive(&self) -> IndexLatchHandleExclusive {
-        self.latch.gl_handle_exclusive()
+    /// Returns true if the index holds (or has held) rows that can expire
+    pub fn expires_rows(&self) -> bool {
+        self.expiring.load(Ordering::Acquire)
+    }
+    /// Mark the index as holding rows that can expire. This must be done before such a row is added
+    pub fn set_expires_rows(&self) {
+        self.expiring.store(true, Ordering::Release)
+    }
+    /// Returns the current time if rows in this index can expire
+    fn expiry_clock(&self) -> Option<u64> {
+        self.expires_rows().then(expiry_now)
+    }
+    fn live<'v>(&self, row: Option<&'v Row>) -> Option<&'v Row> {
+        match self.expiry_clock() {
+            Some(now) => row.filter(|row| !row.d_data().read().is_expired_at(now)),
+            None => row,
+        }
     }
     pub fn select<'a, 'v, 't: 'v, 'g: 't>(&'t self, key: Lit<'a>, g: &'g Guard) -> Option<&'v Row> {
-        self.data.mt_get_element(&key, g)
+        self.live(self.data.mt_get_element(&key, g))
+    }
+    pub fn select_key<'v, 't: 'v, 'g: 't>(
+        &'t self,
+        key: &PrimaryIndexKey,
+        g: &'g Guard,
+    ) -> Option<&'v Row> {
+        self.live(self.data.mt_get_element(key, g))
     }
-    pub fn __raw_index(&self) -> &IndexMTRaw<row::Row> {
+    /// Returns the row with the given key even if it has expired (along with whether it has)
+    pub fn select_key_any<'v, 't: 'v, 'g: 't>(
+        &'t self,
+        key: &PrimaryIndexKey,
+        g: &'g Guard,
+    ) -> Option<(&'v Row, bool)> {
+        let row = self.data.mt_get_element(key, g)?;
+        let expired = self
+            .expiry_clock()
+            .is_some_and(|now| row.d_data().read().is_expired_at(now));
+        Some((row, expired))
+    }
+    /// Returns an iterator over the rows with keys in the given range. With a hashed index, the bounds
+    /// can't be used and all the rows are returned
+    pub fn select_range<'v, 't: 'v, 'g: 't>(
+        &'t self,
+        lo: Bound<PrimaryIndexKey>,
+        hi: Bound<PrimaryIndexKey>,
+        g: &'g Guard,
+    ) -> PrimaryIndexScan<'t, 'g, 'v> {
+        PrimaryIndexScan {
+            iter: match &self.data {
+                PrimaryIndexData::Hashed(idx) => PrimaryIndexIter::Hashed(idx.mt_iter_entry(g)),
+                PrimaryIndexData::Ordered(idx) => {
+                    PrimaryIndexIter::Ordered(idx.mt_iter_entry_range(lo, hi, g))
+                }
+            },
+            now: self.expiry_clock(),
+        }
+    }
+    /// Returns an iterator over the rows that follow the given key in scan order (hash order for a hashed
+    /// index and key order for an ordered index)
+    pub fn select_after<'v, 't: 'v, 'g: 't>(
+        &'t self,
+        key: PrimaryIndexKey,
+        g: &'g Guard,
+    ) -> PrimaryIndexScan<'t, 'g, 'v> {
+        PrimaryIndexScan {
+            iter: match &self.data {
+                PrimaryIndexData::Hashed(idx) => {
+                    PrimaryIndexIter::Hashed(idx.mt_iter_entry_after(&key, g))
+                }
+                PrimaryIndexData::Ordered(idx) => PrimaryIndexIter::Ordered(
+                    idx.mt_iter_entry_range(Bound::Excluded(key), Bound::Unbounded, g),
+                ),
+            },
+            now: self.expiry_clock(),
+        }
+    }
+    /// Returns the rows that have expired as of `now` among (at most) the next `budget` rows in scan order, starting
+    /// from where the last call stopped. Also returns `true` if the end of the index was reached, in which case the
+    /// next call starts from the beginning of the index again
+    pub fn select_expired(&self, now: u64, budget: usize, g: &Guard) -> (Vec<Row>, bool) {
+        let mut cursor = self.purge_cursor.lock();
+        let mut iter = match (&self.data, cursor.take()) {
+            (PrimaryIndexData::Hashed(idx), Some(key)) => {
+                PrimaryIndexIter::Hashed(idx.mt_iter_entry_after(&key, g))
+            }
+            (PrimaryIndexData::Hashed(idx), None) => PrimaryIndexIter::Hashed(idx.mt_iter_entry(g)),
+            (PrimaryIndexData::Ordered(idx), key) => {
+                let lo = key.map_or(Bound::Unbounded, Bound::Excluded);
+                PrimaryIndexIter::Ordered(idx.mt_iter_entry_range(lo, Bound::Unbounded, g))
+            }
+        };
+        let mut expired = Vec::new();
+        let mut last = None;
+        for _ in 0..budget {
+            let Some(row) = iter.next() else {
+                return (expired, true);
+            };
+            if row.d_data().read().is_expired_at(now) {
+                expired.push(row.clone());
+            }
+            last = Some(row);
+        }
+        *cursor = last.map(|row| row.d_key().clone_owned());
+        (expired, cursor.is_none())
+    }
+    /// Returns true if a purge stopped before the end of the index (see [`Self::select_expired`])
+    pub fn purge_pending(&self) -> bool {
+        self.purge_cursor.lock().is_some()
+    }
+    /// Returns true if the index still holds this very row (and not a different row that has since been added with
+    /// the same key)
+    pub fn holds_row(&self, row: &Row, g: &Guard) -> bool {
+        self.data
+            .mt_get_element(row.d_key(), g)
+            .is_some_and(|current| current.is_same_row(row))
+    }
+    /// Remove this very row from the index. Nothing is removed if the row has been removed or replaced by a
+    /// different row with the same key, in which case false is returned. Callers that decide to remove a row
+    /// based on its data must hold the row's lock across the check and the removal
+    pub fn remove_row(&self, row: &Row, g: &Guard) -> bool {
+        let iff = |current: &Row| current.is_same_row(row);
+        match &self.data {
+            PrimaryIndexData::Hashed(idx) => idx.mt_delete_return_entry_if(row.d_key(), iff, g),
+            PrimaryIndexData::Ordered(idx) => idx.mt_delete_return_entry_if(row.d_key(), iff, g),
+        }
+        .is_some()
+    }
+    pub fn __raw_index(&self) -> &PrimaryIndexData {
         &self.data
     }
     pub fn count(&self) -> usize {
@@ -71,12 +257,223 @@ impl PrimaryIndex {
 }
 
 #[derive(Debug)]
-pub struct IndexLatchHandleShared<'t> {
-    _lck: parking_lot::RwLockReadGuard<'t, ()>,
+pub enum PrimaryIndexData {
+    Hashed(IndexMTRaw<Row>),
+    Ordered(IndexMTOrd<Row>),
+}
+
+/// An iterator over the rows in a primary index that skips expired rows
+pub struct PrimaryIndexScan<'t, 'g, 'v>
+where
+    'g: 't + 'v,
+    't: 'v,
+{
+    iter: PrimaryIndexIterEntry<'t, 'g, 'v>,
+    /// the time at which the scan started (if rows can expire)
+    now: Option<u64>,
+}
+
+impl<'t, 'g: 't + 'v, 'v> Iterator for PrimaryIndexScan<'t, 'g, 'v>
+where
+    't: 'v,
+{
+    type Item = &'v Row;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.now {
+            Some(now) => self
+                .iter
+                .find(|row| !row.d_data().read().is_expired_at(now)),
+            None => self.iter.next(),
+        }
+    }
+}
+
+/// An iterator over either kind of primary index
+pub enum PrimaryIndexIter<H, O> {
+    Hashed(H),
+    Ordered(O),
 }
+
+impl<T, H: Iterator<Item = T>, O: Iterator<Item = T>> Iterator for PrimaryIndexIter<H, O> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Hashed(h) => h.next(),
+            Self::Ordered(o) => o.next(),
+        }
+    }
+}
+
+macro_rules! dispatch {
+    ($self:ident, $idx:ident => $expr:expr) => {
+        match $self {
+            PrimaryIndexData::Hashed($idx) => $expr,
+            PrimaryIndexData::Ordered($idx) => $expr,
+        }
+    };
+    ($self:ident, iter $idx:ident => $expr:expr) => {
+        match $self {
+            PrimaryIndexData::Hashed($idx) => PrimaryIndexIter::Hashed($expr),
+            PrimaryIndexData::Ordered($idx) => PrimaryIndexIter::Ordered($expr),
+        }
+    };
+}
+
+impl IndexBaseSpec for PrimaryIndexData {
+    const PREALLOC: bool = false;
+    #[cfg(debug_assertions)]
+    type Metrics = DummyMetrics;
+    fn idx_init() -> Self {
+        Self::Hashed(IndexMTRaw::idx_init())
+    }
+    fn idx_init_with(s: Self) -> Self {
+        s
+    }
+    #[cfg(debug_assertions)]
+    fn idx_metrics(&self) -> &Self::Metrics {
+        &DummyMetrics
+    }
+}
+
+impl MTIndexExt<Row, PrimaryIndexKey, RowDataLck> for PrimaryIndexData {
+    type IterEntry<'t, 'g, 'v>
+        = PrimaryIndexIter<
+        <IndexMTRaw<Row> as MTIndexExt<Row, PrimaryIndexKey, RowDataLck>>::IterEntry<'t, 'g, 'v>,
+        <IndexMTOrd<Row> as MTIndexExt<Row, PrimaryIndexKey, RowDataLck>>::IterEntry<'t, 'g, 'v>,
+    >
+    where
+        'g: 't + 'v,
+        't: 'v,
+        Self: 't;
+    fn mt_iter_entry<'t, 'g, 'v>(&'t self, g: &'g Guard) -> Self::IterEntry<'t, 'g, 'v> {
+        dispatch!(self, iter idx => idx.mt_iter_entry(g))
+    }
+}
+
+impl MTIndex<Row, PrimaryIndexKey, RowDataLck> for PrimaryIndexData {
+    type IterKV<'t, 'g, 'v>
+        = PrimaryIndexIter<
+        <IndexMTRaw<Row> as MTIndex<Row, PrimaryIndexKey, RowDataLck>>::IterKV<'t, 'g, 'v>,
+        <IndexMTOrd<Row> as MTIndex<Row, PrimaryIndexKey, RowDataLck>>::IterKV<'t, 'g, 'v>,
+    >
+    where
+        'g: 't + 'v,
+        't: 'v,
+        Self: 't;
+    type IterKey<'t, 'g, 'v>
+        = PrimaryIndexIter<
+        <IndexMTRaw<Row> as MTIndex<Row, PrimaryIndexKey, RowDataLck>>::IterKey<'t, 'g, 'v>,
+        <IndexMTOrd<Row> as MTIndex<Row, PrimaryIndexKey, RowDataLck>>::IterKey<'t, 'g, 'v>,
+    >
+    where
+        'g: 't + 'v,
+        't: 'v,
+        Self: 't;
+    type IterVal<'t, 'g, 'v>
+        = PrimaryIndexIter<
+        <IndexMTRaw<Row> as MTIndex<Row, PrimaryIndexKey, RowDataLck>>::IterVal<'t, 'g, 'v>,
+        <IndexMTOrd<Row> as MTIndex<Row, PrimaryIndexKey, RowDataLck>>::IterVal<'t, 'g, 'v>,
+    >
+    where
+        'g: 't + 'v,
+        't: 'v,
+        Self: 't;
+    fn mt_iter_kv<'t, 'g, 'v>(&'t self, g: &'g Guard) -> Self::IterKV<'t, 'g, 'v> {
+        dispatch!(self, iter idx => idx.mt_iter_kv(g))
+    }
+    fn mt_iter_key<'t, 'g, 'v>(&'t self, g: &'g Guard) -> Self::IterKey<'t, 'g, 'v> {
+        dispatch!(self, iter idx => idx.mt_iter_key(g))
+    }
+    fn mt_iter_val<'t, 'g, 'v>(&'t self, g: &'g Guard) -> Self::IterVal<'t, 'g, 'v> {
+        dispatch!(self, iter idx => idx.mt_iter_val(g))
+    }
+    fn mt_len(&self) -> usize {
+        dispatch!(self, idx => idx.mt_len())
+    }
+    fn mt_clear(&self, g: &Guard) {
+        dispatch!(self, idx => idx.mt_clear(g))
+    }
+    fn mt_insert(&self, e: Row, g: &Guard) -> bool
+    where
+        RowDataLck: AsValue,
+    {
+        dispatch!(self, idx => idx.mt_insert(e, g))
+    }
+    fn mt_upsert(&self, e: Row, g: &Guard) -> bool
+    where
+        RowDataLck: AsValue,
+    {
+        dispatch!(self, idx => idx.mt_upsert(e, g))
+    }
+    fn mt_contains<Q>(&self, key: &Q, g: &Guard) -> bool
+    where
+        Q: ?Sized + Comparable<PrimaryIndexKey>,
+    {
+        dispatch!(self, idx => idx.mt_contains(key, g))
+    }
+    fn mt_get<'t, 'g, 'v, Q>(&'t self, key: &Q, g: &'g Guard) -> Option<&'v RowDataLck>
+    where
+        Q: ?Sized + Comparable<PrimaryIndexKey>,
+        't: 'v,
+        'g: 't + 'v,
+    {
+        dispatch!(self, idx => idx.mt_get(key, g))
+    }
+    fn mt_get_element<'t, 'g, 'v, Q>(&'t self, key: &Q, g: &'g Guard) -> Option<&'v Row>
+    where
+        Q: ?Sized + Comparable<PrimaryIndexKey>,
+        't: 'v,
+        'g: 't + 'v,
+    {
+        dispatch!(self, idx => idx.mt_get_element(key, g))
+    }
+    /*
+        NOTE(@ohsayan): neither row data nor primary keys can be cloned, so the bounds on the following methods
+        can never be satisfied
+    */
+    fn mt_get_cloned<Q>(&self, _: &Q, _: &Guard) -> Option<RowDataLck>
+    where
+        Q: ?Sized + Comparable<PrimaryIndexKey>,
+    {
+        unreachable!("row data can't be cloned")
+    }
+    fn mt_update(&self, _: Row, _: &Guard) -> bool {
+        unreachable!("primary keys can't be cloned")
+    }
+    fn mt_update_return<'t, 'g, 'v>(&'t self, _: Row, _: &'g Guard) -> Option<&'v RowDataLck>
+    where
+        't: 'v,
+        'g: 't + 'v,
+    {
+        unreachable!("primary keys can't be cloned")
+    }
+    fn mt_delete<Q>(&self, key: &Q, g: &Guard) -> bool
+    where
+        Q: ?Sized + Comparable<PrimaryIndexKey>,
+    {
+        dispatch!(self, idx => idx.mt_delete(key, g))
+    }
+    fn mt_delete_return<'t, 'g, 'v, Q>(&'t self, key: &Q, g: &'g Guard) -> Option<&'v RowDataLck>
+    where
+        Q: ?Sized + Comparable<PrimaryIndexKey>,
+        't: 'v,
+        'g: 't + 'v,
+    {
+        dispatch!(self, idx => idx.mt_delete_return(key, g))
+    }
+    fn mt_delete_return_entry<'t, 'g, 'v, Q>(&'t self, key: &Q, g: &'g Guard) -> Option<&'v Row>
+    where
+        Q: ?Sized + Comparable<PrimaryIndexKey>,
+        't: 'v,
+        'g: 't + 'v,
+    {
+        dispatch!(self, idx => idx.mt_delete_return_entry(key, g))
+    }
+}
+
 #[derive(Debug)]
-pub struct IndexLatchHandleExclusive<'t> {
-    _lck: parking_lot::RwLockWriteGuard<'t, ()>,
+pub struct IndexLatchHandleShared<'t> {
+    _lck: parking_lot::RwLockReadGuard<'t, ()>,
 }
 
 #[derive(Debug)]
@@ -95,9 +492,4 @@ impl IndexLatch {
             _lck: self.glck.read(),
         }
     }
-    fn gl_handle_exclusive(&self) -> IndexLatchHandleExclusive {
-        IndexLatchHandleExclusive {
-            _lck: self.glck.write(),
-        }
-    }
 }
@@ -34,7 +34,7 @@ use {
             mem::RawStr,
             sync::smart::RawRC,
         },
-        util::compiler,
+        util::{compiler, os},
     },
     parking_lot::{RwLock, RwLockReadGuard, RwLockUpgradableReadGuard, RwLockWriteGuard},
     std::mem::ManuallyDrop,
@@ -42,6 +42,11 @@ use {
 
 pub type DcFieldIndex = IndexST<RawStr, Datacell, HasherNativeFx>;
 
+/// Returns the current time in milliseconds since the epoch (the clock used for row expiry)
+pub fn expiry_now() -> u64 {
+    (os::get_epoch_time() / 1_000_000) as u64
+}
+
 #[derive(Debug)]
 pub struct Row {
     __pk: ManuallyDrop<PrimaryIndexKey>,
@@ -53,6 +58,8 @@ pub struct RowData {
     fields: DcFieldIndex,
     txn_revised_data: DeltaVersion,
     txn_revised_schema_version: DeltaVersion,
+    /// the time (in milliseconds since the epoch) at which the row expires; zero if it never does
+    expires_at: u64,
 }
 
 impl RowData {
@@ -72,6 +79,16 @@ impl RowData {
     pub fn get_txn_revised(&self) -> DeltaVersion {
         self.txn_revised_data
     }
+    /// Returns the expiry time of the row (zero if it never expires)
+    pub fn expires_at(&self) -> u64 {
+        self.expires_at
+    }
+    pub fn set_expires_at(&mut self, expires_at: u64) {
+        self.expires_at = expires_at;
+    }
+    pub fn is_expired_at(&self, now: u64) -> bool {
+        (self.expires_at != 0) & (self.expires_at <= now)
+    }
 }
 
 impl TreeElement for Row {
@@ -111,6 +128,16 @@ impl Row {
         data: DcFieldIndex,
         schema_version: DeltaVersion,
         txn_revised_data: DeltaVersion,
+    ) -> Self {
+        Self::new_expiring(pk, data, schema_version, txn_revised_data, 0)
+    }
+    /// Create a row that expires at the given time (zero if it never expires)
+    pub fn new_expiring(
+        pk: PrimaryIndexKey,
+        data: DcFieldIndex,
+        schema_version: DeltaVersion,
+        txn_revised_data: DeltaVersion,
+        expires_at: u64,
     ) -> Self {
         Self {
             __pk: ManuallyDrop::new(pk),
@@ -120,6 +147,7 @@ impl Row {
                     fields: data,
                     txn_revised_schema_version: schema_version,
                     txn_revised_data,
+                    expires_at,
                 }))
             },
         }
@@ -130,6 +158,10 @@ impl Row {
     pub fn d_data(&self) -> &RwLock<RowData> {
         self.__rc.data()
     }
+    /// Returns true if both handles refer to the same row (and not just rows with the same key)
+    pub fn is_same_row(&self, other: &Self) -> bool {
+        std::ptr::eq(self.d_data(), other.d_data())
+    }
     #[cfg(test)]
     pub fn cloned_data(&self) -> Vec<(Box<str>, Datacell)> {
         self.d_data()
@@ -161,7 +193,7 @@ impl Row {
         let mut max_delta = wl.txn_revised_schema_version;
         for (delta_id, delta) in delta_state.resolve_iter_since(wl.txn_revised_schema_version) {
             match delta.kind() {
-                SchemaDeltaKind::FieldAdd(f) => {
+                SchemaDeltaKind::FieldAdd(f, default) => {
                     wl.fields.st_insert(
                         unsafe {
                             // UNSAFE(@ohsayan): a row is inside a model and is valid as long as it is in there!
@@ -169,7 +201,9 @@ impl Row {
                             // neither frees anything nor allocates
                             f.clone()
                         },
-                        Datacell::null(),
+                        default
+                            .as_ref()
+                            .map_or_else(Datacell::null, |d| d.produce()),
                     );
                 }
                 SchemaDeltaKind::FieldRem(f) => {
@@ -0,0 +1,287 @@
+/*
+ * Created on Sun Oct 18 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+use {
+    super::{key::PrimaryIndexKey, row::DcFieldIndex},
+    crate::engine::{
+        data::lit::Lit,
+        idx::{IndexST, STIndex},
+        mem::VInline,
+    },
+    parking_lot::{RwLock, RwLockWriteGuard},
+    std::collections::HashSet,
+};
+
+type SecondaryIndexData = IndexST<PrimaryIndexKey, VInline<1, PrimaryIndexKey>>;
+
+/// The values of a row's indexed fields, in index order (`None` for a value that isn't indexed, i.e a null)
+pub type IndexedValues = Vec<Option<PrimaryIndexKey>>;
+
+#[derive(Debug)]
+/// A secondary index, mapping the values of a (non-primary key) field to the primary keys of the rows that
+/// hold it. Null values are not indexed
+pub struct SecondaryIndex {
+    name: Box<str>,
+    field: Box<str>,
+    unique: bool,
+    data: RwLock<SecondaryIndexData>,
+}
+
+impl SecondaryIndex {
+    pub fn new_empty(name: Box<str>, field: Box<str>, unique: bool) -> Self {
+        Self {
+            name,
+            field,
+            unique,
+            data: RwLock::new(IndexST::default()),
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+    pub fn is_unique(&self) -> bool {
+        self.unique
+    }
+    /// Returns the primary keys of all the rows where the indexed field is equal to the given literal. The
+    /// literal must be an eligible candidate key
+    pub fn lookup(&self, value: Lit) -> Vec<PrimaryIndexKey> {
+        lookup_in(&self.data.read(), value)
+    }
+    /// Exclusively lock the contents of this index alone
+    pub fn lock(&self) -> SecondaryIndexesGuard<'_> {
+        SecondaryIndexesGuard {
+            indexes: vec![(self, self.data.write())],
+        }
+    }
+    #[cfg(test)]
+    pub fn count(&self) -> usize {
+        self.data.read().values().map(|keys| keys.len()).sum()
+    }
+}
+
+#[derive(Debug, Default)]
+/// The secondary indexes of a model. The set of indexes can only be changed by DDL (which has exclusive
+/// access to the model), while the contents are maintained by DML
+pub struct SecondaryIndexes {
+    indexes: Vec<SecondaryIndex>,
+}
+
+impl SecondaryIndexes {
+    pub fn is_empty(&self) -> bool {
+        self.indexes.is_empty()
+    }
+    pub fn indexes(&self) -> &[SecondaryIndex] {
+        &self.indexes
+    }
+    pub fn get(&self, name: &str) -> Option<&SecondaryIndex> {
+        self.indexes.iter().find(|idx| idx.name() == name)
+    }
+    pub fn get_by_field(&self, field: &str) -> Option<&SecondaryIndex> {
+        self.indexes.iter().find(|idx| idx.field() == field)
+    }
+    pub fn add(&mut self, index: SecondaryIndex) -> bool {
+        let okay = self.get(index.name()).is_none();
+        if okay {
+            self.indexes.push(index);
+        }
+        okay
+    }
+    pub fn remove(&mut self, name: &str) -> bool {
+        match self.indexes.iter().position(|idx| idx.name() == name) {
+            Some(pos) => {
+                self.indexes.remove(pos);
+                true
+            }
+            None => false,
+        }
+    }
+    /// Exclusively lock the contents of all indexes. Writers must lock the indexes before locking any rows
+    pub fn lock(&self) -> SecondaryIndexesGuard<'_> {
+        self.lock_affected(|_| true)
+    }
+    /// Exclusively lock the contents of only the indexes that a write affects. The indexes are always locked in
+    /// the same order, so writers locking different (but overlapping) sets of indexes can't deadlock
+    pub fn lock_affected(
+        &self,
+        affected: impl Fn(&SecondaryIndex) -> bool,
+    ) -> SecondaryIndexesGuard<'_> {
+        SecondaryIndexesGuard {
+            indexes: self
+                .indexes
+                .iter()
+                .filter(|idx| affected(idx))
+                .map(|idx| (idx, idx.data.write()))
+                .collect(),
+        }
+    }
+}
+
+fn lookup_in(data: &SecondaryIndexData, value: Lit) -> Vec<PrimaryIndexKey> {
+    let Some(value) = PrimaryIndexKey::try_from_lit(value) else {
+        return Vec::new();
+    };
+    match data.st_get(&value) {
+        Some(keys) => keys.iter().map(PrimaryIndexKey::clone_owned).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// A writer's exclusive handle on the indexes of a model that it affects. Indexes that aren't held are left
+/// untouched by the guard
+pub struct SecondaryIndexesGuard<'a> {
+    indexes: Vec<(&'a SecondaryIndex, RwLockWriteGuard<'a, SecondaryIndexData>)>,
+}
+
+impl<'a> SecondaryIndexesGuard<'a> {
+    pub fn is_empty(&self) -> bool {
+        self.indexes.is_empty()
+    }
+    /// Returns the primary keys of all the rows where the field indexed by `index` is equal to the given literal,
+    /// or `None` if this guard doesn't hold the index
+    pub fn lookup(&self, index: &SecondaryIndex, value: Lit) -> Option<Vec<PrimaryIndexKey>> {
+        self.indexes
+            .iter()
+            .find(|(idx, _)| std::ptr::eq(*idx, index))
+            .map(|(_, data)| lookup_in(data, value))
+    }
+    /// Remove all the indexed values
+    pub fn clear(&mut self) {
+        self.indexes.iter_mut().for_each(|(_, data)| data.clear())
+    }
+    /// Returns the indexed values of the given row
+    pub fn values(&self, fields: &DcFieldIndex) -> IndexedValues {
+        self.indexes
+            .iter()
+            .map(|(idx, _)| {
+                fields
+                    .st_get(idx.field())
+                    .and_then(PrimaryIndexKey::try_from_dc_ref)
+            })
+            .collect()
+    }
+    /// Returns true if the values can be indexed for the given row without violating any unique constraints
+    pub fn can_insert(&self, pk: &PrimaryIndexKey, values: &IndexedValues) -> bool {
+        self.indexes
+            .iter()
+            .zip(values)
+            .all(|((idx, data), value)| match value {
+                Some(value) if idx.is_unique() => data
+                    .st_get(value)
+                    .map_or(true, |keys| keys.iter().all(|key| key == pk)),
+                _ => true,
+            })
+    }
+    /// Returns true if all the rows can be indexed without violating any unique constraints, either with the rows
+    /// that are already indexed or among themselves. The rows must have distinct primary keys
+    pub fn can_insert_all<'r>(
+        &self,
+        rows: impl IntoIterator<Item = (&'r PrimaryIndexKey, &'r IndexedValues)>,
+    ) -> bool {
+        let mut seen: Vec<HashSet<&PrimaryIndexKey>> = vec![HashSet::new(); self.indexes.len()];
+        rows.into_iter().all(|(pk, values)| {
+            self.can_insert(pk, values)
+                && self.indexes.iter().zip(values).zip(seen.iter_mut()).all(
+                    |(((idx, _), value), seen)| match value {
+                        Some(value) if idx.is_unique() => seen.insert(value),
+                        _ => true,
+                    },
+                )
+        })
+    }
+    /// Index the values of a row. This does not check unique constraints (see [`Self::can_insert`])
+    pub fn insert_unchecked(&mut self, pk: &PrimaryIndexKey, values: &IndexedValues) {
+        for ((_, data), value) in self.indexes.iter_mut().zip(values) {
+            let Some(value) = value else {
+                continue;
+            };
+            match data.get_mut(value) {
+                Some(keys) => {
+                    if !keys.contains(pk) {
+                        keys.push(pk.clone_owned())
+                    }
+                }
+                None => {
+                    let mut keys = VInline::new();
+                    keys.push(pk.clone_owned());
+                    data.insert(value.clone_owned(), keys);
+                }
+            }
+        }
+    }
+    /// Index the values of a row, if this doesn't violate any unique constraints
+    pub fn insert(&mut self, pk: &PrimaryIndexKey, values: &IndexedValues) -> bool {
+        let okay = self.can_insert(pk, values);
+        if okay {
+            self.insert_unchecked(pk, values);
+        }
+        okay
+    }
+    /// Remove the values of a row from the indexes
+    pub fn remove(&mut self, pk: &PrimaryIndexKey, values: &IndexedValues) {
+        for ((_, data), value) in self.indexes.iter_mut().zip(values) {
+            let Some(value) = value else {
+                continue;
+            };
+            if let Some(keys) = data.get_mut(value) {
+                if let Some(pos) = keys.iter().position(|key| key == pk) {
+                    keys.remove_compact(pos);
+                }
+                if keys.is_empty() {
+                    data.remove(value);
+                }
+            }
+        }
+    }
+    /// Re-index a set of rows (with the given old values) using their current values. If any unique constraint
+    /// is violated, the old values are restored and false is returned
+    pub fn replace<'r>(
+        &mut self,
+        rows: impl Iterator<Item = (&'r PrimaryIndexKey, &'r DcFieldIndex)>,
+        old: &[IndexedValues],
+    ) -> bool {
+        let rows: Vec<_> = rows.map(|(pk, fields)| (pk, self.values(fields))).collect();
+        for ((pk, _), old) in rows.iter().zip(old) {
+            self.remove(pk, old);
+        }
+        let mut inserted = 0;
+        while (inserted < rows.len()) && self.insert(rows[inserted].0, &rows[inserted].1) {
+            inserted += 1;
+        }
+        if inserted == rows.len() {
+            return true;
+        }
+        for (pk, new) in &rows[..inserted] {
+            self.remove(pk, new);
+        }
+        for ((pk, _), old) in rows.iter().zip(old) {
+            self.insert_unchecked(pk, old);
+        }
+        false
+    }
+}
@@ -181,14 +181,14 @@ pub(self) fn with_model_for_data_update<'a, F>(
     f: F,
 ) -> QueryResult<()>
 where
-    F: FnOnce(&ModelData) -> QueryResult<QueryExecMeta>,
+    F: FnOnce(&Model) -> QueryResult<QueryExecMeta>,
 {
     let mdl_idx = global.state().namespace().idx_mdl.read();
     let Some(model) = mdl_idx.get(&entity) else {
         return Err(QueryError::QExecObjectNotFound);
     };
     if compiler::likely(model.driver().status().is_healthy()) {
-        let r = f(model.data())?;
+        let r = f(model)?;
         model::DeltaState::guard_delta_overflow(
             global,
             entity.space(),
@@ -201,3 +201,24 @@ where
         compiler::cold_call(|| Err(QueryError::SysServerError))
     }
 }
+
+/// Run a batch against a model. Unlike [`with_model_for_data_update`], the batch has access to the model's driver
+/// since it persists its own changes
+pub(self) fn with_model_for_batch<'a, T, F>(
+    global: &impl GlobalInstanceLike,
+    entity: EntityIDRef<'a>,
+    f: F,
+) -> QueryResult<T>
+where
+    F: FnOnce(&Model) -> QueryResult<T>,
+{
+    let mdl_idx = global.state().namespace().idx_mdl.read();
+    let Some(model) = mdl_idx.get(&entity) else {
+        return Err(QueryError::QExecObjectNotFound);
+    };
+    if compiler::likely(model.driver().status().is_healthy()) {
+        f(model)
+    } else {
+        compiler::cold_call(|| Err(QueryError::SysServerError))
+    }
+}
@@ -25,7 +25,7 @@
 */
 
 use {
-    super::{Field, Layer, ModelData},
+    super::{Field, FieldDefault, Layer, ModelData},
     crate::{
         engine::{
             core::EntityIDRef,
@@ -106,7 +106,9 @@ impl<'a> AlterPlan<'a> {
                     let not_pk = mdl.not_pk(id);
                     let exists = !no_field(mdl, id.as_str());
                     not_found = !exists;
-                    not_pk & exists
+                    // an indexed field must have its index dropped first
+                    let not_indexed = mdl.secondary_indexes().get_by_field(id).is_none();
+                    not_pk & exists & not_indexed
                 }) {
                     can_ignore!(AlterAction::Remove(r))
                 } else if not_found {
@@ -126,8 +128,22 @@ impl<'a> AlterPlan<'a> {
                     } = fields.next().unwrap();
                     okay &= no_field(mdl, &field_name) & mdl.not_pk(&field_name);
                     let is_nullable = check_nullable(&mut props)?;
-                    let layers = Field::parse_layers(layers, is_nullable)?;
-                    okay &= add.st_insert(field_name.as_str().into(), layers);
+                    let mut field = Field::parse_layers(layers, is_nullable)?;
+                    // the default can also be set with the field's props (`f { type: uint64, default: 0 }`)
+                    if let Some(default) = props.remove(Field::PROP_DEFAULT) {
+                        if field.default().is_some() {
+                            return Err(QueryError::QExecDdlInvalidProperties);
+                        }
+                        field = field
+                            .with_default(default)
+                            .ok_or(QueryError::QExecDdlInvalidProperties)?;
+                    }
+                    // existing rows are backfilled with the default when they're next read and that value is never
+                    // journaled, so it has to be the same every time
+                    if let Some(FieldDefault::Producer(_)) = field.default() {
+                        return Err(QueryError::QExecDdlInvalidProperties);
+                    }
+                    okay &= add.st_insert(field_name.as_str().into(), field);
                 }
                 can_ignore!(AlterAction::Add(add))
             }
@@ -233,8 +249,22 @@ impl<'a> AlterPlan<'a> {
                     return Err(QueryError::QExecDdlInvalidTypeDefinition);
                 }
             }
+            // constraints can't be changed here, but they must still hold for the new type
+            let new_parsed_layer = match current_layer.props() {
+                Some(props) => match new_parsed_layer.with_props(props.to_dict()) {
+                    Some(layer) => layer,
+                    None => return Err(QueryError::QExecDdlInvalidProperties),
+                },
+                None => new_parsed_layer,
+            };
             *new_layer = new_parsed_layer;
         }
+        // the default must still be valid for the new type
+        if let Some(default) = new_field.default.take() {
+            new_field = new_field
+                .with_default(default.to_entry())
+                .ok_or(QueryError::QExecDdlInvalidProperties)?;
+        }
         *super_nlck &= no_lock;
         *super_okay &= okay;
         if okay {
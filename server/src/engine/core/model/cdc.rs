@@ -0,0 +1,423 @@
+/*
+ * Created on Sun Oct 18 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+/*
+    change feeds
+    ---
+    while a model has subscribers, every change that is made to it is published to its change feed along with its
+    version, the row as it was before the change (the pre-image) and the row as it was after it (the post-image).
+    a writer holds the feed from before it gets its versions until it has published its changes, and holds it
+    exclusively while the feed has subscribers, so changes are published in the order in which they were made.
+
+    a subscriber can also start from an earlier version, in which case all the pending changes are written to the
+    model's journal and the changes since that version are read back from it and replayed before the live changes.
+    since the journal only has the new data of a row, replayed changes have no pre-image.
+
+    versions are restored from the journal, so they carry on across restarts. when the journal is compacted, only the
+    latest version of every row is kept, so a subscriber can't resume from a version before the last compaction
+*/
+
+use {
+    super::{
+        delta::{DataDeltaKind, DeltaVersion},
+        ModelData,
+    },
+    crate::engine::{
+        core::{
+            dml::encode_cell,
+            index::{DcFieldIndex, PrimaryIndexKey},
+        },
+        data::{
+            cell::{Datacell, VirtualDatacell},
+            tag::FullTag,
+        },
+        error::{QueryError, QueryResult},
+        fractal::{GlobalInstanceLike, ModelUniqueIDRef},
+        idx::{STIndex, STIndexSeq},
+        mem::IntegerRepr,
+        net::protocol::{Response, ResponseType},
+        ql::dml::sub::SubscribeStatement,
+        storage::safe_interfaces::{paths_v1, RecordedChange, RecordedChanges},
+    },
+    parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+    std::{fmt, sync::Arc, vec},
+    tokio::sync::broadcast::{self, error::RecvError},
+};
+
+/// The number of changes that a subscriber can fall behind the feed before it misses changes (and its subscription
+/// fails)
+const FEED_CAPACITY: usize = 4096;
+
+/// A model's change feed
+#[derive(Debug)]
+pub struct ChangeFeed {
+    tx: broadcast::Sender<Arc<ChangeEvent>>,
+    /// held by writers from before they get their versions until they've published their changes
+    gate: RwLock<()>,
+}
+
+/// A writer's hold on a change feed (see [`ChangeFeed::hold`])
+pub enum FeedGate<'a> {
+    /// the feed had no subscribers, so the changes don't need to be captured
+    Shared(RwLockReadGuard<'a, ()>),
+    /// the feed has subscribers, so the changes are captured and published
+    Exclusive(RwLockWriteGuard<'a, ()>),
+}
+
+impl<'a> FeedGate<'a> {
+    /// Returns true if the writer needs to capture its changes (and publish them)
+    pub fn captures(&self) -> bool {
+        matches!(self, Self::Exclusive(_))
+    }
+}
+
+impl ChangeFeed {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(FEED_CAPACITY);
+        Self {
+            tx,
+            gate: RwLock::new(()),
+        }
+    }
+    /// Returns true if there are subscribers
+    pub fn is_active(&self) -> bool {
+        self.tx.receiver_count() != 0
+    }
+    /// Hold the feed for a write, before getting any versions. Writers only hold the feed exclusively (and hence one at
+    /// a time) while it has subscribers, so that their changes are published in the order in which they were made
+    pub fn hold(&self) -> FeedGate<'_> {
+        let shared = self.gate.read();
+        if !self.is_active() {
+            return FeedGate::Shared(shared);
+        }
+        drop(shared);
+        FeedGate::Exclusive(self.gate.write())
+    }
+    /// Keep writers out of the feed, so that no versions are given out (or changes published) in the meantime
+    pub fn freeze(&self) -> RwLockWriteGuard<'_, ()> {
+        self.gate.write()
+    }
+    /// Publish changes, in the order in which they were made, while still holding the feed
+    pub fn publish(&self, gate: &FeedGate, events: Vec<ChangeEvent>) {
+        debug_assert!(gate.captures());
+        for event in events {
+            // no subscribers is fine
+            let _ = self.tx.send(Arc::new(event));
+        }
+    }
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<ChangeEvent>> {
+        self.tx.subscribe()
+    }
+}
+
+/// A change to a model, encoded as a row with five cells: the version of the change, the kind of change
+/// (`insert`, `upsert`, `update`, `delete` or `truncate`), the primary key of the changed row and the pre- and
+/// post-images of the row. An image is a list with all the fields of the row in the model's field order, or a
+/// null if there was (or is) no row. A truncation has no key or images
+#[derive(Debug, PartialEq)]
+pub struct ChangeEvent {
+    version: u64,
+    data: Vec<u8>,
+}
+
+impl ChangeEvent {
+    const CELL_COUNT: usize = 5;
+    /// A change to a row
+    pub(in crate::engine::core) fn row(
+        mdl: &ModelData,
+        version: DeltaVersion,
+        kind: DataDeltaKind,
+        pk: &PrimaryIndexKey,
+        pre: Option<&DcFieldIndex>,
+        post: Option<&DcFieldIndex>,
+    ) -> Self {
+        let mut data = Self::encode_head(version, kind_name(kind));
+        encode_cell(&mut data, &VirtualDatacell::new_pk(pk, mdl.p_tag()));
+        encode_image(&mut data, mdl, pk, pre);
+        encode_image(&mut data, mdl, pk, post);
+        Self {
+            version: version.value_u64(),
+            data,
+        }
+    }
+    /// A truncation of the model
+    pub(in crate::engine::core) fn truncate(version: DeltaVersion) -> Self {
+        let mut data = Self::encode_head(version, "truncate");
+        // key, pre-image and post-image
+        (0..3).for_each(|_| encode_cell(&mut data, &Datacell::null()));
+        Self {
+            version: version.value_u64(),
+            data,
+        }
+    }
+    /// A change that was read back from the model's journal (once the model is no longer held)
+    fn recorded(shape: &RowShape, change: RecordedChange) -> Self {
+        let (txn_id, kind, pk, data) = match change {
+            RecordedChange::Truncate(version) => return Self::truncate(version),
+            RecordedChange::Row {
+                txn_id,
+                kind,
+                pk,
+                data,
+            } => (txn_id, kind, pk, data),
+        };
+        let mut out = Self::encode_head(txn_id, kind_name(kind));
+        let pkdc = VirtualDatacell::new_pk(&pk, shape.p_tag);
+        encode_cell(&mut out, &pkdc);
+        // no pre-image
+        encode_cell(&mut out, &Datacell::null());
+        match data {
+            Some(data) => {
+                // the row has the fields other than the primary key (a row written before a field was added is short)
+                let mut data = data.into_iter();
+                encode_list_head(&mut out, shape.field_count);
+                for i in 0..shape.field_count {
+                    if i == shape.pk_pos {
+                        encode_cell(&mut out, &pkdc);
+                    } else {
+                        encode_cell(&mut out, &data.next().unwrap_or_else(Datacell::null));
+                    }
+                }
+            }
+            None => encode_cell(&mut out, &Datacell::null()),
+        }
+        Self {
+            version: txn_id.value_u64(),
+            data: out,
+        }
+    }
+    fn encode_head(version: DeltaVersion, kind: &str) -> Vec<u8> {
+        let mut data = Vec::new();
+        encode_cell(&mut data, &Datacell::new_uint_default(version.value_u64()));
+        encode_cell(&mut data, &Datacell::new_str(kind.into()));
+        data
+    }
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+    pub fn response(&self) -> Response {
+        Response::Serialized {
+            ty: ResponseType::Row,
+            size: Self::CELL_COUNT,
+            data: self.data.clone(),
+        }
+    }
+}
+
+/// Copy the fields of a row (for an image)
+pub(in crate::engine::core) fn copy_fields(fields: &DcFieldIndex) -> DcFieldIndex {
+    fields
+        .iter()
+        .map(|(key, dc)| {
+            (
+                unsafe {
+                    // UNSAFE(@ohsayan): the image never outlives the model
+                    key.clone()
+                },
+                dc.clone(),
+            )
+        })
+        .collect()
+}
+
+fn kind_name(kind: DataDeltaKind) -> &'static str {
+    match kind {
+        DataDeltaKind::Insert => "insert",
+        DataDeltaKind::Upsert => "upsert",
+        DataDeltaKind::Update => "update",
+        DataDeltaKind::Delete => "delete",
+    }
+}
+
+fn encode_list_head(data: &mut Vec<u8>, len: usize) {
+    data.push(ResponseType::List.value_u8());
+    IntegerRepr::scoped(len as u64, |b| data.extend(b));
+    data.push(b'\n');
+}
+
+/// Encode an image as a list with all the fields in the model's field order (a field that is missing because of
+/// an unresolved schema delta is a null)
+fn encode_image(
+    data: &mut Vec<u8>,
+    mdl: &ModelData,
+    pk: &PrimaryIndexKey,
+    fields: Option<&DcFieldIndex>,
+) {
+    let Some(fields) = fields else {
+        return encode_cell(data, &Datacell::null());
+    };
+    encode_list_head(data, mdl.fields().len());
+    let pkdc = VirtualDatacell::new_pk(pk, mdl.p_tag());
+    let null = Datacell::null();
+    for key in mdl.fields().stseq_ord_key() {
+        let dc = match fields.st_get(key.as_str()) {
+            Some(dc) => dc,
+            None if key.as_str() == mdl.p_key() => &*pkdc,
+            None => &null,
+        };
+        encode_cell(data, dc);
+    }
+}
+
+/// A subscription to a model's change feed. Replayed changes (if any) are returned before the live ones
+pub struct Subscription {
+    /// the version that the subscription started from
+    start: u64,
+    replay: vec::IntoIter<ChangeEvent>,
+    live: broadcast::Receiver<Arc<ChangeEvent>>,
+}
+
+impl Subscription {
+    /// Returns the version that the subscription started from (the first change has this version or a newer one)
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+    /// Wait for the next change. Fails if the subscriber fell behind the feed (and missed changes) or if the model
+    /// was dropped
+    pub async fn next(&mut self) -> QueryResult<Arc<ChangeEvent>> {
+        if let Some(event) = self.replay.next() {
+            return Ok(Arc::new(event));
+        }
+        match self.live.recv().await {
+            Ok(event) => Ok(event),
+            Err(RecvError::Lagged(_)) => Err(QueryError::QExecSubscriptionLagged),
+            Err(RecvError::Closed) => Err(QueryError::QExecObjectNotFound),
+        }
+    }
+}
+
+impl fmt::Debug for Subscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscription")
+            .field("start", &self.start)
+            .field("replay", &self.replay.len())
+            .finish()
+    }
+}
+
+impl PartialEq for Subscription {
+    fn eq(&self, other: &Self) -> bool {
+        // a subscription is only equal to itself
+        std::ptr::eq(self, other)
+    }
+}
+
+/// The shape of a model's rows, for encoding the changes that are replayed from its journal
+struct RowShape {
+    p_tag: FullTag,
+    field_count: usize,
+    /// the position of the primary key among the fields
+    pk_pos: usize,
+}
+
+impl RowShape {
+    fn of(mdl: &ModelData) -> Self {
+        Self {
+            p_tag: mdl.p_tag(),
+            field_count: mdl.fields().len(),
+            pk_pos: mdl
+                .fields()
+                .stseq_ord_key()
+                .position(|key| key.as_str() == mdl.p_key())
+                .unwrap(),
+        }
+    }
+}
+
+impl ModelData {
+    /// Subscribe to a model's change feed. If a version to start from is given, the changes since that version
+    /// (inclusive) are replayed from the model's journal first
+    pub fn exec_subscribe<G: GlobalInstanceLike>(
+        global: &G,
+        stmt: SubscribeStatement,
+    ) -> QueryResult<Subscription> {
+        let entity = stmt.entity();
+        let Some(space_uuid) = global
+            .state()
+            .namespace()
+            .idx()
+            .read()
+            .get(entity.space())
+            .map(|space| space.get_uuid())
+        else {
+            return Err(QueryError::QExecObjectNotFound);
+        };
+        let models = global.state().namespace().idx_models().read();
+        let Some(model) = models.get(&entity) else {
+            return Err(QueryError::QExecObjectNotFound);
+        };
+        let mdl = model.data();
+        // no versions are given out (or changes published) while we hold the feed, so every change before the current
+        // version is either already in the journal (once we've flushed it) or is published to the receiver
+        let frozen = mdl.change_feed().freeze();
+        let live = mdl.change_feed().subscribe();
+        let current = mdl.delta_state().data_current_version().value_u64();
+        let log = match stmt.from() {
+            None => None,
+            Some(from) if from > current => {
+                return Err(QueryError::QExecSubscriptionVersionUnavailable)
+            }
+            Some(_) => Some(model.driver().open_changes(
+                global,
+                ModelUniqueIDRef::new(entity.space(), entity.entity(), mdl.get_uuid()),
+                mdl,
+                &paths_v1::model_path(entity.space(), space_uuid, entity.entity(), mdl.get_uuid()),
+            )?),
+        };
+        let shape = RowShape::of(mdl);
+        // the journal is read without holding the model
+        drop(frozen);
+        drop(models);
+        let replay = match (log, stmt.from()) {
+            (Some(log), Some(from)) => {
+                let RecordedChanges { since, mut changes } = log.read()?;
+                if from < since.value_u64() {
+                    // the changes before the last compaction are gone
+                    return Err(QueryError::QExecSubscriptionVersionUnavailable);
+                }
+                changes.retain(|change| (from..current).contains(&change.txn_id().value_u64()));
+                /*
+                    deltas aren't always written in version order (say, when a write is retried) and a row might be
+                    written twice with the same version (once ahead of its delta, see `TxnModelBatch`). the sort is
+                    stable, so we keep the change that was written last
+                */
+                changes.reverse();
+                changes.sort_by_key(|change| change.txn_id());
+                changes.dedup_by(|a, b| (a.txn_id() == b.txn_id()) & (a.pk() == b.pk()));
+                changes
+                    .into_iter()
+                    .map(|change| ChangeEvent::recorded(&shape, change))
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        Ok(Subscription {
+            start: stmt.from().unwrap_or(current),
+            replay: replay.into_iter(),
+            live,
+        })
+    }
+}
@@ -25,13 +25,16 @@
 */
 
 use {
-    super::ModelData,
-    crate::engine::{
-        core::{dml::QueryExecMeta, index::Row},
-        fractal::{FractalToken, GlobalInstanceLike},
-        mem::RawStr,
-        sync::atm::Guard,
-        sync::queue::Queue,
+    super::{FieldDefault, ModelData},
+    crate::{
+        engine::{
+            core::{dml::QueryExecMeta, index::Row},
+            fractal::{FractalToken, GlobalInstanceLike},
+            mem::RawStr,
+            sync::atm::Guard,
+            sync::queue::Queue,
+        },
+        util::os,
     },
     std::{
         collections::btree_map::{BTreeMap, Range},
@@ -62,9 +65,11 @@ impl DeltaState {
             data_deltas_size: AtomicUsize::new(0),
         }
     }
-    pub fn __set_delta_version(&self, version: DeltaVersion) {
+    /// Make sure that the next data delta gets at least the given version (batches aren't always restored in version
+    /// order, so the version counter is never moved back)
+    pub fn __raise_delta_version(&self, version: DeltaVersion) {
         self.data_current_version
-            .store(version.value_u64(), Ordering::Relaxed)
+            .fetch_max(version.value_u64(), Ordering::Relaxed);
     }
 }
 
@@ -83,6 +88,7 @@ impl DeltaState {
 
 // data
 impl DeltaState {
+    #[cfg(test)]
     pub fn append_new_data_delta_with(
         &self,
         kind: DataDeltaKind,
@@ -92,10 +98,23 @@ impl DeltaState {
     ) -> usize {
         self.append_new_data_delta(DataDelta::new(data_version, row, kind), g)
     }
+    /// Returns the number of data deltas that haven't been taken by the fractal manager yet
+    #[cfg(test)]
+    pub fn __data_delta_count(&self) -> usize {
+        self.data_deltas_size.load(Ordering::Acquire)
+    }
     pub fn append_new_data_delta(&self, delta: DataDelta, g: &Guard) -> usize {
         self.data_deltas.blocking_enqueue(delta, g);
         self.data_deltas_size.fetch_add(1, Ordering::Release) + 1
     }
+    /// Append a group of deltas (in order), returning the delta hint
+    pub fn append_new_data_deltas(&self, deltas: Vec<DataDelta>, g: &Guard) -> usize {
+        let count = deltas.len();
+        deltas
+            .into_iter()
+            .for_each(|delta| self.data_deltas.blocking_enqueue(delta, g));
+        self.data_deltas_size.fetch_add(count, Ordering::Release) + count
+    }
     pub fn create_new_data_delta_version(&self) -> DeltaVersion {
         DeltaVersion(self.__data_delta_step())
     }
@@ -108,6 +127,11 @@ impl DeltaState {
     pub fn __data_delta_dequeue(&self, g: &Guard) -> Option<DataDelta> {
         self.data_deltas.blocking_try_dequeue(g)
     }
+    /// Returns true if there are deltas in the queue (including any that the fractal manager has already taken a
+    /// count of but hasn't written yet)
+    pub fn __data_delta_pending(&self, g: &Guard) -> bool {
+        !self.data_deltas.is_empty(g)
+    }
 }
 
 // schema
@@ -121,12 +145,16 @@ impl DeltaState {
     pub fn schema_current_version(&self) -> DeltaVersion {
         DeltaVersion(self.schema_current_version)
     }
-    #[cfg(test)]
+    /// Returns the version that the next data delta will get
     pub fn data_current_version(&self) -> DeltaVersion {
         DeltaVersion(self.data_current_version.load(Ordering::Acquire))
     }
-    pub fn unresolved_append_field_add(&mut self, field_name: RawStr) {
-        self.__schema_append_unresolved_delta(SchemaDeltaPart::field_add(field_name));
+    pub fn unresolved_append_field_add(
+        &mut self,
+        field_name: RawStr,
+        default: Option<FieldDefault>,
+    ) {
+        self.__schema_append_unresolved_delta(SchemaDeltaPart::field_add(field_name, default));
     }
     pub fn unresolved_append_field_rem(&mut self, field_name: RawStr) {
         self.__schema_append_unresolved_delta(SchemaDeltaPart::field_rem(field_name));
@@ -187,7 +215,8 @@ impl SchemaDeltaPart {
 
 #[derive(Debug)]
 pub enum SchemaDeltaKind {
-    FieldAdd(RawStr),
+    /// a field was added (rows get the field's default, if it has one)
+    FieldAdd(RawStr, Option<FieldDefault>),
     FieldRem(RawStr),
 }
 
@@ -195,8 +224,8 @@ impl SchemaDeltaPart {
     fn new(kind: SchemaDeltaKind) -> Self {
         Self { kind }
     }
-    fn field_add(field_name: RawStr) -> Self {
-        Self::new(SchemaDeltaKind::FieldAdd(field_name))
+    fn field_add(field_name: RawStr, default: Option<FieldDefault>) -> Self {
+        Self::new(SchemaDeltaKind::FieldAdd(field_name, default))
     }
     fn field_rem(field_name: RawStr) -> Self {
         Self::new(SchemaDeltaKind::FieldRem(field_name))
@@ -212,14 +241,17 @@ pub struct DataDelta {
     data_version: DeltaVersion,
     row: Row,
     change: DataDeltaKind,
+    /// the time at which the change was made (see [`os::get_commit_time`])
+    committed_at: u64,
 }
 
 impl DataDelta {
-    pub const fn new(data_version: DeltaVersion, row: Row, change: DataDeltaKind) -> Self {
+    pub fn new(data_version: DeltaVersion, row: Row, change: DataDeltaKind) -> Self {
         Self {
             data_version,
             row,
             change,
+            committed_at: os::get_commit_time(),
         }
     }
     pub fn data_version(&self) -> DeltaVersion {
@@ -231,6 +263,9 @@ impl DataDelta {
     pub fn change(&self) -> DataDeltaKind {
         self.change
     }
+    pub fn committed_at(&self) -> u64 {
+        self.committed_at
+    }
 }
 
 #[derive(Debug, Clone, Copy, sky_macros::EnumMethods, PartialEq)]
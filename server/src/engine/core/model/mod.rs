@@ -25,31 +25,48 @@
 */
 
 pub(super) mod alt;
+mod cdc;
 pub(in crate::engine) mod delta;
+mod props;
+mod sidx;
 
 use {
-    super::index::PrimaryIndex,
+    super::index::{PrimaryIndex, PrimaryIndexKind, SecondaryIndexes},
     crate::engine::{
         data::{
             cell::Datacell,
             tag::{DataTag, FloatSpec, FullTag, SIntSpec, TagClass, TagSelector, UIntSpec},
             uuid::Uuid,
+            DictEntryGeneric, DictGeneric,
         },
         error::{QueryError, QueryResult},
-        fractal::{FractalModelDriver, GenericTask, GlobalInstanceLike, Task},
-        idx::{self, IndexBaseSpec, IndexSTSeqCns, STIndex, STIndexSeq},
+        fractal::{FractalModelDriver, GenericTask, GlobalInstanceLike, ModelUniqueIDRef, Task},
+        idx::{self, IndexBaseSpec, IndexSTSeqCns, MTIndex, STIndex, STIndexSeq},
         mem::{RawStr, VInline},
-        ql::ddl::{
-            crt::CreateModel,
-            drop::DropModel,
-            syn::{FieldSpec, LayerSpec},
+        ql::{
+            ddl::{
+                crt::CreateModel,
+                drop::DropModel,
+                syn::{FieldSpec, LayerSpec},
+                TruncateModel,
+            },
+            dml::ins,
         },
+        storage::{safe_interfaces::TruncateModelBatch, BatchStats},
+        sync::atm::cpin,
         txn::{gns, ModelIDRef, SpaceIDRef},
     },
     std::collections::hash_map::{Entry, HashMap},
 };
 
-pub(in crate::engine::core) use self::delta::{DeltaState, DeltaVersion, SchemaDeltaKind};
+pub(in crate::engine::core) use self::{
+    cdc::copy_fields,
+    delta::{DeltaState, DeltaVersion, SchemaDeltaKind},
+};
+pub use self::{
+    cdc::{ChangeEvent, ChangeFeed, FeedGate, Subscription},
+    props::LayerProps,
+};
 
 use super::util::{EntityID, EntityIDRef};
 type Fields = IndexSTSeqCns<RawStr, Field>;
@@ -78,6 +95,38 @@ impl Model {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// When a write to a model is acknowledged, relative to its changes being written to the model's journal
+pub enum Durability {
+    /// the write is acknowledged right away and its changes are written by the fractal manager later
+    #[default]
+    Async,
+    /// the write is acknowledged once its changes have been written and synced, along with those of any other
+    /// writes that came in while the previous group was being written
+    GroupCommit,
+    /// the write is acknowledged once its changes have been written and synced on their own
+    Sync,
+}
+
+impl Durability {
+    pub const PROP_KEY: &'static str = "durability";
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "async" => Some(Self::Async),
+            "group-commit" => Some(Self::GroupCommit),
+            "sync" => Some(Self::Sync),
+            _ => None,
+        }
+    }
+    pub fn name_str(&self) -> &'static str {
+        match self {
+            Self::Async => "async",
+            Self::GroupCommit => "group-commit",
+            Self::Sync => "sync",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ModelData {
     uuid: Uuid,
@@ -85,9 +134,15 @@ pub struct ModelData {
     p_tag: FullTag,
     fields: Fields,
     data: PrimaryIndex,
+    indexes: SecondaryIndexes,
     delta: DeltaState,
     private: ModelPrivate,
     decl: String,
+    /// the default time-to-live (in seconds) for new rows; zero if rows don't expire by default
+    ttl: u64,
+    /// the durability of writes that don't set their own
+    durability: Durability,
+    feed: ChangeFeed,
 }
 
 #[cfg(test)]
@@ -97,10 +152,21 @@ impl PartialEq for ModelData {
             && self.p_key == m.p_key
             && self.p_tag == m.p_tag
             && self.fields == m.fields
+            && self.data.kind() == m.data.kind()
+            && self.ttl == m.ttl
+            && self.durability == m.durability
     }
 }
 
 impl ModelData {
+    pub const PROP_TTL: &'static str = "ttl";
+    /// the largest time-to-live (in seconds) for a model or a row (so that an expiry time in milliseconds can't
+    /// overflow)
+    pub const MAX_TTL: u64 = u64::MAX / 1000 / 2;
+    /// Check if the time-to-live (in seconds) is valid
+    pub fn ttl_okay(ttl: u64) -> bool {
+        (ttl != 0) & (ttl <= Self::MAX_TTL)
+    }
     pub fn get_uuid(&self) -> Uuid {
         self.uuid
     }
@@ -110,6 +176,38 @@ impl ModelData {
     pub fn p_tag(&self) -> FullTag {
         self.p_tag
     }
+    /// Returns the default time-to-live (in seconds) for new rows, if set
+    pub fn ttl(&self) -> Option<u64> {
+        (self.ttl != 0).then_some(self.ttl)
+    }
+    /// Set the default time-to-live for new rows (used on restore)
+    pub fn with_ttl(mut self, ttl: Option<u64>) -> Self {
+        self.ttl = ttl.unwrap_or(0);
+        if self.ttl != 0 {
+            self.data.set_expires_rows();
+        }
+        self
+    }
+    /// Returns the durability of writes that don't set their own
+    pub fn durability(&self) -> Durability {
+        self.durability
+    }
+    /// Set the durability of writes that don't set their own (used on restore)
+    pub fn with_durability(self, durability: Durability) -> Self {
+        Self { durability, ..self }
+    }
+    /// Returns the expiry time for a new row with the given time-to-live (or the model's default). Returns zero
+    /// if the row doesn't expire
+    pub fn row_expiry(&self, ttl: Option<u64>) -> QueryResult<u64> {
+        match ttl.or(self.ttl()) {
+            Some(ttl) if Self::ttl_okay(ttl) => {
+                self.data.set_expires_rows();
+                Ok(super::index::expiry_now() + ttl * 1000)
+            }
+            Some(_) => Err(QueryError::QExecDmlValidationError),
+            None => Ok(0),
+        }
+    }
     fn is_pk(&self, new: &str) -> bool {
         self.p_key.as_bytes() == new.as_bytes()
     }
@@ -126,9 +224,15 @@ impl ModelData {
     pub fn primary_index(&self) -> &PrimaryIndex {
         &self.data
     }
+    pub fn secondary_indexes(&self) -> &SecondaryIndexes {
+        &self.indexes
+    }
     pub fn delta_state(&self) -> &DeltaState {
         &self.delta
     }
+    pub fn change_feed(&self) -> &ChangeFeed {
+        &self.feed
+    }
     pub fn fields(&self) -> &Fields {
         &self.fields
     }
@@ -155,18 +259,21 @@ impl ModelData {
             }
             ret.push_str(&field_name);
             ret.push(':');
-            // TODO(@ohsayan): it's all lists right now, so this is okay but fix it later
-            if field_decl.layers().len() == 1 {
-                ret.push_str(field_decl.layers()[0].tag().tag_selector().name_str());
-            } else {
-                ret.push_str(&"[".repeat(field_decl.layers().len() - 1));
-                ret.push_str(
-                    field_decl.layers()[field_decl.layers().len() - 1]
-                        .tag()
-                        .tag_selector()
-                        .name_str(),
-                );
-                ret.push_str(&"]".repeat(field_decl.layers().len() - 1))
+            // nested layers are wrapped in `[]` for lists and `{}` for dicts and layer properties trail the layer
+            // that they belong to (`[string{maxlen:64}]{maxlen:10}`)
+            let (innermost, outer) = field_decl.layers().split_last().unwrap();
+            for layer in outer {
+                ret.push(Self::describe_delim(layer).0);
+            }
+            ret.push_str(innermost.tag().tag_selector().name_str());
+            if let Some(props) = innermost.props() {
+                props.describe(&mut ret);
+            }
+            for layer in outer.iter().rev() {
+                ret.push(Self::describe_delim(layer).1);
+                if let Some(props) = layer.props() {
+                    props.describe(&mut ret);
+                }
             }
             if it.peek().is_some() {
                 ret.push(',');
@@ -175,6 +282,12 @@ impl ModelData {
         ret.push('}');
         ret
     }
+    fn describe_delim(layer: &Layer) -> (char, char) {
+        match layer.tag().tag_class() {
+            TagClass::Dict => ('{', '}'),
+            _ => ('[', ']'),
+        }
+    }
 }
 
 impl ModelData {
@@ -184,16 +297,21 @@ impl ModelData {
         p_tag: FullTag,
         fields: Fields,
         private: ModelPrivate,
+        index: PrimaryIndexKind,
     ) -> Self {
         let mut slf = Self {
             uuid,
             p_key,
             p_tag,
             fields,
-            data: PrimaryIndex::new_empty(),
+            data: PrimaryIndex::new_empty(index),
+            indexes: SecondaryIndexes::default(),
             delta: DeltaState::new_resolved(),
             private,
             decl: String::new(),
+            ttl: 0,
+            durability: Durability::default(),
+            feed: ChangeFeed::new(),
         };
         slf.sync_decl();
         slf
@@ -203,6 +321,7 @@ impl ModelData {
         p_key: Box<str>,
         p_tag: FullTag,
         decl_fields: IndexSTSeqCns<Box<str>, Field>,
+        index: PrimaryIndexKind,
     ) -> Self {
         let mut private = ModelPrivate::empty();
         let p_key = unsafe {
@@ -224,18 +343,88 @@ impl ModelData {
             .for_each(|(field_key, field)| {
                 fields.st_insert(field_key, field);
             });
-        Self::new_with_private(uuid, p_key, p_tag, fields, private)
+        Self::new_with_private(uuid, p_key, p_tag, fields, private, index)
+    }
+    /// Restore a model along with its properties (see [`Self::props`])
+    pub fn new_restore_with_props(
+        uuid: Uuid,
+        p_key: Box<str>,
+        p_tag: FullTag,
+        decl_fields: IndexSTSeqCns<Box<str>, Field>,
+        mut props: DictGeneric,
+    ) -> Option<Self> {
+        let (index, ttl, durability) = Self::take_props(&mut props)?;
+        props.is_empty().then(|| {
+            Self::new_restore(uuid, p_key, p_tag, decl_fields, index)
+                .with_ttl(ttl)
+                .with_durability(durability)
+        })
+    }
+    /// Returns the model's properties (other than the fields) as a dictionary, leaving out the ones that are set to
+    /// their defaults
+    pub fn props(&self) -> DictGeneric {
+        let mut props = DictGeneric::new();
+        let index = self.data.kind();
+        if index != PrimaryIndexKind::default() {
+            props.insert(
+                PrimaryIndexKind::PROP_KEY.into(),
+                DictEntryGeneric::Data(Datacell::new_str(index.name_str().into())),
+            );
+        }
+        if let Some(ttl) = self.ttl() {
+            props.insert(
+                Self::PROP_TTL.into(),
+                DictEntryGeneric::Data(Datacell::new_uint_default(ttl)),
+            );
+        }
+        if self.durability != Durability::default() {
+            props.insert(
+                Durability::PROP_KEY.into(),
+                DictEntryGeneric::Data(Datacell::new_str(self.durability.name_str().into())),
+            );
+        }
+        props
+    }
+    /// Take the model's properties (the kind of primary index, the default time-to-live for rows and the default
+    /// durability) out of the given dictionary, using the defaults for the ones that aren't set. Returns `None` if
+    /// any of them is invalid
+    fn take_props(props: &mut DictGeneric) -> Option<(PrimaryIndexKind, Option<u64>, Durability)> {
+        let index = match props.remove(PrimaryIndexKind::PROP_KEY) {
+            None => PrimaryIndexKind::default(),
+            Some(DictEntryGeneric::Data(d)) if d.kind() == TagClass::Str => {
+                PrimaryIndexKind::from_name(d.str())?
+            }
+            _ => return None,
+        };
+        let ttl = match props.remove(Self::PROP_TTL) {
+            None => None,
+            Some(DictEntryGeneric::Data(d))
+                if (d.kind() == TagClass::UnsignedInt) && Self::ttl_okay(d.uint()) =>
+            {
+                Some(d.uint())
+            }
+            _ => return None,
+        };
+        let durability = match props.remove(Durability::PROP_KEY) {
+            None => Durability::default(),
+            Some(DictEntryGeneric::Data(d)) if d.kind() == TagClass::Str => {
+                Durability::from_name(d.str())?
+            }
+            _ => return None,
+        };
+        Some((index, ttl, durability))
     }
     pub fn process_create(
         CreateModel {
             model_name: _,
             fields,
-            props,
+            mut props,
             ..
         }: CreateModel,
     ) -> QueryResult<Self> {
         let mut private = ModelPrivate::empty();
-        let mut okay = props.is_empty() & !fields.is_empty();
+        let model_props = Self::take_props(&mut props);
+        let mut okay = model_props.is_some() & props.is_empty() & !fields.is_empty();
         // validate fields
         let mut field_spec = fields.into_iter();
         let mut fields = Fields::idx_init_cap(field_spec.len());
@@ -271,13 +460,17 @@ impl ModelData {
             });
             let tag = fields.st_get(&last_pk).unwrap().layers()[0].tag;
             if tag.tag_unique().is_unique() {
+                let (index, ttl, durability) = model_props.unwrap();
                 return Ok(Self::new_with_private(
                     Uuid::new(),
                     last_pk,
                     tag,
                     fields,
                     private,
-                ));
+                    index,
+                )
+                .with_ttl(ttl)
+                .with_durability(durability));
             }
         }
         Err(QueryError::QExecDdlModelBadDefinition)
@@ -401,6 +594,11 @@ impl ModelData {
                 // update global state
                 let _ = models_idx.remove(&EntityIDRef::new(&space_name, &model_name));
                 let _ = space.models_mut().remove(model_name);
+                global
+                    .state()
+                    .namespace()
+                    .sys_db()
+                    .__raw_purge_grants(space_name, Some(model_name));
                 if stmt.if_exists {
                     Ok(Some(true))
                 } else {
@@ -408,6 +606,47 @@ impl ModelData {
                 }
             })
     }
+    pub fn transactional_exec_truncate<G: GlobalInstanceLike>(
+        global: &G,
+        stmt: TruncateModel,
+    ) -> QueryResult<()> {
+        // get exclusive lock on models (NB: no query or background sync can run against the model while we truncate it)
+        let models_idx = global.state().namespace().idx_models().write();
+        let Some(model) = models_idx.get(&stmt.entity) else {
+            return Err(QueryError::QExecObjectNotFound);
+        };
+        let mdl = model.data();
+        let gate = mdl.change_feed().hold();
+        let truncated_at = mdl.delta_state().create_new_data_delta_version();
+        // commit truncation record
+        model.driver().driver_context(
+            global,
+            ModelUniqueIDRef::new(stmt.entity.space(), stmt.entity.entity(), mdl.get_uuid()),
+            |drv| {
+                drv.commit_with_ctx(
+                    TruncateModelBatch::new(mdl, truncated_at),
+                    BatchStats::new(),
+                )
+            },
+        )?;
+        /*
+            the rows might still have pending deltas that will be synced after the truncation record. revise every row
+            so that these deltas are stale (and hence never written)
+        */
+        let g = cpin();
+        let index = mdl.primary_index().__raw_index();
+        index
+            .mt_iter_entry(&g)
+            .for_each(|row| row.d_data().write().set_txn_revised(truncated_at));
+        // update global state
+        index.mt_clear(&g);
+        mdl.secondary_indexes().lock().clear();
+        if gate.captures() {
+            mdl.change_feed()
+                .publish(&gate, vec![ChangeEvent::truncate(truncated_at)]);
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -488,10 +727,14 @@ impl<'a> ModelMutator<'a> {
         unsafe {
             // allocate
             let fkeyptr = self.model.private.push_allocated(name);
+            // existing rows are backfilled with the default
+            let default = field.default().cloned();
             // add
             let r = self.model.fields.st_insert(fkeyptr.clone(), field);
             // delta
-            self.model.delta.unresolved_append_field_add(fkeyptr);
+            self.model
+                .delta
+                .unresolved_append_field_add(fkeyptr, default);
             r
         }
     }
@@ -510,11 +753,11 @@ impl<'a> Drop for ModelMutator<'a> {
     Layer
 */
 
-static G: [u8; 15] = [0, 13, 12, 5, 6, 4, 3, 6, 1, 10, 4, 5, 7, 5, 5];
-static S1: [u8; 7] = [13, 9, 4, 14, 2, 4, 7];
-static S2: [u8; 7] = [12, 8, 2, 6, 4, 9, 9];
+static G: [u8; 18] = [0, 8, 10, 14, 0, 0, 5, 3, 13, 4, 13, 7, 4, 12, 14, 9, 3, 2];
+static S1: [u8; 7] = [15, 11, 12, 1, 3, 13, 15];
+static S2: [u8; 7] = [7, 7, 3, 8, 10, 14, 16];
 
-static LUT: [(&str, FullTag); 14] = [
+static LUT: [(&str, FullTag); 17] = [
     ("bool", FullTag::BOOL),
     ("uint8", FullTag::new_uint(TagSelector::UInt8)),
     ("uint16", FullTag::new_uint(TagSelector::UInt16)),
@@ -529,6 +772,9 @@ static LUT: [(&str, FullTag); 14] = [
     ("binary", FullTag::BIN),
     ("string", FullTag::STR),
     ("list", FullTag::LIST),
+    ("timestamp", FullTag::TIMESTAMP),
+    ("uuid", FullTag::UUID),
+    ("dict", FullTag::DICT),
 ];
 
 #[cfg(test)]
@@ -545,16 +791,61 @@ pub static TY_BINARY: &str = LUT[11].0;
 pub static TY_STRING: &str = LUT[12].0;
 #[cfg(test)]
 pub static TY_LIST: &str = LUT[13].0;
+#[cfg(test)]
+pub static TY_TIMESTAMP: &str = LUT[14].0;
+#[cfg(test)]
+pub static TY_UUID: &str = LUT[15].0;
+#[cfg(test)]
+pub static TY_DICT: &str = LUT[16].0;
+
+/// The default value of a field, used when an insert omits the field and to backfill rows when the field is
+/// added to a model
+#[derive(Debug, PartialEq, Clone)]
+pub enum FieldDefault {
+    /// a fixed value (with the field's type)
+    Value(Datacell),
+    /// a producer function (like `@timesec()`) that is called every time that a value is needed
+    Producer(Box<str>),
+}
+
+impl FieldDefault {
+    fn from_entry(entry: DictEntryGeneric) -> Option<Self> {
+        match entry {
+            DictEntryGeneric::Producer(name) => ins::producer(&name).map(|_| Self::Producer(name)),
+            entry => entry.into_data().filter(Datacell::is_init).map(Self::Value),
+        }
+    }
+    fn to_entry(&self) -> DictEntryGeneric {
+        match self {
+            Self::Value(v) => DictEntryGeneric::Data(v.clone()),
+            Self::Producer(name) => DictEntryGeneric::Producer(name.clone()),
+        }
+    }
+    /// Returns a value for a row
+    pub fn produce(&self) -> Datacell {
+        match self {
+            Self::Value(v) => v.clone(),
+            // the producer was verified when the default was set
+            Self::Producer(name) => ins::producer(name).unwrap()(),
+        }
+    }
+}
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Field {
     layers: VInline<1, Layer>,
     nullable: bool,
+    default: Option<FieldDefault>,
 }
 
 impl Field {
+    pub const PROP_DEFAULT: &'static str = "default";
     pub fn new(layers: VInline<1, Layer>, nullable: bool) -> Self {
-        Self { layers, nullable }
+        Self {
+            layers,
+            nullable,
+            default: None,
+        }
     }
     pub fn is_nullable(&self) -> bool {
         self.nullable
@@ -562,28 +853,77 @@ impl Field {
     pub fn layers(&self) -> &[Layer] {
         &self.layers
     }
-    pub fn parse_layers(spec: Vec<LayerSpec>, nullable: bool) -> QueryResult<Self> {
+    pub fn default(&self) -> Option<&FieldDefault> {
+        self.default.as_ref()
+    }
+    /// Set the default value from a property. Returns `None` if the default is invalid or produces values that
+    /// don't pass this field's validation
+    pub fn with_default(mut self, entry: DictEntryGeneric) -> Option<Self> {
+        let default = FieldDefault::from_entry(entry)?;
+        let mut sample = default.produce();
+        if !self.vt_data_fpath(&mut sample) {
+            return None;
+        }
+        self.default = Some(match default {
+            // store the value with the field's tag
+            FieldDefault::Value(_) => FieldDefault::Value(sample),
+            producer => producer,
+        });
+        Some(self)
+    }
+    /// Returns the field's properties (other than the layers and nullability) as a dictionary
+    pub fn props(&self) -> DictGeneric {
+        let mut props = DictGeneric::new();
+        if let Some(default) = &self.default {
+            props.insert(Self::PROP_DEFAULT.into(), default.to_entry());
+        }
+        props
+    }
+    /// Restore the field's properties from a dictionary (see [`Self::props`])
+    pub fn with_props(self, mut props: DictGeneric) -> Option<Self> {
+        let slf = match props.remove(Self::PROP_DEFAULT) {
+            Some(default) => self.with_default(default)?,
+            None => self,
+        };
+        props.is_empty().then_some(slf)
+    }
+    pub fn parse_layers(mut spec: Vec<LayerSpec>, nullable: bool) -> QueryResult<Self> {
+        // the default is a property of the field, but it's written with the props of the outermost layer
+        let default = spec
+            .last_mut()
+            .and_then(|layer| layer.props.remove(Self::PROP_DEFAULT));
+        let field = Self::parse_layers_only(spec, nullable)?;
+        match default {
+            Some(default) => field
+                .with_default(default)
+                .ok_or(QueryError::QExecDdlInvalidProperties),
+            None => Ok(field),
+        }
+    }
+    fn parse_layers_only(spec: Vec<LayerSpec>, nullable: bool) -> QueryResult<Self> {
         let mut layers = spec.into_iter().rev();
         let mut okay = true;
+        let mut props_okay = true;
         let mut fin = false;
         let mut layerview = VInline::new();
-        while (layers.len() != 0) & okay & !fin {
+        while (layers.len() != 0) & okay & props_okay & !fin {
             let LayerSpec { ty, props } = layers.next().unwrap();
-            okay &= props.is_empty(); // FIXME(@ohsayan): you know what to do here
             match Layer::get_layer(&ty) {
                 Some(l) => {
-                    fin = l.tag.tag_selector() != TagSelector::List;
-                    layerview.push(l);
+                    fin = l.is_final(layers.len() != 0);
+                    match l.with_props(props) {
+                        Some(l) => layerview.push(l),
+                        None => props_okay = false,
+                    }
                 }
                 None => okay = false,
             }
         }
         okay &= fin & (layers.len() == 0);
-        if okay {
-            Ok(Self {
-                layers: layerview,
-                nullable,
-            })
+        if okay & props_okay {
+            Ok(Self::new(layerview, nullable))
+        } else if okay {
+            Err(QueryError::QExecDdlInvalidProperties)
         } else {
             Err(QueryError::QExecDdlInvalidTypeDefinition)
         }
@@ -595,7 +935,7 @@ impl Field {
                 | ((self.layers[0].tag.tag_class() != dc.kind()) & !dc.is_null())
         } {
             // illegal states: (1) bad null (2) tags don't match
-            7
+            10
         } else {
             dc.kind().value_word()
         }
@@ -603,13 +943,14 @@ impl Field {
     pub fn vt_data_fpath(&self, data: &mut Datacell) -> bool {
         if (self.layers.len() == 1) | (data.is_null()) {
             layertrace("fpath");
-            unsafe { VTFN[self.compute_index(data)](self.layers()[0], data) }
+            unsafe { VTFN[self.compute_index(data)](&self.layers()[0], data) }
         } else {
             Self::rvt_data(self.layers(), data)
         }
     }
-    fn rvt_data(layers: &[Layer], data: &mut Datacell) -> bool {
-        let layer = layers[0];
+    /// Validate the data against the given layers, which must not be empty
+    pub(in crate::engine::core) fn rvt_data(layers: &[Layer], data: &mut Datacell) -> bool {
+        let layer = &layers[0];
         let layers = &layers[1..];
         match (layer.tag().tag_class(), data.kind()) {
             (TagClass::List, TagClass::List) => {
@@ -629,6 +970,28 @@ impl Field {
                 }
                 okay
             }
+            (TagClass::Dict, TagClass::Dict) => {
+                let mut okay = unsafe {
+                    // UNSAFE(@ohsayan): +tagck
+                    VTFN[TagClass::Dict.value_word()](layer, data)
+                };
+                if layers.is_empty() {
+                    // the values are dynamically typed
+                    return okay;
+                }
+                let dict = unsafe {
+                    // UNSAFE(@ohsayan): +tagck
+                    data.read_dict()
+                };
+                let mut dwrite = dict.write();
+                let mut values = dwrite.values_mut();
+                while (values.len() != 0) & okay {
+                    // typed values can't be null
+                    let value = values.next().unwrap();
+                    okay &= value.is_init() && Self::rvt_data(layers, value);
+                }
+                okay
+            }
             (tag_a, tag_b) if tag_a == tag_b => {
                 unsafe {
                     // UNSAFE(@ohsayan): same tags and lists have non-null elements
@@ -640,9 +1003,11 @@ impl Field {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Layer {
     tag: FullTag,
+    /// the constraints on the layer's values (if any)
+    props: Option<Box<LayerProps>>,
 }
 
 #[allow(unused)]
@@ -689,21 +1054,57 @@ impl Layer {
     pub const fn list() -> Self {
         Self::empty(FullTag::LIST)
     }
+    pub const fn timestamp() -> Self {
+        Self::empty(FullTag::TIMESTAMP)
+    }
+    pub const fn uuid() -> Self {
+        Self::empty(FullTag::UUID)
+    }
+    pub const fn dict() -> Self {
+        Self::empty(FullTag::DICT)
+    }
 }
 
 impl Layer {
     pub fn tag(&self) -> FullTag {
         self.tag
     }
+    pub fn props(&self) -> Option<&LayerProps> {
+        self.props.as_deref()
+    }
     pub fn new_empty_props(tag: FullTag) -> Self {
         Self::new(tag)
     }
     pub const fn new(tag: FullTag) -> Self {
-        Self { tag }
+        Self { tag, props: None }
+    }
+    /// Validate and attach the given properties to this layer. Returns `None` if the properties are invalid
+    /// for this layer
+    pub fn with_props(self, props: DictGeneric) -> Option<Self> {
+        if props.is_empty() {
+            return Some(self);
+        }
+        LayerProps::parse(self.tag, props).map(|props| Self {
+            tag: self.tag,
+            props: Some(Box::new(props)),
+        })
+    }
+    /// Check the value against this layer's properties (the value must already have this layer's type)
+    pub fn check_props(&self, dc: &Datacell) -> bool {
+        self.props().map_or(true, |props| props.check(dc))
     }
     const fn empty(tag: FullTag) -> Self {
         Self::new(tag)
     }
+    /// Returns true if this layer can't have a nested layer. A list always has one, while a dict only has one
+    /// if its values are typed
+    pub(crate) fn is_final(&self, has_nested: bool) -> bool {
+        match self.tag.tag_class() {
+            TagClass::List => false,
+            TagClass::Dict => !has_nested,
+            _ => true,
+        }
+    }
     fn hf(key: &[u8], v: [u8; 7]) -> u16 {
         let mut tot = 0;
         let mut i = 0;
@@ -711,10 +1112,10 @@ impl Layer {
             tot += v[i % v.len()] as u16 * key[i] as u16;
             i += 1;
         }
-        tot % 15
+        tot % 18
     }
     fn pf(key: &[u8]) -> u16 {
-        (G[Self::hf(key, S1) as usize] as u16 + G[Self::hf(key, S2) as usize] as u16) % 15
+        (G[Self::hf(key, S1) as usize] as u16 + G[Self::hf(key, S2) as usize] as u16) % 18
     }
     fn get_layer(ident: &str) -> Option<Self> {
         let idx = Self::pf(ident.as_bytes()) as usize;
@@ -745,44 +1146,59 @@ pub(super) fn layer_traces() -> Box<[Box<str>]> {
     local_mut!(LAYER_TRACE, |ltrace| ltrace.drain(..).collect())
 }
 
-static VTFN: [unsafe fn(Layer, &mut Datacell) -> bool; 8] = [
+static VTFN: [unsafe fn(&Layer, &mut Datacell) -> bool; 11] = [
     vt_bool,
     vt_uint,
     vt_sint,
     vt_float,
     vt_bin,
     vt_str,
+    vt_timestamp,
+    vt_uuid,
     vt_list,
+    vt_dict,
     |_, _| false,
 ];
-unsafe fn vt_bool(_: Layer, _: &mut Datacell) -> bool {
+unsafe fn vt_bool(_: &Layer, _: &mut Datacell) -> bool {
     layertrace("bool");
     true
 }
-unsafe fn vt_uint(l: Layer, dc: &mut Datacell) -> bool {
+unsafe fn vt_uint(l: &Layer, dc: &mut Datacell) -> bool {
     layertrace("uint");
     dc.set_tag(l.tag());
-    UIntSpec::from_full(l.tag()).check(dc.read_uint())
+    UIntSpec::from_full(l.tag()).check(dc.read_uint()) && l.check_props(dc)
 }
-unsafe fn vt_sint(l: Layer, dc: &mut Datacell) -> bool {
+unsafe fn vt_sint(l: &Layer, dc: &mut Datacell) -> bool {
     layertrace("sint");
     dc.set_tag(l.tag());
-    SIntSpec::from_full(l.tag()).check(dc.read_sint())
+    SIntSpec::from_full(l.tag()).check(dc.read_sint()) && l.check_props(dc)
 }
-unsafe fn vt_float(l: Layer, dc: &mut Datacell) -> bool {
+unsafe fn vt_float(l: &Layer, dc: &mut Datacell) -> bool {
     layertrace("float");
     dc.set_tag(l.tag());
-    FloatSpec::from_full(l.tag()).check(dc.read_float())
+    FloatSpec::from_full(l.tag()).check(dc.read_float()) && l.check_props(dc)
 }
-unsafe fn vt_bin(_: Layer, _: &mut Datacell) -> bool {
+unsafe fn vt_bin(l: &Layer, dc: &mut Datacell) -> bool {
     layertrace("binary");
-    true
+    l.check_props(dc)
 }
-unsafe fn vt_str(_: Layer, _: &mut Datacell) -> bool {
+unsafe fn vt_str(l: &Layer, dc: &mut Datacell) -> bool {
     layertrace("string");
-    true
+    l.check_props(dc)
+}
+unsafe fn vt_timestamp(l: &Layer, dc: &mut Datacell) -> bool {
+    layertrace("timestamp");
+    l.check_props(dc)
 }
-unsafe fn vt_list(_: Layer, _: &mut Datacell) -> bool {
+unsafe fn vt_uuid(l: &Layer, dc: &mut Datacell) -> bool {
+    layertrace("uuid");
+    l.check_props(dc)
+}
+unsafe fn vt_list(l: &Layer, dc: &mut Datacell) -> bool {
     layertrace("list");
-    true
+    l.check_props(dc)
+}
+unsafe fn vt_dict(l: &Layer, dc: &mut Datacell) -> bool {
+    layertrace("dict");
+    l.check_props(dc)
 }
@@ -0,0 +1,313 @@
+/*
+ * Created on Sun Oct 18 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+/*
+    layer properties
+    ---
+    a layer can have properties that constrain the values that it accepts:
+    - `minlen`/`maxlen`: the length of a string (in characters), binary (in bytes) or list (in elements)
+    - `min`/`max`: the value of an integer or a float
+    - `oneof`: the only values that an integer or a string can have
+    - `pattern`: a regular expression that the whole of a string must match
+    they are validated when the layer is defined and are then checked by the layer's VTFN, after the type
+*/
+
+use {
+    crate::engine::data::{
+        cell::Datacell,
+        dict::{DictEntryGeneric, DictGeneric},
+        tag::{DataTag, FloatSpec, FullTag, SIntSpec, TagClass, UIntSpec},
+    },
+    regex::Regex,
+    std::cmp::Ordering,
+};
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct LayerProps {
+    minlen: Option<u64>,
+    maxlen: Option<u64>,
+    /// the bounds (inclusive), with the layer's tag
+    min: Option<Datacell>,
+    max: Option<Datacell>,
+    /// the allowed values, with the layer's tag
+    oneof: Option<Box<[Datacell]>>,
+    pattern: Option<Pattern>,
+}
+
+/// A pattern that the whole of a string must match
+#[derive(Debug, Clone)]
+struct Pattern {
+    src: Box<str>,
+    regex: Regex,
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.src == other.src
+    }
+}
+
+impl Pattern {
+    fn new(src: &str) -> Option<Self> {
+        Regex::new(&format!("^(?:{src})$")).ok().map(|regex| Self {
+            src: src.into(),
+            regex,
+        })
+    }
+}
+
+impl LayerProps {
+    pub const MINLEN: &'static str = "minlen";
+    pub const MAXLEN: &'static str = "maxlen";
+    pub const MIN: &'static str = "min";
+    pub const MAX: &'static str = "max";
+    pub const ONEOF: &'static str = "oneof";
+    pub const PATTERN: &'static str = "pattern";
+    /// Validate the properties of a layer with the given tag. Returns `None` if a property is unknown, doesn't
+    /// apply to this layer or has an illegal value
+    pub fn parse(tag: FullTag, props: DictGeneric) -> Option<Self> {
+        let class = tag.tag_class();
+        let has_len = matches!(
+            class,
+            TagClass::Bin | TagClass::Str | TagClass::List | TagClass::Dict
+        );
+        let has_range = matches!(
+            class,
+            TagClass::UnsignedInt | TagClass::SignedInt | TagClass::Float
+        );
+        let has_oneof = matches!(
+            class,
+            TagClass::UnsignedInt | TagClass::SignedInt | TagClass::Str
+        );
+        let mut slf = Self::default();
+        for (key, value) in props {
+            let DictEntryGeneric::Data(dc) = value else {
+                return None;
+            };
+            match key.as_ref() {
+                Self::MINLEN if has_len => slf.minlen = Some(Self::len_from(&dc)?),
+                Self::MAXLEN if has_len => slf.maxlen = Some(Self::len_from(&dc)?),
+                Self::MIN if has_range => slf.min = Some(Self::value_from(tag, &dc)?),
+                Self::MAX if has_range => slf.max = Some(Self::value_from(tag, &dc)?),
+                Self::ONEOF if has_oneof & !dc.is_null() && dc.kind() == TagClass::List => {
+                    let values = dc
+                        .list()
+                        .read()
+                        .iter()
+                        .map(|v| Self::value_from(tag, v))
+                        .collect::<Option<Box<[_]>>>()?;
+                    if values.is_empty() {
+                        return None;
+                    }
+                    slf.oneof = Some(values);
+                }
+                Self::PATTERN
+                    if (class == TagClass::Str) & !dc.is_null() && dc.kind() == TagClass::Str =>
+                {
+                    slf.pattern = Some(Pattern::new(dc.str())?)
+                }
+                _ => return None,
+            }
+        }
+        let len_okay = match (slf.minlen, slf.maxlen) {
+            (Some(min), Some(max)) => min <= max,
+            _ => true,
+        };
+        let range_okay = match (&slf.min, &slf.max) {
+            (Some(min), Some(max)) => compare_values(min, max) != Some(Ordering::Greater),
+            _ => true,
+        };
+        (len_okay & range_okay).then_some(slf)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+    /// Returns the number of properties that are set
+    pub fn count(&self) -> usize {
+        self.minlen.is_some() as usize
+            + self.maxlen.is_some() as usize
+            + self.min.is_some() as usize
+            + self.max.is_some() as usize
+            + self.oneof.is_some() as usize
+            + self.pattern.is_some() as usize
+    }
+    /// Check if the value satisfies all the properties. The value must have already been validated against the
+    /// layer's type. Nulls always pass
+    pub fn check(&self, dc: &Datacell) -> bool {
+        if dc.is_null() {
+            return true;
+        }
+        let len_okay =
+            (self.minlen.is_none() & self.maxlen.is_none()) || self.check_len(Self::len_of(dc));
+        let min_okay = self.min.as_ref().map_or(true, |min| {
+            compare_values(dc, min).is_some_and(Ordering::is_ge)
+        });
+        let max_okay = self.max.as_ref().map_or(true, |max| {
+            compare_values(dc, max).is_some_and(Ordering::is_le)
+        });
+        let oneof_okay = self
+            .oneof
+            .as_ref()
+            .map_or(true, |values| values.iter().any(|v| v == dc));
+        let pattern_okay = self
+            .pattern
+            .as_ref()
+            .map_or(true, |pattern| pattern.regex.is_match(dc.str()));
+        len_okay & min_okay & max_okay & oneof_okay & pattern_okay
+    }
+    /// Check if a length satisfies the length properties
+    pub fn check_len(&self, len: u64) -> bool {
+        self.minlen.map_or(true, |min| len >= min) & self.maxlen.map_or(true, |max| len <= max)
+    }
+    /// Returns the properties as a dictionary, in the same form that they are parsed from
+    pub fn to_dict(&self) -> DictGeneric {
+        let mut props = DictGeneric::new();
+        let mut set = |key: &str, dc| {
+            props.insert(key.into(), DictEntryGeneric::Data(dc));
+        };
+        if let Some(minlen) = self.minlen {
+            set(Self::MINLEN, Datacell::new_uint_default(minlen));
+        }
+        if let Some(maxlen) = self.maxlen {
+            set(Self::MAXLEN, Datacell::new_uint_default(maxlen));
+        }
+        if let Some(min) = &self.min {
+            set(Self::MIN, min.clone());
+        }
+        if let Some(max) = &self.max {
+            set(Self::MAX, max.clone());
+        }
+        if let Some(values) = &self.oneof {
+            set(Self::ONEOF, Datacell::new_list(values.to_vec()));
+        }
+        if let Some(pattern) = &self.pattern {
+            set(Self::PATTERN, Datacell::new_str(pattern.src.clone()));
+        }
+        props
+    }
+    /// Append the properties to a model declaration (as `{minlen:1,oneof:["a","b"]}`)
+    pub fn describe(&self, decl: &mut String) {
+        let mut props = Vec::with_capacity(self.count());
+        if let Some(minlen) = self.minlen {
+            props.push(format!("{}:{minlen}", Self::MINLEN));
+        }
+        if let Some(maxlen) = self.maxlen {
+            props.push(format!("{}:{maxlen}", Self::MAXLEN));
+        }
+        if let Some(min) = &self.min {
+            props.push(format!("{}:{}", Self::MIN, describe_value(min)));
+        }
+        if let Some(max) = &self.max {
+            props.push(format!("{}:{}", Self::MAX, describe_value(max)));
+        }
+        if let Some(values) = &self.oneof {
+            let values: Vec<_> = values.iter().map(describe_value).collect();
+            props.push(format!("{}:[{}]", Self::ONEOF, values.join(",")));
+        }
+        if let Some(pattern) = &self.pattern {
+            props.push(format!("{}:{}", Self::PATTERN, quote_str(&pattern.src)));
+        }
+        decl.push('{');
+        decl.push_str(&props.join(","));
+        decl.push('}');
+    }
+    fn len_from(dc: &Datacell) -> Option<u64> {
+        (!dc.is_null() && dc.kind() == TagClass::UnsignedInt).then(|| dc.uint())
+    }
+    /// Convert a literal value into a value for the layer, if it fits
+    fn value_from(tag: FullTag, dc: &Datacell) -> Option<Datacell> {
+        if dc.is_null() {
+            return None;
+        }
+        // UNSAFE(@ohsayan): the specs are only created in the arms that match the layer's class
+        let sint = |v: i64| {
+            let spec = unsafe { SIntSpec::from_full(tag) };
+            spec.check(v).then(|| Datacell::new_sint(v, spec))
+        };
+        let float = |v: f64| {
+            let spec = unsafe { FloatSpec::from_full(tag) };
+            spec.check(v).then(|| Datacell::new_float(v, spec))
+        };
+        match (tag.tag_class(), dc.kind()) {
+            (TagClass::UnsignedInt, TagClass::UnsignedInt) => {
+                let spec = unsafe { UIntSpec::from_full(tag) };
+                spec.check(dc.uint())
+                    .then(|| Datacell::new_uint(dc.uint(), spec))
+            }
+            (TagClass::SignedInt, TagClass::UnsignedInt) => {
+                i64::try_from(dc.uint()).ok().and_then(sint)
+            }
+            (TagClass::SignedInt, TagClass::SignedInt) => sint(dc.sint()),
+            (TagClass::Float, TagClass::UnsignedInt) => float(dc.uint() as f64),
+            (TagClass::Float, TagClass::SignedInt) => float(dc.sint() as f64),
+            (TagClass::Float, TagClass::Float) => float(dc.float()),
+            (TagClass::Str, TagClass::Str) => Some(dc.clone()),
+            _ => None,
+        }
+    }
+    fn len_of(dc: &Datacell) -> u64 {
+        (match dc.kind() {
+            TagClass::Str => dc.str().chars().count(),
+            TagClass::Bin => dc.bin().len(),
+            TagClass::List => dc.list().read().len(),
+            TagClass::Dict => dc.dict().read().len(),
+            _ => 0,
+        }) as u64
+    }
+}
+
+/// Compare two numeric values of the same class
+fn compare_values(a: &Datacell, b: &Datacell) -> Option<Ordering> {
+    match (a.kind(), b.kind()) {
+        (TagClass::UnsignedInt, TagClass::UnsignedInt) => Some(a.uint().cmp(&b.uint())),
+        (TagClass::SignedInt, TagClass::SignedInt) => Some(a.sint().cmp(&b.sint())),
+        (TagClass::Float, TagClass::Float) => a.float().partial_cmp(&b.float()),
+        _ => None,
+    }
+}
+
+fn describe_value(dc: &Datacell) -> String {
+    match dc.kind() {
+        TagClass::UnsignedInt => dc.uint().to_string(),
+        TagClass::SignedInt => dc.sint().to_string(),
+        TagClass::Float => dc.float().to_string(),
+        _ => quote_str(dc.str()),
+    }
+}
+
+/// Quote a string the way it would be written in a query (only the quote and the backslash are escaped)
+fn quote_str(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        if matches!(c, '"' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
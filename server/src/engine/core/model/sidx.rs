@@ -0,0 +1,169 @@
+/*
+ * Created on Sun Oct 18 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+use {
+    super::ModelData,
+    crate::engine::{
+        core::index::{SecondaryIndex, SecondaryIndexesGuard},
+        data::tag::DataTag,
+        error::{QueryError, QueryResult},
+        fractal::GlobalInstanceLike,
+        idx::STIndex,
+        ql::ddl::{crt::CreateIndex, drop::DropIndex},
+        sync::atm::cpin,
+        txn::{gns, ModelIDRef},
+    },
+    std::ops::Bound,
+};
+
+impl ModelData {
+    /// Check if a secondary index can be created on the given field. Only non-primary key fields with
+    /// integer, binary or string values can be indexed, and only once
+    fn guard_indexable(&self, field_name: &str) -> QueryResult<()> {
+        let Some(field) = self.fields().st_get(field_name) else {
+            return Err(QueryError::QExecUnknownField);
+        };
+        if self.is_pk(field_name) | self.indexes.get_by_field(field_name).is_some() {
+            return Err(QueryError::QExecDdlModelAlterIllegal);
+        }
+        if field.layers()[0].tag().tag_unique().is_unique() {
+            Ok(())
+        } else {
+            Err(QueryError::QExecDdlInvalidTypeDefinition)
+        }
+    }
+    /// Index all the rows in the primary index. Returns false if a unique constraint was violated
+    fn populate_secondary_indexes(&self, mut indexes: SecondaryIndexesGuard) -> bool {
+        let g = cpin();
+        indexes.clear();
+        let okay = self
+            .primary_index()
+            .select_range(Bound::Unbounded, Bound::Unbounded, &g)
+            .all(|row| {
+                let data = row.resolve_schema_deltas_and_freeze(self.delta_state());
+                let values = indexes.values(data.fields());
+                indexes.insert(row.d_key(), &values)
+            });
+        okay
+    }
+    /// Rebuild the contents of all secondary indexes. This is used on restore, once all the data has been loaded
+    pub fn rebuild_secondary_indexes(&self) -> bool {
+        self.indexes.is_empty() || self.populate_secondary_indexes(self.indexes.lock())
+    }
+    /// Add a restored secondary index. Its contents must be rebuilt once the data has been loaded
+    pub fn restore_secondary_index(&mut self, index: SecondaryIndex) -> bool {
+        self.guard_indexable(index.field()).is_ok() && self.indexes.add(index)
+    }
+    pub fn restore_drop_secondary_index(&mut self, index_name: &str) -> bool {
+        self.indexes.remove(index_name)
+    }
+}
+
+impl ModelData {
+    pub fn transactional_exec_create_index<G: GlobalInstanceLike>(
+        global: &G,
+        stmt: CreateIndex,
+    ) -> QueryResult<Option<bool>> {
+        let (space_name, model_name) = (stmt.model_name.space(), stmt.model_name.entity());
+        let if_nx = stmt.if_not_exists;
+        global
+            .state()
+            .namespace()
+            .with_model_space_mut_for_ddl(stmt.model_name, |space, model| {
+                if model.indexes.get(stmt.index_name.as_str()).is_some() {
+                    if if_nx {
+                        return Ok(Some(false));
+                    } else {
+                        return Err(QueryError::QExecDdlObjectAlreadyExists);
+                    }
+                }
+                model.guard_indexable(stmt.field.as_str())?;
+                // we have exclusive access to the model, so the data can't change while we build the index
+                let index = SecondaryIndex::new_empty(
+                    stmt.index_name.as_str().into(),
+                    stmt.field.as_str().into(),
+                    stmt.unique,
+                );
+                if !model.populate_secondary_indexes(index.lock()) {
+                    return Err(QueryError::QExecDmlDuplicate);
+                }
+                // prepare txn
+                let txn = gns::model::CreateIndexTxn::new(
+                    ModelIDRef::new_ref(space_name, space, model_name, model),
+                    &index,
+                );
+                // commit txn
+                global.state().gns_driver().driver_context(
+                    global,
+                    |drv| drv.commit_event(txn),
+                    || {},
+                )?;
+                // update global state
+                model.indexes.add(index);
+                if if_nx {
+                    Ok(Some(true))
+                } else {
+                    Ok(None)
+                }
+            })
+    }
+    pub fn transactional_exec_drop_index<G: GlobalInstanceLike>(
+        global: &G,
+        stmt: DropIndex,
+    ) -> QueryResult<Option<bool>> {
+        let (space_name, model_name) = (stmt.model.space(), stmt.model.entity());
+        global
+            .state()
+            .namespace()
+            .with_model_space_mut_for_ddl(stmt.model, |space, model| {
+                if model.indexes.get(stmt.index_name.as_str()).is_none() {
+                    if stmt.if_exists {
+                        return Ok(Some(false));
+                    } else {
+                        return Err(QueryError::QExecObjectNotFound);
+                    }
+                }
+                // prepare txn
+                let txn = gns::model::DropIndexTxn::new(
+                    ModelIDRef::new_ref(space_name, space, model_name, model),
+                    stmt.index_name.as_str(),
+                );
+                // commit txn
+                global.state().gns_driver().driver_context(
+                    global,
+                    |drv| drv.commit_event(txn),
+                    || {},
+                )?;
+                // update global state
+                model.indexes.remove(stmt.index_name.as_str());
+                if stmt.if_exists {
+                    Ok(Some(true))
+                } else {
+                    Ok(None)
+                }
+            })
+    }
+}
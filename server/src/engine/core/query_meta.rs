@@ -42,4 +42,8 @@ pub enum AssignmentOperator {
     SubAssign = 2,
     MulAssign = 3,
     DivAssign = 4,
+    /// remove the element at the index in a list
+    Remove = 5,
+    /// truncate a list to the given length
+    Truncate = 6,
 }
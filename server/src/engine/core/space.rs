@@ -122,7 +122,13 @@ impl Space {
                         let _ =
                             props.insert(Self::KEY_ENV.into(), DictEntryGeneric::Map(into_dict!()));
                     }
+                    DictEntryGeneric::Map(m) if dict::rhas_producer(m) => {
+                        return Err(QueryError::QExecDdlInvalidProperties);
+                    }
                     DictEntryGeneric::Map(_) => {}
+                    DictEntryGeneric::Producer(_) => {
+                        return Err(QueryError::QExecDdlInvalidProperties);
+                    }
                 }
             }
             None if props.is_empty() => {
@@ -280,6 +286,11 @@ impl Space {
                         }
                     }
                     let _ = spaces.st_delete(space_name.as_str());
+                    global
+                        .state()
+                        .namespace()
+                        .sys_db()
+                        .__raw_purge_grants(&space_name, None);
                     if if_exists {
                         Ok(Some(true))
                     } else {
@@ -314,6 +325,11 @@ impl Space {
                     space.get_uuid(),
                 )));
                 let _ = spaces.st_delete(space_name.as_str());
+                global
+                    .state()
+                    .namespace()
+                    .sys_db()
+                    .__raw_purge_grants(&space_name, None);
                 if if_exists {
                     Ok(Some(true))
                 } else {
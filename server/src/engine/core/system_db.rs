@@ -23,36 +23,199 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>.
  *
 */
-
 use {
     super::RWLIdx,
-    crate::engine::{
-        error::{QueryError, QueryResult},
-        fractal::GlobalInstanceLike,
-        txn::gns::sysctl::{AlterUserTxn, CreateUserTxn, DropUserTxn},
+    crate::{
+        engine::{
+            error::{QueryError, QueryResult},
+            fractal::GlobalInstanceLike,
+            idx::IndexST,
+            txn::gns::sysctl::{
+                AlterUserTxn, CreateRoleTxn, CreateUserTxn, DropRoleTxn, DropUserTxn,
+                GrantPrivilegeTxn, GrantRoleTxn, RevokePrivilegeTxn, RevokeRoleTxn,
+            },
+        },
+        util::compiler::TaggedEnum,
     },
+    parking_lot::Mutex,
     std::collections::hash_map::Entry,
 };
 
 #[derive(Debug)]
 pub struct SystemDatabase {
     users: RWLIdx<Box<str>, User>,
+    roles: RWLIdx<Box<str>, Grants>,
+    /// held while a DCL change is validated and committed, so that the users and roles only need to be write
+    /// locked to apply it (and logins and authorization aren't held up by the commit)
+    ///
+    /// NB: the lock order is `dcl`, then `users` and then `roles`
+    dcl: Mutex<()>,
 }
 
 #[derive(Debug, PartialEq)]
 pub struct User {
     phash: Box<[u8]>,
+    grants: Grants,
+    roles: Vec<Box<str>>,
 }
 
 impl User {
     pub fn new(password_hash: Box<[u8]>) -> Self {
         Self {
             phash: password_hash,
+            grants: Grants::default(),
+            roles: Vec::new(),
         }
     }
     pub fn hash(&self) -> &[u8] {
         &self.phash
     }
+    pub fn grants(&self) -> &Grants {
+        &self.grants
+    }
+    /// The roles that were granted to this user
+    pub fn roles(&self) -> impl Iterator<Item = &str> {
+        self.roles.iter().map(AsRef::as_ref)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, sky_macros::TaggedEnum)]
+#[repr(u8)]
+/// A privilege that can be granted to a user or role on a space (and all its models) or on a single model
+pub enum Privilege {
+    Select = 0,
+    Insert = 1,
+    Update = 2,
+    Delete = 3,
+    /// create, alter and drop models and their indexes
+    Ddl = 4,
+}
+
+impl Privilege {
+    const fn mask(&self) -> u8 {
+        1 << *self as u8
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// The user or role that a privilege is granted to (or revoked from)
+pub enum Grantee<'a> {
+    User(&'a str),
+    Role(&'a str),
+}
+
+impl<'a> Grantee<'a> {
+    pub fn name(&self) -> &'a str {
+        match self {
+            Self::User(name) | Self::Role(name) => name,
+        }
+    }
+    pub fn is_role(&self) -> bool {
+        matches!(self, Self::Role(_))
+    }
+}
+
+#[derive(Debug, PartialEq, Default)]
+/// The privileges granted to a user or role, by space and model
+pub struct Grants {
+    spaces: IndexST<Box<str>, SpaceGrants>,
+}
+
+#[derive(Debug, PartialEq, Default)]
+struct SpaceGrants {
+    space: u8,
+    models: IndexST<Box<str>, u8>,
+}
+
+impl Grants {
+    /// Returns true if the privilege was granted on the space, or on the model (if any)
+    pub fn has(&self, space: &str, model: Option<&str>, privilege: Privilege) -> bool {
+        self.spaces.get(space).is_some_and(|sg| {
+            let mut privileges = sg.space;
+            if let Some(model) = model {
+                privileges |= sg.models.get(model).copied().unwrap_or(0);
+            }
+            privileges & privilege.mask() != 0
+        })
+    }
+    /// Returns true if any privilege was granted on the space or on the model. If no model is given, a grant
+    /// on any model in the space also counts
+    pub fn has_any(&self, space: &str, model: Option<&str>) -> bool {
+        self.spaces.get(space).is_some_and(|sg| {
+            (sg.space != 0)
+                | match model {
+                    Some(model) => sg.models.contains_key(model),
+                    None => !sg.models.is_empty(),
+                }
+        })
+    }
+    /// Returns true if the privilege was granted on exactly this space or model
+    fn has_exact(&self, space: &str, model: Option<&str>, privilege: Privilege) -> bool {
+        let privileges = self.spaces.get(space).map_or(0, |sg| match model {
+            Some(model) => sg.models.get(model).copied().unwrap_or(0),
+            None => sg.space,
+        });
+        privileges & privilege.mask() != 0
+    }
+    fn grant(&mut self, space: &str, model: Option<&str>, privilege: Privilege) {
+        let sg = self.spaces.entry(space.into()).or_default();
+        match model {
+            Some(model) => *sg.models.entry(model.into()).or_default() |= privilege.mask(),
+            None => sg.space |= privilege.mask(),
+        }
+    }
+    fn revoke(&mut self, space: &str, model: Option<&str>, privilege: Privilege) {
+        if let Some(sg) = self.spaces.get_mut(space) {
+            match model {
+                Some(model) => {
+                    if let Some(privileges) = sg.models.get_mut(model) {
+                        *privileges &= !privilege.mask();
+                        if *privileges == 0 {
+                            sg.models.remove(model);
+                        }
+                    }
+                }
+                None => sg.space &= !privilege.mask(),
+            }
+            if (sg.space == 0) & sg.models.is_empty() {
+                self.spaces.remove(space);
+            }
+        }
+    }
+    /// Remove all grants on the space (if no model is given) or on the model
+    fn purge(&mut self, space: &str, model: Option<&str>) {
+        match model {
+            Some(model) => {
+                if let Some(sg) = self.spaces.get_mut(space) {
+                    sg.models.remove(model);
+                    if (sg.space == 0) & sg.models.is_empty() {
+                        self.spaces.remove(space);
+                    }
+                }
+            }
+            None => {
+                self.spaces.remove(space);
+            }
+        }
+    }
+    /// Returns every granted privilege as `(space, model, privilege)`
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<&str>, Privilege)> {
+        fn expand(privileges: u8) -> impl Iterator<Item = Privilege> {
+            (0..Privilege::VARIANT_COUNT as u8)
+                .map(|p| unsafe {
+                    // UNSAFE(@ohsayan): the discriminant is in range
+                    Privilege::from_raw(p)
+                })
+                .filter(move |p| privileges & p.mask() != 0)
+        }
+        self.spaces.iter().flat_map(|(space, sg)| {
+            expand(sg.space)
+                .map(move |p| (space.as_ref(), None, p))
+                .chain(sg.models.iter().flat_map(move |(model, privileges)| {
+                    expand(*privileges).map(move |p| (space.as_ref(), Some(model.as_ref()), p))
+                }))
+        })
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -74,11 +237,16 @@ impl SystemDatabase {
     pub fn empty() -> Self {
         Self {
             users: RWLIdx::default(),
+            roles: RWLIdx::default(),
+            dcl: Mutex::new(()),
         }
     }
     pub fn users(&self) -> &RWLIdx<Box<str>, User> {
         &self.users
     }
+    pub fn roles(&self) -> &RWLIdx<Box<str>, Grants> {
+        &self.roles
+    }
     pub fn verify_user(&self, username: &str, password: &[u8]) -> VerifyUser {
         self.users
             .read()
@@ -101,6 +269,48 @@ impl SystemDatabase {
             })
             .unwrap_or(VerifyUser::NotFound)
     }
+    /// Returns true if the user (or one of their roles) can use the privilege on the space or model. The root
+    /// account has all privileges
+    pub fn has_privilege(
+        &self,
+        username: &str,
+        space: &str,
+        model: Option<&str>,
+        privilege: Privilege,
+    ) -> bool {
+        self.check_grants(username, |grants| grants.has(space, model, privilege))
+    }
+    /// Returns true if the user (or one of their roles) has any privilege on the space or model, which is what
+    /// is needed to see its definition. The root account can see everything
+    pub fn can_see(&self, username: &str, space: &str, model: Option<&str>) -> bool {
+        self.check_grants(username, |grants| grants.has_any(space, model))
+    }
+    fn check_grants(&self, username: &str, f: impl Fn(&Grants) -> bool) -> bool {
+        if username == Self::ROOT_ACCOUNT {
+            return true;
+        }
+        let users = self.users.read();
+        let Some(user) = users.get(username) else {
+            return false;
+        };
+        f(&user.grants) || {
+            let roles = self.roles.read();
+            user.roles
+                .iter()
+                .any(|role| roles.get(role).is_some_and(&f))
+        }
+    }
+    /// Run `f` on the grants of the user or role, if it exists
+    fn with_grants<T>(&self, grantee: Grantee, f: impl FnOnce(&mut Grants) -> T) -> Option<T> {
+        match grantee {
+            Grantee::User(username) => self
+                .users
+                .write()
+                .get_mut(username)
+                .map(|user| f(&mut user.grants)),
+            Grantee::Role(role) => self.roles.write().get_mut(role).map(f),
+        }
+    }
 }
 
 impl SystemDatabase {
@@ -125,8 +335,88 @@ impl SystemDatabase {
             None => false,
         }
     }
+    pub fn __raw_create_role(&self, role: Box<str>) -> bool {
+        match self.roles.write().entry(role) {
+            Entry::Vacant(ve) => {
+                ve.insert(Grants::default());
+                true
+            }
+            Entry::Occupied(_) => false,
+        }
+    }
+    /// Drop the role, and take it away from every user that it was granted to
+    pub fn __raw_drop_role(&self, role: &str) -> bool {
+        let mut users = self.users.write();
+        if self.roles.write().remove(role).is_none() {
+            return false;
+        }
+        users
+            .values_mut()
+            .for_each(|user| user.roles.retain(|r| r.as_ref() != role));
+        true
+    }
+    pub fn __raw_grant(
+        &self,
+        grantee: Grantee,
+        space: &str,
+        model: Option<&str>,
+        privilege: Privilege,
+    ) -> bool {
+        self.with_grants(grantee, |grants| grants.grant(space, model, privilege))
+            .is_some()
+    }
+    pub fn __raw_revoke(
+        &self,
+        grantee: Grantee,
+        space: &str,
+        model: Option<&str>,
+        privilege: Privilege,
+    ) -> bool {
+        self.with_grants(grantee, |grants| grants.revoke(space, model, privilege))
+            .is_some()
+    }
+    pub fn __raw_grant_role(&self, username: &str, role: &str) -> bool {
+        let mut users = self.users.write();
+        match users.get_mut(username) {
+            Some(user) if self.roles.read().contains_key(role) => {
+                if !user.roles.iter().any(|r| r.as_ref() == role) {
+                    user.roles.push(role.into());
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+    pub fn __raw_revoke_role(&self, username: &str, role: &str) -> bool {
+        match self.users.write().get_mut(username) {
+            Some(user) => {
+                user.roles.retain(|r| r.as_ref() != role);
+                true
+            }
+            None => false,
+        }
+    }
+    /// Remove all grants on a space (if no model is given) or on a model. This is used when the space or model
+    /// is dropped (and also while replaying the drop) so that the grants don't carry over to a new object with
+    /// the same name
+    pub fn __raw_purge_grants(&self, space: &str, model: Option<&str>) {
+        self.users
+            .write()
+            .values_mut()
+            .for_each(|user| user.grants.purge(space, model));
+        self.roles
+            .write()
+            .values_mut()
+            .for_each(|grants| grants.purge(space, model));
+    }
 }
 
+/*
+    NB: every DCL change is validated and committed under the DCL lock, and then applied with the same `__raw`
+    routine that replays it from the journal. This way the users and roles are only write locked to apply
+    the change
+*/
+
 impl SystemDatabase {
     pub fn create_user(
         &self,
@@ -134,17 +424,17 @@ impl SystemDatabase {
         username: Box<str>,
         password: &str,
     ) -> QueryResult<()> {
-        let mut users = self.users.write();
-        if users.contains_key(&username) {
+        let password_hash = rcrypt::hash(password, rcrypt::DEFAULT_COST).unwrap();
+        let _dcl = self.dcl.lock();
+        if self.users.read().contains_key(&username) {
             return Err(QueryError::SysAuthError);
         }
-        let password_hash = rcrypt::hash(password, rcrypt::DEFAULT_COST).unwrap();
         global.state().gns_driver().driver_context(
             global,
             |drv| drv.commit_event(CreateUserTxn::new(&username, &password_hash)),
             || {},
         )?;
-        users.insert(username, User::new(password_hash.into_boxed_slice()));
+        let _ = self.__raw_create_user(username, password_hash.into_boxed_slice());
         Ok(())
     }
     pub fn alter_user(
@@ -153,23 +443,22 @@ impl SystemDatabase {
         username: &str,
         password: &str,
     ) -> QueryResult<()> {
-        match self.users.write().get_mut(username) {
-            Some(user) => {
-                let password_hash = rcrypt::hash(password, rcrypt::DEFAULT_COST).unwrap();
-                global.state().gns_driver().driver_context(
-                    global,
-                    |drv| drv.commit_event(AlterUserTxn::new(username, &password_hash)),
-                    || {},
-                )?;
-                user.phash = password_hash.into_boxed_slice();
-                Ok(())
-            }
-            None => Err(QueryError::SysAuthError),
+        let password_hash = rcrypt::hash(password, rcrypt::DEFAULT_COST).unwrap();
+        let _dcl = self.dcl.lock();
+        if !self.users.read().contains_key(username) {
+            return Err(QueryError::SysAuthError);
         }
+        global.state().gns_driver().driver_context(
+            global,
+            |drv| drv.commit_event(AlterUserTxn::new(username, &password_hash)),
+            || {},
+        )?;
+        let _ = self.__raw_alter_user(username, password_hash.into_boxed_slice());
+        Ok(())
     }
     pub fn drop_user(&self, global: &impl GlobalInstanceLike, username: &str) -> QueryResult<()> {
-        let mut users = self.users.write();
-        if !users.contains_key(username) {
+        let _dcl = self.dcl.lock();
+        if !self.users.read().contains_key(username) {
             return Err(QueryError::SysAuthError);
         }
         global.state().gns_driver().driver_context(
@@ -177,7 +466,159 @@ impl SystemDatabase {
             |drv| drv.commit_event(DropUserTxn::new(username)),
             || {},
         )?;
-        let _ = users.remove(username);
+        let _ = self.__raw_delete_user(username);
+        Ok(())
+    }
+}
+
+impl SystemDatabase {
+    pub fn create_role(&self, global: &impl GlobalInstanceLike, role: &str) -> QueryResult<()> {
+        let _dcl = self.dcl.lock();
+        if self.roles.read().contains_key(role) {
+            return Err(QueryError::SysAuthError);
+        }
+        global.state().gns_driver().driver_context(
+            global,
+            |drv| drv.commit_event(CreateRoleTxn::new(role)),
+            || {},
+        )?;
+        let _ = self.__raw_create_role(role.into());
+        Ok(())
+    }
+    pub fn drop_role(&self, global: &impl GlobalInstanceLike, role: &str) -> QueryResult<()> {
+        let _dcl = self.dcl.lock();
+        if !self.roles.read().contains_key(role) {
+            return Err(QueryError::SysAuthError);
+        }
+        global.state().gns_driver().driver_context(
+            global,
+            |drv| drv.commit_event(DropRoleTxn::new(role)),
+            || {},
+        )?;
+        let _ = self.__raw_drop_role(role);
+        Ok(())
+    }
+    pub fn grant_role(
+        &self,
+        global: &impl GlobalInstanceLike,
+        username: &str,
+        role: &str,
+    ) -> QueryResult<()> {
+        if username == Self::ROOT_ACCOUNT {
+            // root already has every privilege
+            return Err(QueryError::SysAuthError);
+        }
+        let _dcl = self.dcl.lock();
+        let has_role = match self.users.read().get(username) {
+            Some(user) if self.roles.read().contains_key(role) => {
+                user.roles.iter().any(|r| r.as_ref() == role)
+            }
+            _ => return Err(QueryError::SysAuthError),
+        };
+        if has_role {
+            return Ok(());
+        }
+        global.state().gns_driver().driver_context(
+            global,
+            |drv| drv.commit_event(GrantRoleTxn::new(username, role)),
+            || {},
+        )?;
+        let _ = self.__raw_grant_role(username, role);
+        Ok(())
+    }
+    pub fn revoke_role(
+        &self,
+        global: &impl GlobalInstanceLike,
+        username: &str,
+        role: &str,
+    ) -> QueryResult<()> {
+        let _dcl = self.dcl.lock();
+        let has_role = match self.users.read().get(username) {
+            Some(user) => user.roles.iter().any(|r| r.as_ref() == role),
+            None => return Err(QueryError::SysAuthError),
+        };
+        if !has_role {
+            return Ok(());
+        }
+        global.state().gns_driver().driver_context(
+            global,
+            |drv| drv.commit_event(RevokeRoleTxn::new(username, role)),
+            || {},
+        )?;
+        let _ = self.__raw_revoke_role(username, role);
+        Ok(())
+    }
+}
+
+impl SystemDatabase {
+    /// Returns true if the privilege was granted on exactly this space or model, or `None` if there is no such
+    /// user or role
+    fn has_exact(
+        &self,
+        grantee: Grantee,
+        space: &str,
+        model: Option<&str>,
+        privilege: Privilege,
+    ) -> Option<bool> {
+        match grantee {
+            Grantee::User(username) => self
+                .users
+                .read()
+                .get(username)
+                .map(|user| user.grants.has_exact(space, model, privilege)),
+            Grantee::Role(role) => self
+                .roles
+                .read()
+                .get(role)
+                .map(|grants| grants.has_exact(space, model, privilege)),
+        }
+    }
+    pub fn grant(
+        &self,
+        global: &impl GlobalInstanceLike,
+        grantee: Grantee,
+        space: &str,
+        model: Option<&str>,
+        privilege: Privilege,
+    ) -> QueryResult<()> {
+        if grantee == Grantee::User(Self::ROOT_ACCOUNT) {
+            // root already has every privilege
+            return Err(QueryError::SysAuthError);
+        }
+        let _dcl = self.dcl.lock();
+        match self.has_exact(grantee, space, model, privilege) {
+            Some(false) => {}
+            Some(true) => return Ok(()),
+            None => return Err(QueryError::SysAuthError),
+        }
+        global.state().gns_driver().driver_context(
+            global,
+            |drv| drv.commit_event(GrantPrivilegeTxn::new(grantee, space, model, privilege)),
+            || {},
+        )?;
+        let _ = self.__raw_grant(grantee, space, model, privilege);
+        Ok(())
+    }
+    pub fn revoke(
+        &self,
+        global: &impl GlobalInstanceLike,
+        grantee: Grantee,
+        space: &str,
+        model: Option<&str>,
+        privilege: Privilege,
+    ) -> QueryResult<()> {
+        let _dcl = self.dcl.lock();
+        match self.has_exact(grantee, space, model, privilege) {
+            Some(true) => {}
+            Some(false) => return Ok(()),
+            None => return Err(QueryError::SysAuthError),
+        }
+        global.state().gns_driver().driver_context(
+            global,
+            |drv| drv.commit_event(RevokePrivilegeTxn::new(grantee, space, model, privilege)),
+            || {},
+        )?;
+        let _ = self.__raw_revoke(grantee, space, model, privilege);
         Ok(())
     }
 }
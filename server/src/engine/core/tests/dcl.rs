@@ -0,0 +1,324 @@
+/*
+ * Created on Sun Oct 18 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+use crate::engine::{
+    core::{dcl, exec, model::ModelData, tests::ddl_model::exec_create_new_space},
+    error::{QueryError, QueryResult},
+    fractal::{test_utils::TestGlobal, GlobalInstanceLike},
+    net::protocol::ClientLocalState,
+    ql::{ast::parse_ast_node_full, ddl::drop::DropModel, tests::lex_insecure},
+};
+
+const MODEL: &str = "create model myspace.mymodel(username: string, password: string)";
+
+fn exec_sysctl(
+    global: &impl GlobalInstanceLike,
+    cstate: &ClientLocalState,
+    q: &str,
+) -> QueryResult<()> {
+    let tok = lex_insecure(q.as_bytes()).unwrap();
+    dcl::exec_ref(global, cstate, parse_ast_node_full(&tok[1..]).unwrap())
+}
+
+fn exec_root(global: &impl GlobalInstanceLike, q: &str) -> QueryResult<()> {
+    exec_sysctl(global, &ClientLocalState::test_new("root", true), q)
+}
+
+fn authorize(
+    global: &impl GlobalInstanceLike,
+    cstate: &ClientLocalState,
+    q: &str,
+) -> QueryResult<()> {
+    let tok = lex_insecure(q.as_bytes()).unwrap();
+    exec::authorize(global, cstate, &tok)
+}
+
+fn init(global: &impl GlobalInstanceLike) -> ClientLocalState {
+    exec_create_new_space(global, MODEL).unwrap();
+    exec_root(
+        global,
+        "sysctl create user app with { password: 'password12345678' }",
+    )
+    .unwrap();
+    ClientLocalState::test_new("app", false)
+}
+
+#[test]
+fn root_can_do_anything() {
+    let global = TestGlobal::new_with_driver_id("dcl_root_can_do_anything");
+    let root = ClientLocalState::test_new("root", true);
+    exec_create_new_space(&global, MODEL).unwrap();
+    for q in [
+        "select * from myspace.mymodel where username = 'sayan'",
+        "delete from myspace.mymodel where username = 'sayan'",
+        "create space another",
+        "drop model allow not empty myspace.mymodel",
+    ] {
+        authorize(&global, &root, q).unwrap();
+    }
+}
+
+#[test]
+fn dml_privileges() {
+    let global = TestGlobal::new_with_driver_id("dcl_dml_privileges");
+    let app = init(&global);
+    let select = "select * from myspace.mymodel where username = 'sayan'";
+    let insert = "insert into myspace.mymodel('sayan', 'pass')";
+    let upsert = "upsert into myspace.mymodel('sayan', 'pass')";
+    // nothing was granted
+    for q in [select, insert, upsert] {
+        assert_eq!(
+            authorize(&global, &app, q).unwrap_err(),
+            QueryError::SysPermissionDenied
+        );
+    }
+    // model level grant
+    exec_root(&global, "sysctl grant select on myspace.mymodel to app").unwrap();
+    authorize(&global, &app, select).unwrap();
+    authorize(&global, &app, "select all * from myspace.mymodel limit 10").unwrap();
+    assert_eq!(
+        authorize(&global, &app, insert).unwrap_err(),
+        QueryError::SysPermissionDenied
+    );
+    // space level grant; upsert also needs update
+    exec_root(&global, "sysctl grant insert on myspace to app").unwrap();
+    authorize(&global, &app, insert).unwrap();
+    assert_eq!(
+        authorize(&global, &app, upsert).unwrap_err(),
+        QueryError::SysPermissionDenied
+    );
+    exec_root(&global, "sysctl grant update on myspace to app").unwrap();
+    authorize(&global, &app, upsert).unwrap();
+    // now revoke
+    exec_root(&global, "sysctl revoke select on myspace.mymodel from app").unwrap();
+    assert_eq!(
+        authorize(&global, &app, select).unwrap_err(),
+        QueryError::SysPermissionDenied
+    );
+}
+
+#[test]
+fn dml_privileges_current_space() {
+    let global = TestGlobal::new_with_driver_id("dcl_dml_privileges_current_space");
+    let mut app = init(&global);
+    exec_root(&global, "sysctl grant delete on myspace.mymodel to app").unwrap();
+    let delete = "delete from mymodel where username = 'sayan'";
+    // no space in use
+    assert_eq!(
+        authorize(&global, &app, delete).unwrap_err(),
+        QueryError::SysPermissionDenied
+    );
+    app.set_cs("myspace".into());
+    authorize(&global, &app, delete).unwrap();
+    // a truncate needs the delete privilege, but (like DDL) never uses the current space
+    assert_eq!(
+        authorize(&global, &app, "truncate model mymodel").unwrap_err(),
+        QueryError::SysPermissionDenied
+    );
+    authorize(&global, &app, "truncate model myspace.mymodel").unwrap();
+}
+
+#[test]
+fn ddl_privileges() {
+    let global = TestGlobal::new_with_driver_id("dcl_ddl_privileges");
+    let app = init(&global);
+    let queries = [
+        "create model if not exists myspace.another(username: string, password: string)",
+        "alter model myspace.mymodel add email { type: string }",
+        "drop model allow not empty if exists myspace.mymodel",
+        "create unique index myidx on myspace.mymodel(password)",
+        "drop index myidx on myspace.mymodel",
+    ];
+    for q in queries {
+        assert_eq!(
+            authorize(&global, &app, q).unwrap_err(),
+            QueryError::SysPermissionDenied
+        );
+    }
+    exec_root(&global, "sysctl grant ddl on myspace to app").unwrap();
+    for q in queries {
+        authorize(&global, &app, q).unwrap();
+    }
+    // space DDL is always reserved for root
+    for q in ["create space another", "drop space allow not empty myspace"] {
+        assert_eq!(
+            authorize(&global, &app, q).unwrap_err(),
+            QueryError::SysPermissionDenied
+        );
+    }
+}
+
+#[test]
+fn grant_errors() {
+    let global = TestGlobal::new_with_driver_id("dcl_grant_errors");
+    let app = init(&global);
+    assert_eq!(
+        exec_root(&global, "sysctl grant select on myspace.mymodel to nobody").unwrap_err(),
+        QueryError::SysAuthError
+    );
+    assert_eq!(
+        exec_root(&global, "sysctl grant select on myspace.mymodel to root").unwrap_err(),
+        QueryError::SysAuthError
+    );
+    assert_eq!(
+        exec_root(&global, "sysctl grant select on myspace.nomodel to app").unwrap_err(),
+        QueryError::QExecObjectNotFound
+    );
+    assert_eq!(
+        exec_root(&global, "sysctl grant select on nospace to app").unwrap_err(),
+        QueryError::QExecObjectNotFound
+    );
+    // only root can grant
+    assert_eq!(
+        exec_sysctl(&global, &app, "sysctl grant select on myspace to app").unwrap_err(),
+        QueryError::SysPermissionDenied
+    );
+}
+
+#[test]
+fn drop_model_purges_grants() {
+    let global = TestGlobal::new_with_driver_id("dcl_drop_model_purges_grants");
+    let app = init(&global);
+    let select = "select * from myspace.mymodel where username = 'sayan'";
+    exec_root(&global, "sysctl grant select on myspace.mymodel to app").unwrap();
+    authorize(&global, &app, select).unwrap();
+    // drop and recreate the model
+    let tok = lex_insecure(b"drop model myspace.mymodel").unwrap();
+    let drop = parse_ast_node_full::<DropModel>(&tok[2..]).unwrap();
+    ModelData::transactional_exec_drop(&global, drop).unwrap();
+    crate::engine::core::tests::ddl_model::exec_create(&global, MODEL, false).unwrap();
+    assert_eq!(
+        authorize(&global, &app, select).unwrap_err(),
+        QueryError::SysPermissionDenied
+    );
+}
+
+#[test]
+fn role_privileges() {
+    let global = TestGlobal::new_with_driver_id("dcl_role_privileges");
+    let app = init(&global);
+    let select = "select * from myspace.mymodel where username = 'sayan'";
+    exec_root(&global, "sysctl create role readers").unwrap();
+    exec_root(
+        &global,
+        "sysctl grant select on myspace.mymodel to role readers",
+    )
+    .unwrap();
+    // the role wasn't granted to the user yet
+    assert_eq!(
+        authorize(&global, &app, select).unwrap_err(),
+        QueryError::SysPermissionDenied
+    );
+    exec_root(&global, "sysctl grant role readers to app").unwrap();
+    authorize(&global, &app, select).unwrap();
+    // revoke from the role
+    exec_root(
+        &global,
+        "sysctl revoke select on myspace.mymodel from role readers",
+    )
+    .unwrap();
+    assert_eq!(
+        authorize(&global, &app, select).unwrap_err(),
+        QueryError::SysPermissionDenied
+    );
+    // revoke the role from the user
+    exec_root(&global, "sysctl grant select on myspace to role readers").unwrap();
+    authorize(&global, &app, select).unwrap();
+    exec_root(&global, "sysctl revoke role readers from app").unwrap();
+    assert_eq!(
+        authorize(&global, &app, select).unwrap_err(),
+        QueryError::SysPermissionDenied
+    );
+    // dropping the role takes it away from everyone
+    exec_root(&global, "sysctl grant role readers to app").unwrap();
+    authorize(&global, &app, select).unwrap();
+    exec_root(&global, "sysctl drop role readers").unwrap();
+    assert_eq!(
+        authorize(&global, &app, select).unwrap_err(),
+        QueryError::SysPermissionDenied
+    );
+}
+
+#[test]
+fn role_errors() {
+    let global = TestGlobal::new_with_driver_id("dcl_role_errors");
+    let app = init(&global);
+    exec_root(&global, "sysctl create role readers").unwrap();
+    for (q, e) in [
+        ("sysctl create role readers", QueryError::SysAuthError),
+        ("sysctl drop role writers", QueryError::SysAuthError),
+        ("sysctl grant role writers to app", QueryError::SysAuthError),
+        (
+            "sysctl grant role readers to nobody",
+            QueryError::SysAuthError,
+        ),
+        (
+            "sysctl grant role readers to root",
+            QueryError::SysAuthError,
+        ),
+        (
+            "sysctl grant select on myspace to role writers",
+            QueryError::SysAuthError,
+        ),
+    ] {
+        assert_eq!(exec_root(&global, q).unwrap_err(), e);
+    }
+    // only root can manage roles
+    for q in [
+        "sysctl create role writers",
+        "sysctl grant role readers to app",
+    ] {
+        assert_eq!(
+            exec_sysctl(&global, &app, q).unwrap_err(),
+            QueryError::SysPermissionDenied
+        );
+    }
+}
+
+#[test]
+fn describe_inspect_privileges() {
+    let global = TestGlobal::new_with_driver_id("dcl_describe_inspect_privileges");
+    let app = init(&global);
+    let queries = [
+        "describe model myspace.mymodel",
+        "inspect model myspace.mymodel",
+        "inspect space myspace",
+        "describe space myspace",
+    ];
+    for q in queries {
+        assert_eq!(
+            authorize(&global, &app, q).unwrap_err(),
+            QueryError::SysPermissionDenied
+        );
+    }
+    // only root sees the users
+    authorize(&global, &app, "inspect global").unwrap();
+    // any privilege on the model is enough to see it (and its space)
+    exec_root(&global, "sysctl grant insert on myspace.mymodel to app").unwrap();
+    for q in queries {
+        authorize(&global, &app, q).unwrap();
+    }
+}
@@ -0,0 +1,355 @@
+/*
+ * Created on Sun Oct 18 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+use crate::engine::{
+    core::{dml, model::ModelData, tests::ddl_model::exec_create_new_space, EntityIDRef},
+    data::{cell::Datacell, lit::Lit},
+    error::{QueryError, QueryResult},
+    fractal::{test_utils::TestGlobal, GlobalInstanceLike},
+    ql::{
+        ast::parse_ast_node_full,
+        ddl::{crt::CreateIndex, drop::DropIndex},
+        tests::lex_insecure,
+    },
+};
+
+const MODEL: &str =
+    "create model myspace.mymodel(username: string, email: string, null age: uint8)";
+
+fn exec_create_index(global: &impl GlobalInstanceLike, create: &str) -> QueryResult<Option<bool>> {
+    let tok = lex_insecure(create.as_bytes()).unwrap();
+    let create = parse_ast_node_full::<CreateIndex>(&tok[1..]).unwrap();
+    ModelData::transactional_exec_create_index(global, create)
+}
+
+fn exec_drop_index(global: &impl GlobalInstanceLike, drop: &str) -> QueryResult<Option<bool>> {
+    let tok = lex_insecure(drop.as_bytes()).unwrap();
+    let drop = parse_ast_node_full::<DropIndex>(&tok[2..]).unwrap();
+    ModelData::transactional_exec_drop_index(global, drop)
+}
+
+fn exec_dml(global: &impl GlobalInstanceLike, query: &str) -> QueryResult<()> {
+    let tok = lex_insecure(query.as_bytes()).unwrap();
+    if tok[0] == Token![insert] {
+        dml::insert(global, parse_ast_node_full(&tok[1..]).unwrap())
+    } else if tok[0] == Token![update] {
+        dml::update(global, parse_ast_node_full(&tok[1..]).unwrap())
+    } else {
+        dml::delete(global, parse_ast_node_full(&tok[1..]).unwrap())
+    }
+}
+
+/// Returns the usernames of the rows matched by the scan, sorted
+fn exec_select_usernames(global: &impl GlobalInstanceLike, select: &str) -> Vec<String> {
+    let tok = lex_insecure(select.as_bytes()).unwrap();
+    let mut cells: Vec<Datacell> = vec![];
+    dml::select_custom(
+        global,
+        parse_ast_node_full(&tok[1..]).unwrap(),
+        &mut cells,
+        |_, _| {},
+        |cells, dc| cells.push(dc.clone()),
+    )
+    .unwrap();
+    let mut usernames: Vec<String> = cells.iter().map(|dc| dc.str().to_owned()).collect();
+    usernames.sort();
+    usernames
+}
+
+/// Returns the number of entries in the index and the number of rows that hold the given value
+fn index_stats(global: &impl GlobalInstanceLike, index: &str, value: Lit) -> (usize, usize) {
+    global
+        .state()
+        .namespace()
+        .with_model(EntityIDRef::new("myspace", "mymodel"), |mdl| {
+            let index = mdl.secondary_indexes().get(index).unwrap();
+            Ok((index.count(), index.lookup(value).len()))
+        })
+        .unwrap()
+}
+
+fn init(global: &impl GlobalInstanceLike, inserts: &[&str]) {
+    exec_create_new_space(global, MODEL).unwrap();
+    for insert in inserts {
+        exec_dml(global, insert).unwrap();
+    }
+}
+
+#[test]
+fn create_and_drop_index() {
+    let global = TestGlobal::new_with_driver_id_instant_update("ddl_index_create_and_drop_index");
+    init(
+        &global,
+        &[
+            "insert into myspace.mymodel('sayan', 'sayan@example.com', 24)",
+            "insert into myspace.mymodel('elena', 'elena@example.com', null)",
+            "insert into myspace.mymodel('sam', 'sam@example.com', 24)",
+        ],
+    );
+    assert_eq!(
+        exec_create_index(&global, "create index by_age on myspace.mymodel(age)").unwrap(),
+        None
+    );
+    // nulls aren't indexed
+    assert_eq!(index_stats(&global, "by_age", Lit::new_uint(24)), (2, 2));
+    assert_eq!(
+        exec_select_usernames(
+            &global,
+            "select username from myspace.mymodel where age = 24"
+        ),
+        ["sam", "sayan"]
+    );
+    assert_eq!(
+        exec_create_index(&global, "create index by_age on myspace.mymodel(email)").unwrap_err(),
+        QueryError::QExecDdlObjectAlreadyExists
+    );
+    assert_eq!(
+        exec_create_index(
+            &global,
+            "create index if not exists by_age on myspace.mymodel(email)"
+        )
+        .unwrap(),
+        Some(false)
+    );
+    assert_eq!(
+        exec_drop_index(&global, "drop index by_age on myspace.mymodel").unwrap(),
+        None
+    );
+    assert_eq!(
+        exec_drop_index(&global, "drop index by_age on myspace.mymodel").unwrap_err(),
+        QueryError::QExecObjectNotFound
+    );
+    assert_eq!(
+        exec_drop_index(&global, "drop index if exists by_age on myspace.mymodel").unwrap(),
+        Some(false)
+    );
+    // queries still work without the index
+    assert_eq!(
+        exec_select_usernames(
+            &global,
+            "select username from myspace.mymodel where age = 24"
+        ),
+        ["sam", "sayan"]
+    );
+}
+
+#[test]
+fn create_index_illegal() {
+    let global = TestGlobal::new_with_driver_id("ddl_index_create_index_illegal");
+    exec_create_new_space(
+        &global,
+        "create model myspace.mymodel(username: string, email: string, score: float64, tags: list { type: string })",
+    )
+    .unwrap();
+    assert_eq!(
+        exec_create_index(&global, "create index idx on myspace.mymodel(username)").unwrap_err(),
+        QueryError::QExecDdlModelAlterIllegal
+    );
+    assert_eq!(
+        exec_create_index(&global, "create index idx on myspace.mymodel(password)").unwrap_err(),
+        QueryError::QExecUnknownField
+    );
+    assert_eq!(
+        exec_create_index(&global, "create index idx on myspace.mymodel(score)").unwrap_err(),
+        QueryError::QExecDdlInvalidTypeDefinition
+    );
+    assert_eq!(
+        exec_create_index(&global, "create index idx on myspace.mymodel(tags)").unwrap_err(),
+        QueryError::QExecDdlInvalidTypeDefinition
+    );
+    exec_create_index(&global, "create index idx on myspace.mymodel(email)").unwrap();
+    // only one index per field
+    assert_eq!(
+        exec_create_index(
+            &global,
+            "create unique index idx2 on myspace.mymodel(email)"
+        )
+        .unwrap_err(),
+        QueryError::QExecDdlModelAlterIllegal
+    );
+    assert_eq!(
+        exec_create_index(&global, "create index idx on myspace.othermodel(email)").unwrap_err(),
+        QueryError::QExecObjectNotFound
+    );
+}
+
+#[test]
+fn unique_index_existing_duplicates() {
+    let global = TestGlobal::new_with_driver_id("ddl_index_unique_index_existing_duplicates");
+    init(
+        &global,
+        &[
+            "insert into myspace.mymodel('sayan', 'sayan@example.com', 24)",
+            "insert into myspace.mymodel('sam', 'sam@example.com', 24)",
+        ],
+    );
+    assert_eq!(
+        exec_create_index(
+            &global,
+            "create unique index by_age on myspace.mymodel(age)"
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlDuplicate
+    );
+    exec_create_index(
+        &global,
+        "create unique index by_age on myspace.mymodel(email)",
+    )
+    .unwrap();
+}
+
+#[test]
+fn unique_index_enforced() {
+    let global = TestGlobal::new_with_driver_id_instant_update("ddl_index_unique_index_enforced");
+    init(
+        &global,
+        &[
+            "insert into myspace.mymodel('sayan', 'sayan@example.com', 24)",
+            "insert into myspace.mymodel('sam', 'sam@example.com', null)",
+        ],
+    );
+    exec_create_index(
+        &global,
+        "create unique index by_email on myspace.mymodel(email)",
+    )
+    .unwrap();
+    assert_eq!(
+        exec_dml(
+            &global,
+            "insert into myspace.mymodel('elena', 'sayan@example.com', null)"
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlDuplicate
+    );
+    assert_eq!(
+        exec_dml(
+            &global,
+            "update myspace.mymodel set email = 'sayan@example.com' where username = 'sam'"
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlDuplicate
+    );
+    // the failed update was rolled back
+    assert_eq!(
+        exec_select_usernames(
+            &global,
+            "select username from myspace.mymodel where email = 'sam@example.com'"
+        ),
+        ["sam"]
+    );
+    // a row can be updated to hold its own value
+    exec_dml(
+        &global,
+        "update myspace.mymodel set email = 'sayan@example.com' where username = 'sayan'",
+    )
+    .unwrap();
+    // the value is available once the row holding it is deleted
+    exec_dml(
+        &global,
+        "delete from myspace.mymodel where username = 'sayan'",
+    )
+    .unwrap();
+    exec_dml(
+        &global,
+        "insert into myspace.mymodel('elena', 'sayan@example.com', null)",
+    )
+    .unwrap();
+    assert_eq!(
+        index_stats(&global, "by_email", Lit::new_str("sayan@example.com")),
+        (2, 1)
+    );
+    assert_eq!(
+        exec_select_usernames(
+            &global,
+            "select username from myspace.mymodel where email = 'sayan@example.com'"
+        ),
+        ["elena"]
+    );
+}
+
+#[test]
+fn index_maintained_by_dml() {
+    let global = TestGlobal::new_with_driver_id_instant_update("ddl_index_index_maintained_by_dml");
+    init(
+        &global,
+        &[
+            "insert into myspace.mymodel('sayan', 'sayan@example.com', 24)",
+            "insert into myspace.mymodel('sam', 'sam@example.com', 25)",
+            "insert into myspace.mymodel('elena', 'elena@example.com', 24)",
+        ],
+    );
+    exec_create_index(&global, "create index by_age on myspace.mymodel(age)").unwrap();
+    exec_dml(
+        &global,
+        "update myspace.mymodel set age += 1 where username = 'sayan'",
+    )
+    .unwrap();
+    assert_eq!(
+        exec_select_usernames(
+            &global,
+            "select username from myspace.mymodel where age = 25"
+        ),
+        ["sam", "sayan"]
+    );
+    assert_eq!(
+        exec_select_usernames(
+            &global,
+            "select username from myspace.mymodel where age = 24"
+        ),
+        ["elena"]
+    );
+    // update through the index
+    exec_dml(
+        &global,
+        "update myspace.mymodel set age = 30 where age = 25",
+    )
+    .unwrap();
+    assert_eq!(index_stats(&global, "by_age", Lit::new_uint(30)), (3, 2));
+    // an update of a field that isn't indexed (through the index) leaves the index as is
+    exec_dml(
+        &global,
+        "update myspace.mymodel set email = 'someone@example.com' where age = 30",
+    )
+    .unwrap();
+    assert_eq!(index_stats(&global, "by_age", Lit::new_uint(30)), (3, 2));
+    // delete through the index
+    exec_dml(&global, "delete from myspace.mymodel where age = 30").unwrap();
+    assert_eq!(index_stats(&global, "by_age", Lit::new_uint(30)), (1, 0));
+    assert_eq!(
+        exec_select_usernames(
+            &global,
+            "select username from myspace.mymodel where age = 30"
+        ),
+        Vec::<String>::new()
+    );
+    // an indexed field can't be removed
+    let tok = lex_insecure(b"alter model myspace.mymodel remove age").unwrap();
+    assert_eq!(
+        ModelData::transactional_exec_alter(&global, parse_ast_node_full(&tok[2..]).unwrap())
+            .unwrap_err(),
+        QueryError::QExecDdlModelAlterIllegal
+    );
+}
@@ -0,0 +1,172 @@
+/*
+ * Created on Sun Oct 18 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+use crate::engine::{
+    core::{
+        ddl_misc,
+        dml::encode_cell,
+        tests::ddl_model::{exec_create, exec_create_new_space},
+    },
+    data::cell::Datacell,
+    error::QueryError,
+    fractal::{test_utils::TestGlobal, GlobalInstanceLike},
+    net::protocol::{Response, ResponseType},
+    ql::{ast::parse_ast_node_full, ddl::Describe, tests::lex_insecure},
+};
+
+fn exec_describe(global: &impl GlobalInstanceLike, describe: &str) -> Result<Response, QueryError> {
+    let tok = lex_insecure(describe.as_bytes()).unwrap();
+    let describe = parse_ast_node_full::<Describe>(&tok[1..]).unwrap();
+    ddl_misc::describe(global, describe)
+}
+
+fn row(cells: Vec<Datacell>) -> Response {
+    let mut data = vec![];
+    cells.iter().for_each(|dc| encode_cell(&mut data, dc));
+    Response::Serialized {
+        ty: ResponseType::Row,
+        size: cells.len(),
+        data,
+    }
+}
+
+fn field(name: &str, layers: &[&'static str], nullable: bool, pk: bool) -> Datacell {
+    Datacell::new_list(vec![
+        Datacell::from(name.to_owned()),
+        Datacell::new_list(layers.iter().map(|l| Datacell::from(*l)).collect()),
+        Datacell::from(nullable),
+        Datacell::from(pk),
+    ])
+}
+
+#[test]
+fn describe_model() {
+    let global = TestGlobal::new_with_driver_id("describe_model");
+    exec_create_new_space(
+        &global,
+        "create model myspace.mymodel(username: string, null password: binary, notes: list { type: string })",
+    )
+    .unwrap();
+    assert_eq!(
+        exec_describe(&global, "describe model myspace.mymodel").unwrap(),
+        row(vec![
+            Datacell::from("username"),
+            Datacell::new_uint_default(0),
+            Datacell::new_list(vec![
+                field("username", &["String"], false, true),
+                field("password", &["Binary"], true, false),
+                field("notes", &["List", "String"], false, false),
+            ]),
+            Datacell::new_list(vec![]),
+        ])
+    );
+}
+
+#[test]
+fn describe_ordered_model() {
+    let global = TestGlobal::new_with_driver_id("describe_ordered_model");
+    exec_create_new_space(
+        &global,
+        "create model myspace.mymodel(ts: uint64, reading: float64) with { index: \"ordered\" }",
+    )
+    .unwrap();
+    assert_eq!(
+        exec_describe(&global, "describe model myspace.mymodel").unwrap(),
+        row(vec![
+            Datacell::from("ts"),
+            Datacell::new_uint_default(0),
+            Datacell::new_list(vec![
+                field("ts", &["UInt64"], false, true),
+                field("reading", &["Float64"], false, false),
+            ]),
+            Datacell::new_list(vec![Datacell::new_list(vec![
+                Datacell::from("index"),
+                Datacell::from("ordered"),
+            ])]),
+        ])
+    );
+}
+
+#[test]
+fn describe_durable_model() {
+    let global = TestGlobal::new_with_driver_id("describe_durable_model");
+    exec_create_new_space(
+        &global,
+        "create model myspace.mymodel(id: string, amount: uint64) with { durability: \"group-commit\" }",
+    )
+    .unwrap();
+    assert_eq!(
+        exec_describe(&global, "describe model myspace.mymodel").unwrap(),
+        row(vec![
+            Datacell::from("id"),
+            Datacell::new_uint_default(0),
+            Datacell::new_list(vec![
+                field("id", &["String"], false, true),
+                field("amount", &["UInt64"], false, false),
+            ]),
+            Datacell::new_list(vec![Datacell::new_list(vec![
+                Datacell::from("durability"),
+                Datacell::from("group-commit"),
+            ])]),
+        ])
+    );
+}
+
+#[test]
+fn describe_space() {
+    let global = TestGlobal::new_with_driver_id("describe_space");
+    exec_create_new_space(
+        &global,
+        "create model myspace.b(username: string, password: binary)",
+    )
+    .unwrap();
+    exec_create(
+        &global,
+        "create model myspace.a(username: string, password: binary)",
+        false,
+    )
+    .unwrap();
+    assert_eq!(
+        exec_describe(&global, "describe space myspace").unwrap(),
+        row(vec![
+            Datacell::new_list(vec![Datacell::from("a"), Datacell::from("b")]),
+            Datacell::new_list(vec![]),
+        ])
+    );
+}
+
+#[test]
+fn describe_missing() {
+    let global = TestGlobal::new_with_driver_id("describe_missing");
+    assert_eq!(
+        exec_describe(&global, "describe space myspace").unwrap_err(),
+        QueryError::QExecObjectNotFound
+    );
+    assert_eq!(
+        exec_describe(&global, "describe model myspace.mymodel").unwrap_err(),
+        QueryError::QExecObjectNotFound
+    );
+}
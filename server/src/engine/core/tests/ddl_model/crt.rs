@@ -28,7 +28,10 @@ mod validation {
     use {
         super::super::create,
         crate::engine::{
-            core::model::{DeltaVersion, Field, Layer},
+            core::{
+                index::PrimaryIndexKind,
+                model::{DeltaVersion, Durability, Field, Layer},
+            },
             data::tag::{DataTag, FullTag},
             error::QueryError,
             idx::STIndexSeq,
@@ -110,6 +113,125 @@ mod validation {
     );
     }
 
+    #[test]
+    fn index_kind() {
+        let model = create(
+            "create model myspace.mymodel(primary username: string, password: binary) with { index: \"ordered\" }",
+        )
+        .unwrap();
+        assert_eq!(model.primary_index().kind(), PrimaryIndexKind::Ordered);
+        let model = create(
+            "create model myspace.mymodel(primary username: string, password: binary) with { index: \"hashed\" }",
+        )
+        .unwrap();
+        assert_eq!(model.primary_index().kind(), PrimaryIndexKind::Hashed);
+        let model =
+            create("create model myspace.mymodel(primary username: string, password: binary)")
+                .unwrap();
+        assert_eq!(model.primary_index().kind(), PrimaryIndexKind::Hashed);
+    }
+
+    #[test]
+    fn illegal_index_kind() {
+        for props in [
+            "{ index: \"btree\" }",
+            "{ index: 1 }",
+            "{ index: \"ordered\", lol_prop: false }",
+        ] {
+            assert_eq!(
+                create(&format!(
+                    "create model myspace.mymodel(primary username: string, password: binary) with {props}"
+                ))
+                .unwrap_err(),
+                QueryError::QExecDdlModelBadDefinition
+            );
+        }
+    }
+
+    #[test]
+    fn layer_props_decl() {
+        let model = create(
+            r#"create model myspace.mymodel(primary id: uint64, tag: string { oneof: ["a\"b", "c\\d"], pattern: "[a-z]+" })"#,
+        )
+        .unwrap();
+        assert_eq!(
+            model.describe(),
+            r#"{*id:UInt64,!tag:String{oneof:["a\"b","c\\d"],pattern:"[a-z]+"}}"#
+        );
+    }
+
+    #[test]
+    fn ttl() {
+        let model = create(
+            "create model myspace.mymodel(primary username: string, password: binary) with { ttl: 3600 }",
+        )
+        .unwrap();
+        assert_eq!(model.ttl(), Some(3600));
+        assert!(model.primary_index().expires_rows());
+        let model = create(
+            "create model myspace.mymodel(primary username: string, password: binary) with { index: \"ordered\", ttl: 60 }",
+        )
+        .unwrap();
+        assert_eq!(model.ttl(), Some(60));
+        assert_eq!(model.primary_index().kind(), PrimaryIndexKind::Ordered);
+        let model =
+            create("create model myspace.mymodel(primary username: string, password: binary)")
+                .unwrap();
+        assert_eq!(model.ttl(), None);
+        assert!(!model.primary_index().expires_rows());
+    }
+
+    #[test]
+    fn illegal_ttl() {
+        for props in [
+            "{ ttl: 0 }",
+            "{ ttl: \"1h\" }",
+            "{ ttl: -1 }",
+            "{ ttl: 281474976710656 }",
+        ] {
+            assert_eq!(
+                create(&format!(
+                    "create model myspace.mymodel(primary username: string, password: binary) with {props}"
+                ))
+                .unwrap_err(),
+                QueryError::QExecDdlModelBadDefinition
+            );
+        }
+    }
+
+    #[test]
+    fn durability() {
+        for durability in [Durability::Async, Durability::GroupCommit, Durability::Sync] {
+            let model = create(&format!(
+                "create model myspace.mymodel(primary username: string, password: binary) with {{ durability: \"{}\" }}",
+                durability.name_str()
+            ))
+            .unwrap();
+            assert_eq!(model.durability(), durability);
+        }
+        let model =
+            create("create model myspace.mymodel(primary username: string, password: binary)")
+                .unwrap();
+        assert_eq!(model.durability(), Durability::Async);
+    }
+
+    #[test]
+    fn illegal_durability() {
+        for props in [
+            "{ durability: \"fsync\" }",
+            "{ durability: 1 }",
+            "{ durability: \"Sync\" }",
+        ] {
+            assert_eq!(
+                create(&format!(
+                    "create model myspace.mymodel(primary username: string, password: binary) with {props}"
+                ))
+                .unwrap_err(),
+                QueryError::QExecDdlModelBadDefinition
+            );
+        }
+    }
+
     #[test]
     fn illegal_pk() {
         assert_eq!(
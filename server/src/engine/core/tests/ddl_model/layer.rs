@@ -42,7 +42,11 @@ fn layerview(layer_def: &str) -> QueryResult<Field> {
 mod layer_spec_validation {
     use {
         super::layerview,
-        crate::engine::{core::model::Layer, error::QueryError},
+        crate::engine::{
+            core::model::{FieldDefault, Layer},
+            data::cell::Datacell,
+            error::QueryError,
+        },
     };
 
     #[test]
@@ -60,6 +64,47 @@ mod layer_spec_validation {
         );
     }
 
+    #[test]
+    fn timestamp_uuid() {
+        assert_eq!(
+            layerview("timestamp").unwrap().layers(),
+            [Layer::timestamp()]
+        );
+        assert_eq!(
+            layerview("list { type: uuid }").unwrap().layers(),
+            [Layer::list(), Layer::uuid()]
+        );
+        assert_eq!(
+            layerview("timestamp { default: @now() }")
+                .unwrap()
+                .default(),
+            Some(&FieldDefault::Producer("now".into()))
+        );
+        assert_eq!(
+            layerview("uuid { default: uuid'4593264b-0231-43e9-b0aa-50784f14e204' }")
+                .unwrap()
+                .default(),
+            Some(&FieldDefault::Value(Datacell::new_uuid(
+                0x4593264b_0231_43e9_b0aa_50784f14e204
+            )))
+        );
+    }
+
+    #[test]
+    fn dict() {
+        assert_eq!(layerview("dict").unwrap().layers(), [Layer::dict()]);
+        assert_eq!(
+            layerview("dict { type: list { type: string } }")
+                .unwrap()
+                .layers(),
+            [Layer::dict(), Layer::list(), Layer::str()]
+        );
+        assert_eq!(
+            layerview("list { type: dict }").unwrap().layers(),
+            [Layer::list(), Layer::dict()]
+        );
+    }
+
     #[test]
     fn invalid_list() {
         assert_eq!(
@@ -75,6 +120,80 @@ mod layer_spec_validation {
             QueryError::QExecDdlInvalidTypeDefinition
         );
     }
+
+    #[test]
+    fn props() {
+        let field =
+            layerview("list { type: string { oneof: [\"a\", \"b\"] }, maxlen: 2 }").unwrap();
+        assert!(field.layers()[0].props().is_some());
+        assert!(field.layers()[1].props().is_some());
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(
+            layerview("uint8 { default: 1 }").unwrap().default(),
+            Some(&FieldDefault::Value(Datacell::new_uint_default(1)))
+        );
+        assert_eq!(
+            layerview("string { default: @uuidstr() }")
+                .unwrap()
+                .default(),
+            Some(&FieldDefault::Producer("uuidstr".into()))
+        );
+        assert_eq!(
+            layerview("list { type: string, default: [] }")
+                .unwrap()
+                .default(),
+            Some(&FieldDefault::Value(Datacell::new_list(vec![])))
+        );
+    }
+
+    #[test]
+    fn invalid_default() {
+        for layer in [
+            "uint8 { default: 256 }",
+            "uint8 { default: @timesec() }",
+            "uuid { default: @now() }",
+            "timestamp { default: @timesec() }",
+            "string { default: @nothing() }",
+            "string { default: null }",
+            "string { default: 1 }",
+            "string { minlen: 3, default: \"a\" }",
+        ] {
+            assert_eq!(
+                layerview(layer).unwrap_err(),
+                QueryError::QExecDdlInvalidProperties,
+                "{layer}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_props() {
+        for layer in [
+            "bool { maxlen: 1 }",
+            "string { min: 1 }",
+            "uint8 { max: 256 }",
+            "uint8 { min: -1 }",
+            "sint8 { min: 5, max: -5 }",
+            "string { minlen: 5, maxlen: 1 }",
+            "string { oneof: [] }",
+            "string { oneof: [1, 2] }",
+            "float64 { oneof: [1, 2] }",
+            "binary { maxlen: \"1\" }",
+            "uint8 { pattern: \"[0-9]+\" }",
+            "string { pattern: 1 }",
+            "string { pattern: \"[a-z\" }",
+            "string { unique: true }",
+        ] {
+            assert_eq!(
+                layerview(layer).unwrap_err(),
+                QueryError::QExecDdlInvalidProperties,
+                "{layer}"
+            );
+        }
+    }
 }
 
 mod layer_data_validation {
@@ -226,6 +345,43 @@ mod layer_data_validation {
         );
     }
     #[test]
+    fn dict_dynamic() {
+        let layer = layerview("dict").unwrap();
+        let mut dc = Datacell::new_dict(into_dict! {
+            "name" => Datacell::from("sayan"),
+            "visits" => Datacell::new_uint_default(100),
+            "address" => Datacell::new_dict(into_dict!("city" => Datacell::from("Kolkata"))),
+        });
+        assert!(layer.vt_data_fpath(&mut dc));
+        assert_vecstreq_exact!(model::layer_traces(), ["fpath", "dict"]);
+    }
+    #[test]
+    fn dict_typed() {
+        let layer = layerview("dict { type: string, maxlen: 2 }").unwrap();
+        let mut dc = Datacell::new_dict(into_dict! {
+            "first" => Datacell::from("sayan"),
+            "last" => Datacell::from("nandan"),
+        });
+        assert!(layer.vt_data_fpath(&mut dc));
+        assert_vecstreq_exact!(model::layer_traces(), ["dict", "string", "string"]);
+        // bad value type
+        let mut dc = Datacell::new_dict(into_dict!("age" => Datacell::new_uint_default(25)));
+        assert!(!layer.vt_data_fpath(&mut dc));
+        assert_vecstreq_exact!(model::layer_traces(), ["dict"]);
+        // typed values can't be null
+        let mut dc = Datacell::new_dict(into_dict!("first" => Datacell::null()));
+        assert!(!layer.vt_data_fpath(&mut dc));
+        assert_vecstreq_exact!(model::layer_traces(), ["dict"]);
+        // too many keys
+        let mut dc = Datacell::new_dict(into_dict! {
+            "first" => Datacell::from("sayan"),
+            "middle" => Datacell::from("x"),
+            "last" => Datacell::from("nandan"),
+        });
+        assert!(!layer.vt_data_fpath(&mut dc));
+        assert_vecstreq_exact!(model::layer_traces(), ["dict"]);
+    }
+    #[test]
     fn nullval_fpath() {
         let layer = layerview_nullable("string", true).unwrap();
         assert!(layer.vt_data_fpath(&mut Datacell::null()));
@@ -237,4 +393,85 @@ mod layer_data_validation {
         assert!(layer.vt_data_fpath(&mut Datacell::null()));
         assert_vecstreq_exact!(model::layer_traces(), ["fpath", "bool"]);
     }
+    #[test]
+    fn props_len() {
+        let layer = layerview("string { minlen: 2, maxlen: 4 }").unwrap();
+        assert!(!layer.vt_data_fpath(&mut Datacell::from("a")));
+        assert!(layer.vt_data_fpath(&mut Datacell::from("ab")));
+        // length is in characters
+        assert!(layer.vt_data_fpath(&mut Datacell::from("ññññ")));
+        assert!(!layer.vt_data_fpath(&mut Datacell::from("abcde")));
+        assert_vecstreq_exact!(
+            model::layer_traces(),
+            ["fpath", "string", "fpath", "string", "fpath", "string", "fpath", "string"]
+        );
+        let layer = layerview("binary { maxlen: 2 }").unwrap();
+        assert!(!layer.vt_data_fpath(&mut Datacell::from("ñ!".as_bytes())));
+        assert_vecstreq_exact!(model::layer_traces(), ["fpath", "binary"]);
+    }
+    #[test]
+    fn props_range() {
+        let layer = layerview("sint8 { min: -5, max: 5 }").unwrap();
+        assert!(layer.vt_data_fpath(&mut Datacell::new_sint_default(-5)));
+        assert!(layer.vt_data_fpath(&mut Datacell::new_sint_default(5)));
+        assert!(!layer.vt_data_fpath(&mut Datacell::new_sint_default(-6)));
+        assert!(!layer.vt_data_fpath(&mut Datacell::new_sint_default(6)));
+        assert_vecstreq_exact!(
+            model::layer_traces(),
+            ["fpath", "sint", "fpath", "sint", "fpath", "sint", "fpath", "sint"]
+        );
+        let layer = layerview("float64 { min: 0, max: 2 }").unwrap();
+        assert!(layer.vt_data_fpath(&mut Datacell::new_float_default(1.5)));
+        assert!(!layer.vt_data_fpath(&mut Datacell::new_float_default(2.5)));
+        assert!(!layer.vt_data_fpath(&mut Datacell::new_float_default(-0.5)));
+        assert_vecstreq_exact!(
+            model::layer_traces(),
+            ["fpath", "float", "fpath", "float", "fpath", "float"]
+        );
+    }
+    #[test]
+    fn props_oneof() {
+        let layer = layerview("string { oneof: [\"red\", \"green\"] }").unwrap();
+        assert!(layer.vt_data_fpath(&mut Datacell::from("green")));
+        assert!(!layer.vt_data_fpath(&mut Datacell::from("blue")));
+        assert_vecstreq_exact!(
+            model::layer_traces(),
+            ["fpath", "string", "fpath", "string"]
+        );
+        let layer = layerview("uint8 { oneof: [1, 2, 3] }").unwrap();
+        assert!(layer.vt_data_fpath(&mut Datacell::new_uint_default(3)));
+        assert!(!layer.vt_data_fpath(&mut Datacell::new_uint_default(4)));
+        assert_vecstreq_exact!(model::layer_traces(), ["fpath", "uint", "fpath", "uint"]);
+    }
+    #[test]
+    fn props_pattern() {
+        let layer = layerview("string { pattern: \"[a-z]+\" }").unwrap();
+        assert!(layer.vt_data_fpath(&mut Datacell::from("sayan")));
+        // the whole string must match
+        assert!(!layer.vt_data_fpath(&mut Datacell::from("sayan1")));
+        assert!(!layer.vt_data_fpath(&mut Datacell::from("")));
+        assert_vecstreq_exact!(
+            model::layer_traces(),
+            ["fpath", "string", "fpath", "string", "fpath", "string"]
+        );
+    }
+    #[test]
+    fn props_list() {
+        let layer = layerview("list { type: string { maxlen: 3 }, maxlen: 2 }").unwrap();
+        let mut dc = Datacell::new_list(vec![Datacell::from("a"), Datacell::from("bc")]);
+        assert!(layer.vt_data_fpath(&mut dc));
+        assert_vecstreq_exact!(model::layer_traces(), ["list", "string", "string"]);
+        // too many elements
+        let mut dc = Datacell::new_list(vec![
+            Datacell::from("a"),
+            Datacell::from("b"),
+            Datacell::from("c"),
+        ]);
+        assert!(!layer.vt_data_fpath(&mut dc));
+        assert_vecstreq_exact!(model::layer_traces(), ["list"]);
+        // element is too long
+        let mut dc = Datacell::new_list(vec![Datacell::from("a"), Datacell::from("bcde")]);
+        assert!(!layer.vt_data_fpath(&mut dc));
+        assert_vecstreq_exact!(model::layer_traces(), ["list", "string", "string"]);
+    }
 }
@@ -89,3 +89,17 @@ fn exec_create_space_with_random_property() {
         QueryError::QExecDdlInvalidProperties
     );
 }
+
+#[test]
+fn exec_create_space_with_producer() {
+    let global = TestGlobal::new_with_driver_id("exec_create_space_with_producer");
+    for stmt in [
+        "create space myspace with { env: @timesec() }",
+        "create space myspace with { env: { created: @timesec() } }",
+    ] {
+        assert_eq!(
+            super::exec_create(&global, stmt, |_| {}).unwrap_err(),
+            QueryError::QExecDdlInvalidProperties
+        );
+    }
+}
@@ -0,0 +1,155 @@
+/*
+ * Created on Sun Oct 18 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+use crate::engine::{
+    core::dml::{self, StagedBatch},
+    data::cell::Datacell,
+    error::{QueryError, QueryResult},
+    fractal::test_utils::TestGlobal,
+    ql::{ast::State, lex::SecureLexer},
+};
+
+const MODEL: &str = "create model myspace.accounts(username: string, balance: uint64)";
+
+/// Stage a query (with `myspace` in use) like the executor would
+fn stage(batch: &mut StagedBatch, query: &str, params: &[u8]) -> QueryResult<()> {
+    let tokens = SecureLexer::new_with_segments(query.as_bytes(), params).lex()?;
+    let mut state = State::new_inplace(&tokens);
+    state.set_space_maybe(Some("myspace"));
+    let stmt = state.try_statement()?;
+    batch.stage(&mut state, stmt, query.as_bytes(), params)
+}
+
+pub(super) fn exec_batch(global: &TestGlobal, queries: &[(&str, &[u8])]) -> QueryResult<()> {
+    let mut batch = StagedBatch::new(Some("myspace"));
+    for (query, params) in queries {
+        stage(&mut batch, query, params)?;
+    }
+    dml::commit_batch(global, batch)
+}
+
+fn select_balance(global: &TestGlobal, username: &str) -> QueryResult<Vec<Datacell>> {
+    super::exec_select_only(
+        global,
+        &format!("select balance from myspace.accounts where username = '{username}'"),
+    )
+}
+
+#[test]
+fn commit() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_batch_commit");
+    super::_exec_only_create_space_model(&global, MODEL).unwrap();
+    super::exec_insert_only(&global, "insert into myspace.accounts('sayan', 100)").unwrap();
+    exec_batch(
+        &global,
+        &[
+            (
+                "update myspace.accounts set balance -= ? where username = ?",
+                b"\x0240\n\x065\nsayan",
+            ),
+            (
+                "insert into myspace.accounts(?, ?)",
+                b"\x0616\nledger-sayan-001\x0240\n",
+            ),
+        ],
+    )
+    .unwrap();
+    assert_eq!(select_balance(&global, "sayan").unwrap(), intovec![60_u64]);
+    assert_eq!(
+        select_balance(&global, "ledger-sayan-001").unwrap(),
+        intovec![40_u64]
+    );
+}
+
+#[test]
+fn rollback_on_failure() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_batch_rollback_on_failure");
+    super::_exec_only_create_space_model(&global, MODEL).unwrap();
+    super::exec_insert_only(&global, "insert into myspace.accounts('sayan', 100)").unwrap();
+    super::exec_insert_only(&global, "insert into myspace.accounts('nobody', 0)").unwrap();
+    assert_eq!(
+        exec_batch(
+            &global,
+            &[
+                (
+                    "insert into myspace.accounts(?, ?)",
+                    b"\x0616\nledger-sayan-001\x0240\n",
+                ),
+                (
+                    "update myspace.accounts set balance -= ? where username = ?",
+                    b"\x0240\n\x065\nsayan",
+                ),
+                (
+                    "delete from myspace.accounts where username = ?",
+                    b"\x066\nnobody"
+                ),
+                // duplicate
+                ("insert into myspace.accounts(?, ?)", b"\x065\nsayan\x020\n"),
+            ],
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlDuplicate
+    );
+    // none of the changes should have been applied
+    assert_eq!(select_balance(&global, "sayan").unwrap(), intovec![100_u64]);
+    assert_eq!(select_balance(&global, "nobody").unwrap(), intovec![0_u64]);
+    assert_eq!(
+        select_balance(&global, "ledger-sayan-001").unwrap_err(),
+        QueryError::QExecDmlRowNotFound
+    );
+}
+
+#[test]
+fn stage_illegal_statement() {
+    let mut batch = StagedBatch::new(Some("myspace"));
+    stage(
+        &mut batch,
+        "insert into accounts(?, ?)",
+        b"\x065\nsayan\x020\n",
+    )
+    .unwrap();
+    // a different model
+    assert_eq!(
+        stage(
+            &mut batch,
+            "insert into ledger(?, ?)",
+            b"\x065\nsayan\x020\n"
+        )
+        .unwrap_err(),
+        QueryError::QExecBatchMultipleModels
+    );
+    // not a DML write
+    assert_eq!(
+        stage(
+            &mut batch,
+            "select * from accounts where username = ?",
+            b"\x065\nsayan"
+        )
+        .unwrap_err(),
+        QueryError::QExecBatchIllegalStatement
+    );
+    assert_eq!(batch.len(), 1);
+}
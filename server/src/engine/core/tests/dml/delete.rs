@@ -54,3 +54,164 @@ fn delete_nonexisting() {
         QueryError::QExecDmlRowNotFound
     );
 }
+
+#[test]
+fn delete_filtered() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_delete_delete_filtered");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            "create model myspace.mymodel(username: string, followers: uint64)",
+            &[
+                "insert into myspace.mymodel('douglas', 42)",
+                "insert into myspace.mymodel('orwell', 1984)",
+                "insert into myspace.mymodel('sayan', 100)",
+            ],
+            Some("delete from myspace.mymodel where followers < 1000"),
+            "select * from myspace.mymodel where followers >= 0",
+        )
+        .unwrap(),
+        vec![intovec!["orwell", 1984u64]]
+    );
+}
+
+#[test]
+fn delete_filtered_range() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_delete_delete_filtered_range");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            "create model myspace.mymodel(username: string, followers: uint64)",
+            &[
+                "insert into myspace.mymodel('douglas', 42)",
+                "insert into myspace.mymodel('orwell', 1984)",
+                "insert into myspace.mymodel('sayan', 100)",
+            ],
+            Some("delete from myspace.mymodel where followers >= 42 and followers < 1000"),
+            "select * from myspace.mymodel where followers >= 0",
+        )
+        .unwrap(),
+        vec![intovec!["orwell", 1984u64]]
+    );
+}
+
+#[test]
+fn delete_pk_with_filter() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_delete_delete_pk_with_filter");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            "create model myspace.mymodel(username: string, followers: uint64)",
+            &["insert into myspace.mymodel('sayan', 100)"],
+            Some("delete from myspace.mymodel where username = 'sayan' and followers > 100"),
+            "select * from myspace.mymodel where followers >= 0",
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlPreconditionFailed
+    );
+    // the row should still be there
+    assert_eq!(
+        super::_exec_only_select(
+            &global,
+            "select followers from myspace.mymodel where username = 'sayan'"
+        )
+        .unwrap(),
+        intovec![100u64]
+    );
+}
+
+#[test]
+fn delete_conditional() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_delete_delete_conditional");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            "create model myspace.mymodel(username: string, version: uint64)",
+            &["insert into myspace.mymodel('sayan', 2)"],
+            Some("delete from myspace.mymodel where username = 'sayan' if version = 1"),
+            "select * from myspace.mymodel where version >= 0",
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlPreconditionFailed
+    );
+    assert_eq!(
+        super::_exec_delete_only(
+            &global,
+            "delete from myspace.mymodel where username = 'nobody' if version = 2",
+            "nobody"
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlRowNotFound
+    );
+    super::_exec_delete_only(
+        &global,
+        "delete from myspace.mymodel where username = 'sayan' if version = 2",
+        "sayan",
+    )
+    .unwrap();
+}
+
+#[test]
+fn delete_ordered_range() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_delete_delete_ordered_range");
+    assert_eq!(
+        super::exec_scan_ordered(
+            &global,
+            "create model myspace.readings(ts: uint64, sensor: string) with { index: \"ordered\" }",
+            &[
+                "insert into myspace.readings(30, 'c')",
+                "insert into myspace.readings(10, 'a')",
+                "insert into myspace.readings(20, 'b')",
+                "insert into myspace.readings(40, 'd')",
+            ],
+            Some("delete from myspace.readings where ts > 10 and ts <= 30"),
+            "select * from myspace.readings where ts >= 0",
+        )
+        .unwrap(),
+        vec![intovec![10u64, "a"], intovec![40u64, "d"]]
+    );
+}
+
+#[test]
+fn delete_returning() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_delete_delete_returning");
+    super::_exec_only_create_space_model(
+        &global,
+        "create model myspace.readings(ts: uint64, sensor: string) with { index: \"ordered\" }",
+    )
+    .unwrap();
+    for insert in [
+        "insert into myspace.readings(1, 'a')",
+        "insert into myspace.readings(2, 'b')",
+        "insert into myspace.readings(3, 'c')",
+        "insert into myspace.readings(4, 'd')",
+    ] {
+        super::_exec_only_insert(&global, insert, |_| {}).unwrap();
+    }
+    // point deletes return the removed row
+    assert_eq!(
+        super::_exec_only_dml_resp(
+            &global,
+            "delete from myspace.readings where ts = 1 returning *"
+        )
+        .unwrap(),
+        super::row_resp(intovec![1u64, "a"])
+    );
+    assert_eq!(
+        super::_exec_only_dml_resp(
+            &global,
+            "delete from myspace.readings where ts = 2 if sensor = 'b' returning sensor"
+        )
+        .unwrap(),
+        super::row_resp(intovec!["b"])
+    );
+    // scans return every removed row
+    assert_eq!(
+        super::_exec_only_dml_resp(
+            &global,
+            "delete from myspace.readings where ts >= 3 returning sensor"
+        )
+        .unwrap(),
+        super::multirow_resp(1, vec![intovec!["c"], intovec!["d"]])
+    );
+}
@@ -24,7 +24,14 @@
  *
 */
 
-use crate::engine::{data::cell::Datacell, error::QueryError, fractal::test_utils::TestGlobal};
+use crate::engine::{
+    core::{dml, EntityIDRef},
+    data::cell::Datacell,
+    error::QueryError,
+    fractal::{test_utils::TestGlobal, GlobalInstanceLike},
+    net::protocol::Response,
+    ql::dml::ins::T_TIMESEC,
+};
 
 #[derive(sky_macros::Wrapper, Debug)]
 struct Tuple(Vec<(Box<str>, Datacell)>);
@@ -86,3 +93,441 @@ fn insert_duplicate() {
         QueryError::QExecDmlDuplicate
     );
 }
+
+#[test]
+fn insert_layer_props_violation() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_insert_layer_props_violation");
+    super::exec_insert(
+        &global,
+        "create model myspace.mymodel(username: string, password: string { minlen: 8 }, tags: list { type: string, maxlen: 2 })",
+        "insert into myspace.mymodel('sayan', 'pass1234', ['a', 'b'])",
+        "sayan",
+        |row| {
+            assert_veceq_transposed!(
+                row.cloned_data(),
+                Tuple(pairvec!(
+                    ("password", "pass1234"),
+                    ("tags", Datacell::new_list(intovec!["a", "b"]))
+                ))
+            );
+        },
+    )
+    .unwrap();
+    for insert in [
+        "insert into myspace.mymodel('ohsayan', 'pass', ['a'])",
+        "insert into myspace.mymodel('ohsayan', 'pass1234', ['a', 'b', 'c'])",
+    ] {
+        assert_eq!(
+            super::exec_insert_only(&global, insert).unwrap_err(),
+            QueryError::QExecDmlValidationError
+        );
+    }
+}
+
+#[test]
+fn insert_with_default() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_insert_with_default");
+    super::exec_insert(
+        &global,
+        "create model myspace.mymodel(username: string, followers: uint64 { default: 0 }, joined: uint64 { default: @timesec() }, null bio: string)",
+        "insert into myspace.mymodel { username: 'sayan', bio: null }",
+        "sayan",
+        |row| {
+            assert_veceq_transposed!(
+                row.cloned_data(),
+                Tuple(pairvec!(
+                    ("followers", 0u64),
+                    ("joined", T_TIMESEC),
+                    ("bio", Datacell::null())
+                ))
+            );
+        },
+    )
+    .unwrap();
+    // explicit values win over the defaults
+    super::exec_insert_core(
+        &global,
+        "insert into myspace.mymodel { username: 'ohsayan', followers: 100, bio: 'hi' }",
+        "ohsayan",
+        |row| {
+            assert_veceq_transposed!(
+                row.cloned_data(),
+                Tuple(pairvec!(
+                    ("followers", 100u64),
+                    ("joined", T_TIMESEC),
+                    ("bio", "hi")
+                ))
+            );
+        },
+    )
+    .unwrap();
+    // fields without a default can't be omitted
+    assert_eq!(
+        super::exec_insert_only(
+            &global,
+            "insert into myspace.mymodel { username: 'nobody' }"
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlValidationError
+    );
+}
+
+#[test]
+fn insert_timestamp_uuid() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_insert_timestamp_uuid");
+    super::exec_insert(
+        &global,
+        "create model myspace.events(name: string, at: timestamp { default: @now() }, trace: uuid { default: @uuid() })",
+        "insert into myspace.events { name: 'signup' }",
+        "signup",
+        |row| {
+            assert_veceq_transposed!(
+                row.cloned_data(),
+                Tuple(pairvec!(
+                    ("at", Datacell::new_timestamp(T_TIMESEC * 1_000_000_000)),
+                    ("trace", Datacell::new_uuid(0x4593264b_0231_43e9_b0aa_50784f14e204))
+                ))
+            );
+        },
+    )
+    .unwrap();
+    // values aren't coerced from other types
+    for insert in [
+        "insert into myspace.events('beta', 1673187839, @uuid())",
+        "insert into myspace.events('beta', @now(), '4593264b-0231-43e9-b0aa-50784f14e204')",
+    ] {
+        assert_eq!(
+            super::exec_insert_only(&global, insert).unwrap_err(),
+            QueryError::QExecDmlValidationError
+        );
+    }
+}
+
+#[test]
+fn insert_dict() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_insert_dict");
+    super::exec_insert(
+        &global,
+        "create model myspace.docs(name: string, meta: dict, labels: dict { type: string })",
+        "insert into myspace.docs { name: 'doc', meta: { views: 1, tags: ['a'], author: { name: 'sayan' } }, labels: { lang: 'en' } }",
+        "doc",
+        |row| {
+            assert_veceq_transposed!(
+                row.cloned_data(),
+                Tuple(pairvec!(
+                    (
+                        "meta",
+                        Datacell::new_dict(into_dict! {
+                            "views" => Datacell::new_uint_default(1),
+                            "tags" => Datacell::new_list(intovec!["a"]),
+                            "author" => Datacell::new_dict(into_dict!("name" => Datacell::from("sayan"))),
+                        })
+                    ),
+                    (
+                        "labels",
+                        Datacell::new_dict(into_dict!("lang" => Datacell::from("en")))
+                    )
+                ))
+            );
+        },
+    )
+    .unwrap();
+    for insert in [
+        // values of typed dicts must match the type
+        "insert into myspace.docs('beta', {}, { lang: 1 })",
+        // and can't be null
+        "insert into myspace.docs('beta', {}, { lang: null })",
+        // keys must be unique
+        "insert into myspace.docs('beta', { a: 1, a: 2 }, {})",
+    ] {
+        assert!(
+            super::exec_insert_only(&global, insert).is_err(),
+            "{insert}"
+        );
+    }
+}
+
+#[test]
+fn alter_add_backfills_default() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_alter_add_backfills_default");
+    super::exec_insert(
+        &global,
+        "create model myspace.mymodel(username: string, password: string)",
+        "insert into myspace.mymodel('sayan', 'pass123')",
+        "sayan",
+        |_| {},
+    )
+    .unwrap();
+    super::_exec_only_alter(
+        &global,
+        "alter model myspace.mymodel add (followers { type: uint64, default: 10 }, email { type: string, nullable: true })",
+    )
+    .unwrap();
+    assert_eq!(
+        super::exec_select_only(
+            &global,
+            "select * from myspace.mymodel where username = 'sayan'"
+        )
+        .unwrap(),
+        intovec!["sayan", "pass123", 10u64, Datacell::null()]
+    );
+    // the backfilled value has to be fixed
+    assert_eq!(
+        super::_exec_only_alter(
+            &global,
+            "alter model myspace.mymodel add created { type: uint64, default: @timesec() }",
+        )
+        .unwrap_err(),
+        QueryError::QExecDdlInvalidProperties
+    );
+}
+
+#[test]
+fn insert_returning() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_insert_returning");
+    super::_exec_only_create_space_model(
+        &global,
+        "create model myspace.posts(id: string, title: string, views: uint64 { default: 0 })",
+    )
+    .unwrap();
+    assert_eq!(
+        super::_exec_only_dml_resp(
+            &global,
+            "insert into myspace.posts { id: 'p1', title: 'hello' } returning *"
+        )
+        .unwrap(),
+        super::row_resp(intovec!["p1", "hello", 0u64])
+    );
+    assert_eq!(
+        super::_exec_only_dml_resp(
+            &global,
+            "insert into myspace.posts('p2', 'world', 5) returning views, id"
+        )
+        .unwrap(),
+        super::row_resp(intovec![5u64, "p2"])
+    );
+    assert_eq!(
+        super::_exec_only_dml_resp(
+            &global,
+            "upsert into myspace.posts('p2', 'again', 6) returning title"
+        )
+        .unwrap(),
+        super::row_resp(intovec!["again"])
+    );
+    // nothing is inserted if a returned field doesn't exist
+    assert_eq!(
+        super::_exec_only_dml_resp(
+            &global,
+            "insert into myspace.posts('p3', 'nope', 0) returning id, likes"
+        )
+        .unwrap_err(),
+        QueryError::QExecUnknownField
+    );
+    assert_eq!(
+        super::exec_select_only(&global, "select * from myspace.posts where id = 'p3'")
+            .unwrap_err(),
+        QueryError::QExecDmlRowNotFound
+    );
+    assert_eq!(
+        super::_exec_only_dml_resp(
+            &global,
+            "insert into myspace.posts('p1', 'again', 0) returning id"
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlDuplicate
+    );
+}
+
+#[test]
+fn insert_bulk() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_insert_bulk");
+    super::_exec_only_create_space_model(
+        &global,
+        "create model myspace.posts(id: string, title: string, views: uint64 { default: 0 })",
+    )
+    .unwrap();
+    assert_eq!(
+        super::_exec_only_dml_resp(
+            &global,
+            "insert into myspace.posts('p1', 'hello', 1), { id: 'p2', title: 'world' }, ('p3', 'again', 3)"
+        )
+        .unwrap(),
+        Response::Empty
+    );
+    assert_eq!(
+        super::exec_select_only(&global, "select * from myspace.posts where id = 'p2'").unwrap(),
+        intovec!["p2", "world", 0u64]
+    );
+    // all or nothing: a duplicate (in the model or within the rows) or an invalid row fails the whole insert
+    for query in [
+        "insert into myspace.posts('p4', 'new', 0), ('p1', 'dup', 0)",
+        "insert into myspace.posts('p4', 'new', 0), ('p4', 'dup', 0)",
+    ] {
+        assert_eq!(
+            super::_exec_only_dml_resp(&global, query).unwrap_err(),
+            QueryError::QExecDmlDuplicate
+        );
+    }
+    assert_eq!(
+        super::_exec_only_dml_resp(
+            &global,
+            "insert into myspace.posts('p4', 'new', 0), ('p5', 'bad', 'views')"
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlValidationError
+    );
+    assert_eq!(
+        super::exec_select_only(&global, "select * from myspace.posts where id = 'p4'")
+            .unwrap_err(),
+        QueryError::QExecDmlRowNotFound
+    );
+    assert_eq!(
+        super::exec_select_only(&global, "select title from myspace.posts where id = 'p1'")
+            .unwrap(),
+        intovec!["hello"]
+    );
+    // returning
+    assert_eq!(
+        super::_exec_only_dml_resp(
+            &global,
+            "insert into myspace.posts('p4', 'new', 4), ('p5', 'newer', 5) returning id, views"
+        )
+        .unwrap(),
+        super::multirow_resp(2, vec![intovec!["p4", 4u64], intovec!["p5", 5u64]])
+    );
+    // upserts only take a single row
+    assert_eq!(
+        super::_exec_only_dml_resp(
+            &global,
+            "upsert into myspace.posts('p1', 'a', 0), ('p6', 'b', 0)"
+        )
+        .unwrap_err(),
+        QueryError::QLInvalidSyntax
+    );
+}
+
+#[test]
+fn insert_with_ttl() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_insert_with_ttl");
+    super::_exec_only_create_space_model(
+        &global,
+        "create model myspace.sessions(id: string, user: string)",
+    )
+    .unwrap();
+    super::exec_insert_only(
+        &global,
+        "insert into myspace.sessions('s1', 'sayan') with ttl 3600",
+    )
+    .unwrap();
+    super::exec_insert_only(&global, "insert into myspace.sessions('s2', 'sayan')").unwrap();
+    let entity = EntityIDRef::new("myspace", "sessions");
+    // force the rows to expire
+    let expire = |key: &str| {
+        super::_exec_only_read_key_and_then(&global, entity, key, |row| {
+            assert_ne!(row.d_data().read().expires_at(), 0);
+            row.d_data().write().set_expires_at(1);
+        })
+        .unwrap()
+    };
+    expire("s1");
+    // an expired row is absent
+    assert_eq!(
+        super::exec_select_only(&global, "select * from myspace.sessions where id = 's1'")
+            .unwrap_err(),
+        QueryError::QExecDmlRowNotFound
+    );
+    assert_eq!(
+        super::exec_select_only(&global, "select * from myspace.sessions where id = 's2'").unwrap(),
+        intovec!["s2", "sayan"]
+    );
+    // and can be replaced
+    super::exec_insert_only(
+        &global,
+        "insert into myspace.sessions('s1', 'ferris') with ttl 60",
+    )
+    .unwrap();
+    assert_eq!(
+        super::exec_select_only(&global, "select * from myspace.sessions where id = 's1'").unwrap(),
+        intovec!["s1", "ferris"]
+    );
+    // expired rows are purged, visiting a bounded number of rows at a time
+    expire("s1");
+    let purge = |budget| {
+        global
+            .state()
+            .namespace()
+            .with_model(entity, |mdl| Ok(dml::purge_expired(mdl, budget)))
+            .unwrap()
+    };
+    let (mut purged, mut rounds) = (0, 0);
+    loop {
+        let (p, done) = purge(1);
+        purged += p;
+        rounds += 1;
+        if done {
+            break;
+        }
+    }
+    // two rows, and the last round finds the end of the index
+    assert_eq!((purged, rounds), (1, 3));
+    assert_eq!(purge(usize::MAX), (0, true));
+    // bad ttl
+    assert_eq!(
+        super::exec_insert_only(
+            &global,
+            "insert into myspace.sessions('s3', 'sayan') with ttl 0"
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlValidationError
+    );
+}
+
+#[test]
+fn insert_with_durability() {
+    let global = TestGlobal::new_with_driver_id("dml_insert_with_durability");
+    super::_exec_only_create_space_model(
+        &global,
+        "create model myspace.payments(id: string, amount: uint64) with { durability: \"sync\" }",
+    )
+    .unwrap();
+    let entity = EntityIDRef::new("myspace", "payments");
+    let pending = || {
+        global
+            .state()
+            .namespace()
+            .with_model(entity, |mdl| Ok(mdl.delta_state().__data_delta_count()))
+            .unwrap()
+    };
+    // the model's durability: written before the insert returns
+    super::exec_insert_only(&global, "insert into myspace.payments('p1', 100)").unwrap();
+    assert_eq!(pending(), 0);
+    // overridden for a single insert
+    super::exec_insert_only(
+        &global,
+        "insert into myspace.payments('p2', 200) with durability \"async\"",
+    )
+    .unwrap();
+    assert_eq!(pending(), 1);
+    super::exec_insert_only(
+        &global,
+        "insert into myspace.payments('p3', 300), ('p4', 400) with durability \"group-commit\"",
+    )
+    .unwrap();
+    assert_eq!(pending(), 1);
+    for (id, amount) in [("p1", 100u64), ("p2", 200), ("p3", 300), ("p4", 400)] {
+        assert_eq!(
+            super::exec_select_only(
+                &global,
+                &format!("select * from myspace.payments where id = '{id}'")
+            )
+            .unwrap(),
+            intovec![id, amount]
+        );
+    }
+    // a failed insert isn't written
+    assert_eq!(
+        super::exec_insert_only(&global, "insert into myspace.payments('p1', 500)").unwrap_err(),
+        QueryError::QExecDmlDuplicate
+    );
+    assert_eq!(pending(), 1);
+}
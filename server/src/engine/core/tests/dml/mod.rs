@@ -24,9 +24,11 @@
  *
 */
 
+mod batch;
 mod delete;
 mod insert;
 mod select;
+mod subscribe;
 mod update;
 
 use crate::engine::{
@@ -34,6 +36,8 @@ use crate::engine::{
     data::{cell::Datacell, lit::Lit},
     error::QueryResult,
     fractal::GlobalInstanceLike,
+    mem::IntegerRepr,
+    net::protocol::{Response, ResponseType},
     ql::{
         ast::parse_ast_node_full,
         dml::{del::DeleteStatement, ins::InsertStatement},
@@ -54,6 +58,11 @@ fn _exec_only_create_space_model(global: &impl GlobalInstanceLike, model: &str)
     ModelData::transactional_exec_create(global, stmt_create_model).map(|_| ())
 }
 
+fn _exec_only_alter(global: &impl GlobalInstanceLike, alter: &str) -> QueryResult<()> {
+    let lex_alter = lex_insecure(alter.as_bytes()).unwrap();
+    ModelData::transactional_exec_alter(global, parse_ast_node_full(&lex_alter[2..]).unwrap())
+}
+
 fn _exec_only_insert<T>(
     global: &impl GlobalInstanceLike,
     insert: &str,
@@ -67,6 +76,41 @@ fn _exec_only_insert<T>(
     Ok(r)
 }
 
+/// Run an `insert`, `upsert`, `update` or `delete` and return its response
+fn _exec_only_dml_resp(global: &impl GlobalInstanceLike, query: &str) -> QueryResult<Response> {
+    let lex = lex_insecure(query.as_bytes()).unwrap();
+    match lex[0] {
+        Token![insert] => dml::insert_resp(global, parse_ast_node_full(&lex[1..]).unwrap()),
+        Token![update] => dml::update_resp(global, parse_ast_node_full(&lex[1..]).unwrap()),
+        Token![delete] => dml::delete_resp(global, parse_ast_node_full(&lex[1..]).unwrap()),
+        _ => dml::upsert_resp(global, parse_ast_node_full(&lex[1..]).unwrap()),
+    }
+}
+
+fn row_resp(cells: Vec<Datacell>) -> Response {
+    let mut data = vec![];
+    cells.iter().for_each(|dc| dml::encode_cell(&mut data, dc));
+    Response::Serialized {
+        ty: ResponseType::Row,
+        size: cells.len(),
+        data,
+    }
+}
+
+fn multirow_resp(col_c: usize, rows: Vec<Vec<Datacell>>) -> Response {
+    let mut data = vec![];
+    IntegerRepr::scoped(col_c as u64, |repr| data.extend(repr));
+    data.push(b'\n');
+    rows.iter()
+        .flatten()
+        .for_each(|dc| dml::encode_cell(&mut data, dc));
+    Response::Serialized {
+        ty: ResponseType::MultiRow,
+        size: rows.len(),
+        data,
+    }
+}
+
 fn _exec_only_read_key_and_then<T>(
     global: &impl GlobalInstanceLike,
     entity: EntityIDRef,
@@ -104,10 +148,48 @@ fn _exec_only_select(global: &impl GlobalInstanceLike, select: &str) -> QueryRes
     let lex_sel = lex_insecure(select.as_bytes()).unwrap();
     let select = parse_ast_node_full(&lex_sel[1..]).unwrap();
     let mut r = Vec::new();
-    dml::select_custom(global, select, |cell| r.push(cell.clone()))?;
+    dml::select_custom(
+        global,
+        select,
+        &mut r,
+        |_, _| {},
+        |r, cell| r.push(cell.clone()),
+    )?;
     Ok(r)
 }
 
+/// Run a select that requires a scan, returning the matched rows sorted by their first (string) column
+fn _exec_only_select_scan(
+    global: &impl GlobalInstanceLike,
+    select: &str,
+) -> QueryResult<Vec<Vec<Datacell>>> {
+    let mut rows = _exec_only_select_scan_unsorted(global, select)?;
+    rows.sort_by(|a, b| a[0].str().cmp(b[0].str()));
+    Ok(rows)
+}
+
+/// Returns the rows in the order that the scan produced them
+fn _exec_only_select_scan_unsorted(
+    global: &impl GlobalInstanceLike,
+    select: &str,
+) -> QueryResult<Vec<Vec<Datacell>>> {
+    let lex_sel = lex_insecure(select.as_bytes()).unwrap();
+    let select = parse_ast_node_full(&lex_sel[1..]).unwrap();
+    let mut col_c = 0;
+    let mut cells = Vec::new();
+    let row_c = dml::select_custom(
+        global,
+        select,
+        &mut cells,
+        |_, c| col_c = c,
+        |cells, cell| cells.push(cell.clone()),
+    )?
+    .expect("expected a scan");
+    let rows: Vec<Vec<Datacell>> = cells.chunks(col_c).map(|row| row.to_vec()).collect();
+    assert_eq!(rows.len(), row_c);
+    Ok(rows)
+}
+
 fn _exec_only_update(global: &impl GlobalInstanceLike, update: &str) -> QueryResult<()> {
     let lex_upd = lex_insecure(update.as_bytes()).unwrap();
     let update = parse_ast_node_full(&lex_upd[1..]).unwrap();
@@ -175,10 +257,17 @@ pub(self) fn exec_select_all(
     for insert in inserts {
         _exec_only_insert(global, insert, |_| {})?;
     }
-    let lex_sel = lex_insecure(select.as_bytes()).unwrap();
+    _exec_only_select_all(global, select.as_bytes()).map(|(rows, _)| rows)
+}
+
+fn _exec_only_select_all(
+    global: &impl GlobalInstanceLike,
+    select: &[u8],
+) -> QueryResult<(Vec<Vec<Datacell>>, Option<Datacell>)> {
+    let lex_sel = lex_insecure(select).unwrap();
     let select = parse_ast_node_full(&lex_sel[2..]).unwrap();
     let mut r: Vec<Vec<Datacell>> = Vec::new();
-    dml::select_all(
+    let (_, cursor) = dml::select_all(
         global,
         select,
         &mut r,
@@ -188,7 +277,51 @@ pub(self) fn exec_select_all(
             _ => rows.push(vec![dc.clone()]),
         },
     )?;
-    Ok(r)
+    Ok((r, cursor))
+}
+
+/// Page through the model using the given `select all ... after` query (with `{cursor}` in place of the cursor),
+/// returning every page
+fn exec_select_all_pages(
+    global: &impl GlobalInstanceLike,
+    model: &str,
+    inserts: &[&str],
+    select: &str,
+) -> QueryResult<Vec<Vec<Vec<Datacell>>>> {
+    _exec_only_create_space_model(global, model)?;
+    for insert in inserts {
+        _exec_only_insert(global, insert, |_| {})?;
+    }
+    let (head, tail) = select.split_once("{cursor}").unwrap();
+    let mut pages = vec![];
+    let mut cursor = vec![];
+    loop {
+        let mut query = format!("{head}\r{}\n", cursor.len()).into_bytes();
+        query.extend(&cursor);
+        query.extend(tail.as_bytes());
+        let (rows, next) = _exec_only_select_all(global, &query)?;
+        pages.push(rows);
+        match next.unwrap().into_bin() {
+            Some(next) => cursor = next,
+            None => break,
+        }
+    }
+    Ok(pages)
+}
+
+fn exec_exists(
+    global: &impl GlobalInstanceLike,
+    model: &str,
+    inserts: &[&str],
+    exists: &str,
+) -> QueryResult<usize> {
+    _exec_only_create_space_model(global, model)?;
+    for insert in inserts {
+        _exec_only_insert(global, insert, |_| {})?;
+    }
+    let lex_exists = lex_insecure(exists.as_bytes()).unwrap();
+    let exists = parse_ast_node_full(&lex_exists[1..]).unwrap();
+    dml::exists(global, &exists)
 }
 
 pub(self) fn exec_select_only(
@@ -198,6 +331,65 @@ pub(self) fn exec_select_only(
     _exec_only_select(global, select)
 }
 
+fn exec_scan(
+    global: &impl GlobalInstanceLike,
+    model: &str,
+    inserts: &[&str],
+    query: Option<&str>,
+    select: &str,
+) -> QueryResult<Vec<Vec<Datacell>>> {
+    _exec_only_create_space_model(global, model)?;
+    for insert in inserts {
+        _exec_only_insert(global, insert, |_| {})?;
+    }
+    if let Some(query) = query {
+        let lex = lex_insecure(query.as_bytes()).unwrap();
+        if lex[0] == Token![update] {
+            dml::update(global, parse_ast_node_full(&lex[1..]).unwrap())?
+        } else {
+            dml::delete(global, parse_ast_node_full(&lex[1..]).unwrap())?
+        }
+    }
+    _exec_only_select_scan(global, select)
+}
+
+fn exec_aggregate(
+    global: &impl GlobalInstanceLike,
+    model: &str,
+    inserts: &[&str],
+    select: &str,
+) -> QueryResult<Vec<Datacell>> {
+    _exec_only_create_space_model(global, model)?;
+    for insert in inserts {
+        _exec_only_insert(global, insert, |_| {})?;
+    }
+    let lex_sel = lex_insecure(select.as_bytes()).unwrap();
+    dml::select_aggregate(global, parse_ast_node_full(&lex_sel[1..]).unwrap())
+}
+
+/// Same as [`exec_scan`], but the rows are returned in the order that the scan produced them
+fn exec_scan_ordered(
+    global: &impl GlobalInstanceLike,
+    model: &str,
+    inserts: &[&str],
+    query: Option<&str>,
+    select: &str,
+) -> QueryResult<Vec<Vec<Datacell>>> {
+    _exec_only_create_space_model(global, model)?;
+    for insert in inserts {
+        _exec_only_insert(global, insert, |_| {})?;
+    }
+    if let Some(query) = query {
+        let lex = lex_insecure(query.as_bytes()).unwrap();
+        if lex[0] == Token![update] {
+            dml::update(global, parse_ast_node_full(&lex[1..]).unwrap())?
+        } else {
+            dml::delete(global, parse_ast_node_full(&lex[1..]).unwrap())?
+        }
+    }
+    _exec_only_select_scan_unsorted(global, select)
+}
+
 pub(self) fn exec_update(
     global: &impl GlobalInstanceLike,
     model: &str,
@@ -165,3 +165,664 @@ fn select_all_onefield() {
     assert_eq!(ret.get("hgwells").unwrap(), &intovec![]);
     assert_eq!(ret.get("orwell").unwrap(), &intovec![]);
 }
+
+/*
+    filtered select
+*/
+
+const FILTER_MODEL: &str =
+    "create model myspace.mymodel(username: string, followers: uint64, score: sint64, null email: string)";
+const FILTER_INSERTS: [&str; 4] = [
+    "insert into myspace.mymodel('douglas', 42, -5, null)",
+    "insert into myspace.mymodel('orwell', 1984, -1, 'orwell@example.com')",
+    "insert into myspace.mymodel('robot', 0, -100, 'robot@example.com')",
+    "insert into myspace.mymodel('sayan', 100, -50, 'sayan@example.com')",
+];
+
+#[test]
+fn select_range_non_pk() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_select_range_non_pk");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            FILTER_MODEL,
+            &FILTER_INSERTS,
+            None,
+            "select username, followers from myspace.mymodel where followers >= 100",
+        )
+        .unwrap(),
+        vec![intovec!["orwell", 1984u64], intovec!["sayan", 100u64]]
+    );
+}
+
+#[test]
+fn select_multiple_predicates() {
+    let global =
+        TestGlobal::new_with_driver_id_instant_update("dml_select_select_multiple_predicates");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            FILTER_MODEL,
+            &FILTER_INSERTS,
+            None,
+            "select username from myspace.mymodel where score > -10 and followers != 0",
+        )
+        .unwrap(),
+        vec![intovec!["douglas"], intovec!["orwell"]]
+    );
+}
+
+#[test]
+fn select_range_pk() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_select_range_pk");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            FILTER_MODEL,
+            &FILTER_INSERTS,
+            None,
+            "select username from myspace.mymodel where username > 'p'",
+        )
+        .unwrap(),
+        vec![intovec!["robot"], intovec!["sayan"]]
+    );
+}
+
+#[test]
+fn select_range_between_non_pk() {
+    let global =
+        TestGlobal::new_with_driver_id_instant_update("dml_select_select_range_between_non_pk");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            FILTER_MODEL,
+            &FILTER_INSERTS,
+            None,
+            "select username from myspace.mymodel where followers > 40 and followers <= 100",
+        )
+        .unwrap(),
+        vec![intovec!["douglas"], intovec!["sayan"]]
+    );
+}
+
+#[test]
+fn select_filter_skips_null() {
+    let global =
+        TestGlobal::new_with_driver_id_instant_update("dml_select_select_filter_skips_null");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            FILTER_MODEL,
+            &FILTER_INSERTS,
+            None,
+            "select username from myspace.mymodel where email != 'sayan@example.com'",
+        )
+        .unwrap(),
+        vec![intovec!["orwell"], intovec!["robot"]]
+    );
+}
+
+#[test]
+fn select_pk_with_filter() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_select_pk_with_filter");
+    assert_eq!(
+        super::exec_select(
+            &global,
+            "create model myspace.mymodel(username: string, followers: uint64)",
+            "insert into myspace.mymodel('sayan', 100)",
+            "select * from myspace.mymodel where username = 'sayan' and followers > 10",
+        )
+        .unwrap(),
+        intovec!["sayan", 100u64]
+    );
+    assert_eq!(
+        super::exec_select_only(
+            &global,
+            "select * from myspace.mymodel where username = 'sayan' and followers > 100",
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlRowNotFound
+    );
+}
+
+#[test]
+fn select_filter_bad_type() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_select_filter_bad_type");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            FILTER_MODEL,
+            &FILTER_INSERTS,
+            None,
+            "select username from myspace.mymodel where followers > 'many'",
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlValidationError
+    );
+    assert_eq!(
+        super::_exec_only_select_scan(
+            &global,
+            "select username from myspace.mymodel where rank > 1",
+        )
+        .unwrap_err(),
+        QueryError::QExecUnknownField
+    );
+}
+
+#[test]
+fn select_filter_list_contains() {
+    let global =
+        TestGlobal::new_with_driver_id_instant_update("dml_select_select_filter_list_contains");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            "create model myspace.mymodel(username: string, null tags: list { type: string })",
+            &[
+                "insert into myspace.mymodel('sayan', ['rust', 'db'])",
+                "insert into myspace.mymodel('orwell', ['books'])",
+                "insert into myspace.mymodel('robot', null)",
+                "insert into myspace.mymodel('ferris', ['rust'])",
+            ],
+            None,
+            "select username from myspace.mymodel where tags contains 'rust'",
+        )
+        .unwrap(),
+        vec![intovec!["ferris"], intovec!["sayan"]]
+    );
+    for select in [
+        // the element type doesn't match
+        "select username from myspace.mymodel where tags contains 1",
+        // not a list
+        "select username from myspace.mymodel where username contains 'sayan'",
+    ] {
+        assert_eq!(
+            super::_exec_only_select_scan(&global, select).unwrap_err(),
+            QueryError::QExecDmlValidationError
+        );
+    }
+}
+
+/*
+    exists
+*/
+
+#[test]
+fn exists_single() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_exists_single");
+    assert_eq!(
+        super::exec_exists(
+            &global,
+            "create model myspace.mymodel(username: string, password: string)",
+            &["insert into myspace.mymodel('sayan', 'pass123')"],
+            "exists from myspace.mymodel where username = 'sayan'",
+        )
+        .unwrap(),
+        1
+    );
+    assert_eq!(
+        super::exec_exists(
+            &global,
+            "create model myspace.mymodel2(username: string, password: string)",
+            &[],
+            "exists from myspace.mymodel2 where username = 'sayan'",
+        )
+        .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn exists_multi() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_exists_multi");
+    assert_eq!(
+        super::exec_exists(
+            &global,
+            "create model myspace.mymodel(username: string, password: string)",
+            &[
+                "insert into myspace.mymodel('sayan', 'pass123')",
+                "insert into myspace.mymodel('robot', 'robot123')",
+            ],
+            "exists from myspace.mymodel where username in ('sayan', 'douglas', 'robot')",
+        )
+        .unwrap(),
+        2
+    );
+    // a repeated key is only counted once
+    assert_eq!(
+        super::exec_exists(
+            &global,
+            "create model myspace.mymodel2(username: string, password: string)",
+            &["insert into myspace.mymodel2('sayan', 'pass123')"],
+            "exists from myspace.mymodel2 where username in ('sayan', 'robot', 'sayan')",
+        )
+        .unwrap(),
+        1
+    );
+}
+
+#[test]
+fn exists_non_pk() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_exists_non_pk");
+    assert_eq!(
+        super::exec_exists(
+            &global,
+            "create model myspace.mymodel(username: string, password: string)",
+            &["insert into myspace.mymodel('sayan', 'pass123')"],
+            "exists from myspace.mymodel where password = 'pass123'",
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlWhereHasUnindexedColumn
+    );
+}
+
+const ORDERED_MODEL: &str =
+    "create model myspace.readings(ts: uint64, sensor: string) with { index: \"ordered\" }";
+const ORDERED_INSERTS: [&str; 5] = [
+    "insert into myspace.readings(50, 'e')",
+    "insert into myspace.readings(10, 'a')",
+    "insert into myspace.readings(40, 'd')",
+    "insert into myspace.readings(20, 'b')",
+    "insert into myspace.readings(30, 'c')",
+];
+
+#[test]
+fn select_ordered_range() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_ordered_range");
+    assert_eq!(
+        super::exec_scan_ordered(
+            &global,
+            ORDERED_MODEL,
+            &ORDERED_INSERTS,
+            None,
+            "select ts, sensor from myspace.readings where ts >= 20 and ts < 50",
+        )
+        .unwrap(),
+        vec![
+            intovec![20u64, "b"],
+            intovec![30u64, "c"],
+            intovec![40u64, "d"]
+        ]
+    );
+}
+
+#[test]
+fn select_ordered_range_with_filter() {
+    let global =
+        TestGlobal::new_with_driver_id_instant_update("dml_select_ordered_range_with_filter");
+    assert_eq!(
+        super::exec_scan_ordered(
+            &global,
+            ORDERED_MODEL,
+            &ORDERED_INSERTS,
+            None,
+            "select sensor from myspace.readings where ts > 10 and sensor != 'c' and ts <= 40",
+        )
+        .unwrap(),
+        vec![intovec!["b"], intovec!["d"]]
+    );
+}
+
+#[test]
+fn select_ordered_empty_range() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_ordered_empty_range");
+    assert_eq!(
+        super::exec_scan_ordered(
+            &global,
+            ORDERED_MODEL,
+            &ORDERED_INSERTS,
+            None,
+            "select sensor from myspace.readings where ts > 30 and ts < 30",
+        )
+        .unwrap(),
+        Vec::<Vec<Datacell>>::new()
+    );
+}
+
+#[test]
+fn select_all_ordered() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_all_ordered");
+    assert_eq!(
+        super::exec_select_all(
+            &global,
+            ORDERED_MODEL,
+            &ORDERED_INSERTS,
+            "select all sensor from myspace.readings limit 3",
+        )
+        .unwrap(),
+        vec![intovec!["a"], intovec!["b"], intovec!["c"]]
+    );
+}
+
+/*
+    paginated select all
+*/
+
+#[test]
+fn select_all_paged_ordered() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_all_paged_ordered");
+    assert_eq!(
+        super::exec_select_all_pages(
+            &global,
+            ORDERED_MODEL,
+            &ORDERED_INSERTS,
+            "select all sensor from myspace.readings after {cursor} limit 2",
+        )
+        .unwrap(),
+        vec![
+            vec![intovec!["a"], intovec!["b"]],
+            vec![intovec!["c"], intovec!["d"]],
+            vec![intovec!["e"]],
+        ]
+    );
+}
+
+#[test]
+fn select_all_paged_hashed() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_all_paged_hashed");
+    let inserts: Vec<String> = (0..25)
+        .map(|i| format!("insert into myspace.mymodel('user{i}', {i})"))
+        .collect();
+    let inserts: Vec<&str> = inserts.iter().map(String::as_str).collect();
+    let pages = super::exec_select_all_pages(
+        &global,
+        "create model myspace.mymodel(username: string, followers: uint64)",
+        &inserts,
+        "select all followers from myspace.mymodel after {cursor} limit 4",
+    )
+    .unwrap();
+    assert_eq!(
+        pages.iter().map(Vec::len).collect::<Vec<_>>(),
+        [4, 4, 4, 4, 4, 4, 1]
+    );
+    let mut followers: Vec<u64> = pages
+        .into_iter()
+        .flatten()
+        .map(|mut row| row.remove(0).uint())
+        .collect();
+    followers.sort();
+    assert_eq!(followers, (0..25).collect::<Vec<u64>>());
+}
+
+#[test]
+fn select_all_paged_exact() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_all_paged_exact");
+    assert_eq!(
+        super::exec_select_all_pages(
+            &global,
+            ORDERED_MODEL,
+            &ORDERED_INSERTS[..4],
+            "select all ts from myspace.readings after {cursor} limit 2",
+        )
+        .unwrap(),
+        vec![
+            vec![intovec![10u64], intovec![20u64]],
+            vec![intovec![40u64], intovec![50u64]],
+            vec![],
+        ]
+    );
+}
+
+#[test]
+fn select_all_bad_cursor() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_all_bad_cursor");
+    assert_eq!(
+        super::exec_select_all(
+            &global,
+            ORDERED_MODEL,
+            &ORDERED_INSERTS,
+            "select all * from myspace.readings after \r3\nabc limit 2",
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlValidationError
+    );
+}
+
+#[test]
+fn select_aggregates() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_aggregates");
+    assert_eq!(
+        super::exec_aggregate(
+            &global,
+            ORDERED_MODEL,
+            &ORDERED_INSERTS,
+            "select count(*), sum(ts), min(sensor), max(ts), avg(ts) from myspace.readings",
+        )
+        .unwrap(),
+        vec![
+            Datacell::new_uint_default(5),
+            Datacell::new_uint_default(150),
+            Datacell::from("a"),
+            Datacell::new_uint_default(50),
+            Datacell::new_float_default(30.0),
+        ]
+    );
+}
+
+#[test]
+fn select_aggregates_where() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_aggregates_where");
+    assert_eq!(
+        super::exec_aggregate(
+            &global,
+            ORDERED_MODEL,
+            &ORDERED_INSERTS,
+            "select count(sensor), sum(ts), max(sensor) from myspace.readings where ts > 20",
+        )
+        .unwrap(),
+        vec![
+            Datacell::new_uint_default(3),
+            Datacell::new_uint_default(120),
+            Datacell::from("e"),
+        ]
+    );
+}
+
+#[test]
+fn select_aggregates_no_rows() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_aggregates_no_rows");
+    assert_eq!(
+        super::exec_aggregate(
+            &global,
+            ORDERED_MODEL,
+            &ORDERED_INSERTS,
+            "select count(*), sum(ts), min(ts), avg(ts) from myspace.readings where ts > 100",
+        )
+        .unwrap(),
+        vec![
+            Datacell::new_uint_default(0),
+            Datacell::null(),
+            Datacell::null(),
+            Datacell::null(),
+        ]
+    );
+}
+
+#[test]
+fn select_aggregates_bad_field() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_aggregates_bad_field");
+    assert_eq!(
+        super::exec_aggregate(
+            &global,
+            ORDERED_MODEL,
+            &ORDERED_INSERTS,
+            "select sum(sensor) from myspace.readings",
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlValidationError
+    );
+}
+
+#[test]
+fn select_aggregates_unknown_field() {
+    let global =
+        TestGlobal::new_with_driver_id_instant_update("dml_select_aggregates_unknown_field");
+    assert_eq!(
+        super::exec_aggregate(
+            &global,
+            ORDERED_MODEL,
+            &ORDERED_INSERTS,
+            "select count(humidity) from myspace.readings",
+        )
+        .unwrap_err(),
+        QueryError::QExecUnknownField
+    );
+}
+
+/*
+    ordered select all
+*/
+
+#[test]
+fn select_all_order_by() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_all_order_by");
+    assert_eq!(
+        super::exec_select_all(
+            &global,
+            FILTER_MODEL,
+            &FILTER_INSERTS,
+            "select all username, followers from myspace.mymodel order by followers limit 10",
+        )
+        .unwrap(),
+        vec![
+            intovec!["robot", 0u64],
+            intovec!["douglas", 42u64],
+            intovec!["sayan", 100u64],
+            intovec!["orwell", 1984u64],
+        ]
+    );
+}
+
+#[test]
+fn select_all_order_by_desc_top_n() {
+    let global =
+        TestGlobal::new_with_driver_id_instant_update("dml_select_all_order_by_desc_top_n");
+    assert_eq!(
+        super::exec_select_all(
+            &global,
+            FILTER_MODEL,
+            &FILTER_INSERTS,
+            "select all username from myspace.mymodel order by score desc limit 2",
+        )
+        .unwrap(),
+        vec![intovec!["orwell"], intovec!["douglas"]]
+    );
+}
+
+#[test]
+fn select_all_order_by_nulls_first() {
+    let global =
+        TestGlobal::new_with_driver_id_instant_update("dml_select_all_order_by_nulls_first");
+    assert_eq!(
+        super::exec_select_all(
+            &global,
+            FILTER_MODEL,
+            &FILTER_INSERTS,
+            "select all username from myspace.mymodel order by email asc limit 2",
+        )
+        .unwrap(),
+        vec![intovec!["douglas"], intovec!["orwell"]]
+    );
+}
+
+#[test]
+fn select_all_order_by_zero_limit() {
+    let global =
+        TestGlobal::new_with_driver_id_instant_update("dml_select_all_order_by_zero_limit");
+    assert_eq!(
+        super::exec_select_all(
+            &global,
+            FILTER_MODEL,
+            &FILTER_INSERTS,
+            "select all * from myspace.mymodel order by username limit 0",
+        )
+        .unwrap(),
+        Vec::<Vec<Datacell>>::new()
+    );
+}
+
+#[test]
+fn select_all_order_by_unknown_field() {
+    let global =
+        TestGlobal::new_with_driver_id_instant_update("dml_select_all_order_by_unknown_field");
+    assert_eq!(
+        super::exec_select_all(
+            &global,
+            FILTER_MODEL,
+            &FILTER_INSERTS,
+            "select all * from myspace.mymodel order by karma limit 10",
+        )
+        .unwrap_err(),
+        QueryError::QExecUnknownField
+    );
+}
+
+/*
+    timestamp and uuid
+*/
+
+const TYPED_MODEL: &str = "create model myspace.events(name: string, at: timestamp, trace: uuid)";
+const TYPED_INSERTS: [&str; 3] = [
+    "insert into myspace.events('signup', timestamp'2023-01-08T14:23:59Z', uuid'4593264b-0231-43e9-b0aa-50784f14e204')",
+    "insert into myspace.events('beta', timestamp'2021-06-01T00:00:00Z', uuid'00000000-0000-0000-0000-000000000001')",
+    "insert into myspace.events('launch', timestamp'2024-12-31T23:59:59.999999999Z', uuid'ffffffff-ffff-ffff-ffff-ffffffffffff')",
+];
+
+#[test]
+fn select_timestamp_range() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_timestamp_range");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            TYPED_MODEL,
+            &TYPED_INSERTS,
+            None,
+            "select name, at from myspace.events where at >= timestamp'2022-01-01T00:00:00Z'",
+        )
+        .unwrap(),
+        vec![
+            intovec!["launch", Datacell::new_timestamp(1735689599_999_999_999)],
+            intovec!["signup", Datacell::new_timestamp(1673187839_000_000_000)],
+        ]
+    );
+}
+
+#[test]
+fn select_uuid_eq() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_select_uuid_eq");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            TYPED_MODEL,
+            &TYPED_INSERTS,
+            None,
+            "select name, trace from myspace.events where trace = uuid'00000000-0000-0000-0000-000000000001'",
+        )
+        .unwrap(),
+        vec![intovec!["beta", Datacell::new_uuid(1)]]
+    );
+}
+
+#[test]
+fn select_all_order_by_timestamp_and_uuid() {
+    let global =
+        TestGlobal::new_with_driver_id_instant_update("dml_select_all_order_by_timestamp_and_uuid");
+    assert_eq!(
+        super::exec_select_all(
+            &global,
+            TYPED_MODEL,
+            &TYPED_INSERTS,
+            "select all name from myspace.events order by at desc limit 10",
+        )
+        .unwrap(),
+        vec![intovec!["launch"], intovec!["signup"], intovec!["beta"]]
+    );
+    assert_eq!(
+        super::_exec_only_select_all(
+            &global,
+            b"select all name from myspace.events order by trace limit 10"
+        )
+        .unwrap()
+        .0,
+        vec![intovec!["beta"], intovec!["signup"], intovec!["launch"]]
+    );
+}
@@ -0,0 +1,299 @@
+/*
+ * Created on Sun Oct 18 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+use {
+    crate::engine::{
+        core::{
+            model::{ChangeEvent, ModelData},
+            EntityIDRef,
+        },
+        data::cell::Datacell,
+        error::QueryError,
+        fractal::{test_utils::TestGlobal, GlobalInstanceLike},
+        net::protocol::Response,
+        ql::{ast::parse_ast_node_full, dml::sub::SubscribeStatement, tests::lex_insecure},
+        storage::common::interface::fs::{FSContext, FileSystem},
+    },
+    std::sync::Arc,
+    tokio::sync::broadcast::{error::TryRecvError, Receiver},
+};
+
+const MODEL: &str = "create model myspace.accounts(username: string, balance: uint64)";
+
+fn subscribe(global: &TestGlobal) -> Receiver<Arc<ChangeEvent>> {
+    global
+        .state()
+        .namespace()
+        .with_model(EntityIDRef::new("myspace", "accounts"), |mdl| {
+            Ok(mdl.change_feed().subscribe())
+        })
+        .unwrap()
+}
+
+fn next_event(rx: &mut Receiver<Arc<ChangeEvent>>) -> Response {
+    rx.try_recv().unwrap().response()
+}
+
+fn image(username: &'static str, balance: u64) -> Option<Datacell> {
+    Some(Datacell::new_list(intovec![username, balance]))
+}
+
+fn event(
+    version: u64,
+    kind: &'static str,
+    key: &'static str,
+    pre: Option<Datacell>,
+    post: Option<Datacell>,
+) -> Response {
+    super::row_resp(vec![
+        Datacell::new_uint_default(version),
+        Datacell::new_str(kind.into()),
+        Datacell::from(key),
+        pre.unwrap_or_else(Datacell::null),
+        post.unwrap_or_else(Datacell::null),
+    ])
+}
+
+#[test]
+fn feed_images() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_subscribe_feed_images");
+    super::_exec_only_create_space_model(&global, MODEL).unwrap();
+    // nobody is subscribed yet
+    super::exec_insert_only(&global, "insert into myspace.accounts('sayan', 100)").unwrap();
+    let mut rx = subscribe(&global);
+    for query in [
+        "update myspace.accounts set balance += 10 where username = 'sayan'",
+        "upsert into myspace.accounts('sayan', 500)",
+        "delete from myspace.accounts where username = 'sayan'",
+        "insert into myspace.accounts('sayan', 1)",
+    ] {
+        super::_exec_only_dml_resp(&global, query).unwrap();
+    }
+    assert_eq!(
+        next_event(&mut rx),
+        event(
+            1,
+            "update",
+            "sayan",
+            image("sayan", 100),
+            image("sayan", 110)
+        )
+    );
+    assert_eq!(
+        next_event(&mut rx),
+        event(
+            2,
+            "upsert",
+            "sayan",
+            image("sayan", 110),
+            image("sayan", 500)
+        )
+    );
+    assert_eq!(
+        next_event(&mut rx),
+        event(3, "delete", "sayan", image("sayan", 500), None)
+    );
+    assert_eq!(
+        next_event(&mut rx),
+        event(4, "insert", "sayan", None, image("sayan", 1))
+    );
+    assert_eq!(rx.try_recv().unwrap_err(), TryRecvError::Empty);
+}
+
+#[test]
+fn feed_durable_writes() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_subscribe_feed_durable_writes");
+    super::_exec_only_create_space_model(&global, MODEL).unwrap();
+    super::exec_insert_only(&global, "insert into myspace.accounts('sayan', 100)").unwrap();
+    let mut rx = subscribe(&global);
+    for durability in ["sync", "group-commit"] {
+        super::_exec_only_dml_resp(
+            &global,
+            &format!(
+                "update myspace.accounts set balance += 10 where username = 'sayan' with durability \"{durability}\""
+            ),
+        )
+        .unwrap();
+    }
+    assert_eq!(
+        next_event(&mut rx),
+        event(
+            1,
+            "update",
+            "sayan",
+            image("sayan", 100),
+            image("sayan", 110)
+        )
+    );
+    assert_eq!(
+        next_event(&mut rx),
+        event(
+            2,
+            "update",
+            "sayan",
+            image("sayan", 110),
+            image("sayan", 120)
+        )
+    );
+}
+
+#[test]
+fn feed_failed_write() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_subscribe_feed_failed_write");
+    super::_exec_only_create_space_model(&global, MODEL).unwrap();
+    super::exec_insert_only(&global, "insert into myspace.accounts('sayan', 100)").unwrap();
+    let mut rx = subscribe(&global);
+    assert_eq!(
+        super::_exec_only_dml_resp(&global, "insert into myspace.accounts('sayan', 200)")
+            .unwrap_err(),
+        QueryError::QExecDmlDuplicate
+    );
+    assert_eq!(rx.try_recv().unwrap_err(), TryRecvError::Empty);
+}
+
+#[test]
+fn feed_batch() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_subscribe_feed_batch");
+    super::_exec_only_create_space_model(&global, MODEL).unwrap();
+    super::exec_insert_only(&global, "insert into myspace.accounts('sayan', 100)").unwrap();
+    let mut rx = subscribe(&global);
+    super::batch::exec_batch(
+        &global,
+        &[
+            (
+                "update myspace.accounts set balance -= ? where username = ?",
+                b"\x0240\n\x065\nsayan",
+            ),
+            (
+                "update myspace.accounts set balance -= ? where username = ?",
+                b"\x0210\n\x065\nsayan",
+            ),
+            (
+                "delete from myspace.accounts where username = ?",
+                b"\x065\nsayan",
+            ),
+        ],
+    )
+    .unwrap();
+    // every change in the batch has the images as they were at that point in the batch
+    assert_eq!(
+        next_event(&mut rx),
+        event(
+            1,
+            "update",
+            "sayan",
+            image("sayan", 100),
+            image("sayan", 60)
+        )
+    );
+    assert_eq!(
+        next_event(&mut rx),
+        event(2, "update", "sayan", image("sayan", 60), image("sayan", 50))
+    );
+    assert_eq!(
+        next_event(&mut rx),
+        event(3, "delete", "sayan", image("sayan", 50), None)
+    );
+}
+
+#[test]
+fn feed_truncate() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_subscribe_feed_truncate");
+    super::_exec_only_create_space_model(&global, MODEL).unwrap();
+    super::exec_insert_only(&global, "insert into myspace.accounts('sayan', 100)").unwrap();
+    let mut rx = subscribe(&global);
+    let tokens = lex_insecure(b"truncate model myspace.accounts").unwrap();
+    ModelData::transactional_exec_truncate(&global, parse_ast_node_full(&tokens[2..]).unwrap())
+        .unwrap();
+    assert_eq!(
+        next_event(&mut rx),
+        super::row_resp(vec![
+            Datacell::new_uint_default(1),
+            Datacell::new_str("truncate".into()),
+            Datacell::null(),
+            Datacell::null(),
+            Datacell::null(),
+        ])
+    );
+}
+
+#[test]
+fn subscribe_start() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_subscribe_start");
+    super::_exec_only_create_space_model(&global, MODEL).unwrap();
+    super::exec_insert_only(&global, "insert into myspace.accounts('sayan', 100)").unwrap();
+    let entity = EntityIDRef::new("myspace", "accounts");
+    // the next change gets version 1
+    assert_eq!(
+        ModelData::exec_subscribe(&global, SubscribeStatement::new(entity, None))
+            .unwrap()
+            .start(),
+        1
+    );
+    assert_eq!(
+        ModelData::exec_subscribe(&global, SubscribeStatement::new(entity, Some(2))).unwrap_err(),
+        QueryError::QExecSubscriptionVersionUnavailable
+    );
+}
+
+#[tokio::test]
+async fn subscribe_replay() {
+    // the journal is read back while it's still open, so this needs the local filesystem
+    FileSystem::set_context(FSContext::Local);
+    let mut fs = FileSystem::instance();
+    fs.mark_file_for_removal("dml_subscribe_replay");
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_subscribe_replay");
+    super::_exec_only_create_space_model(&global, MODEL).unwrap();
+    for query in [
+        "insert into myspace.accounts('sayan', 100)",
+        "insert into myspace.accounts('ferris', 200)",
+        "update myspace.accounts set balance += 10 where username = 'sayan'",
+        "delete from myspace.accounts where username = 'ferris'",
+    ] {
+        super::_exec_only_dml_resp(&global, query).unwrap();
+    }
+    let mut sub = ModelData::exec_subscribe(
+        &global,
+        SubscribeStatement::new(EntityIDRef::new("myspace", "accounts"), Some(2)),
+    )
+    .unwrap();
+    assert_eq!(sub.start(), 2);
+    // made after subscribing, so this is published to the feed
+    super::exec_insert_only(&global, "insert into myspace.accounts('ferris', 1)").unwrap();
+    // replayed changes have no pre-image
+    assert_eq!(
+        sub.next().await.unwrap().response(),
+        event(2, "update", "sayan", None, image("sayan", 110))
+    );
+    assert_eq!(
+        sub.next().await.unwrap().response(),
+        event(3, "delete", "ferris", None, None)
+    );
+    assert_eq!(
+        sub.next().await.unwrap().response(),
+        event(4, "insert", "ferris", None, image("ferris", 1))
+    );
+}
@@ -158,3 +158,444 @@ fn fail_typedef_violation() {
         intovec!["sayan", "pass123", 1u64]
     );
 }
+
+#[test]
+fn update_filtered() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_update_update_filtered");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            "create model myspace.mymodel(username: string, followers: uint64, verified: bool)",
+            &[
+                "insert into myspace.mymodel('douglas', 42, false)",
+                "insert into myspace.mymodel('orwell', 1984, false)",
+                "insert into myspace.mymodel('sayan', 100, false)",
+            ],
+            Some("update myspace.mymodel set verified = true where followers >= 100"),
+            "select * from myspace.mymodel where followers > 0",
+        )
+        .unwrap(),
+        vec![
+            intovec!["douglas", 42u64, false],
+            intovec!["orwell", 1984u64, true],
+            intovec!["sayan", 100u64, true],
+        ]
+    );
+}
+
+#[test]
+fn update_ordered_range() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_update_update_ordered_range");
+    assert_eq!(
+        super::exec_scan_ordered(
+            &global,
+            "create model myspace.readings(ts: uint64, sensor: string) with { index: \"ordered\" }",
+            &[
+                "insert into myspace.readings(30, 'c')",
+                "insert into myspace.readings(10, 'a')",
+                "insert into myspace.readings(20, 'b')",
+            ],
+            Some("update myspace.readings set sensor += '!' where ts >= 20 and ts < 100"),
+            "select * from myspace.readings where ts >= 0",
+        )
+        .unwrap(),
+        vec![
+            intovec![10u64, "a"],
+            intovec![20u64, "b!"],
+            intovec![30u64, "c!"],
+        ]
+    );
+}
+
+#[test]
+fn update_filtered_no_match() {
+    let global =
+        TestGlobal::new_with_driver_id_instant_update("dml_update_update_filtered_no_match");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            "create model myspace.mymodel(username: string, followers: uint64, verified: bool)",
+            &["insert into myspace.mymodel('sayan', 100, false)"],
+            Some("update myspace.mymodel set verified = true where followers > 100"),
+            "select * from myspace.mymodel where followers > 0",
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlRowNotFound
+    );
+}
+
+#[test]
+fn update_filtered_rollback() {
+    let global =
+        TestGlobal::new_with_driver_id_instant_update("dml_update_update_filtered_rollback");
+    assert_eq!(
+        super::exec_scan(
+            &global,
+            "create model myspace.mymodel(username: string, followers: uint64, null email: string)",
+            &[
+                "insert into myspace.mymodel('orwell', 1984, 'orwell@example.com')",
+                "insert into myspace.mymodel('sayan', 100, null)",
+            ],
+            Some("update myspace.mymodel set email += '.in' where followers >= 100"),
+            "select * from myspace.mymodel where followers > 0",
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlValidationError
+    );
+    // verify integrity: none of the rows should have been updated
+    assert_eq!(
+        super::_exec_only_select_scan(&global, "select * from myspace.mymodel where followers > 0")
+            .unwrap(),
+        vec![
+            intovec!["orwell", 1984u64, "orwell@example.com"],
+            intovec!["sayan", 100u64, Datacell::null()],
+        ]
+    );
+}
+
+#[test]
+fn update_layer_props_violation() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_update_layer_props_violation");
+    assert_eq!(
+        super::exec_update(
+            &global,
+            "create model myspace.mymodel(username: string, level: uint8 { max: 10 }, tags: list { type: string, maxlen: 1 })",
+            "insert into myspace.mymodel('sayan', 9, ['a'])",
+            "update myspace.mymodel set level += 2 where username = 'sayan'",
+            "select * from myspace.mymodel where username = 'sayan'"
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlValidationError
+    );
+    assert_eq!(
+        dml::update_flow_trace(),
+        ["sametag;nonnull", "badvalue", "rollback"]
+    );
+    assert_eq!(
+        super::_exec_only_update(
+            &global,
+            "update myspace.mymodel set tags += 'b' where username = 'sayan'"
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlValidationError
+    );
+    assert_eq!(dml::update_flow_trace(), ["list;badvalue", "rollback"]);
+    // verify integrity
+    assert_eq!(
+        super::exec_select_only(
+            &global,
+            "select * from myspace.mymodel where username = 'sayan'"
+        )
+        .unwrap(),
+        intovec!["sayan", 9u64, Datacell::new_list(intovec!["a"])]
+    );
+}
+
+#[test]
+fn update_timestamp_uuid() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_update_timestamp_uuid");
+    assert_eq!(
+        super::exec_update(
+            &global,
+            "create model myspace.events(name: string, at: timestamp, null trace: uuid)",
+            "insert into myspace.events('signup', timestamp'2023-01-08T14:23:59Z', null)",
+            "update myspace.events set at = timestamp'2024-01-01T00:00:00Z', trace = uuid'4593264b-0231-43e9-b0aa-50784f14e204' where name = 'signup'",
+            "select * from myspace.events where name = 'signup'"
+        )
+        .unwrap(),
+        intovec![
+            "signup",
+            Datacell::new_timestamp(1704067200_000_000_000),
+            Datacell::new_uuid(0x4593264b_0231_43e9_b0aa_50784f14e204)
+        ]
+    );
+    assert_eq!(
+        dml::update_flow_trace(),
+        ["sametag;nonnull", "sametag;orignull"]
+    );
+    // timestamps can only be assigned
+    assert_eq!(
+        super::_exec_only_update(
+            &global,
+            "update myspace.events set at += timestamp'2024-01-01T00:00:00Z' where name = 'signup'"
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlValidationError
+    );
+    assert_eq!(
+        dml::update_flow_trace(),
+        ["sametag;nonnull", "badvalue", "rollback"]
+    );
+}
+
+#[test]
+fn update_dict_path() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_update_dict_path");
+    assert_eq!(
+        super::exec_update(
+            &global,
+            "create model myspace.docs(name: string, meta: dict)",
+            "insert into myspace.docs('doc', { views: 1, author: { name: 'sayan' } })",
+            "update myspace.docs set meta.views += 1, meta.author.city = 'Kolkata', meta.tags.first = 'a' where name = 'doc'",
+            "select * from myspace.docs where name = 'doc'"
+        )
+        .unwrap(),
+        intovec![
+            "doc",
+            Datacell::new_dict(into_dict! {
+                "views" => Datacell::new_uint_default(2),
+                "author" => Datacell::new_dict(into_dict! {
+                    "name" => Datacell::from("sayan"),
+                    "city" => Datacell::from("Kolkata"),
+                }),
+                "tags" => Datacell::new_dict(into_dict!("first" => Datacell::from("a"))),
+            })
+        ]
+    );
+    assert_eq!(
+        dml::update_flow_trace(),
+        ["dict;path", "dict;path", "dict;path"]
+    );
+    // can't walk through a value that isn't a dict; the first assignment is rolled back
+    assert_eq!(
+        super::_exec_only_update(
+            &global,
+            "update myspace.docs set meta.author.name = 'nandan', meta.views.total = 1 where name = 'doc'"
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlValidationError
+    );
+    assert_eq!(
+        dml::update_flow_trace(),
+        ["dict;path", "dict;badvalue", "rollback"]
+    );
+    assert_eq!(
+        super::exec_select_only(&global, "select meta from myspace.docs where name = 'doc'")
+            .unwrap(),
+        intovec![Datacell::new_dict(into_dict! {
+            "views" => Datacell::new_uint_default(2),
+            "author" => Datacell::new_dict(into_dict! {
+                "name" => Datacell::from("sayan"),
+                "city" => Datacell::from("Kolkata"),
+            }),
+            "tags" => Datacell::new_dict(into_dict!("first" => Datacell::from("a"))),
+        })]
+    );
+}
+
+#[test]
+fn update_typed_dict_path() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_update_typed_dict_path");
+    assert_eq!(
+        super::exec_update(
+            &global,
+            "create model myspace.docs(name: string, null scores: dict { type: uint8, maxlen: 2 })",
+            "insert into myspace.docs('doc', null)",
+            "update myspace.docs set scores.math = 90 where name = 'doc'",
+            "select * from myspace.docs where name = 'doc'"
+        )
+        .unwrap(),
+        intovec![
+            "doc",
+            Datacell::new_dict(into_dict!("math" => Datacell::new_uint_default(90)))
+        ]
+    );
+    assert_eq!(dml::update_flow_trace(), ["dict;path"]);
+    for update in [
+        // bad value type
+        "update myspace.docs set scores.physics = 'A' where name = 'doc'",
+        // out of range for the value type
+        "update myspace.docs set scores.math += 200 where name = 'doc'",
+    ] {
+        assert_eq!(
+            super::_exec_only_update(&global, update).unwrap_err(),
+            QueryError::QExecDmlValidationError
+        );
+        assert_eq!(dml::update_flow_trace(), ["dict;badvalue", "rollback"]);
+    }
+}
+
+#[test]
+fn update_list_elements() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_update_list_elements");
+    assert_eq!(
+        super::exec_update(
+            &global,
+            "create model myspace.posts(id: string, tags: list { type: string }, scores: list { type: uint8 })",
+            "insert into myspace.posts('post', ['a', 'b', 'c', 'b'], [1, 2, 3])",
+            "update myspace.posts set tags[0] = 'z', scores[-1] += 10, tags -= 'b' where id = 'post'",
+            "select * from myspace.posts where id = 'post'"
+        )
+        .unwrap(),
+        intovec![
+            "post",
+            Datacell::new_list(intovec!["z", "c"]),
+            Datacell::new_list(intovec![1u64, 2u64, 13u64])
+        ]
+    );
+    assert_eq!(
+        dml::update_flow_trace(),
+        ["list;index", "list;index", "list;remove"]
+    );
+    super::_exec_only_update(
+        &global,
+        "update myspace.posts set remove tags[-1], truncate scores to 1 where id = 'post'",
+    )
+    .unwrap();
+    assert_eq!(dml::update_flow_trace(), ["list;remove", "list;truncate"]);
+    assert_eq!(
+        super::exec_select_only(&global, "select * from myspace.posts where id = 'post'").unwrap(),
+        intovec![
+            "post",
+            Datacell::new_list(intovec!["z"]),
+            Datacell::new_list(intovec![1u64])
+        ]
+    );
+}
+
+#[test]
+fn update_list_elements_rollback() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_update_list_elements_rollback");
+    assert_eq!(
+        super::exec_update(
+            &global,
+            "create model myspace.posts(id: string, tags: list { type: string, minlen: 1 }, scores: list { type: uint8 })",
+            "insert into myspace.posts('post', ['a', 'b'], [250])",
+            "update myspace.posts set tags[1] = 'c' where id = 'post'",
+            "select * from myspace.posts where id = 'post'"
+        )
+        .unwrap(),
+        intovec![
+            "post",
+            Datacell::new_list(intovec!["a", "c"]),
+            Datacell::new_list(intovec![250u64])
+        ]
+    );
+    assert_eq!(dml::update_flow_trace(), ["list;index"]);
+    for update in [
+        // out of bounds
+        "update myspace.posts set tags[0] = 'x', tags[2] = 'y' where id = 'post'",
+        "update myspace.posts set tags[0] = 'x', remove tags[-3] where id = 'post'",
+        // bad element type
+        "update myspace.posts set tags[0] = 'x', tags[1] = 1 where id = 'post'",
+        // out of range for the element type
+        "update myspace.posts set tags[0] = 'x', scores[0] += 10 where id = 'post'",
+        // violates the length constraint
+        "update myspace.posts set tags[0] = 'x', truncate tags to 0 where id = 'post'",
+    ] {
+        assert_eq!(
+            super::_exec_only_update(&global, update).unwrap_err(),
+            QueryError::QExecDmlValidationError
+        );
+        assert_eq!(
+            dml::update_flow_trace(),
+            ["list;index", "list;badvalue", "rollback"]
+        );
+    }
+    assert_eq!(
+        super::exec_select_only(&global, "select * from myspace.posts where id = 'post'").unwrap(),
+        intovec![
+            "post",
+            Datacell::new_list(intovec!["a", "c"]),
+            Datacell::new_list(intovec![250u64])
+        ]
+    );
+}
+
+#[test]
+fn update_conditional() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_update_conditional");
+    assert_eq!(
+        super::exec_update(
+            &global,
+            "create model myspace.accounts(username: string, balance: uint64, version: uint64)",
+            "insert into myspace.accounts('sayan', 100, 1)",
+            "update myspace.accounts set balance -= 10, version += 1 where username = 'sayan' and version = 1",
+            "select * from myspace.accounts where username = 'sayan'"
+        )
+        .unwrap(),
+        intovec!["sayan", 90u64, 2u64]
+    );
+    // a stale version
+    for update in [
+        "update myspace.accounts set balance -= 10, version += 1 where username = 'sayan' and version = 1",
+        "update myspace.accounts set balance -= 10, version += 1 where username = 'sayan' if version = 1",
+    ] {
+        assert_eq!(
+            super::_exec_only_update(&global, update).unwrap_err(),
+            QueryError::QExecDmlPreconditionFailed
+        );
+        assert_eq!(dml::update_flow_trace(), ["precondition"]);
+    }
+    assert_eq!(
+        super::_exec_only_update(
+            &global,
+            "update myspace.accounts set balance -= 10 where username = 'nobody' if version = 2"
+        )
+        .unwrap_err(),
+        QueryError::QExecDmlRowNotFound
+    );
+    super::_exec_only_update(
+        &global,
+        "update myspace.accounts set balance -= 10, version += 1 where username = 'sayan' if version = 2",
+    )
+    .unwrap();
+    assert_eq!(
+        super::exec_select_only(
+            &global,
+            "select * from myspace.accounts where username = 'sayan'"
+        )
+        .unwrap(),
+        intovec!["sayan", 80u64, 3u64]
+    );
+}
+
+#[test]
+fn update_returning() {
+    let global = TestGlobal::new_with_driver_id_instant_update("dml_update_returning");
+    super::_exec_only_create_space_model(
+        &global,
+        "create model myspace.counters(id: uint64, name: string, hits: uint64) with { index: \"ordered\" }",
+    )
+    .unwrap();
+    for insert in [
+        "insert into myspace.counters(1, 'a', 0)",
+        "insert into myspace.counters(2, 'b', 5)",
+        "insert into myspace.counters(3, 'c', 10)",
+    ] {
+        super::_exec_only_insert(&global, insert, |_| {}).unwrap();
+    }
+    // point updates return the new version of the row
+    assert_eq!(
+        super::_exec_only_dml_resp(
+            &global,
+            "update myspace.counters set hits += 1 where id = 1 returning hits"
+        )
+        .unwrap(),
+        super::row_resp(intovec![1u64])
+    );
+    // scans return every updated row
+    assert_eq!(
+        super::_exec_only_dml_resp(
+            &global,
+            "update myspace.counters set hits += 1 where hits >= 5 returning *"
+        )
+        .unwrap(),
+        super::multirow_resp(
+            3,
+            vec![intovec![2u64, "b", 6u64], intovec![3u64, "c", 11u64]]
+        )
+    );
+    assert_eq!(
+        super::_exec_only_dml_resp(
+            &global,
+            "update myspace.counters set hits += 1 where id = 1 returning visits"
+        )
+        .unwrap_err(),
+        QueryError::QExecUnknownField
+    );
+    assert_eq!(
+        super::exec_select_only(&global, "select hits from myspace.counters where id = 1").unwrap(),
+        intovec![1u64]
+    );
+}
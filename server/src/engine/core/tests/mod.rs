@@ -24,6 +24,9 @@
  *
 */
 
+mod dcl;
+mod ddl_index;
+mod ddl_misc;
 mod ddl_model;
 mod ddl_space;
 mod dml;
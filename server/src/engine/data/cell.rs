@@ -26,12 +26,14 @@
 
 use crate::engine::core::index::PrimaryIndexKey;
 
+#[cfg(test)]
 use super::tag::TagUnique;
 
 use {
     crate::engine::{
         self,
         data::{
+            dict::DataDict,
             lit::Lit,
             tag::{DataTag, FloatSpec, FullTag, SIntSpec, TagClass, UIntSpec},
         },
@@ -239,6 +241,48 @@ impl Datacell {
             ))
         }
     }
+    // timestamp
+    /// Create a new timestamp from the number of nanoseconds elapsed since the Unix epoch (UTC)
+    pub fn new_timestamp(ns: u64) -> Self {
+        unsafe {
+            // UNSAFE(@ohsayan): Correct because we are initializing Self with the correct tag
+            Self::new(
+                FullTag::TIMESTAMP,
+                DataRaw::word(SpecialPaddedWord::store(ns).dwordqn_promote()),
+            )
+        }
+    }
+    pub unsafe fn read_timestamp(&self) -> u64 {
+        self.load_word()
+    }
+    pub fn try_timestamp(&self) -> Option<u64> {
+        self.checked_tag(TagClass::Timestamp, || unsafe {
+            // UNSAFE(@ohsayan): Correct because we just verified the tag
+            self.read_timestamp()
+        })
+    }
+    pub fn timestamp(&self) -> u64 {
+        self.try_timestamp().unwrap()
+    }
+    // uuid
+    pub fn new_uuid(u: u128) -> Self {
+        unsafe {
+            // UNSAFE(@ohsayan): Correct because we are initializing Self with the correct tag
+            Self::new(FullTag::UUID, DataRaw::word(WordIO::store(u)))
+        }
+    }
+    pub unsafe fn read_uuid(&self) -> u128 {
+        self.load_word()
+    }
+    pub fn try_uuid(&self) -> Option<u128> {
+        self.checked_tag(TagClass::Uuid, || unsafe {
+            // UNSAFE(@ohsayan): Correct because we just verified the tag
+            self.read_uuid()
+        })
+    }
+    pub fn uuid(&self) -> u128 {
+        self.try_uuid().unwrap()
+    }
     // list
     pub fn new_list(l: Vec<Self>) -> Self {
         unsafe {
@@ -269,6 +313,28 @@ impl Datacell {
             Some(ManuallyDrop::into_inner(rwl).into_inner())
         }
     }
+    // dict
+    pub fn new_dict(d: DataDict) -> Self {
+        unsafe {
+            // UNSAFE(@ohsayan): Correct because we are initializing Self with the correct tag
+            Self::new(FullTag::DICT, DataRaw::dict(Box::new(RwLock::new(d))))
+        }
+    }
+    pub unsafe fn read_dict(&self) -> &RwLock<DataDict> {
+        &self.data.dict
+    }
+    pub unsafe fn read_dict_mut(&mut self) -> &mut DataDict {
+        (*self.data.dict).get_mut()
+    }
+    pub fn try_dict(&self) -> Option<&RwLock<DataDict>> {
+        self.checked_tag(TagClass::Dict, || unsafe {
+            // UNSAFE(@ohsayan): Correct because we just verified the tag
+            self.read_dict()
+        })
+    }
+    pub fn dict(&self) -> &RwLock<DataDict> {
+        self.try_dict().unwrap()
+    }
     pub unsafe fn new_qw(qw: u64, tag: FullTag) -> Datacell {
         Self::new(
             tag,
@@ -294,6 +360,7 @@ direct_from! {
         &'static str as new_str,
         Vec<Self> as new_list,
         Box<[Self]> as new_list,
+        DataDict as new_dict,
     }
 }
 
@@ -319,8 +386,16 @@ impl<'a> From<Lit<'a>> for Datacell {
                     )),
                 )
             },
+            TagClass::Timestamp => unsafe {
+                // UNSAFE(@ohsayan): +tagck
+                Datacell::new_timestamp(l.timestamp())
+            },
+            TagClass::Uuid => unsafe {
+                // UNSAFE(@ohsayan): +tagck; the literal only holds a reference to the bytes so we need to load them
+                Datacell::new_uuid(l.uuid())
+            },
             _ => unsafe {
-                // UNSAFE(@ohsayan): a Lit will never be higher than a string
+                // UNSAFE(@ohsayan): a Lit will never be a list or a dict
                 impossible!()
             },
         }
@@ -407,7 +482,10 @@ impl fmt::Debug for Datacell {
             Float => self.float(),
             Bin => self.bin(),
             Str => self.str(),
+            Timestamp => self.timestamp(),
+            Uuid => self.uuid(),
             List => self.list(),
+            Dict => self.dict(),
         );
         f.finish()
     }
@@ -425,6 +503,8 @@ impl PartialEq for Datacell {
             (TagClass::Float, TagClass::Float) => self.float() == other.float(),
             (TagClass::Bin, TagClass::Bin) => self.bin() == other.bin(),
             (TagClass::Str, TagClass::Str) => self.str() == other.str(),
+            (TagClass::Timestamp, TagClass::Timestamp) => self.timestamp() == other.timestamp(),
+            (TagClass::Uuid, TagClass::Uuid) => self.uuid() == other.uuid(),
             (TagClass::List, TagClass::List) => {
                 let l1_l = self.list().read();
                 let l2_l = other.list().read();
@@ -432,6 +512,11 @@ impl PartialEq for Datacell {
                 let l2: &[Self] = l2_l.as_ref();
                 l1 == l2
             }
+            (TagClass::Dict, TagClass::Dict) => {
+                let d1 = self.dict().read();
+                let d2 = other.dict().read();
+                *d1 == *d2
+            }
             _ => false,
         }
     }
@@ -443,6 +528,7 @@ union! {
     union DataRaw {
         !word: NativeQword,
         !rwl: RwLock<Vec<Datacell>>,
+        !dict: Box<RwLock<DataDict>>,
     }
 }
 
@@ -457,6 +543,11 @@ impl DataRaw {
             rwl: ManuallyDrop::new(rwl),
         }
     }
+    fn dict(dict: Box<RwLock<DataDict>>) -> Self {
+        Self {
+            dict: ManuallyDrop::new(dict),
+        }
+    }
 }
 
 impl Drop for Datacell {
@@ -471,6 +562,10 @@ impl Drop for Datacell {
                 // UNSAFE(@ohsayan): we have checked that the cell is initialized (uninit will not satisfy this class), and we have checked its class
                 ManuallyDrop::drop(&mut self.data.rwl)
             },
+            TagClass::Dict => unsafe {
+                // UNSAFE(@ohsayan): we have checked that the cell is initialized (uninit will not satisfy this class), and we have checked its class
+                ManuallyDrop::drop(&mut self.data.dict)
+            },
             _ => {}
         }
     }
@@ -493,6 +588,11 @@ impl Clone for Datacell {
                 let data = self.read_list().read().iter().cloned().collect();
                 DataRaw::rwl(RwLock::new(data))
             },
+            TagClass::Dict => unsafe {
+                // UNSAFE(@ohsayan): we have checked that the cell is initialized (uninit will not satisfy this class), and we have checked its class
+                let data = self.read_dict().read().clone();
+                DataRaw::dict(Box::new(RwLock::new(data)))
+            },
             _ => unsafe {
                 // UNSAFE(@ohsayan): we have checked that the cell is a stack class
                 DataRaw::word(mem::transmute_copy(&self.data.word))
@@ -512,6 +612,7 @@ pub struct VirtualDatacell<'a> {
 }
 
 impl<'a> VirtualDatacell<'a> {
+    #[cfg(test)]
     pub fn new(lit: Lit<'a>, tag: TagUnique) -> Self {
         debug_assert_eq!(lit.kind().tag_unique(), tag);
         Self {
@@ -34,15 +34,20 @@ use {
 
 /// A generic dictionary built from scratch from syntactical elements
 pub type DictGeneric = HashMap<Box<str>, DictEntryGeneric>;
+/// The dictionary held by a `dict` field in a model. Nested dictionaries are cells themselves
+pub type DataDict = HashMap<Box<str>, Datacell>;
 
 #[derive(Debug, PartialEq)]
 #[cfg_attr(test, derive(Clone))]
-/// A generic dict entry: either a literal or a recursive dictionary
+/// A generic dict entry: either a literal, a recursive dictionary or a producer function
 pub enum DictEntryGeneric {
     /// A literal
     Data(Datacell),
     /// A map
     Map(DictGeneric),
+    /// A producer function (`@name()`), which isn't called since its value is only needed later on. Only a field
+    /// default can be a producer
+    Producer(Box<str>),
 }
 
 impl DictEntryGeneric {
@@ -77,9 +82,19 @@ fn _rflatten_metadata(new: &mut DictGeneric) {
             _rflatten_metadata(m);
             true
         }
+        DictEntryGeneric::Producer(_) => true,
     });
 }
 
+/// Returns true if the dict or any of its nested dicts has a producer
+pub fn rhas_producer(dict: &DictGeneric) -> bool {
+    dict.values().any(|v| match v {
+        DictEntryGeneric::Data(_) => false,
+        DictEntryGeneric::Map(m) => rhas_producer(m),
+        DictEntryGeneric::Producer(_) => true,
+    })
+}
+
 /// Recursively merge a [`DictGeneric`] into a [`DictGeneric`] with the use of an intermediary
 /// patchset to avoid inconsistent states
 pub fn rmerge_metadata(current: &mut DictGeneric, new: DictGeneric) -> bool {
@@ -111,6 +126,9 @@ pub fn rmerge_data_with_patch(current: &mut DictGeneric, patch: DictGeneric) {
                 // null
                 let _ = current.remove(&key);
             }
+            DictEntryGeneric::Producer(p) => {
+                current.st_upsert(key, DictEntryGeneric::Producer(p));
+            }
             DictEntryGeneric::Map(m) => match current.get_mut(&key) {
                 Some(current_recursive) => match current_recursive {
                     DictEntryGeneric::Map(current_m) => {
@@ -140,6 +158,10 @@ fn rmerge_metadata_prepare_patch(
     while new.len() != 0 && okay {
         let (key, new_entry) = new.next().unwrap();
         match (current.get(&key), new_entry) {
+            // producers are only allowed as field defaults
+            (_, DictEntryGeneric::Producer(_)) | (Some(DictEntryGeneric::Producer(_)), _) => {
+                okay = false;
+            }
             // non-null -> non-null: merge flatten update
             (Some(DictEntryGeneric::Data(this_data)), DictEntryGeneric::Data(new_data))
                 if new_data.is_init() =>
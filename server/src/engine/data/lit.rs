@@ -67,6 +67,10 @@ impl<'a> Lit<'a> {
     pub fn new_float(f: f64) -> Self {
         Self::_quad(f.to_bits(), FullTag::FLOAT)
     }
+    /// Create a new timestamp from the number of nanoseconds elapsed since the Unix epoch (UTC)
+    pub fn new_timestamp(ns: u64) -> Self {
+        Self::_quad(ns, FullTag::TIMESTAMP)
+    }
     /// Returns a "shallow clone"
     ///
     /// This function will fall apart if lifetimes aren't handled correctly (aka will segfault)
@@ -110,6 +114,13 @@ impl<'a> Lit<'a> {
             self.float()
         })
     }
+    /// Attempt to read a timestamp
+    pub fn try_timestamp(&self) -> Option<u64> {
+        (self.tag.tag_class() == TagClass::Timestamp).then_some(unsafe {
+            // UNSAFE(@ohsayan): +tagck
+            self.timestamp()
+        })
+    }
     /// Read a bool directly. This function isn't exactly unsafe, but we want to provide a type preserving API
     pub unsafe fn bool(&self) -> bool {
         self.uint() == 1
@@ -129,6 +140,10 @@ impl<'a> Lit<'a> {
     pub unsafe fn float(&self) -> f64 {
         f64::from_bits(self.uint())
     }
+    /// Read a timestamp directly. This function isn't exactly unsafe, but we want to provide a type preserving API
+    pub unsafe fn timestamp(&self) -> u64 {
+        self.uint()
+    }
 }
 
 impl<'a> Lit<'a> {
@@ -148,6 +163,23 @@ impl<'a> Lit<'a> {
             self.str()
         })
     }
+    #[allow(unused)]
+    /// Attempt to read a UUID
+    pub fn try_uuid(&self) -> Option<u128> {
+        (self.tag.tag_class() == TagClass::Uuid).then(|| unsafe {
+            // UNSAFE(@ohsayan): +tagck
+            self.uuid()
+        })
+    }
+    /// Read a UUID directly
+    ///
+    /// ## Safety
+    /// The underlying repr MUST be a UUID. Otherwise you'll segfault
+    pub unsafe fn uuid(&self) -> u128 {
+        let mut uuid = [0; 16];
+        uuid.copy_from_slice(self.bin());
+        u128::from_be_bytes(uuid)
+    }
     /// Read a string value directly
     ///
     /// ## Safety
@@ -195,6 +227,21 @@ impl<'a> Lit<'a> {
             Self::_wide_word(b.as_ptr() as *mut _, b.len(), Self::DTC_NONE, FullTag::BIN)
         }
     }
+    /// Create a new UUID (referenced) from its bytes (in RFC 4122 order)
+    pub fn new_uuid(b: &'a [u8; 16]) -> Self {
+        unsafe {
+            // UNSAFE(@ohsayan): mut cast is once again just a typesake change
+            Self::_wide_word(b.as_ptr() as *mut _, b.len(), Self::DTC_NONE, FullTag::UUID)
+        }
+    }
+    /// Create a new boxed UUID
+    pub fn new_boxed_uuid(u: u128) -> Self {
+        let mut md = ManuallyDrop::new(Box::<[u8]>::from(u.to_be_bytes()));
+        unsafe {
+            // UNSAFE(@ohsayan): correct aliasing, and DTC to destroy heap
+            Self::_wide_word(md.as_mut_ptr(), md.len(), Self::DTC_HSTR, FullTag::UUID)
+        }
+    }
 }
 
 impl<'a> Lit<'a> {
@@ -299,7 +346,9 @@ impl<'a> fmt::Debug for Lit<'a> {
                 TagClass::Float => d!(self.float()),
                 TagClass::Bin => d!(self.bin()),
                 TagClass::Str => d!(self.str()),
-                TagClass::List => panic!("found 2D in 1D"),
+                TagClass::Timestamp => d!(self.timestamp()),
+                TagClass::Uuid => d!(self.uuid()),
+                TagClass::List | TagClass::Dict => panic!("found 2D in 1D"),
             }
         }
         field.finish()
@@ -324,6 +373,8 @@ impl<'a> PartialEq for Lit<'a> {
                 (TagClass::Float, TagClass::Float) => self.float() == other.float(),
                 (TagClass::Bin, TagClass::Bin) => self.bin() == other.bin(),
                 (TagClass::Str, TagClass::Str) => self.str() == other.str(),
+                (TagClass::Timestamp, TagClass::Timestamp) => self.timestamp() == other.timestamp(),
+                (TagClass::Uuid, TagClass::Uuid) => self.uuid() == other.uuid(),
                 _ => false,
             }
         }
@@ -353,7 +404,9 @@ impl<'a> ToString for Lit<'a> {
                 TagClass::Float => self.float().to_string(),
                 TagClass::Bin => format!("{:?}", self.bin()),
                 TagClass::Str => format!("{:?}", self.str()),
-                TagClass::List => panic!("found 2D in 1D"),
+                TagClass::Timestamp => self.timestamp().to_string(),
+                TagClass::Uuid => uuid::Uuid::from_u128(self.uuid()).to_string(),
+                TagClass::List | TagClass::Dict => panic!("found 2D in 1D"),
             }
         }
     }
@@ -368,6 +421,8 @@ fn stk_variants() {
         Lit::new_float(f64::MIN),
         Lit::new_str("hello"),
         Lit::new_bin(b"world"),
+        Lit::new_timestamp(u64::MAX),
+        Lit::new_uuid(&[0xA5; 16]),
     ];
     let stk2 = stk1.clone();
     assert_eq!(stk1, stk2);
@@ -378,6 +433,7 @@ fn hp_variants() {
     let hp1 = [
         Lit::new_string("hello".into()),
         Lit::new_string("world".into()),
+        Lit::new_boxed_uuid(u128::MAX),
     ];
     let hp2 = hp1.clone();
     assert_eq!(hp1, hp2);
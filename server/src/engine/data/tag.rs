@@ -53,7 +53,10 @@ pub enum TagClass {
     Float = 3,
     Bin = 4,
     Str = 5,
-    List = 6,
+    Timestamp = 6,
+    Uuid = 7,
+    List = 8,
+    Dict = 9,
 }
 
 strid! {
@@ -85,6 +88,9 @@ strid! {
         Binary = 11,
         String = 12,
         List = 13,
+        Timestamp = 14,
+        Uuid = 15,
+        Dict = 16,
     }
 }
 
@@ -108,6 +114,9 @@ impl TagSelector {
             TagUnique::Bin,         // bin
             TagUnique::Str,         // str
             TagUnique::Illegal,     // list
+            TagUnique::Illegal,     // timestamp
+            TagUnique::Illegal,     // uuid
+            TagUnique::Illegal,     // dict
         ][self.value_word()]
     }
     pub const fn tag_class(&self) -> TagClass {
@@ -126,6 +135,9 @@ impl TagSelector {
             TagClass::Bin,         // bin
             TagClass::Str,         // str
             TagClass::List,        // recursive list
+            TagClass::Timestamp,   // timestamp
+            TagClass::Uuid,        // uuid
+            TagClass::Dict,        // recursive dict
         ][self.value_word()]
     }
 }
@@ -165,6 +177,9 @@ pub trait DataTag {
     const BIN: Self;
     const STR: Self;
     const LIST: Self;
+    const TIMESTAMP: Self;
+    const UUID: Self;
+    const DICT: Self;
     fn tag_class(&self) -> TagClass;
     fn tag_selector(&self) -> TagSelector;
     fn tag_unique(&self) -> TagUnique;
@@ -213,6 +228,9 @@ impl DataTag for FullTag {
     const BIN: Self = fulltag!(Bin, Binary, Bin);
     const STR: Self = fulltag!(Str, String, Str);
     const LIST: Self = fulltag!(List, List);
+    const TIMESTAMP: Self = fulltag!(Timestamp, Timestamp);
+    const UUID: Self = fulltag!(Uuid, Uuid);
+    const DICT: Self = fulltag!(Dict, Dict);
     fn tag_class(&self) -> TagClass {
         self.class
     }
@@ -95,6 +95,18 @@ pub enum QueryError {
     QExecDmlRowNotFound = 111,
     /// this query needs a lock for execution, but that wasn't explicitly allowed anywhere
     QExecNeedLock = 112,
+    /// a batch control statement was used when it wasn't expected (for example, a `commit` without a `begin`)
+    QExecBatchIllegalState = 113,
+    /// the statement can't be run inside a batch
+    QExecBatchIllegalStatement = 114,
+    /// the row was found, but it didn't satisfy the conditions of a conditional `update` or `delete`
+    QExecDmlPreconditionFailed = 115,
+    /// a subscription was asked to start from a version that the model hasn't reached yet
+    QExecSubscriptionVersionUnavailable = 116,
+    /// the subscriber fell too far behind the model's change feed and missed changes
+    QExecSubscriptionLagged = 117,
+    /// the statement targets a model other than the one that the batch is for (a batch can only change one model)
+    QExecBatchMultipleModels = 118,
 }
 
 direct_from! {
@@ -253,6 +265,7 @@ enumerate_err! {
         */
         RawJournalRuntimeDirty = "journal-in-dirty-state",
         RuntimeRestoreValidationFailure = "restore-data-validation-failed",
+        RuntimeBackupValidationFailure = "backup-data-validation-failed",
         RuntimeEngineLoadError = "runtime-storage-engine-load-failure",
     }
 }
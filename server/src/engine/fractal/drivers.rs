@@ -28,13 +28,27 @@ use {
     super::{util, GlobalInstanceLike},
     crate::{
         engine::{
+            core::model::{delta::DataDelta, ModelData},
             error::{QueryError, QueryResult, RuntimeResult},
-            fractal::{CriticalTask, Task},
-            storage::{GNSDriver, ModelDriver},
+            fractal::{CriticalTask, FractalToken, ModelUniqueID, ModelUniqueIDRef, Task},
+            storage::{
+                safe_interfaces::{ModelChangeLog, StdModelBatch, TxnModelBatch},
+                BatchStats, GNSDriver, ModelDriver,
+            },
+            sync::atm::cpin,
         },
         util::compiler,
     },
     parking_lot::Mutex,
+    std::{
+        mem,
+        sync::{
+            atomic::{AtomicU8, Ordering},
+            Arc,
+        },
+        thread,
+        time::{Duration, Instant},
+    },
 };
 
 /// GNS driver
@@ -76,6 +90,17 @@ impl FractalGNSDriver {
             }),
         }
     }
+    /// Run `f` while holding the driver, so that no DDL (or DCL) transaction can be committed in the meantime. A
+    /// failure in `f` doesn't affect the driver
+    pub fn frozen_context<T>(
+        &self,
+        f: impl FnOnce(&mut GNSDriver) -> QueryResult<T>,
+    ) -> QueryResult<T> {
+        if self.status.is_iffy() {
+            return Err(QueryError::SysServerError);
+        }
+        f(&mut self.txn_driver.lock())
+    }
 }
 
 /// Model driver
@@ -84,13 +109,38 @@ impl FractalGNSDriver {
 pub struct FractalModelDriver {
     status: util::Status,
     batch_driver: Mutex<Option<ModelDriver>>,
+    commit_group: Mutex<CommitGroup>,
+}
+
+/// The changes of `group-commit` writes that are waiting to be written to the journal (together)
+#[derive(Debug, Default)]
+struct CommitGroup {
+    deltas: Vec<DataDelta>,
+    /// pending until the group has either been written or has failed
+    status: Arc<AtomicU8>,
+    /// when the first write joined the group
+    opened: Option<Instant>,
 }
 
+impl CommitGroup {
+    const PENDING: u8 = 0;
+    const WRITTEN: u8 = 1;
+    const FAILED: u8 = 2;
+    /// the longest that a group is held open for other writes to join it, once its first write is waiting on it
+    const MAX_WAIT: Duration = Duration::from_millis(2);
+}
+
+/// A `group-commit` write's place in a commit group (see [`FractalModelDriver::join_group`])
+#[derive(Debug)]
+#[must_use]
+pub struct GroupTicket(Arc<AtomicU8>, Instant);
+
 impl FractalModelDriver {
-    pub const fn uninitialized() -> Self {
+    pub fn uninitialized() -> Self {
         Self {
             status: util::Status::new_okay(),
             batch_driver: Mutex::new(None),
+            commit_group: Mutex::new(CommitGroup::default()),
         }
     }
     pub fn initialize_model_driver(&self, driver: ModelDriver) {
@@ -105,6 +155,7 @@ impl FractalModelDriver {
         Self {
             status: util::Status::new_okay(),
             batch_driver: Mutex::new(Some(batch_driver)),
+            commit_group: Mutex::new(CommitGroup::default()),
         }
     }
     pub fn status(&self) -> &util::Status {
@@ -114,6 +165,154 @@ impl FractalModelDriver {
     pub fn batch_driver(&self) -> &Mutex<Option<ModelDriver>> {
         &self.batch_driver
     }
+    /// Run a write against the batch driver directly (instead of via the fractal manager). On failure, the driver
+    /// is marked as iffy and an autorecovery task is queued
+    pub fn driver_context<T>(
+        &self,
+        g: &impl GlobalInstanceLike,
+        mdl_id: ModelUniqueIDRef,
+        f: impl FnOnce(&mut ModelDriver) -> RuntimeResult<T>,
+    ) -> QueryResult<T> {
+        if self.status.is_iffy() {
+            return Err(QueryError::SysServerError);
+        }
+        let mut batch_driver = self.batch_driver.lock();
+        match f(batch_driver.as_mut().unwrap()) {
+            Ok(v) => Ok(v),
+            Err(e) => compiler::cold_call(|| self.on_failure(g, mdl_id, e)),
+        }
+    }
+    /// Add the changes of a `group-commit` write to the group that is waiting to be written. This must be done
+    /// while the write still holds its locks, so that the changes are never in neither the delta state nor a group
+    pub fn join_group(&self, deltas: Vec<DataDelta>) -> GroupTicket {
+        let mut group = self.commit_group.lock();
+        group.deltas.extend(deltas);
+        let opened = *group.opened.get_or_insert_with(Instant::now);
+        GroupTicket(group.status.clone(), opened)
+    }
+    /// Wait for the group that a `group-commit` write joined to be written to the journal (together with any other
+    /// writes that joined it). The group is held open for other writes to join it for at most
+    /// [`CommitGroup::MAX_WAIT`] after it was opened, and then whoever gets the batch driver first writes (and syncs)
+    /// the whole group while the others wait. A write hence waits for at most the max wait, the group ahead of it and
+    /// its own group. If the group can't be written, its changes (which have already been applied) are published to
+    /// the delta state so that they're written once the driver recovers, and every write in the group fails
+    pub fn group_commit(
+        &self,
+        g: &impl GlobalInstanceLike,
+        mdl_id: ModelUniqueIDRef,
+        mdl: &ModelData,
+        GroupTicket(status, opened): GroupTicket,
+    ) -> QueryResult<()> {
+        let waited = opened.elapsed();
+        if (waited < CommitGroup::MAX_WAIT)
+            & (status.load(Ordering::Acquire) == CommitGroup::PENDING)
+        {
+            thread::sleep(CommitGroup::MAX_WAIT - waited);
+        }
+        let mut batch_driver = self.batch_driver.lock();
+        match status.load(Ordering::Acquire) {
+            CommitGroup::WRITTEN => return Ok(()),
+            CommitGroup::FAILED => return Err(QueryError::SysServerError),
+            _ => {}
+        }
+        // our group hasn't been written yet, so we write it (along with anyone who has joined it since)
+        let group = mem::take(&mut *self.commit_group.lock());
+        let r = if self.status.is_iffy() {
+            Err(QueryError::SysServerError)
+        } else {
+            match batch_driver
+                .as_mut()
+                .unwrap()
+                .commit_with_ctx(TxnModelBatch::new(mdl, &group.deltas), BatchStats::new())
+            {
+                Ok(()) => Ok(()),
+                Err(e) => compiler::cold_call(|| self.on_failure(g, mdl_id, e)),
+            }
+        };
+        if r.is_ok() {
+            group.status.store(CommitGroup::WRITTEN, Ordering::Release);
+        } else {
+            mdl.delta_state()
+                .append_new_data_deltas(group.deltas, &cpin());
+            group.status.store(CommitGroup::FAILED, Ordering::Release);
+        }
+        r
+    }
+    /// Write all the changes that are pending for the model (the deltas waiting for the fractal manager and any
+    /// group that is waiting to be written) and then open its journal to read back all the changes in it. The caller
+    /// must keep writers out of the model's change feed so that no changes are made in the meantime, but the changes
+    /// can be read once it no longer does
+    pub fn open_changes(
+        &self,
+        g: &impl GlobalInstanceLike,
+        mdl_id: ModelUniqueIDRef,
+        mdl: &ModelData,
+        journal_path: &str,
+    ) -> QueryResult<ModelChangeLog> {
+        self.flushed_driver_context(g, mdl_id, mdl, |_| ModelDriver::open_changes(journal_path))
+    }
+    /// Write all the changes that are pending for the model (the deltas waiting for the fractal manager and any
+    /// group that is waiting to be written) and then run `f` while still holding the batch driver. A failure in `f`
+    /// doesn't affect the driver
+    pub fn flushed_driver_context<T>(
+        &self,
+        g: &impl GlobalInstanceLike,
+        mdl_id: ModelUniqueIDRef,
+        mdl: &ModelData,
+        f: impl FnOnce(&mut ModelDriver) -> RuntimeResult<T>,
+    ) -> QueryResult<T> {
+        if self.status.is_iffy() {
+            return Err(QueryError::SysServerError);
+        }
+        let mut batch_driver = self.batch_driver.lock();
+        let drv = batch_driver.as_mut().unwrap();
+        // anyone waiting on this group will find that we've written it
+        let group = mem::take(&mut *self.commit_group.lock());
+        if !group.deltas.is_empty() {
+            if let Err(e) =
+                drv.commit_with_ctx(TxnModelBatch::new(mdl, &group.deltas), BatchStats::new())
+            {
+                mdl.delta_state()
+                    .append_new_data_deltas(group.deltas, &cpin());
+                group.status.store(CommitGroup::FAILED, Ordering::Release);
+                return compiler::cold_call(|| self.on_failure(g, mdl_id, e));
+            }
+        }
+        group.status.store(CommitGroup::WRITTEN, Ordering::Release);
+        /*
+            NB: deltas are only dequeued when they're written, so if the fractal manager has already taken a count (but
+            doesn't have the driver yet), the deltas are still in the queue. we write every delta in the queue here and
+            it finds fewer deltas (if any) once it gets the driver
+        */
+        let observed_size = mdl
+            .delta_state()
+            .__fractal_take_full_from_data_delta(FractalToken::new());
+        if mdl.delta_state().__data_delta_pending(&cpin()) {
+            if let Err(e) = drv.commit_with_ctx(StdModelBatch::drain(mdl), BatchStats::new()) {
+                // hand the deltas back to the fractal manager, which retries once the driver has recovered
+                g.taskmgr_post_high_priority(Task::new(CriticalTask::WriteBatch(
+                    mdl_id.into(),
+                    observed_size,
+                )));
+                return compiler::cold_call(|| self.on_failure(g, mdl_id, e));
+            }
+        }
+        Ok(f(drv)?)
+    }
+    /// Mark the driver as iffy and queue an autorecovery task
+    fn on_failure<T>(
+        &self,
+        g: &impl GlobalInstanceLike,
+        mdl_id: ModelUniqueIDRef,
+        e: super::error::Error,
+    ) -> QueryResult<T> {
+        let mdl_id: ModelUniqueID = mdl_id.into();
+        self.status.set_iffy();
+        g.health().report_fault();
+        error!("driver for {mdl_id} failed with: {e}");
+        g.taskmgr_post_high_priority(Task::new(CriticalTask::TryModelAutorecover(mdl_id)));
+        Err(QueryError::SysServerError)
+    }
     pub fn close(self) -> RuntimeResult<()> {
         ModelDriver::close_driver(&mut self.batch_driver.into_inner().unwrap())
     }
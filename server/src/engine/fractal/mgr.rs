@@ -29,6 +29,7 @@ use {
     crate::{
         engine::{
             core::{
+                dml,
                 model::{delta::DataDelta, ModelData},
                 EntityIDRef,
             },
@@ -56,6 +57,8 @@ use {
 pub const GENERAL_EXECUTOR_WINDOW: u64 = 5 * 60;
 const TASK_THRESHOLD: usize = 10;
 const TASK_FAILURE_SLEEP_DURATION: u64 = 30;
+/// the number of rows that a single purge task visits
+const PURGE_BUDGET: usize = 16 * 1024;
 
 /// A task for the [`FractalMgr`] to perform
 #[derive(Debug)]
@@ -91,6 +94,8 @@ pub enum GenericTask {
     DeleteFile(PathBuf),
     /// Delete a directory (and all its children)
     DeleteDirAll(PathBuf),
+    /// Purge the expired rows in a model
+    PurgeExpiredRows(ModelUniqueID),
 }
 
 impl GenericTask {
@@ -459,19 +464,51 @@ impl FractalMgr {
                                 ).unwrap();
                             }
                         }
+                        GenericTask::PurgeExpiredRows(model_id) => {
+                            let global = global.clone();
+                            let id = model_id.clone();
+                            if let Ok(false) = tokio::task::spawn_blocking(move || Self::purge_expired_rows(global, id)).await {
+                                // purge the rest of the model after the other pending tasks
+                                self.general_dispatcher.send(Task::new(GenericTask::PurgeExpiredRows(model_id))).unwrap();
+                            }
+                        }
                     }
                 }
             }
         }
     }
     fn general_executor(&'static self, global: super::Global) {
-        for (model_id, model) in global.state().namespace().idx_models().read().iter() {
+        // the namespace is only locked while a single model is synced, so that DDL isn't held off by a whole cycle
+        let model_ids: Vec<_> = global
+            .state()
+            .namespace()
+            .idx_models()
+            .read()
+            .iter()
+            .map(|(model_id, model)| {
+                ModelUniqueID::new(model_id.space(), model_id.entity(), model.data().get_uuid())
+            })
+            .collect();
+        for model_id in model_ids {
+            let mdl_read = global.state().namespace().idx_models().read();
+            let model = match mdl_read.get(&EntityIDRef::new(model_id.space(), model_id.model())) {
+                Some(mdl) if mdl.data().get_uuid() == model_id.uuid() => mdl,
+                Some(_) | None => continue,
+            };
+            if model.data().primary_index().expires_rows()
+                && !model.data().primary_index().purge_pending()
+            {
+                // expired rows are purged lazily; the deletes are synced along with the next batch
+                self.general_dispatcher
+                    .send(Task::new(GenericTask::PurgeExpiredRows(model_id.clone())))
+                    .unwrap();
+            }
             let observed_len = model
                 .data()
                 .delta_state()
                 .__fractal_take_full_from_data_delta(super::FractalToken::new());
             match self.try_write_model_data_batch(
-                ModelUniqueIDRef::new(model_id.space(), model_id.entity(), model.data().get_uuid()),
+                ModelUniqueIDRef::from(&model_id),
                 model.data(),
                 observed_len,
                 model.driver(),
@@ -481,26 +518,17 @@ impl FractalMgr {
                         info!(
                             "flp: completed maintenance task for {}.{}, synced={observed_len}",
                             model_id.space(),
-                            model_id.entity()
+                            model_id.model()
                         )
                     }
                 }
                 Err((e, stats)) => {
                     info!(
                         "flp: failed to sync data for {}.{} with erro `{e}`. promoting to higher priority",
-                        model_id.space(), model_id.entity(),
+                        model_id.space(), model_id.model(),
                     );
                     // this failure is *not* good, so we want to promote this to a critical task
-                    self.re_enqueue_model_sync(
-                        ModelUniqueID::new(
-                            model_id.space(),
-                            model_id.entity(),
-                            model.data().get_uuid(),
-                        ),
-                        observed_len,
-                        stats,
-                        TASK_THRESHOLD,
-                    )
+                    self.re_enqueue_model_sync(model_id, observed_len, stats, TASK_THRESHOLD)
                 }
             }
         }
@@ -509,6 +537,24 @@ impl FractalMgr {
 
 // util
 impl FractalMgr {
+    /// Remove the expired rows among the next [`PURGE_BUDGET`] rows of the model (if it still exists), which publishes
+    /// a delete for each of them. Returns false if the rest of the model still needs to be purged
+    fn purge_expired_rows(global: super::Global, model_id: ModelUniqueID) -> bool {
+        let mdl_read = global.state().namespace().idx_models().read();
+        let mdl = match mdl_read.get(&EntityIDRef::new(model_id.space(), model_id.model())) {
+            Some(mdl) if mdl.data().get_uuid() == model_id.uuid() => mdl,
+            Some(_) | None => return true,
+        };
+        if !mdl.driver().status().is_healthy() {
+            // the rows will be purged once the driver has recovered
+            return true;
+        }
+        let (purged, done) = dml::purge_expired(mdl.data(), PURGE_BUDGET);
+        if purged != 0 {
+            info!("flp: purged {purged} expired rows from {model_id}");
+        }
+        done
+    }
     /// Attempt to write a model data batch with the observed size.
     ///
     /// The zero check is essential
@@ -51,7 +51,7 @@ mod mgr;
 pub mod test_utils;
 mod util;
 pub use {
-    drivers::{FractalGNSDriver, FractalModelDriver},
+    drivers::{FractalGNSDriver, FractalModelDriver, GroupTicket},
     mgr::{CriticalTask, GenericTask, Task, GENERAL_EXECUTOR_WINDOW},
     util::FractalToken,
 };
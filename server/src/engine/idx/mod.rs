@@ -28,6 +28,7 @@
 
 pub mod meta;
 pub mod mtchm;
+mod mtord;
 mod stdhm;
 mod stord;
 #[cfg(test)]
@@ -48,6 +49,7 @@ pub type IndexSTSeqCns<K, V> = stord::IndexSTSeqDll<K, V, stord::config::Conserv
 #[cfg(test)]
 pub type IndexSTSeqLib<K, V> = stord::IndexSTSeqDll<K, V, stord::config::LiberalConfig<K, V>>;
 pub type IndexMTRaw<E> = mtchm::imp::Raw<E, mtchm::meta::DefConfig>;
+pub type IndexMTOrd<E> = mtord::MTOrd<E, mtchm::meta::DefConfig>;
 pub type IndexST<K, V, S = std::collections::hash_map::RandomState> =
     std::collections::hash_map::HashMap<K, V, S>;
 
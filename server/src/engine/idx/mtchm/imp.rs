@@ -32,7 +32,10 @@ use {
     super::{
         iter::{IterKV, IterKey, IterVal},
         meta::{Config, TreeElement},
-        patch::{DeleteRetEntry, VanillaInsert, VanillaUpdate, VanillaUpdateRet, VanillaUpsert},
+        patch::{
+            DeleteRetEntry, DeleteRetEntryIf, VanillaInsert, VanillaUpdate, VanillaUpdateRet,
+            VanillaUpsert,
+        },
         RawTree,
     },
     crate::engine::{
@@ -45,6 +48,34 @@ pub type Raw<E, C> = RawTree<E, C>;
 #[cfg(test)]
 pub type ChmCopy<K, V, C> = Raw<(K, V), C>;
 
+impl<E: TreeElement, C: Config> Raw<E, C> {
+    /// Returns an iterator over all the entries that follow the given key in iteration order
+    pub fn mt_iter_entry_after<'t, 'g, 'v, Q>(
+        &'t self,
+        key: &Q,
+        g: &'g Guard,
+    ) -> super::iter::IterEntry<'t, 'g, 'v, E, C>
+    where
+        Q: ?Sized + Comparable<E::Key>,
+    {
+        super::iter::IterEntry::new_after(self, key, g)
+    }
+    /// Remove the entry with the given key, but only if `iff` returns true for it
+    pub fn mt_delete_return_entry_if<'t, 'g, 'v, Q>(
+        &'t self,
+        key: &Q,
+        iff: impl Fn(&E) -> bool,
+        g: &'g Guard,
+    ) -> Option<&'v E>
+    where
+        Q: ?Sized + Comparable<E::Key>,
+        't: 'v,
+        'g: 't + 'v,
+    {
+        self._remove(DeleteRetEntryIf::new(key, iff), g)
+    }
+}
+
 impl<E, C: Config> IndexBaseSpec for Raw<E, C> {
     const PREALLOC: bool = false;
 
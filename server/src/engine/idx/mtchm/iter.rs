@@ -30,10 +30,11 @@ use {
         Node, RawTree,
     },
     crate::engine::{
+        idx::meta::Comparable,
         mem::UArray,
         sync::atm::{Guard, Shared},
     },
-    std::marker::PhantomData,
+    std::{cmp::Ordering, marker::PhantomData},
 };
 
 pub struct IterKV<'t, 'g, 'v, T, C>
@@ -98,6 +99,25 @@ where
     }
 }
 
+impl<'t, 'g, 'v, T, C> IterEntry<'t, 'g, 'v, T, C>
+where
+    't: 'v,
+    'g: 'v + 't,
+    C: Config,
+    T: TreeElement,
+{
+    /// Returns an iterator that resumes right after the given key, in the order of the DFS. If the key
+    /// is no longer present, iteration resumes from the first entry that would have followed it
+    pub fn new_after<Q>(t: &'t RawTree<T, C>, key: &Q, g: &'g Guard) -> Self
+    where
+        Q: ?Sized + Comparable<T::Key>,
+    {
+        Self {
+            i: RawIter::new_after(t, key, g),
+        }
+    }
+}
+
 impl<'t, 'g, 'v, T, C> Iterator for IterEntry<'t, 'g, 'v, T, C>
 where
     't: 'v,
@@ -260,6 +280,77 @@ where
             _m: PhantomData,
         }
     }
+    /*
+        the DFS visits branches in ascending order of the hash chunk at every level, which means that entries are
+        visited in the order of their hash chunks (least significant chunk first). since data nodes only ever hold
+        entries with the same hash, we can rebuild the exact DFS stack for any key by walking down its hash and
+        marking every branch as visited up until (and including) the chunk we descended into
+    */
+    fn new_after<Q>(tree: &'t RawTree<T, C>, key: &Q, g: &'g Guard) -> Self
+    where
+        Q: ?Sized + Comparable<T::Key>,
+        T: TreeElement,
+    {
+        let mut me = Self::new(tree, g);
+        let hash = tree.hash(key);
+        let mut level = C::LEVEL_ZERO;
+        loop {
+            let l = me.stack.len() - 1;
+            let current = &mut me.stack[l];
+            let node = current.sptr;
+            let flag = super::ldfl(&node);
+            match flag {
+                _ if node.is_null() => {
+                    me.stack.pop();
+                    break;
+                }
+                flag if super::hf(flag, NodeFlag::DATA) => {
+                    let data = unsafe {
+                        // UNSAFE(@ohsayan): flagck
+                        RawTree::<T, C>::read_data(node)
+                    };
+                    current.idx = match data.first() {
+                        Some(e) => match Self::cmp_dfs(tree.hash(e.key()), hash) {
+                            Ordering::Less => data.len(),
+                            Ordering::Greater => 0,
+                            Ordering::Equal => data
+                                .iter()
+                                .position(|e| key.cmp_eq(e.key()))
+                                .map(|i| i + 1)
+                                .unwrap_or(0),
+                        },
+                        None => 0,
+                    };
+                    break;
+                }
+                _ if level < C::MAX_TREE_HEIGHT_UB => {
+                    let this_node = unsafe {
+                        // UNSAFE(@ohsayan): guard
+                        node.deref()
+                    };
+                    let chunk = ((hash >> level) & C::MASK) as usize;
+                    let sptr = this_node.branch[chunk].ld_acq(me.g);
+                    current.idx = chunk + 1;
+                    me.stack.push(DFSCNodeCtx { sptr, idx: 0 });
+                    level += C::BRANCH_LG;
+                }
+                _ => {
+                    me.stack.pop();
+                    break;
+                }
+            }
+        }
+        me
+    }
+    /// compare two hashes in the order in which the DFS would visit them
+    fn cmp_dfs(a: u64, b: u64) -> Ordering {
+        let diff = a ^ b;
+        if diff == 0 {
+            return Ordering::Equal;
+        }
+        let level = (diff.trailing_zeros() as usize / C::BRANCH_LG) * C::BRANCH_LG;
+        ((a >> level) & C::MASK).cmp(&((b >> level) & C::MASK))
+    }
     /// depth-first search the tree
     fn _next(&mut self) -> Option<I::Ret<'v>> {
         while !self.stack.is_empty() {
@@ -532,7 +532,8 @@ impl<T: TreeElement, C: Config> RawTree<T, C> {
                     let r: LNode<T> = data
                         .iter()
                         .filter_map(|this_elem| {
-                            if patch.target().cmp_eq(this_elem.key()) {
+                            if patch.target().cmp_eq(this_elem.key()) && patch.ex_remove(this_elem)
+                            {
                                 ret = P::ex(this_elem);
                                 rem = true;
                                 None
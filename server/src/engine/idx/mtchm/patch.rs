@@ -182,6 +182,11 @@ pub trait PatchDelete<T: TreeElement> {
     fn target(&self) -> &Self::Target;
     fn ex<'a>(v: &'a T) -> Self::Ret<'a>;
     fn nx<'a>() -> Self::Ret<'a>;
+    /// Returns true if the element (which has the target key) should be removed
+    #[inline(always)]
+    fn ex_remove(&self, _: &T) -> bool {
+        true
+    }
 }
 
 pub struct Delete<'a, T: TreeElement, U: ?Sized> {
@@ -277,3 +282,41 @@ impl<'dr, T: TreeElement, U: Comparable<T::Key> + ?Sized> PatchDelete<T> for Del
         None
     }
 }
+
+pub struct DeleteRetEntryIf<'a, T: TreeElement, U: ?Sized, F> {
+    target: &'a U,
+    iff: F,
+    _m: PhantomData<T>,
+}
+
+impl<'a, T: TreeElement, U: ?Sized, F: Fn(&T) -> bool> DeleteRetEntryIf<'a, T, U, F> {
+    pub fn new(target: &'a U, iff: F) -> Self {
+        Self {
+            target,
+            iff,
+            _m: PhantomData,
+        }
+    }
+}
+
+impl<'dr, T: TreeElement, U: Comparable<T::Key> + ?Sized, F: Fn(&T) -> bool> PatchDelete<T>
+    for DeleteRetEntryIf<'dr, T, U, F>
+{
+    type Ret<'a> = Option<&'a T>;
+    type Target = U;
+    fn target(&self) -> &Self::Target {
+        self.target
+    }
+    #[inline(always)]
+    fn ex<'a>(v: &'a T) -> Self::Ret<'a> {
+        Some(v)
+    }
+    #[inline(always)]
+    fn nx<'a>() -> Self::Ret<'a> {
+        None
+    }
+    #[inline(always)]
+    fn ex_remove(&self, v: &T) -> bool {
+        (self.iff)(v)
+    }
+}
@@ -341,3 +341,38 @@ fn multispam_lol() {
     modify_and_verify_integrity(&token, &idx, &data, _action_put, _verify_eq);
     assert_eq!(idx.idx_metrics().replnode(), SPAM_QCOUNT - 1);
 }
+
+// iter
+#[test]
+fn iter_after() {
+    let idx = Chm::<u64, u64>::idx_init();
+    let g = cpin();
+    for i in 0..2048u64 {
+        assert!(idx.mt_insert((i, i), &g));
+    }
+    let full: Vec<u64> = idx.mt_iter_key(&g).cloned().collect();
+    assert_eq!(full.len(), 2048);
+    for (pos, key) in full.iter().enumerate().step_by(7) {
+        let after: Vec<u64> = idx.mt_iter_entry_after(key, &g).map(|(k, _)| *k).collect();
+        assert_eq!(after, full[pos + 1..]);
+    }
+    // resuming after a key that was removed should still pick up right where it left off
+    let (pos, key) = (1000, full[1000]);
+    assert!(idx.mt_delete(&key, &g));
+    let after: Vec<u64> = idx.mt_iter_entry_after(&key, &g).map(|(k, _)| *k).collect();
+    assert_eq!(after, full[pos + 1..]);
+}
+
+#[test]
+fn iter_after_collision() {
+    let idx = super::RawTree::<(u64, u64), super::meta::Config2B<LolState>>::new();
+    let g = cpin();
+    for i in 0..64u64 {
+        assert!(idx.mt_insert((i, i), &g));
+    }
+    let full: Vec<u64> = idx.mt_iter_key(&g).cloned().collect();
+    for (pos, key) in full.iter().enumerate() {
+        let after: Vec<u64> = idx.mt_iter_entry_after(key, &g).map(|(k, _)| *k).collect();
+        assert_eq!(after, full[pos + 1..]);
+    }
+}
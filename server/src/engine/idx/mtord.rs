@@ -0,0 +1,615 @@
+/*
+ * Created on Sun Oct 18 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+/*
+    An ordered concurrent index.
+
+    Point reads go straight to a hash trie, so they are just as fast (and just as lock-free) as with
+    the unordered index. Alongside the trie, we maintain a handle (a clone) to every element in one of
+    `ORD_SHARD_COUNT` B-trees, picked by the key's hash, which gives us key order within each shard. A
+    writer holds the lock of its key's shard for the whole write, so writes to different shards run in
+    parallel while writes to the same key are still ordered (which ensures that the trie and the B-trees
+    always agree). Iterators never hold a lock across calls to `next`: they copy out a batch of handles
+    from each shard at a time, merge the shards by key and then resolve each handle through the trie
+    using the caller's guard, silently skipping entries that were removed in the meantime.
+
+    Why shard by hash (and not by range)?
+    ---
+    Ordered indexes are meant for append-heavy (time-series style) keys. With range partitioning (or a
+    skip list or B-link tree) all of these writes would land on the same rightmost partition (or nodes),
+    while hashing spreads consecutive keys over all the shards. A shard lock is only ever held for a
+    single B-tree operation (an O(log n) insert or remove for a writer, or copying out at most
+    `ITER_BATCH_SIZE` handles for an iterator), never across a row lock, a guard or any I/O, so it can't
+    deadlock with anything else. What it costs us is that a scan has to merge every shard. The handles
+    are cheap: cloning an element only bumps the reference count of the row and shares the key's memory,
+    so the B-trees cost a couple of words per element rather than a copy of the data.
+*/
+
+use {
+    super::{
+        meta::Comparable,
+        mtchm::{
+            imp::Raw,
+            meta::{Config, TreeElement},
+        },
+        AsKeyClone, AsValue, AsValueClone, IndexBaseSpec, MTIndex, MTIndexExt,
+    },
+    crate::engine::sync::atm::{cpin, Guard},
+    parking_lot::RwLock,
+    std::{
+        borrow::Borrow,
+        cmp::Ordering,
+        collections::{BTreeSet, VecDeque},
+        fmt,
+        hash::{BuildHasher, Hash, Hasher},
+        marker::PhantomData,
+        ops::Bound,
+    },
+};
+
+/// The number of handles that an iterator copies out of a shard at a time
+const ITER_BATCH_SIZE: usize = 64;
+/// The number of B-trees that the elements are spread over
+const ORD_SHARD_COUNT: usize = 16;
+
+pub struct MTOrd<E, C: Config> {
+    raw: Raw<E, C>,
+    /// the key order (of each shard); writers hold the key's shard for the whole write so that the trie and the
+    /// B-trees always agree
+    ord: [RwLock<BTreeSet<OrdEntry<E>>>; ORD_SHARD_COUNT],
+    /// picks the shard of a key
+    h: C::HState,
+}
+
+impl<E, C: Config> MTOrd<E, C> {
+    /// Returns the shard that holds the given key
+    fn shard<Q: ?Sized + Hash>(&self, key: &Q) -> &RwLock<BTreeSet<OrdEntry<E>>> {
+        let mut state = self.h.build_hasher();
+        key.hash(&mut state);
+        &self.ord[state.finish() as usize % ORD_SHARD_COUNT]
+    }
+}
+
+/// A handle to an element in the B-tree, ordered by the element's key
+struct OrdEntry<E>(E);
+
+/// A key, as seen by the B-tree
+#[repr(transparent)]
+struct OrdKey<K>(K);
+
+impl<K> OrdKey<K> {
+    fn from_ref(k: &K) -> &Self {
+        unsafe {
+            // UNSAFE(@ohsayan): repr(transparent)
+            &*(k as *const K as *const Self)
+        }
+    }
+    fn bound(b: Bound<&K>) -> Bound<&Self> {
+        b.map(Self::from_ref)
+    }
+}
+
+impl<K: Ord> PartialEq for OrdKey<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.cmp(&other.0) == Ordering::Equal
+    }
+}
+
+impl<K: Ord> Eq for OrdKey<K> {}
+
+impl<K: Ord> PartialOrd for OrdKey<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord> Ord for OrdKey<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<E: TreeElement> Borrow<OrdKey<E::Key>> for OrdEntry<E> {
+    fn borrow(&self) -> &OrdKey<E::Key> {
+        OrdKey::from_ref(self.0.key())
+    }
+}
+
+impl<E: TreeElement> PartialEq for OrdEntry<E>
+where
+    E::Key: Ord,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<E: TreeElement> Eq for OrdEntry<E> where E::Key: Ord {}
+
+impl<E: TreeElement> PartialOrd for OrdEntry<E>
+where
+    E::Key: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E: TreeElement> Ord for OrdEntry<E>
+where
+    E::Key: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.key().cmp(other.0.key())
+    }
+}
+
+/// Returns true if no key can fall within the given bounds
+fn range_is_empty<K: Ord>(lo: Bound<&K>, hi: Bound<&K>) -> bool {
+    match (lo, hi) {
+        (Bound::Included(lo), Bound::Included(hi)) => lo > hi,
+        (Bound::Included(lo), Bound::Excluded(hi))
+        | (Bound::Excluded(lo), Bound::Included(hi))
+        | (Bound::Excluded(lo), Bound::Excluded(hi)) => lo >= hi,
+        _ => false,
+    }
+}
+
+impl<E: TreeElement, C: Config> MTOrd<E, C>
+where
+    E::Key: Ord,
+{
+    /// Returns an iterator over all the entries with keys within the given bounds, in key order
+    pub fn mt_iter_entry_range<'t, 'g, 'v>(
+        &'t self,
+        lo: Bound<E::Key>,
+        hi: Bound<E::Key>,
+        g: &'g Guard,
+    ) -> IterOrdEntry<'t, 'g, 'v, E, C> {
+        IterOrdEntry::new(self, lo, hi, g)
+    }
+    /// Remove the entry with the given key, but only if `iff` returns true for it
+    pub fn mt_delete_return_entry_if<'t, 'g, 'v, Q>(
+        &'t self,
+        key: &Q,
+        iff: impl Fn(&E) -> bool,
+        g: &'g Guard,
+    ) -> Option<&'v E>
+    where
+        Q: ?Sized + Comparable<E::Key>,
+        't: 'v,
+        'g: 't + 'v,
+    {
+        let mut ord = self.shard(key).write();
+        let removed = self.raw.mt_delete_return_entry_if(key, iff, g);
+        if let Some(e) = removed {
+            ord.remove(OrdKey::from_ref(e.key()));
+        }
+        removed
+    }
+}
+
+impl<E, C: Config> IndexBaseSpec for MTOrd<E, C> {
+    const PREALLOC: bool = false;
+
+    #[cfg(debug_assertions)]
+    type Metrics = <Raw<E, C> as IndexBaseSpec>::Metrics;
+
+    fn idx_init() -> Self {
+        Self {
+            raw: Raw::idx_init(),
+            ord: std::array::from_fn(|_| RwLock::new(BTreeSet::new())),
+            h: C::HState::default(),
+        }
+    }
+
+    fn idx_init_with(s: Self) -> Self {
+        s
+    }
+
+    #[cfg(debug_assertions)]
+    fn idx_metrics(&self) -> &Self::Metrics {
+        self.raw.idx_metrics()
+    }
+}
+
+impl<E: TreeElement, C: Config> MTIndexExt<E, E::Key, E::Value> for MTOrd<E, C>
+where
+    E::Key: Ord,
+{
+    type IterEntry<'t, 'g, 'v> = IterOrdEntry<'t, 'g, 'v, E, C>
+    where
+        'g: 't + 'v,
+        't: 'v,
+        E::Key: 'v,
+        E::Value: 'v,
+        E: 'v,
+        Self: 't;
+    fn mt_iter_entry<'t, 'g, 'v>(&'t self, g: &'g Guard) -> Self::IterEntry<'t, 'g, 'v> {
+        IterOrdEntry::new(self, Bound::Unbounded, Bound::Unbounded, g)
+    }
+}
+
+impl<E: TreeElement, C: Config> MTIndex<E, E::Key, E::Value> for MTOrd<E, C>
+where
+    E::Key: Ord,
+{
+    type IterKV<'t, 'g, 'v> = IterOrdKV<'t, 'g, 'v, E, C>
+    where
+        'g: 't + 'v,
+        't: 'v,
+        E::Key: 'v,
+        E::Value: 'v,
+        Self: 't;
+
+    type IterKey<'t, 'g, 'v> = IterOrdKey<'t, 'g, 'v, E, C>
+    where
+        'g: 't + 'v,
+        't: 'v,
+        E::Key: 'v,
+        Self: 't;
+
+    type IterVal<'t, 'g, 'v> = IterOrdVal<'t, 'g, 'v, E, C>
+    where
+        'g: 't + 'v,
+        't: 'v,
+        E::Value: 'v,
+        Self: 't;
+
+    fn mt_iter_kv<'t, 'g, 'v>(&'t self, g: &'g Guard) -> Self::IterKV<'t, 'g, 'v> {
+        IterOrdKV(self.mt_iter_entry(g))
+    }
+
+    fn mt_iter_key<'t, 'g, 'v>(&'t self, g: &'g Guard) -> Self::IterKey<'t, 'g, 'v> {
+        IterOrdKey(self.mt_iter_entry(g))
+    }
+
+    fn mt_iter_val<'t, 'g, 'v>(&'t self, g: &'g Guard) -> Self::IterVal<'t, 'g, 'v> {
+        IterOrdVal(self.mt_iter_entry(g))
+    }
+
+    fn mt_len(&self) -> usize {
+        self.raw.mt_len()
+    }
+
+    fn mt_clear(&self, g: &Guard) {
+        // shards are always locked in the same order, and writers only ever hold one
+        let mut ord: Vec<_> = self.ord.iter().map(RwLock::write).collect();
+        self.raw.mt_clear(g);
+        ord.iter_mut().for_each(|shard| shard.clear());
+    }
+
+    fn mt_insert(&self, e: E, g: &Guard) -> bool
+    where
+        E::Value: AsValue,
+    {
+        let mut ord = self.shard(e.key()).write();
+        let inserted = self.raw.mt_insert(e.clone(), g);
+        if inserted {
+            ord.insert(OrdEntry(e));
+        }
+        inserted
+    }
+
+    fn mt_upsert(&self, e: E, g: &Guard) -> bool
+    where
+        E::Value: AsValue,
+    {
+        let mut ord = self.shard(e.key()).write();
+        let ret = self.raw.mt_upsert(e.clone(), g);
+        ord.replace(OrdEntry(e));
+        ret
+    }
+
+    fn mt_contains<Q>(&self, key: &Q, g: &Guard) -> bool
+    where
+        Q: ?Sized + Comparable<E::Key>,
+    {
+        self.raw.mt_contains(key, g)
+    }
+
+    fn mt_get<'t, 'g, 'v, Q>(&'t self, key: &Q, g: &'g Guard) -> Option<&'v E::Value>
+    where
+        Q: ?Sized + Comparable<E::Key>,
+        't: 'v,
+        'g: 't + 'v,
+    {
+        self.raw.mt_get(key, g)
+    }
+
+    fn mt_get_element<'t, 'g, 'v, Q>(&'t self, key: &Q, g: &'g Guard) -> Option<&'v E>
+    where
+        Q: ?Sized + Comparable<E::Key>,
+        't: 'v,
+        'g: 't + 'v,
+    {
+        self.raw.mt_get_element(key, g)
+    }
+
+    fn mt_get_cloned<Q>(&self, key: &Q, g: &Guard) -> Option<E::Value>
+    where
+        Q: ?Sized + Comparable<E::Key>,
+        E::Value: AsValueClone,
+    {
+        self.raw.mt_get_cloned(key, g)
+    }
+
+    fn mt_update(&self, e: E, g: &Guard) -> bool
+    where
+        E::Key: AsKeyClone,
+        E::Value: AsValue,
+    {
+        let mut ord = self.shard(e.key()).write();
+        let updated = self.raw.mt_update(e.clone(), g);
+        if updated {
+            ord.replace(OrdEntry(e));
+        }
+        updated
+    }
+
+    fn mt_update_return<'t, 'g, 'v>(&'t self, e: E, g: &'g Guard) -> Option<&'v E::Value>
+    where
+        E::Key: AsKeyClone,
+        E::Value: AsValue,
+        't: 'v,
+        'g: 't + 'v,
+    {
+        let mut ord = self.shard(e.key()).write();
+        let old = self.raw.mt_update_return(e.clone(), g);
+        if old.is_some() {
+            ord.replace(OrdEntry(e));
+        }
+        old
+    }
+
+    fn mt_delete<Q>(&self, key: &Q, g: &Guard) -> bool
+    where
+        Q: ?Sized + Comparable<E::Key>,
+    {
+        self.mt_delete_return_entry(key, g).is_some()
+    }
+
+    fn mt_delete_return<'t, 'g, 'v, Q>(&'t self, key: &Q, g: &'g Guard) -> Option<&'v E::Value>
+    where
+        Q: ?Sized + Comparable<E::Key>,
+        't: 'v,
+        'g: 't + 'v,
+    {
+        self.mt_delete_return_entry(key, g).map(TreeElement::val)
+    }
+
+    fn mt_delete_return_entry<'t, 'g, 'v, Q>(&'t self, key: &Q, g: &'g Guard) -> Option<&'v E>
+    where
+        Q: ?Sized + Comparable<E::Key>,
+        't: 'v,
+        'g: 't + 'v,
+    {
+        let mut ord = self.shard(key).write();
+        let removed = self.raw.mt_delete_return_entry(key, g);
+        if let Some(e) = removed {
+            ord.remove(OrdKey::from_ref(e.key()));
+        }
+        removed
+    }
+}
+
+impl<E: TreeElement, C: Config> fmt::Debug for MTOrd<E, C>
+where
+    E::Key: Ord + fmt::Debug,
+    E::Value: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let g = cpin();
+        f.debug_map().entries(self.mt_iter_kv(&g)).finish()
+    }
+}
+
+/*
+    iterators
+*/
+
+enum IterCursor<E: TreeElement> {
+    /// nothing has been read yet
+    Start,
+    /// everything upto (and including) this element has been read
+    After(E),
+    /// the range has been exhausted
+    Done,
+}
+
+/// The position of an iterator in a shard
+struct ShardCursor<E: TreeElement> {
+    cursor: IterCursor<E>,
+    batch: VecDeque<E>,
+}
+
+impl<E: TreeElement> ShardCursor<E>
+where
+    E::Key: Ord,
+{
+    /// Copy out the next batch of handles from the shard
+    fn fill(
+        &mut self,
+        shard: &RwLock<BTreeSet<OrdEntry<E>>>,
+        lo: Bound<&E::Key>,
+        hi: Bound<&E::Key>,
+    ) {
+        let lo = match &self.cursor {
+            IterCursor::Start => lo,
+            IterCursor::After(e) => Bound::Excluded(e.key()),
+            IterCursor::Done => return,
+        };
+        if !range_is_empty(lo, hi) {
+            let ord = shard.read();
+            self.batch.extend(
+                ord.range::<OrdKey<E::Key>, _>((OrdKey::bound(lo), OrdKey::bound(hi)))
+                    .take(ITER_BATCH_SIZE)
+                    .map(|OrdEntry(e)| e.clone()),
+            );
+        }
+        self.cursor = match self.batch.back() {
+            Some(last) if self.batch.len() == ITER_BATCH_SIZE => IterCursor::After(last.clone()),
+            _ => IterCursor::Done,
+        };
+    }
+}
+
+pub struct IterOrdEntry<'t, 'g, 'v, E, C>
+where
+    't: 'v,
+    'g: 'v + 't,
+    C: Config,
+    E: TreeElement,
+{
+    idx: &'t MTOrd<E, C>,
+    g: &'g Guard,
+    lo: Bound<E::Key>,
+    hi: Bound<E::Key>,
+    shards: Vec<ShardCursor<E>>,
+    _lt: PhantomData<&'v E>,
+}
+
+impl<'t, 'g, 'v, E, C> IterOrdEntry<'t, 'g, 'v, E, C>
+where
+    't: 'v,
+    'g: 'v + 't,
+    C: Config,
+    E: TreeElement,
+    E::Key: Ord,
+{
+    fn new(idx: &'t MTOrd<E, C>, lo: Bound<E::Key>, hi: Bound<E::Key>, g: &'g Guard) -> Self {
+        Self {
+            idx,
+            g,
+            lo,
+            hi,
+            shards: (0..ORD_SHARD_COUNT)
+                .map(|_| ShardCursor {
+                    cursor: IterCursor::Start,
+                    batch: VecDeque::new(),
+                })
+                .collect(),
+            _lt: PhantomData,
+        }
+    }
+    /// Returns the next handle in key order (across all shards)
+    fn next_handle(&mut self) -> Option<E> {
+        for (shard, cursor) in self.idx.ord.iter().zip(self.shards.iter_mut()) {
+            if cursor.batch.is_empty() {
+                cursor.fill(shard, self.lo.as_ref(), self.hi.as_ref());
+            }
+        }
+        self.shards
+            .iter_mut()
+            .filter(|cursor| !cursor.batch.is_empty())
+            .min_by(|a, b| a.batch[0].key().cmp(b.batch[0].key()))?
+            .batch
+            .pop_front()
+    }
+}
+
+impl<'t, 'g, 'v, E, C> Iterator for IterOrdEntry<'t, 'g, 'v, E, C>
+where
+    't: 'v,
+    'g: 'v + 't,
+    C: Config,
+    E: TreeElement,
+    E::Key: Ord,
+{
+    type Item = &'v E;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let handle = self.next_handle()?;
+            // the entry might have been removed since we copied out the batch
+            if let Some(e) = self.idx.raw.mt_get_element(handle.key(), self.g) {
+                return Some(e);
+            }
+        }
+    }
+}
+
+pub struct IterOrdKV<'t, 'g, 'v, E, C>(IterOrdEntry<'t, 'g, 'v, E, C>)
+where
+    't: 'v,
+    'g: 'v + 't,
+    C: Config,
+    E: TreeElement;
+
+impl<'t, 'g, 'v, E, C> Iterator for IterOrdKV<'t, 'g, 'v, E, C>
+where
+    't: 'v,
+    'g: 'v + 't,
+    C: Config,
+    E: TreeElement,
+    E::Key: Ord,
+{
+    type Item = (&'v E::Key, &'v E::Value);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|e| (e.key(), e.val()))
+    }
+}
+
+pub struct IterOrdKey<'t, 'g, 'v, E, C>(IterOrdEntry<'t, 'g, 'v, E, C>)
+where
+    't: 'v,
+    'g: 'v + 't,
+    C: Config,
+    E: TreeElement;
+
+impl<'t, 'g, 'v, E, C> Iterator for IterOrdKey<'t, 'g, 'v, E, C>
+where
+    't: 'v,
+    'g: 'v + 't,
+    C: Config,
+    E: TreeElement,
+    E::Key: Ord,
+{
+    type Item = &'v E::Key;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(TreeElement::key)
+    }
+}
+
+pub struct IterOrdVal<'t, 'g, 'v, E, C>(IterOrdEntry<'t, 'g, 'v, E, C>)
+where
+    't: 'v,
+    'g: 'v + 't,
+    C: Config,
+    E: TreeElement;
+
+impl<'t, 'g, 'v, E, C> Iterator for IterOrdVal<'t, 'g, 'v, E, C>
+where
+    't: 'v,
+    'g: 'v + 't,
+    C: Config,
+    E: TreeElement,
+    E::Key: Ord,
+{
+    type Item = &'v E::Value;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(TreeElement::val)
+    }
+}
@@ -185,3 +185,130 @@ mod idx_st_seq_dll {
             });
     }
 }
+
+mod idx_mt_ord {
+    use {
+        super::{IndexBaseSpec, IndexMTOrd, MTIndex, MTIndexExt},
+        crate::engine::sync::atm::cpin,
+        std::{ops::Bound, sync::Arc, thread},
+    };
+    type Idx = IndexMTOrd<(usize, String)>;
+    const SPAM_CNT: usize = 1000;
+    /// insert keys in a scrambled order
+    fn mkidx() -> Idx {
+        let idx = Idx::idx_init();
+        let g = cpin();
+        for i in 0..SPAM_CNT {
+            let k = (i * 7919) % SPAM_CNT;
+            assert!(idx.mt_insert((k, k.to_string()), &g));
+        }
+        idx
+    }
+    fn keys(it: impl Iterator<Item = &'static (usize, String)>) -> Vec<usize> {
+        it.map(|(k, _)| *k).collect()
+    }
+    #[test]
+    fn iter_ord() {
+        let idx = mkidx();
+        let g = cpin();
+        assert_eq!(idx.mt_len(), SPAM_CNT);
+        let kv: Vec<_> = idx.mt_iter_kv(&g).map(|(k, v)| (*k, v.clone())).collect();
+        assert_eq!(
+            kv,
+            (0..SPAM_CNT)
+                .map(|i| (i, i.to_string()))
+                .collect::<Vec<_>>()
+        );
+    }
+    #[test]
+    fn iter_range() {
+        let idx = Box::leak(Box::new(mkidx()));
+        let g = Box::leak(Box::new(cpin()));
+        assert_eq!(
+            keys(idx.mt_iter_entry_range(Bound::Included(100), Bound::Excluded(300), g)),
+            (100..300).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            keys(idx.mt_iter_entry_range(Bound::Excluded(990), Bound::Unbounded, g)),
+            (991..SPAM_CNT).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            keys(idx.mt_iter_entry_range(Bound::Excluded(10), Bound::Excluded(10), g)).len(),
+            0
+        );
+        assert_eq!(
+            keys(idx.mt_iter_entry_range(Bound::Included(20), Bound::Included(10), g)).len(),
+            0
+        );
+    }
+    #[test]
+    fn delete_and_upsert() {
+        let idx = mkidx();
+        let g = cpin();
+        for i in (0..SPAM_CNT).filter(|i| i % 2 == 0) {
+            assert!(idx.mt_delete(&i, &g));
+        }
+        assert!(!idx.mt_delete(&0, &g));
+        assert!(idx.mt_upsert((1, "one".into()), &g));
+        assert_eq!(idx.mt_len(), SPAM_CNT / 2);
+        let kv: Vec<_> = idx.mt_iter_kv(&g).map(|(k, v)| (*k, v.clone())).collect();
+        assert_eq!(kv[0], (1, "one".to_owned()));
+        assert_eq!(
+            kv.iter().map(|(k, _)| *k).collect::<Vec<_>>(),
+            (0..SPAM_CNT).filter(|i| i % 2 == 1).collect::<Vec<_>>()
+        );
+        idx.mt_clear(&g);
+        assert_eq!(idx.mt_iter_entry(&g).count(), 0);
+    }
+    #[test]
+    fn concurrent_insert_iter() {
+        let idx = Arc::new(Idx::idx_init());
+        let writers: Vec<_> = (0..4)
+            .map(|t| {
+                let idx = idx.clone();
+                thread::spawn(move || {
+                    let g = cpin();
+                    for i in (t..SPAM_CNT).step_by(4) {
+                        assert!(idx.mt_insert((i, i.to_string()), &g));
+                    }
+                })
+            })
+            .collect();
+        // iterators must always observe keys in order, even while the index is being written to
+        let g = cpin();
+        for _ in 0..10 {
+            let keys: Vec<_> = idx.mt_iter_key(&g).copied().collect();
+            assert!(keys.windows(2).all(|w| w[0] < w[1]));
+        }
+        writers.into_iter().for_each(|w| w.join().unwrap());
+        assert_eq!(
+            idx.mt_iter_key(&g).copied().collect::<Vec<_>>(),
+            (0..SPAM_CNT).collect::<Vec<_>>()
+        );
+    }
+    #[test]
+    fn concurrent_insert_delete() {
+        let idx = Arc::new(Idx::idx_init());
+        let writers: Vec<_> = (0..4)
+            .map(|t| {
+                let idx = idx.clone();
+                thread::spawn(move || {
+                    let g = cpin();
+                    for i in (t..SPAM_CNT).step_by(4) {
+                        assert!(idx.mt_insert((i, i.to_string()), &g));
+                    }
+                    for i in (t..SPAM_CNT).step_by(4).filter(|i| i % 2 == 1) {
+                        assert!(idx.mt_delete(&i, &g));
+                    }
+                })
+            })
+            .collect();
+        writers.into_iter().for_each(|w| w.join().unwrap());
+        let g = cpin();
+        assert_eq!(idx.mt_len(), SPAM_CNT / 2);
+        assert_eq!(
+            idx.mt_iter_key(&g).copied().collect::<Vec<_>>(),
+            (0..SPAM_CNT).filter(|i| i % 2 == 0).collect::<Vec<_>>()
+        );
+    }
+}
@@ -206,6 +206,9 @@ macro_rules! Token {
     (primary) => {
         __kw_misc!(Primary)
     };
+    (index) => {
+        __kw_misc!(Index)
+    };
     // ddl misc
     (with) => {
         __kw_misc!(With)
@@ -222,6 +225,9 @@ macro_rules! Token {
     (type) => {
         __kw_misc!(Type)
     };
+    (default) => {
+        __kw_misc!(Default)
+    };
     // dml
     (insert) => {
         __kw_stmt!(Insert)
@@ -248,9 +254,21 @@ macro_rules! Token {
     (into) => {
         __kw_misc!(Into)
     };
+    (on) => {
+        __kw_misc!(On)
+    };
+    (to) => {
+        __kw_misc!(To)
+    };
+    (truncate) => {
+        __kw_stmt!(Truncate)
+    };
     (where) => {
         __kw_misc!(Where)
     };
+    (in) => {
+        __kw_misc!(In)
+    };
     (if) => {
         __kw_misc!(If)
     };
@@ -263,6 +281,9 @@ macro_rules! Token {
     (by) => {
         __kw_misc!(By)
     };
+    (order) => {
+        __kw_misc!(Order)
+    };
     (asc) => {
         __kw_misc!(Asc)
     };
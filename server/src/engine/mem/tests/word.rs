@@ -128,6 +128,14 @@ fn twordnnn_all() {
 #[test]
 fn qwordnnn_all() {
     check_primitives::<NativeQword>(|_| {}, |_| {});
+    for v in [
+        u128::MIN,
+        u128::MAX,
+        0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210,
+    ] {
+        let x = NativeQword::store(v);
+        assert_wordeq!(x, v);
+    }
 }
 
 #[test]
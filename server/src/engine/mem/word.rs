@@ -376,3 +376,13 @@ impl<T: DwordNN> WordIO<(usize, *const u8)> for T {
         (a, b as *const u8)
     }
 }
+
+impl<T: QwordNNNN> WordIO<u128> for T {
+    fn store(v: u128) -> Self {
+        Self::qwordnnnn_store_qw_qw((v >> 64) as u64, v as u64)
+    }
+    fn load(&self) -> u128 {
+        let [a, b] = self.qwordnnnn_load_qw_qw();
+        ((a as u128) << 64) | (b as u128)
+    }
+}
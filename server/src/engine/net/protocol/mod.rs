@@ -55,7 +55,7 @@ use {
     super::{IoResult, QueryLoopResult, Socket},
     crate::{
         engine::{
-            core::{exec, system_db::VerifyUser},
+            core::{dml::StagedBatch, exec, model::Subscription, system_db::VerifyUser},
             error::{QueryError, QueryResult},
             fractal::{Global, GlobalInstanceLike},
             mem::{BufferedScanner, IntegerRepr},
@@ -79,6 +79,9 @@ pub struct ClientLocalState {
     root: bool,
     hs: handshake::CHandshakeStatic,
     cs: Option<Box<str>>,
+    batch: Option<StagedBatch>,
+    /// a subscription that was just started (its changes are streamed once the query's response is written)
+    subscription: Option<Subscription>,
 }
 
 impl ClientLocalState {
@@ -88,6 +91,8 @@ impl ClientLocalState {
             root,
             hs,
             cs: None,
+            batch: None,
+            subscription: None,
         }
     }
     pub fn is_root(&self) -> bool {
@@ -105,6 +110,22 @@ impl ClientLocalState {
     pub fn get_cs(&self) -> Option<&str> {
         self.cs.as_deref()
     }
+    /// Returns the batch that is currently being staged (if any)
+    pub fn batch_mut(&mut self) -> Option<&mut StagedBatch> {
+        self.batch.as_mut()
+    }
+    pub fn set_batch(&mut self, batch: StagedBatch) {
+        self.batch = Some(batch);
+    }
+    pub fn take_batch(&mut self) -> Option<StagedBatch> {
+        self.batch.take()
+    }
+    pub fn set_subscription(&mut self, subscription: Subscription) {
+        self.subscription = Some(subscription);
+    }
+    pub fn take_subscription(&mut self) -> Option<Subscription> {
+        self.subscription.take()
+    }
     #[cfg(test)]
     pub fn test_new(username: &str, root: bool) -> Self {
         Self::new(
@@ -285,10 +306,16 @@ pub(super) async fn query_loop<S: Socket>(
                     }
                     ExchangeResult::Simple(query) => {
                         exec_simple(con, &mut client_state, global, query).await?;
+                        if let Some(r) = stream_subscription(con, buf, &mut client_state).await? {
+                            return Ok(r);
+                        }
                         (state, cursor) = cleanup_for_next_query(con, buf).await?;
                     }
                     ExchangeResult::Pipeline(pipe) => {
                         exec_pipe(con, &mut client_state, global, pipe).await?;
+                        if let Some(r) = stream_subscription(con, buf, &mut client_state).await? {
+                            return Ok(r);
+                        }
                         (state, cursor) = cleanup_for_next_query(con, buf).await?;
                     }
                 },
@@ -306,6 +333,47 @@ pub(super) async fn query_loop<S: Socket>(
     );
 }
 
+/*
+    subscriptions
+    ---
+    once a subscription has been started (and the response to the query has been written), the changes are streamed
+    to the client as rows until the client sends anything, at which point the stream is ended with an empty response
+    and the connection goes back to running queries (whatever the client sent is discarded). if the subscription
+    fails (say, because the client fell too far behind), the stream is ended with the error instead
+*/
+
+/// Stream the changes of the subscription that was started by the last query (if any). Returns the result of the
+/// query loop if the client closed the connection
+async fn stream_subscription<S: Socket>(
+    con: &mut BufWriter<S>,
+    buf: &mut BytesMut,
+    cs: &mut ClientLocalState,
+) -> IoResult<Option<QueryLoopResult>> {
+    let Some(mut subscription) = cs.take_subscription() else {
+        return Ok(None);
+    };
+    con.flush().await?;
+    buf.clear();
+    loop {
+        tokio::select! {
+            event = subscription.next() => {
+                match event {
+                    Ok(event) => write_response(Ok(event.response()), con).await?,
+                    Err(e) => return write_response(Err(e), con).await.map(|_| None),
+                }
+                con.flush().await?;
+            }
+            read = con.read_buf(buf) => {
+                if read? == 0 {
+                    return Ok(Some(QueryLoopResult::Fin));
+                }
+                con.write_u8(ResponseType::Empty.value_u8()).await?;
+                return Ok(None);
+            }
+        }
+    }
+}
+
 /*
     responses
 */
@@ -334,6 +402,9 @@ pub enum ResponseType {
     Row = 0x11,
     Empty = 0x12,
     MultiRow = 0x13,
+    MultiRowCursor = 0x14,
+    Timestamp = 0x15,
+    Uuid = 0x16,
 }
 
 #[derive(Debug, PartialEq)]
@@ -25,12 +25,13 @@
 */
 
 use crate::engine::{
+    core::system_db::{Grantee, Privilege},
     data::DictGeneric,
     error::{QueryError, QueryResult},
     ql::{
         ast::{traits, QueryData, State},
         ddl::syn,
-        lex::Ident,
+        lex::{Ident, Token},
     },
 };
 
@@ -44,6 +45,20 @@ pub enum SysctlCommand<'a> {
     AlterUser(UserDecl<'a>),
     /// `sysctl status`
     ReportStatus,
+    /// `sysctl grant ...`
+    Grant(PrivilegeDecl<'a>),
+    /// `sysctl revoke ...`
+    Revoke(PrivilegeDecl<'a>),
+    /// `sysctl create role ...`
+    CreateRole(RoleDecl<'a>),
+    /// `sysctl drop role ...`
+    DropRole(RoleDecl<'a>),
+    /// `sysctl grant role ...`
+    GrantRole(RoleGrantDecl<'a>),
+    /// `sysctl revoke role ...`
+    RevokeRole(RoleGrantDecl<'a>),
+    /// `sysctl backup ...`
+    Backup(BackupDecl<'a>),
 }
 
 impl<'a> SysctlCommand<'a> {
@@ -65,16 +80,35 @@ impl<'a> traits::ASTNode<'a> for SysctlCommand<'a> {
         let alter = Token![alter].eq(a) & b.ident_eq("user");
         let create = Token![create].eq(a) & b.ident_eq("user");
         let drop = Token![drop].eq(a) & b.ident_eq("user");
+        let create_role = Token![create].eq(a) & b.ident_eq("role");
+        let drop_role = Token![drop].eq(a) & b.ident_eq("role");
         let status = a.ident_eq("report") & b.ident_eq("status");
-        if !(create | drop | status | alter) {
+        let grant = a.ident_eq("grant");
+        let revoke = a.ident_eq("revoke");
+        let backup = a.ident_eq("backup") & Token![to].eq(b);
+        if !(create | drop | create_role | drop_role | status | alter | grant | revoke | backup) {
             return Err(QueryError::QLUnknownStatement);
         }
-        if create {
+        if grant & b.ident_eq("role") {
+            RoleGrantDecl::parse(state, Token![to]).map(SysctlCommand::GrantRole)
+        } else if revoke & b.ident_eq("role") {
+            RoleGrantDecl::parse(state, Token![from]).map(SysctlCommand::RevokeRole)
+        } else if grant {
+            PrivilegeDecl::parse(state, b, Token![to]).map(SysctlCommand::Grant)
+        } else if revoke {
+            PrivilegeDecl::parse(state, b, Token![from]).map(SysctlCommand::Revoke)
+        } else if create_role {
+            RoleDecl::parse(state).map(SysctlCommand::CreateRole)
+        } else if drop_role {
+            RoleDecl::parse(state).map(SysctlCommand::DropRole)
+        } else if create {
             UserDecl::parse(state).map(SysctlCommand::CreateUser)
         } else if drop {
             UserDel::parse(state).map(SysctlCommand::DropUser)
         } else if alter {
             UserDecl::parse(state).map(SysctlCommand::AlterUser)
+        } else if backup {
+            BackupDecl::parse(state).map(SysctlCommand::Backup)
         } else {
             Ok(SysctlCommand::ReportStatus)
         }
@@ -171,3 +205,196 @@ impl<'a> UserDel<'a> {
         self.username.as_str()
     }
 }
+
+#[derive(Debug, PartialEq)]
+pub struct PrivilegeDecl<'a> {
+    privilege: Privilege,
+    space: Ident<'a>,
+    model: Option<Ident<'a>>,
+    grantee: Ident<'a>,
+    role: bool,
+}
+
+impl<'a> PrivilegeDecl<'a> {
+    pub(in crate::engine::ql) fn new(
+        privilege: Privilege,
+        space: Ident<'a>,
+        model: Option<Ident<'a>>,
+        grantee: Ident<'a>,
+        role: bool,
+    ) -> Self {
+        Self {
+            privilege,
+            space,
+            model,
+            grantee,
+            role,
+        }
+    }
+    fn parse_privilege(tok: &Token<'a>) -> Option<Privilege> {
+        Some(match tok {
+            Token![select] => Privilege::Select,
+            Token![insert] => Privilege::Insert,
+            Token![update] => Privilege::Update,
+            Token![delete] => Privilege::Delete,
+            tok if tok.ident_eq("ddl") => Privilege::Ddl,
+            _ => return None,
+        })
+    }
+    /// Parse a `grant` or `revoke` DCL command (`relation` is `to` for grants and `from` for revokes)
+    ///
+    /// MUSTENDSTREAM: YES
+    pub fn parse<Qd: QueryData<'a>>(
+        state: &mut State<'a, Qd>,
+        privilege: &Token<'a>,
+        relation: Token<'static>,
+    ) -> QueryResult<Self> {
+        /*
+            [privilege] on [space][.model] (to|from) [role] [username or role]
+                        ^cursor
+            4 to 7 tokens
+        */
+        let Some(privilege) = Self::parse_privilege(privilege) else {
+            return Err(QueryError::QLInvalidSyntax);
+        };
+        let token_buffer = state.current();
+        let (space, model, grantee) = match token_buffer {
+            [Token![on], Token::Ident(space), Token![.], Token::Ident(model), grantee @ ..] => {
+                (*space, Some(*model), grantee)
+            }
+            [Token![on], Token::Ident(space), grantee @ ..] => (*space, None, grantee),
+            _ => return Err(QueryError::QLInvalidSyntax),
+        };
+        let (grantee, role) = match grantee {
+            [tok_rel, Token::Ident(username)] if *tok_rel == relation => (*username, false),
+            [tok_rel, Token::Ident(kw), Token::Ident(role)]
+                if (*tok_rel == relation) & kw.eq_ignore_ascii_case("role") =>
+            {
+                (*role, true)
+            }
+            _ => return Err(QueryError::QLInvalidSyntax),
+        };
+        state.cursor_ahead_by(token_buffer.len());
+        Ok(Self::new(privilege, space, model, grantee, role))
+    }
+    pub fn privilege(&self) -> Privilege {
+        self.privilege
+    }
+    pub fn space(&self) -> &str {
+        self.space.as_str()
+    }
+    pub fn model(&self) -> Option<&str> {
+        self.model.as_ref().map(Ident::as_str)
+    }
+    /// The user or role that the privilege is granted to (or revoked from)
+    pub fn grantee(&self) -> Grantee<'_> {
+        if self.role {
+            Grantee::Role(self.grantee.as_str())
+        } else {
+            Grantee::User(self.grantee.as_str())
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RoleDecl<'a> {
+    role: Ident<'a>,
+}
+
+impl<'a> RoleDecl<'a> {
+    pub(in crate::engine::ql) fn new(role: Ident<'a>) -> Self {
+        Self { role }
+    }
+    /// Parse a `create role` or `drop role` DCL command
+    ///
+    /// MUSTENDSTREAM: YES
+    pub fn parse<Qd: QueryData<'a>>(state: &mut State<'a, Qd>) -> QueryResult<Self> {
+        if state.cursor_has_ident_rounded() & (state.remaining() == 1) {
+            let role = unsafe {
+                // UNSAFE(@ohsayan): +boundck
+                state.read().uck_read_ident()
+            };
+            state.cursor_ahead();
+            return Ok(Self::new(role));
+        }
+        Err(QueryError::QLInvalidSyntax)
+    }
+    pub fn role(&self) -> &str {
+        self.role.as_str()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RoleGrantDecl<'a> {
+    role: Ident<'a>,
+    username: Ident<'a>,
+}
+
+impl<'a> RoleGrantDecl<'a> {
+    pub(in crate::engine::ql) fn new(role: Ident<'a>, username: Ident<'a>) -> Self {
+        Self { role, username }
+    }
+    /// Parse a `grant role` or `revoke role` DCL command (`relation` is `to` for grants and `from` for revokes)
+    ///
+    /// MUSTENDSTREAM: YES
+    pub fn parse<Qd: QueryData<'a>>(
+        state: &mut State<'a, Qd>,
+        relation: Token<'static>,
+    ) -> QueryResult<Self> {
+        /*
+            role [role] (to|from) [username]
+                 ^cursor
+            3 tokens
+        */
+        match state.current() {
+            [Token::Ident(role), tok_rel, Token::Ident(username)] if *tok_rel == relation => {
+                let (role, username) = (*role, *username);
+                state.cursor_ahead_by(3);
+                Ok(Self::new(role, username))
+            }
+            _ => Err(QueryError::QLInvalidSyntax),
+        }
+    }
+    pub fn role(&self) -> &str {
+        self.role.as_str()
+    }
+    pub fn username(&self) -> &str {
+        self.username.as_str()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BackupDecl<'a> {
+    path: &'a str,
+}
+
+impl<'a> BackupDecl<'a> {
+    pub(in crate::engine::ql) fn new(path: &'a str) -> Self {
+        Self { path }
+    }
+    /// Parse a `backup` DCL command
+    ///
+    /// MUSTENDSTREAM: YES
+    pub fn parse<Qd: QueryData<'a>>(state: &mut State<'a, Qd>) -> QueryResult<Self> {
+        /*
+            backup to [path]
+                      ^cursor
+            1 token
+        */
+        if (state.remaining() == 1) & state.can_read_lit_rounded() {
+            let path = unsafe {
+                // UNSAFE(@ohsayan): +boundck
+                state.read_cursor_lit_unchecked()
+            };
+            state.cursor_ahead();
+            if let Some(path) = path.try_str() {
+                return Ok(Self::new(path));
+            }
+        }
+        Err(QueryError::QLInvalidSyntax)
+    }
+    /// The directory to back up into
+    pub fn path(&self) -> &str {
+        self.path
+    }
+}
@@ -180,9 +180,95 @@ impl<'a> CreateModel<'a> {
     }
 }
 
+#[derive(Debug, PartialEq)]
+/// A secondary index definition
+pub struct CreateIndex<'a> {
+    /// the index name
+    pub(in crate::engine) index_name: Ident<'a>,
+    /// the model
+    pub(in crate::engine) model_name: EntityIDRef<'a>,
+    /// the indexed field
+    pub(in crate::engine) field: Ident<'a>,
+    /// if every value can only be held by one row
+    pub(in crate::engine) unique: bool,
+    /// if not exists
+    pub(in crate::engine) if_not_exists: bool,
+}
+
+/*
+    index definition:
+    create [unique] index [if not exists] myindex on myspace.mymodel(myfield)
+*/
+
+impl<'a> CreateIndex<'a> {
+    #[cfg(test)]
+    pub fn new(
+        index_name: Ident<'a>,
+        model_name: EntityIDRef<'a>,
+        field: Ident<'a>,
+        unique: bool,
+        if_not_exists: bool,
+    ) -> Self {
+        Self {
+            index_name,
+            model_name,
+            field,
+            unique,
+            if_not_exists,
+        }
+    }
+    fn parse<Qd: QueryData<'a>>(state: &mut State<'a, Qd>) -> QueryResult<Self> {
+        // smallest declaration: `index myindex on mymodel(myfield)` -> >= 7 tokens
+        if compiler::unlikely(state.remaining() < 7) {
+            return compiler::cold_rerr(QueryError::QLUnexpectedEndOfStatement);
+        }
+        // unique? (not a keyword, since it's a common property name)
+        let unique = state.read().ident_eq("unique");
+        state.cursor_ahead_if(unique);
+        state.poison_if_not(state.cursor_eq(Token![index]));
+        state.cursor_ahead();
+        // if not exists?
+        let if_not_exists = sig_if_not_exists(state);
+        state.cursor_ahead_by(if_not_exists as usize * 3);
+        if compiler::unlikely(state.remaining() < 6) {
+            return compiler::cold_rerr(QueryError::QLUnexpectedEndOfStatement);
+        }
+        // index name
+        let index_name = state.fw_read();
+        state.poison_if_not(index_name.is_ident());
+        state.poison_if_not(state.cursor_eq(Token![on]));
+        state.cursor_ahead();
+        // model name; ignore errors
+        let model_uninit = state.try_entity_buffered_into_state_uninit();
+        // field
+        let has_field = Token![() open].eq(state.offset_current_r(0))
+            & state.offset_current_r(1).is_ident()
+            & Token![() close].eq(state.offset_current_r(2))
+            & state.has_remaining(3);
+        state.poison_if_not(has_field);
+        if state.okay() {
+            state.cursor_ahead();
+            let field = state.fw_read();
+            state.cursor_ahead();
+            unsafe {
+                // UNSAFE(@ohsayan): we verified both the idents and the entity through the state
+                Ok(Self {
+                    index_name: index_name.uck_read_ident(),
+                    model_name: model_uninit.assume_init(),
+                    field: field.uck_read_ident(),
+                    unique,
+                    if_not_exists,
+                })
+            }
+        } else {
+            Err(QueryError::QLInvalidSyntax)
+        }
+    }
+}
+
 mod impls {
     use {
-        super::{CreateModel, CreateSpace},
+        super::{CreateIndex, CreateModel, CreateSpace},
         crate::engine::{
             error::QueryResult,
             ql::ast::{traits::ASTNode, QueryData, State},
@@ -206,4 +292,13 @@ mod impls {
             Self::parse(state)
         }
     }
+    impl<'a> ASTNode<'a> for CreateIndex<'a> {
+        const MUST_USE_FULL_TOKEN_RANGE: bool = true;
+        const VERIFIES_FULL_TOKEN_RANGE_USAGE: bool = false;
+        fn __base_impl_parse_from_state<Qd: QueryData<'a>>(
+            state: &mut State<'a, Qd>,
+        ) -> QueryResult<Self> {
+            Self::parse(state)
+        }
+    }
 }
@@ -113,3 +113,65 @@ impl<'a> ASTNode<'a> for Inspect<'a> {
         Ok(me)
     }
 }
+
+#[derive(Debug, PartialEq)]
+pub enum Describe<'a> {
+    Space(Ident<'a>),
+    Model(EntityIDRef<'a>),
+}
+
+impl<'a> ASTNode<'a> for Describe<'a> {
+    const MUST_USE_FULL_TOKEN_RANGE: bool = true;
+    const VERIFIES_FULL_TOKEN_RANGE_USAGE: bool = false;
+    fn __base_impl_parse_from_state<Qd: QueryData<'a>>(
+        state: &mut State<'a, Qd>,
+    ) -> QueryResult<Self> {
+        if state.exhausted() {
+            return Err(QueryError::QLUnexpectedEndOfStatement);
+        }
+        let me = match state.fw_read() {
+            Token![space] => {
+                if state.exhausted() {
+                    return Err(QueryError::QLUnexpectedEndOfStatement);
+                }
+                match state.fw_read() {
+                    Token::Ident(space) => Self::Space(*space),
+                    _ => return Err(QueryError::QLInvalidSyntax),
+                }
+            }
+            Token![model] => {
+                let entity = state.try_entity_ref_result()?;
+                Self::Model(entity)
+            }
+            _ => return Err(QueryError::QLInvalidSyntax),
+        };
+        Ok(me)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+/// A `truncate model` query: `truncate model myspace.mymodel`
+pub struct TruncateModel<'a> {
+    pub(in crate::engine) entity: EntityIDRef<'a>,
+}
+
+impl<'a> TruncateModel<'a> {
+    #[inline(always)]
+    pub fn new(entity: EntityIDRef<'a>) -> Self {
+        Self { entity }
+    }
+}
+
+impl<'a> ASTNode<'a> for TruncateModel<'a> {
+    const MUST_USE_FULL_TOKEN_RANGE: bool = true;
+    const VERIFIES_FULL_TOKEN_RANGE_USAGE: bool = false;
+    fn __base_impl_parse_from_state<Qd: QueryData<'a>>(
+        state: &mut State<'a, Qd>,
+    ) -> QueryResult<Self> {
+        if state.exhausted() {
+            return Err(QueryError::QLUnexpectedEndOfStatement);
+        }
+        let entity = state.try_entity_ref_result()?;
+        Ok(Self::new(entity))
+    }
+}
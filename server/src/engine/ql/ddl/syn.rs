@@ -52,6 +52,7 @@ use crate::{
         },
         error::{QueryError, QueryResult},
         ql::{
+            self,
             ast::{QueryData, State},
             lex::{Ident, Token},
         },
@@ -129,7 +130,8 @@ where
         <closebrace> ::= "}"
         <comma> ::= ","
         <colon> ::= ":"
-        <dict> ::= <openbrace> (<ident> <colon> (<lit> | <dict>) <comma>)* <comma>* <closebrace>
+        <producer> ::= "@" <ident> "(" ")"
+        <dict> ::= <openbrace> ((<ident> | "default" | "index") <colon> (<lit> | <list> | <dict> | <producer>) <comma>)* <comma>* <closebrace>
     */
     let mut key = MaybeInit::uninit();
     while state.loop_tt() {
@@ -148,6 +150,16 @@ where
                 // found a key, now expect colon
                 mstate = DictFoldState::COLON;
             }
+            (Token![default], DictFoldState::CB_OR_IDENT) => {
+                // `default` is a keyword, but we want to be able to use it as a key
+                key = MaybeInit::new(Ident::from("default"));
+                mstate = DictFoldState::COLON;
+            }
+            (Token![index], DictFoldState::CB_OR_IDENT) => {
+                // same for `index` (a model's `index` property)
+                key = MaybeInit::new(Ident::from("index"));
+                mstate = DictFoldState::COLON;
+            }
             (Token![:], DictFoldState::COLON) => {
                 // found colon, now lit or ob
                 mstate = DictFoldState::LIT_OR_OB;
@@ -202,6 +214,43 @@ where
                 );
                 mstate = DictFoldState::COMMA_OR_CB;
             }
+            (Token![@], DictFoldState::LIT_OR_OB)
+                if state.cursor_signature_match_fn_arity0_rounded() =>
+            {
+                // found a producer function; it isn't called here since its value is needed later on
+                let func = unsafe {
+                    // UNSAFE(@ohsayan): verified at guard
+                    state.fw_read().uck_read_ident()
+                };
+                state.cursor_ahead_by(2); // skip tt:paren
+                state.poison_if_not(
+                    dict.insert(
+                        unsafe {
+                            // UNSAFE(@ohsayan): we're in an expression position so the key was read in
+                            key.take().as_str().into()
+                        },
+                        DictEntryGeneric::Producer(func.as_str().into()),
+                    )
+                    .is_none(),
+                );
+                mstate = DictFoldState::COMMA_OR_CB;
+            }
+            (Token![open []], DictFoldState::LIT_OR_OB) => {
+                // found a list
+                let mut list = Vec::new();
+                let _ = ql::dml::ins::parse_list(state, &mut list);
+                state.poison_if_not(
+                    dict.insert(
+                        unsafe {
+                            // UNSAFE(@ohsayan): same as above; we're in an expression position so the key was read in
+                            key.take().as_str().into()
+                        },
+                        DictEntryGeneric::Data(Datacell::new_list(list)),
+                    )
+                    .is_none(),
+                );
+                mstate = DictFoldState::COMMA_OR_CB;
+            }
             (Token![,], DictFoldState::COMMA_OR_CB) => {
                 // expecting a comma, found it. now expect a close brace or an ident
                 mstate = DictFoldState::CB_OR_IDENT;
@@ -27,10 +27,10 @@
 #[cfg(test)]
 use super::WhereClauseCollection;
 use {
-    super::WhereClause,
+    super::{ReturningClause, WhereClause},
     crate::{
         engine::{
-            core::EntityIDRef,
+            core::{model::Durability, EntityIDRef},
             error::{QueryError, QueryResult},
             ql::ast::{QueryData, State},
         },
@@ -43,18 +43,32 @@ use {
     ---
     Smallest statement:
     delete model:primary_key
+    With conditions:
+    delete from model where primary_key = 1 if version = 2
 */
 
 #[derive(Debug, PartialEq)]
 pub struct DeleteStatement<'a> {
     pub(super) entity: EntityIDRef<'a>,
     pub(super) wc: WhereClause<'a>,
+    /// the durability for the write, if set with `with durability`
+    pub(super) durability: Option<Durability>,
+    pub(super) returning: Option<ReturningClause<'a>>,
 }
 
 impl<'a> DeleteStatement<'a> {
     pub const fn entity(&self) -> EntityIDRef<'a> {
         self.entity
     }
+    pub fn durability(&self) -> Option<Durability> {
+        self.durability
+    }
+    pub fn returning(&self) -> Option<&ReturningClause<'a>> {
+        self.returning.as_ref()
+    }
+    pub fn take_returning(&mut self) -> Option<ReturningClause<'a>> {
+        self.returning.take()
+    }
     pub fn clauses_mut(&mut self) -> &mut WhereClause<'a> {
         &mut self.wc
     }
@@ -64,7 +78,26 @@ impl<'a> DeleteStatement<'a> {
     #[inline(always)]
     #[cfg(test)]
     pub(super) fn new(entity: EntityIDRef<'a>, wc: WhereClause<'a>) -> Self {
-        Self { entity, wc }
+        Self {
+            entity,
+            wc,
+            durability: None,
+            returning: None,
+        }
+    }
+    #[cfg(test)]
+    pub fn with_durability(self, durability: Durability) -> Self {
+        Self {
+            durability: Some(durability),
+            ..self
+        }
+    }
+    #[cfg(test)]
+    pub fn with_returning(self, returning: ReturningClause<'a>) -> Self {
+        Self {
+            returning: Some(returning),
+            ..self
+        }
     }
     #[inline(always)]
     #[cfg(test)]
@@ -89,7 +122,13 @@ impl<'a> DeleteStatement<'a> {
         // where + clauses
         state.poison_if_not(state.cursor_eq(Token![where]));
         state.cursor_ahead(); // ignore errors
-        let wc = WhereClause::parse_where(state);
+        let mut wc = WhereClause::parse_where(state);
+        // conditions
+        if state.cursor_rounded_eq(Token![if]) {
+            WhereClause::parse_if_and_append_to(state, &mut wc);
+        }
+        let durability = super::parse_durability_if_present(state);
+        let returning = ReturningClause::parse_if_present(state);
         if compiler::likely(state.okay()) {
             Ok(Self {
                 entity: unsafe {
@@ -97,6 +136,8 @@ impl<'a> DeleteStatement<'a> {
                     entity.assume_init()
                 },
                 wc,
+                durability,
+                returning,
             })
         } else {
             compiler::cold_rerr(QueryError::QLInvalidSyntax)
@@ -25,10 +25,11 @@
 */
 
 use {
+    super::ReturningClause,
     crate::{
         engine::{
             core::EntityIDRef,
-            data::cell::Datacell,
+            data::{cell::Datacell, dict::DataDict},
             error::{QueryError, QueryResult},
             ql::{
                 ast::{QueryData, State},
@@ -51,7 +52,7 @@ use {
 pub const T_UUIDSTR: &str = "4593264b-0231-43e9-b0aa-50784f14e204";
 pub const T_TIMESEC: u64 = 1673187839_u64;
 
-type ProducerFn = fn() -> Datacell;
+pub(in crate::engine) type ProducerFn = fn() -> Datacell;
 
 // base
 #[inline(always)]
@@ -83,12 +84,22 @@ fn pfn_uuidstr() -> Datacell {
 fn pfn_uuidbin() -> Datacell {
     Datacell::new_bin(pfnbase_uuid().as_bytes().to_vec().into_boxed_slice())
 }
+#[inline(always)]
+fn pfn_now() -> Datacell {
+    Datacell::new_timestamp(pfnbase_time().as_nanos() as u64)
+}
+#[inline(always)]
+fn pfn_uuid() -> Datacell {
+    Datacell::new_uuid(pfnbase_uuid().as_u128())
+}
 
-static PRODUCER_G: [u8; 4] = [0, 2, 3, 0];
-static PRODUCER_F: [(&[u8], ProducerFn); 3] = [
+static PRODUCER_G: [u8; 9] = [0, 0, 8, 0, 4, 0, 1, 1, 3];
+static PRODUCER_F: [(&[u8], ProducerFn); 5] = [
     (b"uuidstr", pfn_uuidstr),
     (b"uuidbin", pfn_uuidbin),
     (b"timesec", pfn_timesec),
+    (b"now", pfn_now),
+    (b"uuid", pfn_uuid),
 ];
 const MAGIC_1: [u8; 7] = *b"cp21rLd";
 const MAGIC_2: [u8; 7] = *b"zS8zgaK";
@@ -122,9 +133,14 @@ fn ldfunc(func: Ident<'_>) -> Option<ProducerFn> {
     }
 }
 
+/// Returns the producer function with the given name (for example, `timesec` for `@timesec()`)
+pub(in crate::engine) fn producer(name: &str) -> Option<ProducerFn> {
+    ldfunc(Ident::from(name))
+}
+
 /// ## Panics
 /// - If tt length is less than 1
-pub(super) fn parse_list<'a, Qd: QueryData<'a>>(
+pub(in crate::engine::ql) fn parse_list<'a, Qd: QueryData<'a>>(
     state: &mut State<'a, Qd>,
     list: &mut Vec<Datacell>,
 ) -> Option<TagClass> {
@@ -155,6 +171,11 @@ pub(super) fn parse_list<'a, Qd: QueryData<'a>>(
                 }
                 Datacell::new_list(nested_list)
             }
+            Token![open {}] if state.not_exhausted() => {
+                let mut dict = DataDict::new();
+                parse_dict(state, &mut dict);
+                Datacell::new_dict(dict)
+            }
             Token![@] if state.cursor_signature_match_fn_arity0_rounded() => match unsafe {
                 // UNSAFE(@ohsayan): Just verified at guard
                 handle_func_sub(state)
@@ -183,6 +204,62 @@ pub(super) fn parse_list<'a, Qd: QueryData<'a>>(
     overall_dscr
 }
 
+/// Parse a dict literal (`{key: value, ...}`). The values can be literals, nulls, lists, dicts or producers
+///
+/// ## Panics
+/// - If tt length is less than 1
+fn parse_dict<'a, Qd: QueryData<'a>>(state: &mut State<'a, Qd>, dict: &mut DataDict) {
+    let mut stop = state.cursor_eq(Token![close {}]);
+    state.cursor_ahead_if(stop);
+    while state.has_remaining(3) && state.okay() && !stop {
+        let key = state.fw_read();
+        let colon = state.fw_read();
+        let expr = state.fw_read();
+        state.poison_if_not(Token![:].eq(colon));
+        let (key, value) = match (key, expr) {
+            (Token::Ident(id), tok) if state.can_read_lit_from(tok) => (id, unsafe {
+                // UNSAFE(@ohsayan): The if guard guarantees correctness
+                state.read_lit_into_data_type_unchecked_from(tok)
+            }),
+            (Token::Ident(id), Token![null]) => (id, Datacell::null()),
+            (Token::Ident(id), Token![open []]) if state.not_exhausted() => {
+                let mut l = Vec::new();
+                let _ = parse_list(state, &mut l);
+                (id, l.into())
+            }
+            (Token::Ident(id), Token![open {}]) if state.not_exhausted() => {
+                let mut nested = DataDict::new();
+                parse_dict(state, &mut nested);
+                (id, Datacell::new_dict(nested))
+            }
+            (Token::Ident(id), Token![@]) if state.cursor_signature_match_fn_arity0_rounded() => {
+                match unsafe {
+                    // UNSAFE(@ohsayan): Just verified at guard
+                    handle_func_sub(state)
+                } {
+                    Some(value) => (id, value),
+                    None => {
+                        state.poison();
+                        break;
+                    }
+                }
+            }
+            _ => {
+                state.cursor_back_by(3);
+                state.poison();
+                break;
+            }
+        };
+        state.poison_if_not(dict.insert(key.boxed_str(), value).is_none());
+        let nx_comma = state.cursor_rounded_eq(Token![,]);
+        let nx_csbrc = state.cursor_rounded_eq(Token![close {}]);
+        state.poison_if_not(nx_comma | nx_csbrc);
+        state.cursor_ahead_if(state.okay());
+        stop = nx_csbrc;
+    }
+    state.poison_if_not(stop);
+}
+
 #[inline(always)]
 /// ## Safety
 /// - Cursor must match arity(0) function signature
@@ -211,6 +288,11 @@ pub(super) fn parse_data_tuple_syntax<'a, Qd: QueryData<'a>>(
                 let _ = parse_list(state, &mut l);
                 data.push(l.into());
             }
+            Token![open {}] if state.not_exhausted() => {
+                let mut d = DataDict::new();
+                parse_dict(state, &mut d);
+                data.push(d.into());
+            }
             Token![null] => data.push(Datacell::null()),
             Token![@] if state.cursor_signature_match_fn_arity0_rounded() => match unsafe {
                 // UNSAFE(@ohsayan): Just verified at guard
@@ -266,6 +348,11 @@ pub(super) fn parse_data_map_syntax<'a, Qd: QueryData<'a>>(
                 let _ = parse_list(state, &mut l);
                 state.poison_if_not(data.insert(*id, l.into()).is_none());
             }
+            (Token::Ident(id), Token![open {}]) if state.not_exhausted() => {
+                let mut d = DataDict::new();
+                parse_dict(state, &mut d);
+                state.poison_if_not(data.insert(*id, d.into()).is_none());
+            }
             (Token::Ident(id), Token![@]) if state.cursor_signature_match_fn_arity0_rounded() => {
                 match unsafe {
                     // UNSAFE(@ohsayan): Just verified at guard
@@ -323,24 +410,118 @@ impl<'a> From<HashMap<Ident<'static>, Datacell>> for InsertData<'a> {
 #[derive(Debug, PartialEq)]
 pub struct InsertStatement<'a> {
     pub(super) entity: EntityIDRef<'a>,
-    pub(super) data: InsertData<'a>,
+    pub(super) data: Vec<InsertData<'a>>,
+    /// the time-to-live for the rows (in seconds), if set with `with ttl`
+    pub(super) ttl: Option<u64>,
+    /// the durability for the write, if set with `with durability`
+    pub(super) durability: Option<Durability>,
+    pub(super) returning: Option<ReturningClause<'a>>,
 }
 
 impl<'a> InsertStatement<'a> {
     #[inline(always)]
     #[cfg(test)]
     pub fn new(entity: EntityIDRef<'a>, data: InsertData<'a>) -> Self {
-        Self { entity, data }
+        Self::new_bulk(entity, vec![data])
+    }
+    #[inline(always)]
+    #[cfg(test)]
+    pub fn new_bulk(entity: EntityIDRef<'a>, data: Vec<InsertData<'a>>) -> Self {
+        Self {
+            entity,
+            data,
+            ttl: None,
+            durability: None,
+            returning: None,
+        }
+    }
+    #[cfg(test)]
+    pub fn with_ttl(self, ttl: u64) -> Self {
+        Self {
+            ttl: Some(ttl),
+            ..self
+        }
+    }
+    #[cfg(test)]
+    pub fn with_durability(self, durability: Durability) -> Self {
+        Self {
+            durability: Some(durability),
+            ..self
+        }
+    }
+    #[cfg(test)]
+    pub fn with_returning(self, returning: ReturningClause<'a>) -> Self {
+        Self {
+            returning: Some(returning),
+            ..self
+        }
     }
     pub fn entity(&self) -> EntityIDRef<'a> {
         self.entity
     }
-    pub fn data(self) -> InsertData<'a> {
+    pub fn ttl(&self) -> Option<u64> {
+        self.ttl
+    }
+    pub fn durability(&self) -> Option<Durability> {
+        self.durability
+    }
+    pub fn returning(&self) -> Option<&ReturningClause<'a>> {
+        self.returning.as_ref()
+    }
+    pub fn take_returning(&mut self) -> Option<ReturningClause<'a>> {
+        self.returning.take()
+    }
+    /// Returns the rows to be inserted (there's always at least one)
+    pub fn data(self) -> Vec<InsertData<'a>> {
         self.data
     }
 }
 
 impl<'a> InsertStatement<'a> {
+    /// Parse a `with` clause with the (comma separated) `ttl <seconds>` and `durability "<mode>"` options, if present
+    fn parse_with_if_present<Qd: QueryData<'a>>(
+        state: &mut State<'a, Qd>,
+    ) -> (Option<u64>, Option<Durability>) {
+        let (mut ttl, mut durability) = (None, None);
+        if !(state.okay() && state.has_remaining(3) && state.cursor_rounded_eq(Token![with])) {
+            return (ttl, durability);
+        }
+        state.cursor_ahead();
+        let mut more = true;
+        while more && state.okay() {
+            // every option has a name and a value, and can only be set once
+            state.poison_if_not(state.has_remaining(2));
+            if !state.okay() {
+                break;
+            }
+            if state.read().ident_eq("ttl") & ttl.is_none() {
+                ttl = Self::parse_ttl_option(state);
+            } else if state.read().ident_eq("durability") & durability.is_none() {
+                durability = super::parse_durability_option(state);
+            } else {
+                state.poison();
+            }
+            more = state.cursor_rounded_eq(Token![,]);
+            state.cursor_ahead_if(more);
+        }
+        (ttl, durability)
+    }
+    /// Parse a `ttl <seconds>` option of a `with` clause. The cursor must be at the option
+    fn parse_ttl_option<Qd: QueryData<'a>>(state: &mut State<'a, Qd>) -> Option<u64> {
+        state.cursor_ahead();
+        state.poison_if_not(state.can_read_lit_rounded());
+        if !state.okay() {
+            return None;
+        }
+        let ttl = unsafe {
+            // UNSAFE(@ohsayan): checked lit
+            state.read_cursor_lit_unchecked()
+        };
+        state.cursor_ahead();
+        let ttl = ttl.try_uint();
+        state.poison_if(ttl.is_none());
+        ttl
+    }
     pub fn parse_insert<Qd: QueryData<'a>>(state: &mut State<'a, Qd>) -> QueryResult<Self> {
         /*
             smallest:
@@ -355,31 +536,40 @@ impl<'a> InsertStatement<'a> {
 
         // entity
         let entity = state.try_entity_buffered_into_state_uninit();
-        let mut data = None;
-        match state.fw_read() {
-            Token![() open] if state.not_exhausted() => {
-                let this_data = parse_data_tuple_syntax(state);
-                data = Some(InsertData::Ordered(this_data));
-            }
-            Token![open {}] if state.not_exhausted() => {
-                let this_data = parse_data_map_syntax(state);
-                data = Some(InsertData::Map(this_data));
-            }
-            _ => {
-                state.poison();
+        // rows (more than one for a bulk insert: `(..), (..)`)
+        let mut data = Vec::with_capacity(1);
+        let mut more = true;
+        while more && state.okay() {
+            match state.fw_read() {
+                Token![() open] if state.not_exhausted() => {
+                    let this_data = parse_data_tuple_syntax(state);
+                    data.push(InsertData::Ordered(this_data));
+                }
+                Token![open {}] if state.not_exhausted() => {
+                    let this_data = parse_data_map_syntax(state);
+                    data.push(InsertData::Map(this_data));
+                }
+                _ => {
+                    state.poison();
+                }
             }
+            more = state.cursor_rounded_eq(Token![,]);
+            state.cursor_ahead_if(more);
+            // a trailing comma must be followed by another row
+            state.poison_if(more & state.exhausted());
         }
+        let (ttl, durability) = Self::parse_with_if_present(state);
+        let returning = ReturningClause::parse_if_present(state);
         if state.okay() {
-            let data = unsafe {
-                // UNSAFE(@ohsayan): state's flag guarantees correctness (see wildcard branch)
-                data.unwrap_unchecked()
-            };
             Ok(InsertStatement {
                 entity: unsafe {
                     // UNSAFE(@ohsayan): state's flag ensures correctness (see Entity::parse_entity)
                     entity.assume_init()
                 },
                 data,
+                ttl,
+                durability,
+                returning,
             })
         } else {
             compiler::cold_rerr(QueryError::QLInvalidSyntax)
@@ -32,15 +32,23 @@
 pub mod del;
 pub mod ins;
 pub mod sel;
+pub mod sub;
 pub mod upd;
 
 use {
     super::{
         ast::{QueryData, State},
-        lex::Ident,
+        lex::{Ident, Token},
+    },
+    crate::{
+        engine::{core::model::Durability, data::lit::Lit},
+        util::compiler,
+    },
+    std::{
+        cmp::Ordering,
+        collections::{hash_map::Entry, HashMap},
+        ops::Bound,
     },
-    crate::{engine::data::lit::Lit, util::compiler},
-    std::collections::HashMap,
 };
 
 #[inline(always)]
@@ -52,6 +60,80 @@ fn u(b: bool) -> u8 {
     Misc
 */
 
+#[derive(Debug, PartialEq)]
+/// The fields returned by a DML query: `returning a, b` or `returning *` (`return` is also accepted). Inserts and
+/// updates return the new version of the row while deletes return the removed row
+pub struct ReturningClause<'a> {
+    /// fields in order of returning. will be empty when wildcard is set
+    fields: Vec<Ident<'a>>,
+    wildcard: bool,
+}
+
+impl<'a> ReturningClause<'a> {
+    #[cfg(test)]
+    pub fn new(fields: Vec<Ident<'a>>, wildcard: bool) -> Self {
+        Self { fields, wildcard }
+    }
+    pub fn fields(&self) -> &[Ident<'a>] {
+        &self.fields
+    }
+    pub fn is_wildcard(&self) -> bool {
+        self.wildcard
+    }
+    /// Parse a returning clause if the cursor is at one. The clause always ends the statement
+    pub(super) fn parse_if_present<Qd: QueryData<'a>>(state: &mut State<'a, Qd>) -> Option<Self> {
+        let is_returning = state.not_exhausted()
+            && (state.cursor_eq(Token![return]) || state.read().ident_eq("returning"));
+        if !is_returning {
+            return None;
+        }
+        state.cursor_ahead();
+        let wildcard = state.cursor_rounded_eq(Token![*]);
+        state.cursor_ahead_if(wildcard);
+        let mut fields = Vec::new();
+        let mut has_more = !wildcard;
+        while has_more && state.not_exhausted() && state.okay() {
+            match state.fw_read() {
+                Token::Ident(id) => fields.push(*id),
+                _ => state.poison(),
+            }
+            has_more = state.cursor_rounded_eq(Token![,]);
+            state.cursor_ahead_if(has_more);
+        }
+        // no fields or a trailing comma
+        state.poison_if(has_more);
+        Some(Self { fields, wildcard })
+    }
+}
+
+/// Parse a `durability "<mode>"` option of a `with` clause. The cursor must be at the option
+fn parse_durability_option<'a, Qd: QueryData<'a>>(state: &mut State<'a, Qd>) -> Option<Durability> {
+    state.poison_if_not(state.fw_read().ident_eq("durability"));
+    state.poison_if_not(state.can_read_lit_rounded());
+    if !state.okay() {
+        return None;
+    }
+    let durability = unsafe {
+        // UNSAFE(@ohsayan): checked lit
+        state.read_cursor_lit_unchecked()
+    };
+    state.cursor_ahead();
+    let durability = durability.try_str().and_then(Durability::from_name);
+    state.poison_if(durability.is_none());
+    durability
+}
+
+/// Parse a `with durability "<mode>"` clause (that overrides the model's durability for a single write), if present
+fn parse_durability_if_present<'a, Qd: QueryData<'a>>(
+    state: &mut State<'a, Qd>,
+) -> Option<Durability> {
+    if !(state.okay() && state.has_remaining(3) && state.cursor_rounded_eq(Token![with])) {
+        return None;
+    }
+    state.cursor_ahead();
+    parse_durability_option(state)
+}
+
 /*
     Contexts
 */
@@ -74,12 +156,50 @@ impl<'a> RelationalExpr<'a> {
     pub(super) const OP_GE: u8 = 4;
     pub(super) const OP_LT: u8 = 5;
     pub(super) const OP_LE: u8 = 6;
+    pub(super) const OP_CONTAINS: u8 = 7;
     pub fn filter_hint_none(&self) -> bool {
         self.opc == Self::OP_EQ
     }
+    /// Returns true if this checks if the lhs (a list) contains the rhs
+    pub fn is_contains(&self) -> bool {
+        self.opc == Self::OP_CONTAINS
+    }
     pub fn rhs(&self) -> Lit<'a> {
         self.rhs.clone()
     }
+    pub fn lhs(&self) -> Ident<'a> {
+        self.lhs
+    }
+    /// Returns true if the result of comparing `lhs` against `rhs` satisfies this expression's operator
+    pub fn eval_ordering(&self, ord: Ordering) -> bool {
+        match self.opc {
+            Self::OP_EQ => ord == Ordering::Equal,
+            Self::OP_NE => ord != Ordering::Equal,
+            Self::OP_GT => ord == Ordering::Greater,
+            Self::OP_GE => ord != Ordering::Less,
+            Self::OP_LT => ord == Ordering::Less,
+            Self::OP_LE => ord != Ordering::Greater,
+            _ => unreachable!("illegal operator"),
+        }
+    }
+    /// If this is a range predicate, returns the bound that it places on the lhs along with `true` if it is
+    /// a lower bound or `false` if it is an upper bound
+    pub fn range_bound(&self) -> Option<(bool, Bound<Lit<'a>>)> {
+        match self.opc {
+            Self::OP_GT => Some((true, Bound::Excluded(self.rhs()))),
+            Self::OP_GE => Some((true, Bound::Included(self.rhs()))),
+            Self::OP_LT => Some((false, Bound::Excluded(self.rhs()))),
+            Self::OP_LE => Some((false, Bound::Included(self.rhs()))),
+            _ => None,
+        }
+    }
+    /// Returns true if `self` and `other` bound the same column from opposite sides
+    fn forms_range_with(&self, other: &Self) -> bool {
+        match (self.range_bound(), other.range_bound()) {
+            (Some((a, _)), Some((b, _))) => a != b,
+            _ => false,
+        }
+    }
     #[inline(always)]
     fn parse_operator<Qd: QueryData<'a>>(state: &mut State<'a, Qd>) -> u8 {
         let tok = state.current();
@@ -89,7 +209,8 @@ impl<'a> RelationalExpr<'a> {
         let op_gt = u(tok[0] == Token![>] && op_ge == 0) * Self::OP_GT;
         let op_le = u(tok[0] == Token![<] && tok[1] == Token![=]) * Self::OP_LE;
         let op_lt = u(tok[0] == Token![<] && op_le == 0) * Self::OP_LT;
-        let opc = op_eq + op_ne + op_ge + op_gt + op_le + op_lt;
+        let op_contains = u(tok[0].ident_eq("contains")) * Self::OP_CONTAINS;
+        let opc = op_eq + op_ne + op_ge + op_gt + op_le + op_lt + op_contains;
         state.poison_if_not(opc != 0);
         state.cursor_ahead_by(1 + (opc & 1 == 0) as usize);
         opc
@@ -118,45 +239,170 @@ impl<'a> RelationalExpr<'a> {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
 pub struct WhereClause<'a> {
     c: WhereClauseCollection<'a>,
+    /// the second half of range predicates (`a > 1 and a < 10`), keyed by column
+    r: WhereClauseCollection<'a>,
+    /// membership predicates (`a in (1, 2, 3)`), keyed by column. only parsed when explicitly allowed
+    m: HashMap<Ident<'a>, Vec<Lit<'a>>>,
 }
 
 type WhereClauseCollection<'a> = HashMap<Ident<'a>, RelationalExpr<'a>>;
 
 impl<'a> WhereClause<'a> {
     #[inline(always)]
+    #[cfg(test)]
     pub(super) fn new(c: WhereClauseCollection<'a>) -> Self {
-        Self { c }
+        Self {
+            c,
+            r: HashMap::new(),
+            m: HashMap::new(),
+        }
+    }
+    #[cfg(test)]
+    pub(super) fn new_with_ranges(
+        c: WhereClauseCollection<'a>,
+        r: WhereClauseCollection<'a>,
+    ) -> Self {
+        Self {
+            c,
+            r,
+            m: HashMap::new(),
+        }
     }
     pub fn clauses_mut(&mut self) -> &mut WhereClauseCollection<'a> {
         &mut self.c
     }
+    pub fn range_clauses_mut(&mut self) -> &mut WhereClauseCollection<'a> {
+        &mut self.r
+    }
+    pub(super) fn is_empty(&self) -> bool {
+        self.c.is_empty()
+    }
+    /// Parse a membership predicate (`a in (1, 2, 3)`); there must be at least one value
+    fn parse_in<Qd: QueryData<'a>>(state: &mut State<'a, Qd>) -> Option<(Ident<'a>, Vec<Lit<'a>>)> {
+        /*
+            a in ( v1 , v2 )
+            ^1 ^2^3^4
+        */
+        if compiler::unlikely(state.remaining() < 5) {
+            state.poison();
+            return None;
+        }
+        let ident = match state.fw_read() {
+            Token::Ident(id) => *id,
+            _ => {
+                state.poison();
+                return None;
+            }
+        };
+        state.cursor_ahead(); // skip in
+        state.poison_if_not(state.cursor_eq(Token![() open]));
+        state.cursor_ahead();
+        let mut values = Vec::new();
+        let mut stop = false;
+        while state.not_exhausted() && state.okay() && !stop {
+            state.poison_if_not(state.can_read_lit_rounded());
+            if state.okay() {
+                values.push(unsafe {
+                    // UNSAFE(@ohsayan): verified above
+                    state.read_cursor_lit_unchecked()
+                });
+                state.cursor_ahead();
+            }
+            let nx_comma = state.cursor_rounded_eq(Token![,]);
+            let nx_close = state.cursor_rounded_eq(Token![() close]);
+            state.poison_if_not(nx_comma | nx_close);
+            state.cursor_ahead_if(state.okay());
+            stop = nx_close;
+        }
+        state.poison_if_not(stop);
+        state.okay().then_some((ident, values))
+    }
     #[inline(always)]
     fn parse_where_and_append_to<Qd: QueryData<'a>>(
         state: &mut State<'a, Qd>,
-        c: &mut WhereClauseCollection<'a>,
+        wc: &mut Self,
+        allow_in: bool,
     ) {
         let mut has_more = true;
         while has_more && state.not_exhausted() && state.okay() {
-            if let Some(expr) = RelationalExpr::try_parse(state) {
-                state.poison_if_not(c.insert(expr.lhs, expr).is_none());
+            let is_in = allow_in && state.has_remaining(2) && state.current()[1] == Token![in];
+            if is_in {
+                if let Some((ident, values)) = Self::parse_in(state) {
+                    // a column can only have one membership predicate and can't be mixed with other predicates
+                    state.poison_if(
+                        wc.c.contains_key(&ident) | wc.m.insert(ident, values).is_some(),
+                    );
+                }
+            } else if let Some(expr) = RelationalExpr::try_parse(state) {
+                state.poison_if(wc.m.contains_key(&expr.lhs));
+                match wc.c.entry(expr.lhs) {
+                    Entry::Vacant(ve) => {
+                        ve.insert(expr);
+                    }
+                    Entry::Occupied(oe) => {
+                        // a column can only be repeated if the two predicates form a range
+                        state.poison_if_not(
+                            oe.get().forms_range_with(&expr)
+                                && wc.r.insert(expr.lhs, expr).is_none(),
+                        );
+                    }
+                }
             }
             has_more = state.cursor_rounded_eq(Token![and]);
             state.cursor_ahead_if(has_more);
         }
     }
+    /// Parse the conditions of an `if` clause (`if x = 1 and y = 2`) which are added to the clauses (they must hold
+    /// for the row to be changed). The cursor must be at the `if` and there must be at least one condition
+    pub(super) fn parse_if_and_append_to<Qd: QueryData<'a>>(
+        state: &mut State<'a, Qd>,
+        wc: &mut Self,
+    ) {
+        let clause_count = wc.c.len() + wc.r.len();
+        state.cursor_ahead();
+        Self::parse_where_and_append_to(state, wc, false);
+        state.poison_if(wc.c.len() + wc.r.len() == clause_count);
+    }
     #[inline(always)]
     /// Parse a where context
     ///
     /// Notes:
     /// - Enforce a minimum of 1 clause
     pub(super) fn parse_where<Qd: QueryData<'a>>(state: &mut State<'a, Qd>) -> Self {
-        let mut c = HashMap::with_capacity(2);
-        Self::parse_where_and_append_to(state, &mut c);
-        state.poison_if(c.is_empty());
-        Self { c }
+        let mut wc = Self::default();
+        Self::parse_where_and_append_to(state, &mut wc, false);
+        state.poison_if(wc.is_empty());
+        wc
+    }
+    /// Parse a where context that can also have membership predicates (`a in (1, 2, 3)`)
+    ///
+    /// Notes:
+    /// - Enforce a minimum of 1 clause
+    pub(super) fn parse_where_with_in<Qd: QueryData<'a>>(state: &mut State<'a, Qd>) -> Self {
+        let mut wc = Self::default();
+        Self::parse_where_and_append_to(state, &mut wc, true);
+        state.poison_if(wc.c.is_empty() & wc.m.is_empty());
+        wc
+    }
+    /// Returns the only predicate in this clause, if it is an equality (`a = 1`) or a membership (`a in (1, 2)`)
+    /// predicate along with `true` if it was a membership predicate
+    pub(super) fn into_single_eq_or_in(mut self) -> Option<(Ident<'a>, Vec<Lit<'a>>, bool)> {
+        match (self.c.len(), self.r.len(), self.m.len()) {
+            (1, 0, 0) => {
+                let (_, expr) = self.c.drain().next().unwrap();
+                expr.filter_hint_none()
+                    .then(|| (expr.lhs, vec![expr.rhs], false))
+            }
+            (0, 0, 1) => self
+                .m
+                .drain()
+                .next()
+                .map(|(lhs, values)| (lhs, values, true)),
+            _ => None,
+        }
     }
 }
 
@@ -31,6 +31,7 @@ use {
     crate::{
         engine::{
             core::EntityIDRef,
+            data::lit::Lit,
             error::{QueryError, QueryResult},
             ql::{
                 ast::{QueryData, State},
@@ -53,10 +54,86 @@ pub struct SelectStatement<'a> {
     pub(super) fields: Vec<Ident<'a>>,
     /// whether a wildcard was passed
     pub(super) wildcard: bool,
+    /// aggregates in order of querying. can't be used with fields or a wildcard
+    pub(super) aggregates: Vec<Aggregate<'a>>,
     /// where clause
     pub(super) clause: WhereClause<'a>,
 }
 
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// An aggregate function
+pub enum AggregateFn {
+    Count,
+    Sum,
+    Min,
+    Max,
+    Avg,
+}
+
+impl AggregateFn {
+    fn from_ident(id: Ident) -> Option<Self> {
+        [
+            ("count", Self::Count),
+            ("sum", Self::Sum),
+            ("min", Self::Min),
+            ("max", Self::Max),
+            ("avg", Self::Avg),
+        ]
+        .into_iter()
+        .find_map(|(name, f)| id.eq_ignore_ascii_case(name).then_some(f))
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// An aggregate (`fn(field)`, or `count(*)`) in a select
+pub struct Aggregate<'a> {
+    func: AggregateFn,
+    /// the field; `None` if the wildcard was passed
+    field: Option<Ident<'a>>,
+}
+
+impl<'a> Aggregate<'a> {
+    pub const fn new(func: AggregateFn, field: Option<Ident<'a>>) -> Self {
+        Self { func, field }
+    }
+    pub fn func(&self) -> AggregateFn {
+        self.func
+    }
+    pub fn field(&self) -> Option<Ident<'a>> {
+        self.field
+    }
+    /// Parse the arguments, after the open paren
+    fn parse_args<Qd: QueryData<'a>>(
+        func: Option<AggregateFn>,
+        state: &mut State<'a, Qd>,
+    ) -> Option<Self> {
+        /*
+            fn(*) or fn(field)
+               ^^ ^     ^^^^^^
+        */
+        state.poison_if(func.is_none() | (state.remaining() < 2));
+        if !state.okay() {
+            return None;
+        }
+        let field = match state.fw_read() {
+            Token![*] => None,
+            Token::Ident(id) => Some(*id),
+            _ => {
+                state.poison();
+                None
+            }
+        };
+        state.poison_if_not(state.cursor_eq(Token![() close]));
+        state.cursor_ahead();
+        // only count can use the wildcard
+        state.poison_if(field.is_none() & (func != Some(AggregateFn::Count)));
+        match func {
+            Some(func) if state.okay() => Some(Self::new(func, field)),
+            _ => None,
+        }
+    }
+}
+
 impl<'a> SelectStatement<'a> {
     #[inline(always)]
     #[cfg(test)]
@@ -66,7 +143,16 @@ impl<'a> SelectStatement<'a> {
         wildcard: bool,
         clauses: WhereClauseCollection<'a>,
     ) -> SelectStatement<'a> {
-        Self::new(entity, fields, wildcard, clauses)
+        Self::new(entity, fields, wildcard, vec![], clauses)
+    }
+    #[inline(always)]
+    #[cfg(test)]
+    pub(crate) fn new_test_aggregate(
+        entity: EntityIDRef<'a>,
+        aggregates: Vec<Aggregate<'a>>,
+        clauses: WhereClauseCollection<'a>,
+    ) -> SelectStatement<'a> {
+        Self::new(entity, vec![], false, aggregates, clauses)
     }
     #[inline(always)]
     #[cfg(test)]
@@ -74,12 +160,14 @@ impl<'a> SelectStatement<'a> {
         entity: EntityIDRef<'a>,
         fields: Vec<Ident<'a>>,
         wildcard: bool,
+        aggregates: Vec<Aggregate<'a>>,
         clauses: WhereClauseCollection<'a>,
     ) -> SelectStatement<'a> {
         Self {
             entity,
             fields,
             wildcard,
+            aggregates,
             clause: WhereClause::new(clauses),
         }
     }
@@ -92,8 +180,14 @@ impl<'a> SelectStatement<'a> {
     pub fn is_wildcard(&self) -> bool {
         self.wildcard
     }
-    pub fn into_fields(self) -> Vec<Ident<'a>> {
-        self.fields
+    pub fn fields(&self) -> &[Ident<'a>] {
+        &self.fields
+    }
+    pub fn aggregates(&self) -> &[Aggregate<'a>] {
+        &self.aggregates
+    }
+    pub fn is_aggregate(&self) -> bool {
+        !self.aggregates.is_empty()
     }
 }
 
@@ -109,20 +203,29 @@ impl<'a> SelectStatement<'a> {
             return compiler::cold_rerr(QueryError::QLUnexpectedEndOfStatement);
         }
         let mut select_fields = Vec::new();
+        let mut aggregates = Vec::new();
         let is_wildcard = state.cursor_eq(Token![*]);
         state.cursor_ahead_if(is_wildcard);
         while state.not_exhausted() && state.okay() && !is_wildcard {
-            match state.read() {
-                Token::Ident(id) => select_fields.push(*id),
+            let id = match state.read() {
+                Token::Ident(id) => *id,
                 _ => break,
-            }
+            };
             state.cursor_ahead();
+            if state.cursor_rounded_eq(Token![() open]) {
+                // an aggregate
+                state.cursor_ahead();
+                aggregates.extend(Aggregate::parse_args(AggregateFn::from_ident(id), state));
+            } else {
+                select_fields.push(id);
+            }
             let nx_comma = state.cursor_rounded_eq(Token![,]);
             let nx_from = state.cursor_rounded_eq(Token![from]);
             state.poison_if_not(nx_comma | nx_from);
             state.cursor_ahead_if(nx_comma);
         }
-        state.poison_if_not(is_wildcard | !select_fields.is_empty());
+        // aggregates can't be mixed with fields
+        state.poison_if_not(is_wildcard | (select_fields.is_empty() != aggregates.is_empty()));
         // we should have from + model
         if compiler::unlikely(state.remaining() < 2 || !state.okay()) {
             return compiler::cold_rerr(QueryError::QLInvalidSyntax);
@@ -130,11 +233,11 @@ impl<'a> SelectStatement<'a> {
         state.poison_if_not(state.cursor_eq(Token![from]));
         state.cursor_ahead(); // ignore errors
         let entity = state.try_entity_buffered_into_state_uninit();
-        let mut clauses = <_ as Default>::default();
+        let mut clause = WhereClause::default();
         if state.cursor_rounded_eq(Token![where]) {
             state.cursor_ahead();
-            WhereClause::parse_where_and_append_to(state, &mut clauses);
-            state.poison_if(clauses.is_empty());
+            WhereClause::parse_where_and_append_to(state, &mut clause, false);
+            state.poison_if(clause.is_empty());
         }
         if compiler::likely(state.okay()) {
             Ok(SelectStatement {
@@ -144,7 +247,8 @@ impl<'a> SelectStatement<'a> {
                 },
                 fields: select_fields,
                 wildcard: is_wildcard,
-                clause: WhereClause::new(clauses),
+                aggregates,
+                clause,
             })
         } else {
             compiler::cold_rerr(QueryError::QLInvalidSyntax)
@@ -158,6 +262,29 @@ pub struct SelectAllStatement<'a> {
     pub fields: Vec<Ident<'a>>,
     pub wildcard: bool,
     pub limit: u64,
+    /// the cursor to resume from, if the query is paginated (an empty cursor starts from the beginning)
+    pub after: Option<&'a [u8]>,
+    /// the field to order the rows by (can't be used with a cursor)
+    pub order_by: Option<OrderBy<'a>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// An `order by field [asc|desc]` clause
+pub struct OrderBy<'a> {
+    field: Ident<'a>,
+    desc: bool,
+}
+
+impl<'a> OrderBy<'a> {
+    pub const fn new(field: Ident<'a>, desc: bool) -> Self {
+        Self { field, desc }
+    }
+    pub fn field(&self) -> Ident<'a> {
+        self.field
+    }
+    pub fn is_desc(&self) -> bool {
+        self.desc
+    }
 }
 
 impl<'a> SelectAllStatement<'a> {
@@ -168,19 +295,50 @@ impl<'a> SelectAllStatement<'a> {
         wildcard: bool,
         limit: u64,
     ) -> Self {
-        Self::new(entity, fields, wildcard, limit)
+        Self::new(entity, fields, wildcard, limit, None, None)
     }
-    fn new(entity: EntityIDRef<'a>, fields: Vec<Ident<'a>>, wildcard: bool, limit: u64) -> Self {
+    #[cfg(test)]
+    pub fn test_new_after(
+        entity: EntityIDRef<'a>,
+        fields: Vec<Ident<'a>>,
+        wildcard: bool,
+        limit: u64,
+        after: &'a [u8],
+    ) -> Self {
+        Self::new(entity, fields, wildcard, limit, Some(after), None)
+    }
+    #[cfg(test)]
+    pub fn test_new_ordered(
+        entity: EntityIDRef<'a>,
+        fields: Vec<Ident<'a>>,
+        wildcard: bool,
+        limit: u64,
+        order_by: OrderBy<'a>,
+    ) -> Self {
+        Self::new(entity, fields, wildcard, limit, None, Some(order_by))
+    }
+    fn new(
+        entity: EntityIDRef<'a>,
+        fields: Vec<Ident<'a>>,
+        wildcard: bool,
+        limit: u64,
+        after: Option<&'a [u8]>,
+        order_by: Option<OrderBy<'a>>,
+    ) -> Self {
         Self {
             entity,
             fields,
             wildcard,
             limit,
+            after,
+            order_by,
         }
     }
     fn parse<Qd: QueryData<'a>>(state: &mut State<'a, Qd>) -> QueryResult<Self> {
         /*
             smallest query: select all * from mymodel limit 10
+            paginated: select all * from mymodel after <cursor> limit 10
+            ordered: select all * from mymodel order by field [asc|desc] limit 10
         */
         if state.remaining() < 5 {
             return Err(QueryError::QLUnexpectedEndOfStatement);
@@ -206,6 +364,40 @@ impl<'a> SelectAllStatement<'a> {
         state.poison_if_not(state.cursor_eq(Token![from]));
         state.cursor_ahead(); // ignore error
         let entity = state.try_entity_buffered_into_state_uninit();
+        let mut after = None;
+        // `after` is contextual (and not a keyword) so that it can still be used as a name
+        if state.not_exhausted() && state.read().ident_eq("after") {
+            // after <cursor>; the cursor is always a binary blob
+            state.cursor_ahead();
+            state.poison_if_not(state.can_read_lit_rounded());
+            if state.okay() {
+                let lit = unsafe {
+                    // UNSAFE(@ohsayan): verified above
+                    state.read_cursor_lit_unchecked()
+                };
+                state.cursor_ahead();
+                after = lit.try_bin();
+                state.poison_if(after.is_none());
+            }
+        }
+        let mut order_by = None;
+        if state.cursor_rounded_eq(Token![order]) {
+            // order by <field> [asc|desc]; an ordered scan can't be resumed with a cursor
+            state.cursor_ahead();
+            state.poison_if(after.is_some() | (state.remaining() < 2));
+            if state.okay() {
+                state.poison_if_not(state.cursor_eq(Token![by]));
+                state.cursor_ahead();
+                match state.fw_read() {
+                    Token::Ident(field) => {
+                        let desc = state.cursor_rounded_eq(Token![desc]);
+                        state.cursor_ahead_if(desc | state.cursor_rounded_eq(Token![asc]));
+                        order_by = Some(OrderBy::new(*field, desc));
+                    }
+                    _ => state.poison(),
+                }
+            }
+        }
         state.poison_if_not(state.cursor_rounded_eq(Token![limit]));
         state.cursor_ahead_if(state.okay()); // we did read limit
         state.poison_if(state.exhausted()); // we MUST have the limit
@@ -220,6 +412,8 @@ impl<'a> SelectAllStatement<'a> {
                             select_fields,
                             is_wildcard,
                             limit,
+                            after,
+                            order_by,
                         ))
                     };
                 }
@@ -230,9 +424,83 @@ impl<'a> SelectAllStatement<'a> {
     }
 }
 
+/*
+    Impls for exists
+*/
+
+#[derive(Debug, PartialEq)]
+pub struct ExistsStatement<'a> {
+    /// the entity
+    pub(super) entity: EntityIDRef<'a>,
+    /// the key column
+    pub(super) key: Ident<'a>,
+    /// the keys to probe for
+    pub(super) keys: Vec<Lit<'a>>,
+    /// whether the multi-key (`in`) form was used
+    pub(super) multi: bool,
+}
+
+impl<'a> ExistsStatement<'a> {
+    #[cfg(test)]
+    pub fn test_new(
+        entity: EntityIDRef<'a>,
+        key: Ident<'a>,
+        keys: Vec<Lit<'a>>,
+        multi: bool,
+    ) -> Self {
+        Self {
+            entity,
+            key,
+            keys,
+            multi,
+        }
+    }
+    pub fn entity(&self) -> EntityIDRef<'a> {
+        self.entity
+    }
+    pub fn key(&self) -> Ident<'a> {
+        self.key
+    }
+    pub fn keys(&self) -> &[Lit<'a>] {
+        &self.keys
+    }
+    pub fn is_multi(&self) -> bool {
+        self.multi
+    }
+    fn parse<Qd: QueryData<'a>>(state: &mut State<'a, Qd>) -> QueryResult<Self> {
+        /*
+            smallest query: exists from mymodel where k = v
+                                   ^1   ^2      ^3    ^4^5^6
+            multi: exists from mymodel where k in (v1, v2, ...)
+        */
+        if compiler::unlikely(state.remaining() < 6) {
+            return compiler::cold_rerr(QueryError::QLUnexpectedEndOfStatement);
+        }
+        state.poison_if_not(state.cursor_eq(Token![from]));
+        state.cursor_ahead(); // ignore errors
+        let entity = state.try_entity_buffered_into_state_uninit();
+        state.poison_if_not(state.cursor_rounded_eq(Token![where]));
+        state.cursor_ahead_if(state.okay());
+        // we only take a single `=` or `in` predicate (on the primary key; checked during execution)
+        let predicate = WhereClause::parse_where_with_in(state).into_single_eq_or_in();
+        match predicate {
+            Some((key, keys, multi)) if compiler::likely(state.okay()) => Ok(Self {
+                entity: unsafe {
+                    // UNSAFE(@ohsayan): `okay` asserts correctness
+                    entity.assume_init()
+                },
+                key,
+                keys,
+                multi,
+            }),
+            _ => compiler::cold_rerr(QueryError::QLInvalidSyntax),
+        }
+    }
+}
+
 mod impls {
     use {
-        super::{SelectAllStatement, SelectStatement},
+        super::{ExistsStatement, SelectAllStatement, SelectStatement},
         crate::engine::{
             error::QueryResult,
             ql::ast::{traits::ASTNode, QueryData, State},
@@ -256,4 +524,13 @@ mod impls {
             Self::parse(state)
         }
     }
+    impl<'a> ASTNode<'a> for ExistsStatement<'a> {
+        const MUST_USE_FULL_TOKEN_RANGE: bool = true;
+        const VERIFIES_FULL_TOKEN_RANGE_USAGE: bool = false;
+        fn __base_impl_parse_from_state<Qd: QueryData<'a>>(
+            state: &mut State<'a, Qd>,
+        ) -> QueryResult<Self> {
+            Self::parse(state)
+        }
+    }
 }
@@ -0,0 +1,112 @@
+/*
+ * Created on Sun Oct 18 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+use crate::{
+    engine::{
+        core::EntityIDRef,
+        error::{QueryError, QueryResult},
+        ql::ast::{QueryData, State},
+    },
+    util::compiler,
+};
+
+/*
+    Impls for subscribe
+    ---
+    Smallest statement:
+    subscribe model
+    Resuming from a version:
+    subscribe space.model from 42
+*/
+
+#[derive(Debug, PartialEq)]
+pub struct SubscribeStatement<'a> {
+    pub(super) entity: EntityIDRef<'a>,
+    /// the version to start from (inclusive), if the changes since then are to be replayed
+    pub(super) from: Option<u64>,
+}
+
+impl<'a> SubscribeStatement<'a> {
+    #[cfg(test)]
+    pub fn new(entity: EntityIDRef<'a>, from: Option<u64>) -> Self {
+        Self { entity, from }
+    }
+    pub const fn entity(&self) -> EntityIDRef<'a> {
+        self.entity
+    }
+    pub fn from(&self) -> Option<u64> {
+        self.from
+    }
+    pub fn parse_subscribe<Qd: QueryData<'a>>(state: &mut State<'a, Qd>) -> QueryResult<Self> {
+        /*
+            smallest tt:
+            subscribe model
+                      ^1
+        */
+        if compiler::unlikely(state.exhausted()) {
+            return compiler::cold_rerr(QueryError::QLUnexpectedEndOfStatement);
+        }
+        let entity = state.try_entity_ref_result()?;
+        let mut from = None;
+        if state.cursor_rounded_eq(Token![from]) {
+            state.cursor_ahead();
+            state.poison_if_not(state.can_read_lit_rounded());
+            if state.okay() {
+                let version = unsafe {
+                    // UNSAFE(@ohsayan): checked lit
+                    state.read_cursor_lit_unchecked()
+                };
+                state.cursor_ahead();
+                from = version.try_uint();
+                state.poison_if(from.is_none());
+            }
+        }
+        if compiler::likely(state.okay()) {
+            Ok(Self { entity, from })
+        } else {
+            compiler::cold_rerr(QueryError::QLInvalidSyntax)
+        }
+    }
+}
+
+mod impls {
+    use {
+        super::SubscribeStatement,
+        crate::engine::{
+            error::QueryResult,
+            ql::ast::{traits::ASTNode, QueryData, State},
+        },
+    };
+    impl<'a> ASTNode<'a> for SubscribeStatement<'a> {
+        const MUST_USE_FULL_TOKEN_RANGE: bool = true;
+        const VERIFIES_FULL_TOKEN_RANGE_USAGE: bool = false;
+        fn __base_impl_parse_from_state<Qd: QueryData<'a>>(
+            state: &mut State<'a, Qd>,
+        ) -> QueryResult<Self> {
+            Self::parse_subscribe(state)
+        }
+    }
+}
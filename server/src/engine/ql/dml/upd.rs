@@ -25,10 +25,10 @@
 */
 
 use {
-    super::{u, WhereClause},
+    super::{u, ReturningClause, WhereClause},
     crate::{
         engine::{
-            core::{query_meta::AssignmentOperator, EntityIDRef},
+            core::{model::Durability, query_meta::AssignmentOperator, EntityIDRef},
             data::lit::Lit,
             error::{QueryError, QueryResult},
             ql::{
@@ -57,16 +57,49 @@ static OPERATOR: [AssignmentOperator; 6] = [
 pub struct AssignmentExpression<'a> {
     /// the LHS ident
     pub lhs: Ident<'a>,
-    /// the RHS lit
+    /// the keys of the value in a dict field that is assigned to (`lhs.key1.key2`), if any
+    pub path: Vec<Ident<'a>>,
+    /// the index of the element in a list field that is assigned to (`lhs[i]`), if any. Negative indexes
+    /// count from the end of the list
+    pub index: Option<i64>,
+    /// the RHS lit (the index for [`AssignmentOperator::Remove`] and the length for
+    /// [`AssignmentOperator::Truncate`])
     pub rhs: Lit<'a>,
     /// operator
     pub operator_fn: AssignmentOperator,
 }
 
 impl<'a> AssignmentExpression<'a> {
+    #[cfg(test)]
     pub fn new(lhs: Ident<'a>, rhs: Lit<'a>, operator_fn: AssignmentOperator) -> Self {
+        Self::new_with_path(lhs, Vec::new(), rhs, operator_fn)
+    }
+    #[cfg(test)]
+    pub fn new_with_path(
+        lhs: Ident<'a>,
+        path: Vec<Ident<'a>>,
+        rhs: Lit<'a>,
+        operator_fn: AssignmentOperator,
+    ) -> Self {
+        Self {
+            lhs,
+            path,
+            index: None,
+            rhs,
+            operator_fn,
+        }
+    }
+    #[cfg(test)]
+    pub fn new_with_index(
+        lhs: Ident<'a>,
+        index: i64,
+        rhs: Lit<'a>,
+        operator_fn: AssignmentOperator,
+    ) -> Self {
         Self {
             lhs,
+            path: Vec::new(),
+            index: Some(index),
             rhs,
             operator_fn,
         }
@@ -83,8 +116,32 @@ impl<'a> AssignmentExpression<'a> {
             state.poison();
             return;
         }
+        if state.cursor_eq(Token![remove]) | state.cursor_eq(Token![truncate]) {
+            Self::parse_and_append_list_operation(state, expressions);
+            return;
+        }
         let lhs = state.fw_read();
         state.poison_if_not(lhs.is_ident());
+        // path into a dict: x.y.z = w
+        let mut path = Vec::new();
+        while state.has_remaining(4)
+            && state.cursor_eq(Token![.])
+            && state.offset_current_r(1).is_ident()
+        {
+            state.cursor_ahead();
+            path.push(unsafe {
+                // UNSAFE(@ohsayan): checked in the loop condition
+                state.fw_read().uck_read_ident()
+            });
+        }
+        // index into a list: x[i] = y
+        let mut index = None;
+        if path.is_empty() && state.has_remaining(5) && state.cursor_eq(Token![open []]) {
+            state.cursor_ahead();
+            index = Self::parse_index(state);
+            state.poison_if_not(state.cursor_eq(Token![close []]));
+            state.cursor_ahead();
+        }
         let op_ass = u(state.cursor_eq(Token![=]));
         let op_add = u(state.cursor_eq(Token![+])) * 2;
         let op_sub = u(state.cursor_eq(Token![-])) * 3;
@@ -111,15 +168,89 @@ impl<'a> AssignmentExpression<'a> {
                 // UNSAFE(@ohsayan): Checked lit, state flag ensures we have ident for lhs
                 let rhs = state.read_cursor_lit_unchecked();
                 state.cursor_ahead();
-                expressions.push(AssignmentExpression::new(
+                expressions.push(AssignmentExpression {
                     // UNSAFE(@ohsayan): we verified if `lhs` returns `is_ident`
-                    lhs.uck_read_ident(),
+                    lhs: lhs.uck_read_ident(),
+                    path,
+                    index,
                     rhs,
-                    OPERATOR[operator_code as usize],
-                ))
+                    operator_fn: OPERATOR[operator_code as usize],
+                })
             }
         }
     }
+    /// Parse a list operation that isn't an assignment: `remove x[i]` (remove the element at the index) or
+    /// `truncate x to n` (keep the first `n` elements)
+    fn parse_and_append_list_operation<Qd: QueryData<'a>>(
+        state: &mut State<'a, Qd>,
+        expressions: &mut Vec<Self>,
+    ) {
+        /*
+            smallest exprs:
+            remove x [ i ]
+            truncate x to n
+        */
+        if compiler::unlikely(state.remaining() < 4) {
+            state.poison();
+            return;
+        }
+        let is_remove = state.cursor_eq(Token![remove]);
+        state.cursor_ahead();
+        let lhs = state.fw_read();
+        state.poison_if_not(lhs.is_ident());
+        state.poison_if_not(
+            (state.cursor_eq(Token![open []]) & is_remove)
+                | (state.cursor_eq(Token![to]) & !is_remove),
+        );
+        state.cursor_ahead();
+        state.poison_if_not(state.can_read_lit_rounded());
+        if !state.okay() {
+            return;
+        }
+        let rhs = unsafe {
+            // UNSAFE(@ohsayan): Checked lit
+            state.read_cursor_lit_unchecked()
+        };
+        state.cursor_ahead();
+        if is_remove {
+            state.poison_if_not(state.cursor_rounded_eq(Token![close []]));
+            state.cursor_ahead_if(state.okay());
+        }
+        if state.okay() {
+            expressions.push(AssignmentExpression {
+                lhs: unsafe {
+                    // UNSAFE(@ohsayan): we verified if `lhs` returns `is_ident`
+                    lhs.uck_read_ident()
+                },
+                path: Vec::new(),
+                index: None,
+                rhs,
+                operator_fn: if is_remove {
+                    AssignmentOperator::Remove
+                } else {
+                    AssignmentOperator::Truncate
+                },
+            });
+        }
+    }
+    /// Parse a (signed) integer index, poisoning the state if there isn't one
+    fn parse_index<Qd: QueryData<'a>>(state: &mut State<'a, Qd>) -> Option<i64> {
+        state.poison_if_not(state.can_read_lit_rounded());
+        if !state.okay() {
+            return None;
+        }
+        let lit = unsafe {
+            // UNSAFE(@ohsayan): Checked lit
+            state.read_cursor_lit_unchecked()
+        };
+        state.cursor_ahead();
+        let index = lit
+            .try_uint()
+            .and_then(|index| i64::try_from(index).ok())
+            .or_else(|| lit.try_sint());
+        state.poison_if(index.is_none());
+        index
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -127,21 +258,30 @@ pub struct UpdateStatement<'a> {
     pub(super) entity: EntityIDRef<'a>,
     pub(super) expressions: Vec<AssignmentExpression<'a>>,
     pub(super) wc: WhereClause<'a>,
+    /// the durability for the write, if set with `with durability`
+    pub(super) durability: Option<Durability>,
+    pub(super) returning: Option<ReturningClause<'a>>,
 }
 
 impl<'a> UpdateStatement<'a> {
     pub fn entity(&self) -> EntityIDRef<'a> {
         self.entity
     }
+    pub fn durability(&self) -> Option<Durability> {
+        self.durability
+    }
+    pub fn returning(&self) -> Option<&ReturningClause<'a>> {
+        self.returning.as_ref()
+    }
+    pub fn take_returning(&mut self) -> Option<ReturningClause<'a>> {
+        self.returning.take()
+    }
     pub fn expressions(&self) -> &[AssignmentExpression<'a>] {
         &self.expressions
     }
     pub fn clauses_mut(&mut self) -> &mut WhereClause<'a> {
         &mut self.wc
     }
-    pub fn into_expressions(self) -> Vec<AssignmentExpression<'a>> {
-        self.expressions
-    }
 }
 
 impl<'a> UpdateStatement<'a> {
@@ -156,6 +296,22 @@ impl<'a> UpdateStatement<'a> {
             entity,
             expressions,
             wc,
+            durability: None,
+            returning: None,
+        }
+    }
+    #[cfg(test)]
+    pub fn with_durability(self, durability: Durability) -> Self {
+        Self {
+            durability: Some(durability),
+            ..self
+        }
+    }
+    #[cfg(test)]
+    pub fn with_returning(self, returning: ReturningClause<'a>) -> Self {
+        Self {
+            returning: Some(returning),
+            ..self
         }
     }
     #[inline(always)]
@@ -191,9 +347,15 @@ impl<'a> UpdateStatement<'a> {
         state.poison_if_not(nx_where);
         state.cursor_ahead_if(state.okay());
         // check where clauses
-        let mut clauses = <_ as Default>::default();
-        WhereClause::parse_where_and_append_to(state, &mut clauses);
-        state.poison_if(clauses.is_empty()); // NOTE: volcano
+        let mut wc = WhereClause::default();
+        WhereClause::parse_where_and_append_to(state, &mut wc, false);
+        state.poison_if(wc.is_empty()); // NOTE: volcano
+                                        // conditions
+        if state.cursor_rounded_eq(Token![if]) {
+            WhereClause::parse_if_and_append_to(state, &mut wc);
+        }
+        let durability = super::parse_durability_if_present(state);
+        let returning = ReturningClause::parse_if_present(state);
         if compiler::likely(state.okay()) {
             Ok(Self {
                 entity: unsafe {
@@ -201,7 +363,9 @@ impl<'a> UpdateStatement<'a> {
                     entity.assume_init()
                 },
                 expressions,
-                wc: WhereClause::new(clauses),
+                wc,
+                durability,
+                returning,
             })
         } else {
             compiler::cold_rerr(QueryError::QLInvalidSyntax)
@@ -98,6 +98,10 @@ impl<'a> Lexer<'a> {
     /// Scan an identifier or keyword
     fn scan_ident_or_keyword(&mut self) {
         let s = self.scan_ident();
+        self.push_ident_or_keyword(s)
+    }
+    /// Push an already scanned identifier or keyword
+    fn push_ident_or_keyword(&mut self, s: Slice<'a>) {
         match Keyword::get(s) {
             Some(kw) => self.tokens.push(kw.into()),
             // FIXME(@ohsayan): Uh, mind fixing this? The only advantage is that I can keep the graph *memory* footprint small
@@ -171,9 +175,9 @@ mod insecure_impl {
                             self.l.token_buffer.incr_cursor();
                         }
                     }
-                    // ident
+                    // ident (or a typed literal)
                     byte if byte.is_ascii_alphabetic() | (byte == b'_') => {
-                        self.l.scan_ident_or_keyword()
+                        self.scan_ident_or_typed_literal()
                     }
                     // uint
                     byte if byte.is_ascii_digit() => self.scan_unsigned_integer(),
@@ -230,6 +234,47 @@ mod insecure_impl {
                 None => self.l.set_error(QueryError::LexInvalidInput),
             }
         }
+        /// Scan an identifier, keyword or a typed literal (`timestamp'<RFC 3339>'` or `uuid'<uuid>'`)
+        pub(crate) fn scan_ident_or_typed_literal(&mut self) {
+            let ident = self.l.scan_ident();
+            let is_timestamp = ident.eq_ignore_ascii_case(b"timestamp");
+            let is_uuid = ident.eq_ignore_ascii_case(b"uuid");
+            if !((is_timestamp | is_uuid)
+                & self
+                    .l
+                    .token_buffer
+                    .rounded_cursor_not_eof_matches(|b| (*b == b'"') | (*b == b'\'')))
+            {
+                return self.l.push_ident_or_keyword(ident);
+            }
+            let quote_style = unsafe {
+                // UNSAFE(@ohsayan): not eof
+                self.l.token_buffer.next_byte()
+            };
+            self.scan_quoted_string(quote_style);
+            if !self.l.no_error() {
+                return;
+            }
+            let Some(Token::Lit(quoted)) = self.l.tokens.pop() else {
+                unreachable!()
+            };
+            let text = quoted.try_str().unwrap();
+            let lit = if is_timestamp {
+                chrono::DateTime::parse_from_rfc3339(text)
+                    .ok()
+                    .and_then(|ts| ts.timestamp_nanos_opt())
+                    .and_then(|ns| u64::try_from(ns).ok())
+                    .map(Lit::new_timestamp)
+            } else {
+                uuid::Uuid::parse_str(text)
+                    .ok()
+                    .map(|uuid| Lit::new_boxed_uuid(uuid.as_u128()))
+            };
+            match lit {
+                Some(lit) => self.l.push_token(lit),
+                None => self.l.set_error(QueryError::LexInvalidInput),
+            }
+        }
         pub(crate) fn scan_quoted_string(&mut self, quote_style: u8) {
             // cursor is at beginning of `"`; we need to scan until the end of quote or an escape
             let mut buf = Vec::new();
@@ -415,8 +460,8 @@ impl<'a> SecureLexer<'a> {
     }
 }
 
-const SCAN_PARAM_EXPECT: [u8; 8] = [0, 1, 2, 2, 2, 2, 2, 0];
-static SCAN_PARAM: [unsafe fn(&mut SecureLexer); 8] = unsafe {
+const SCAN_PARAM_EXPECT: [u8; 10] = [0, 1, 2, 2, 2, 2, 2, 2, 16, 0];
+static SCAN_PARAM: [unsafe fn(&mut SecureLexer); 10] = unsafe {
     [
         // null
         |s| s.l.push_token(Token![null]),
@@ -499,6 +544,20 @@ static SCAN_PARAM: [unsafe fn(&mut SecureLexer); 8] = unsafe {
                 _ => slf.l.set_error(QueryError::LexInvalidInput),
             }
         },
+        // timestamp (nanoseconds since the unix epoch)
+        |slf| match slf
+            .param_buffer
+            .try_next_ascii_u64_lf_separated_or_restore_cursor()
+        {
+            Some(ns) => slf.l.push_token(Lit::new_timestamp(ns)),
+            None => slf.l.set_error(QueryError::LexInvalidInput),
+        },
+        // uuid
+        |slf| {
+            // we already know that we have enough bytes
+            let uuid = slf.param_buffer.next_chunk_variable(16);
+            slf.l.push_token(Lit::new_uuid(uuid.try_into().unwrap()))
+        },
         // ecc
         |s| s.l.set_error(QueryError::LexInvalidInput),
     ]
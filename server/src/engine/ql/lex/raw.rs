@@ -354,17 +354,24 @@ flattened_lut! {
                 Create = 1,
                 Alter = 2,
                 Drop = 3,
+                Truncate = 4,
                 // system/DDL misc
-                Use = 4,
-                Inspect = 5,
-                Describe = 6,
+                Use = 5,
+                Inspect = 6,
+                Describe = 7,
                 // DML
-                Insert: Ins | Insert = 7,
-                Select: Sel | Select = 8,
-                Update: Upd | Update  = 9,
-                Delete: Del | Delete = 10,
-                Upsert: Ups | Upsert = 11,
-                Exists = 12,
+                Insert: Ins | Insert = 8,
+                Select: Sel | Select = 9,
+                Update: Upd | Update  = 10,
+                Delete: Del | Delete = 11,
+                Upsert: Ups | Upsert = 12,
+                Exists = 13,
+                // batches
+                Begin = 14,
+                Commit = 15,
+                Rollback = 16,
+                // change feeds
+                Subscribe = 17,
             }
         },
         /// Hi
@@ -425,14 +432,11 @@ flattened_lut! {
                 Lock,
                 Read,
                 Write,
-                Begin,
                 End,
                 // misc
                 Key,
                 Value,
                 Primary,
-                // temporarily reserved (will probably be removed in the future)
-                Truncate, // TODO: decide what we want to do with this
             }
         }
     }
@@ -448,14 +452,14 @@ impl Keyword {
         }
     }
     fn compute(key: &[u8]) -> Option<Self> {
-        static G: [u8; 78] = [
-            0, 19, 60, 52, 68, 26, 43, 57, 15, 10, 77, 0, 0, 38, 35, 55, 40, 27, 13, 50, 20, 5, 51,
-            53, 34, 62, 22, 43, 0, 9, 8, 0, 28, 35, 22, 0, 33, 1, 34, 44, 9, 14, 0, 76, 70, 30, 29,
-            0, 73, 50, 18, 57, 57, 31, 5, 31, 1, 22, 33, 64, 30, 6, 19, 34, 32, 0, 70, 10, 28, 37,
-            62, 0, 64, 0, 0, 30, 12, 57,
+        static G: [u8; 84] = [
+            0, 45, 71, 69, 21, 0, 37, 49, 52, 42, 38, 29, 75, 58, 42, 16, 14, 0, 42, 83, 0, 44, 82,
+            20, 61, 37, 0, 66, 0, 69, 10, 0, 48, 77, 45, 29, 64, 53, 67, 17, 55, 21, 77, 0, 64, 32,
+            1, 59, 33, 18, 13, 73, 27, 29, 60, 43, 34, 65, 0, 15, 49, 63, 0, 65, 2, 29, 73, 63, 15,
+            0, 46, 14, 0, 1, 0, 72, 63, 11, 23, 38, 6, 72, 23, 57,
         ];
-        static M1: [u8; 11] = *b"H7ulUIcOFSG";
-        static M2: [u8; 11] = *b"7wE0VbAtQQa";
+        static M1: [u8; 11] = *b"cId1gm2YXnc";
+        static M2: [u8; 11] = *b"f75cFaCIybU";
         let h1 = Self::_sum(key, M1) % G.len();
         let h2 = Self::_sum(key, M2) % G.len();
         let h = (G[h1] + G[h2]) as usize % G.len();
@@ -480,7 +484,10 @@ impl Keyword {
 
 impl KeywordStmt {
     pub const fn is_blocking(&self) -> bool {
-        self.value_u8() <= Self::Drop.value_u8()
+        self.value_u8() <= Self::Truncate.value_u8()
+    }
+    pub const fn is_batch_control(&self) -> bool {
+        matches!(self, Self::Begin | Self::Commit | Self::Rollback)
     }
     pub const NONBLOCKING_COUNT: usize = Self::BLK_NBLK.1;
     const BLK_NBLK: (usize, usize) = {
@@ -492,6 +499,7 @@ impl KeywordStmt {
                 KeywordStmt::Create
                 | KeywordStmt::Alter
                 | KeywordStmt::Drop
+                | KeywordStmt::Truncate
                 | KeywordStmt::Sysctl => blk += 1,
                 KeywordStmt::Use
                 | KeywordStmt::Inspect
@@ -502,6 +510,10 @@ impl KeywordStmt {
                 | KeywordStmt::Delete
                 | KeywordStmt::Exists
                 | KeywordStmt::Upsert => nb += 1,
+                // batch control statements are executed with the connection state and are never dispatched
+                KeywordStmt::Begin | KeywordStmt::Commit | KeywordStmt::Rollback => {}
+                // a subscription is started with the connection state and is never dispatched either
+                KeywordStmt::Subscribe => {}
             }
             i += 1;
         }
@@ -93,6 +93,18 @@ impl<'a> NullableDictEntry for crate::engine::data::lit::Lit<'a> {
     }
 }
 
+impl NullableDictEntry for Datacell {
+    fn data(self) -> crate::engine::data::DictEntryGeneric {
+        crate::engine::data::DictEntryGeneric::Data(self)
+    }
+}
+
+impl NullableDictEntry for crate::engine::data::DictEntryGeneric {
+    fn data(self) -> crate::engine::data::DictEntryGeneric {
+        self
+    }
+}
+
 impl NullableDictEntry for crate::engine::data::DictGeneric {
     fn data(self) -> crate::engine::data::DictEntryGeneric {
         crate::engine::data::DictEntryGeneric::Map(self)
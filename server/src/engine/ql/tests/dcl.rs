@@ -24,10 +24,14 @@
  *
 */
 
-use crate::engine::ql::{
-    ast,
-    dcl::{self, SysctlCommand},
-    tests::lex_insecure,
+use crate::engine::{
+    core::system_db::Privilege,
+    error::QueryError,
+    ql::{
+        ast,
+        dcl::{self, SysctlCommand},
+        tests::lex_insecure,
+    },
 };
 
 #[test]
@@ -72,3 +76,149 @@ fn delete_user_simple() {
         SysctlCommand::DropUser(dcl::UserDel::new("monster".into()))
     );
 }
+
+#[test]
+fn grant_space() {
+    let query = lex_insecure(b"sysctl grant select on myspace to sayan").unwrap();
+    let q = ast::parse_ast_node_full::<dcl::SysctlCommand>(&query[1..]).unwrap();
+    assert_eq!(
+        q,
+        SysctlCommand::Grant(dcl::PrivilegeDecl::new(
+            Privilege::Select,
+            "myspace".into(),
+            None,
+            "sayan".into(),
+            false
+        ))
+    );
+}
+
+#[test]
+fn grant_model() {
+    let query = lex_insecure(b"sysctl grant ddl on myspace.mymodel to sayan").unwrap();
+    let q = ast::parse_ast_node_full::<dcl::SysctlCommand>(&query[1..]).unwrap();
+    assert_eq!(
+        q,
+        SysctlCommand::Grant(dcl::PrivilegeDecl::new(
+            Privilege::Ddl,
+            "myspace".into(),
+            Some("mymodel".into()),
+            "sayan".into(),
+            false
+        ))
+    );
+}
+
+#[test]
+fn revoke_model() {
+    let query = lex_insecure(b"sysctl revoke delete on myspace.mymodel from sayan").unwrap();
+    let q = ast::parse_ast_node_full::<dcl::SysctlCommand>(&query[1..]).unwrap();
+    assert_eq!(
+        q,
+        SysctlCommand::Revoke(dcl::PrivilegeDecl::new(
+            Privilege::Delete,
+            "myspace".into(),
+            Some("mymodel".into()),
+            "sayan".into(),
+            false
+        ))
+    );
+}
+
+#[test]
+fn grant_role_privilege() {
+    let query = lex_insecure(b"sysctl grant insert on myspace.mymodel to role writers").unwrap();
+    let q = ast::parse_ast_node_full::<dcl::SysctlCommand>(&query[1..]).unwrap();
+    assert_eq!(
+        q,
+        SysctlCommand::Grant(dcl::PrivilegeDecl::new(
+            Privilege::Insert,
+            "myspace".into(),
+            Some("mymodel".into()),
+            "writers".into(),
+            true
+        ))
+    );
+}
+
+#[test]
+fn create_drop_role() {
+    let query = lex_insecure(b"sysctl create role writers").unwrap();
+    let q = ast::parse_ast_node_full::<dcl::SysctlCommand>(&query[1..]).unwrap();
+    assert_eq!(
+        q,
+        SysctlCommand::CreateRole(dcl::RoleDecl::new("writers".into()))
+    );
+    let query = lex_insecure(b"sysctl drop role writers").unwrap();
+    let q = ast::parse_ast_node_full::<dcl::SysctlCommand>(&query[1..]).unwrap();
+    assert_eq!(
+        q,
+        SysctlCommand::DropRole(dcl::RoleDecl::new("writers".into()))
+    );
+}
+
+#[test]
+fn grant_revoke_role() {
+    let query = lex_insecure(b"sysctl grant role writers to sayan").unwrap();
+    let q = ast::parse_ast_node_full::<dcl::SysctlCommand>(&query[1..]).unwrap();
+    assert_eq!(
+        q,
+        SysctlCommand::GrantRole(dcl::RoleGrantDecl::new("writers".into(), "sayan".into()))
+    );
+    let query = lex_insecure(b"sysctl revoke role writers from sayan").unwrap();
+    let q = ast::parse_ast_node_full::<dcl::SysctlCommand>(&query[1..]).unwrap();
+    assert_eq!(
+        q,
+        SysctlCommand::RevokeRole(dcl::RoleGrantDecl::new("writers".into(), "sayan".into()))
+    );
+}
+
+#[test]
+fn grant_revoke_bad() {
+    for query in [
+        &b"sysctl grant all on myspace to sayan"[..],
+        b"sysctl grant select on myspace from sayan",
+        b"sysctl revoke select on myspace to sayan",
+        b"sysctl grant select myspace to sayan",
+        b"sysctl grant select on myspace.mymodel to",
+        b"sysctl grant select on myspace to sayan extra",
+        b"sysctl grant select on myspace to group sayan",
+        b"sysctl grant role writers from sayan",
+        b"sysctl revoke role writers to sayan",
+        b"sysctl grant role writers to",
+        b"sysctl create role",
+        b"sysctl drop role writers extra",
+    ] {
+        let query = lex_insecure(query).unwrap();
+        assert_eq!(
+            ast::parse_ast_node_full::<dcl::SysctlCommand>(&query[1..]).unwrap_err(),
+            QueryError::QLInvalidSyntax
+        );
+    }
+}
+
+#[test]
+fn backup() {
+    let query = lex_insecure(b"sysctl backup to \"backups/nightly\"").unwrap();
+    let q = ast::parse_ast_node_full::<dcl::SysctlCommand>(&query[1..]).unwrap();
+    assert_eq!(
+        q,
+        SysctlCommand::Backup(dcl::BackupDecl::new("backups/nightly"))
+    );
+}
+
+#[test]
+fn backup_bad() {
+    for query in [
+        &b"sysctl backup to"[..],
+        b"sysctl backup to backups",
+        b"sysctl backup to 100",
+        b"sysctl backup to \"backups/nightly\" extra",
+    ] {
+        let query = lex_insecure(query).unwrap();
+        assert_eq!(
+            ast::parse_ast_node_full::<dcl::SysctlCommand>(&query[1..]).unwrap_err(),
+            QueryError::QLInvalidSyntax
+        );
+    }
+}
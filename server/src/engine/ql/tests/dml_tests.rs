@@ -193,7 +193,10 @@ mod tuple_syntax {
 }
 mod map_syntax {
     use super::*;
-    use crate::engine::ql::{ast::parse_ast_node_full, dml::ins::DataMap};
+    use crate::engine::{
+        data::cell::Datacell,
+        ql::{ast::parse_ast_node_full, dml::ins::DataMap},
+    };
 
     #[test]
     fn map_mini() {
@@ -290,14 +293,63 @@ mod map_syntax {
             }
         )
     }
+
+    #[test]
+    fn map_nested_dict() {
+        let tok = lex_insecure(
+            br#"
+                {
+                    name: "John Appletree",
+                    profile: {
+                        bio: null,
+                        links: ["example.com"],
+                        address: { city: "Kolkata" }
+                    },
+                    settings: {}
+                }
+            "#,
+        )
+        .unwrap();
+        let r = parse_ast_node_full::<DataMap>(&tok[1..]).unwrap();
+        assert_eq!(
+            r,
+            dict_nullable! {
+                "name" => "John Appletree",
+                "profile" => Datacell::new_dict(dict_nullable! {
+                    "bio" => Null,
+                    "links" => into_array!["example.com"],
+                    "address" => Datacell::new_dict(dict_nullable! {
+                        "city" => "Kolkata",
+                    }),
+                }),
+                "settings" => Datacell::new_dict(dict_nullable! {}),
+            }
+        )
+    }
+
+    #[test]
+    fn map_bad_nested_dict() {
+        for src in [
+            &b"{ profile: { bio: 'a', bio: 'b' } }"[..],
+            b"{ profile: { bio 'a' } }",
+            b"{ profile: { 'bio': 'a' } }",
+        ] {
+            let tok = lex_insecure(src).unwrap();
+            assert!(parse_ast_node_full::<DataMap>(&tok[1..]).is_err());
+        }
+    }
 }
 mod stmt_insert {
     use {
         super::*,
-        crate::engine::ql::{
-            ast::parse_ast_node_full,
-            dml::{self, ins::InsertStatement},
-            lex::Ident,
+        crate::engine::{
+            core::model::Durability,
+            data::cell::Datacell,
+            ql::{
+                ast::parse_ast_node_full,
+                dml::{self, ins::InsertStatement, ReturningClause},
+                lex::Ident,
+            },
         },
     };
 
@@ -491,6 +543,183 @@ mod stmt_insert {
         );
         assert_eq!(ret, expected);
     }
+    #[test]
+    fn insert_tuple_typed() {
+        let tok = lex_insecure(
+            br#"insert into jotsy.app(@uuid(), timestamp'2023-01-08T14:23:59Z', @now(), uuid'4593264b-0231-43e9-b0aa-50784f14e204')"#,
+        )
+        .unwrap();
+        let ret = parse_ast_node_full::<InsertStatement>(&tok[1..]).unwrap();
+        let uuid = Datacell::new_uuid(
+            uuid::Uuid::parse_str(dml::ins::T_UUIDSTR)
+                .unwrap()
+                .as_u128(),
+        );
+        let timestamp = Datacell::new_timestamp(dml::ins::T_TIMESEC * 1_000_000_000);
+        let expected = InsertStatement::new(
+            ("jotsy", "app").into(),
+            into_array_nullable![uuid.clone(), timestamp.clone(), timestamp, uuid]
+                .to_vec()
+                .into(),
+        );
+        assert_eq!(ret, expected);
+    }
+    #[test]
+    fn insert_returning() {
+        let tok =
+            lex_insecure(br#"insert into jotsy.app { username: "sayan" } returning username, id"#)
+                .unwrap();
+        let r = parse_ast_node_full::<InsertStatement>(&tok[1..]).unwrap();
+        let e = InsertStatement::new(
+            ("jotsy", "app").into(),
+            dict_nullable! {
+                Ident::from("username") => "sayan"
+            }
+            .into(),
+        )
+        .with_returning(ReturningClause::new(
+            vec![Ident::from("username"), Ident::from("id")],
+            false,
+        ));
+        assert_eq!(e, r);
+        // `return` is the same as `returning`
+        let tok = lex_insecure(br#"insert into jotsy.app("sayan") return *"#).unwrap();
+        let r = parse_ast_node_full::<InsertStatement>(&tok[1..]).unwrap();
+        let e = InsertStatement::new(
+            ("jotsy", "app").into(),
+            into_array_nullable!["sayan"].to_vec().into(),
+        )
+        .with_returning(ReturningClause::new(vec![], true));
+        assert_eq!(e, r);
+    }
+    #[test]
+    fn insert_bad_returning() {
+        for src in [
+            br#"insert into jotsy.app("sayan") returning"#.as_slice(),
+            br#"insert into jotsy.app("sayan") returning username,"#,
+            br#"insert into jotsy.app("sayan") returning username id"#,
+            br#"insert into jotsy.app("sayan") returning * username"#,
+        ] {
+            let tok = lex_insecure(src).unwrap();
+            assert!(parse_ast_node_full::<InsertStatement>(&tok[1..]).is_err());
+        }
+    }
+    #[test]
+    fn insert_bulk() {
+        let tok = lex_insecure(
+            br#"insert into jotsy.app("sayan", 1), { username: "ohsayan" }, ("nandan", 2) returning *"#,
+        )
+        .unwrap();
+        let r = parse_ast_node_full::<InsertStatement>(&tok[1..]).unwrap();
+        let e = InsertStatement::new_bulk(
+            ("jotsy", "app").into(),
+            vec![
+                into_array_nullable!["sayan", 1].to_vec().into(),
+                dict_nullable! {
+                    Ident::from("username") => "ohsayan"
+                }
+                .into(),
+                into_array_nullable!["nandan", 2].to_vec().into(),
+            ],
+        )
+        .with_returning(ReturningClause::new(vec![], true));
+        assert_eq!(e, r);
+    }
+    #[test]
+    fn insert_bad_bulk() {
+        for src in [
+            br#"insert into jotsy.app("sayan"),"#.as_slice(),
+            br#"insert into jotsy.app("sayan"), returning *"#,
+            br#"insert into jotsy.app("sayan") ("nandan")"#,
+            br#"insert into jotsy.app("sayan"),, ("nandan")"#,
+        ] {
+            let tok = lex_insecure(src).unwrap();
+            assert!(parse_ast_node_full::<InsertStatement>(&tok[1..]).is_err());
+        }
+    }
+    #[test]
+    fn insert_with_ttl() {
+        let tok =
+            lex_insecure(br#"insert into jotsy.sessions("s1", "sayan") with ttl 60"#).unwrap();
+        let r = parse_ast_node_full::<InsertStatement>(&tok[1..]).unwrap();
+        let e = InsertStatement::new(
+            ("jotsy", "sessions").into(),
+            into_array_nullable!["s1", "sayan"].to_vec().into(),
+        )
+        .with_ttl(60);
+        assert_eq!(e, r);
+        let tok = lex_insecure(
+            br#"insert into jotsy.sessions("s1", "sayan"), ("s2", "nandan") with ttl 3600 returning *"#,
+        )
+        .unwrap();
+        let r = parse_ast_node_full::<InsertStatement>(&tok[1..]).unwrap();
+        let e = InsertStatement::new_bulk(
+            ("jotsy", "sessions").into(),
+            vec![
+                into_array_nullable!["s1", "sayan"].to_vec().into(),
+                into_array_nullable!["s2", "nandan"].to_vec().into(),
+            ],
+        )
+        .with_ttl(3600)
+        .with_returning(ReturningClause::new(vec![], true));
+        assert_eq!(e, r);
+    }
+    #[test]
+    fn insert_bad_ttl() {
+        for src in [
+            br#"insert into jotsy.sessions("s1") with ttl"#.as_slice(),
+            br#"insert into jotsy.sessions("s1") with ttl "1h""#,
+            br#"insert into jotsy.sessions("s1") with ttl -1"#,
+            br#"insert into jotsy.sessions("s1") with expiry 60"#,
+            br#"insert into jotsy.sessions("s1") returning * with ttl 60"#,
+        ] {
+            let tok = lex_insecure(src).unwrap();
+            assert!(parse_ast_node_full::<InsertStatement>(&tok[1..]).is_err());
+        }
+    }
+    #[test]
+    fn insert_with_durability() {
+        let tok = lex_insecure(br#"insert into bank.payments("p1", 100) with durability "sync""#)
+            .unwrap();
+        let r = parse_ast_node_full::<InsertStatement>(&tok[1..]).unwrap();
+        let e = InsertStatement::new(
+            ("bank", "payments").into(),
+            into_array_nullable!["p1", 100].to_vec().into(),
+        )
+        .with_durability(Durability::Sync);
+        assert_eq!(e, r);
+        for src in [
+            br#"insert into bank.payments("p1", 100) with ttl 60, durability "group-commit" returning *"#
+                .as_slice(),
+            br#"insert into bank.payments("p1", 100) with durability "group-commit", ttl 60 returning *"#,
+        ] {
+            let tok = lex_insecure(src).unwrap();
+            let r = parse_ast_node_full::<InsertStatement>(&tok[1..]).unwrap();
+            let e = InsertStatement::new(
+                ("bank", "payments").into(),
+                into_array_nullable!["p1", 100].to_vec().into(),
+            )
+            .with_ttl(60)
+            .with_durability(Durability::GroupCommit)
+            .with_returning(ReturningClause::new(vec![], true));
+            assert_eq!(e, r);
+        }
+    }
+    #[test]
+    fn insert_bad_durability() {
+        for src in [
+            br#"insert into bank.payments("p1") with durability"#.as_slice(),
+            br#"insert into bank.payments("p1") with durability "fsync""#,
+            br#"insert into bank.payments("p1") with durability sync"#,
+            br#"insert into bank.payments("p1") with durability 1"#,
+            br#"insert into bank.payments("p1") with durability "sync", durability "async""#,
+            br#"insert into bank.payments("p1") with durability "sync","#,
+            br#"insert into bank.payments("p1") with ttl 60 durability "sync""#,
+        ] {
+            let tok = lex_insecure(src).unwrap();
+            assert!(parse_ast_node_full::<InsertStatement>(&tok[1..]).is_err());
+        }
+    }
 }
 
 mod stmt_select {
@@ -498,9 +727,13 @@ mod stmt_select {
         super::*,
         crate::engine::{
             data::lit::Lit,
+            error::QueryError,
             ql::{
                 ast::{parse_ast_node_full, parse_ast_node_full_with_space},
-                dml::{sel::SelectStatement, RelationalExpr},
+                dml::{
+                    sel::{Aggregate, AggregateFn, SelectStatement},
+                    RelationalExpr,
+                },
                 lex::Ident,
             },
         },
@@ -589,6 +822,70 @@ mod stmt_select {
         );
         assert_eq!(r, e);
     }
+    #[test]
+    fn select_aggregates() {
+        let tok = lex_insecure(
+            br#"
+                select count(*), sum(followers), AVG(followers) from twitter.users
+            "#,
+        )
+        .unwrap();
+        let r = parse_ast_node_full::<SelectStatement>(&tok[1..]).unwrap();
+        let e = SelectStatement::new_test_aggregate(
+            ("twitter", "users").into(),
+            [
+                Aggregate::new(AggregateFn::Count, None),
+                Aggregate::new(AggregateFn::Sum, Some(Ident::from("followers"))),
+                Aggregate::new(AggregateFn::Avg, Some(Ident::from("followers"))),
+            ]
+            .to_vec(),
+            dict! {},
+        );
+        assert_eq!(r, e);
+    }
+    #[test]
+    fn select_aggregates_where() {
+        let tok = lex_insecure(
+            br#"
+                select min(joined), max(joined) from twitter.users where username = "sayan"
+            "#,
+        )
+        .unwrap();
+        let r = parse_ast_node_full::<SelectStatement>(&tok[1..]).unwrap();
+        let e = SelectStatement::new_test_aggregate(
+            ("twitter", "users").into(),
+            [
+                Aggregate::new(AggregateFn::Min, Some(Ident::from("joined"))),
+                Aggregate::new(AggregateFn::Max, Some(Ident::from("joined"))),
+            ]
+            .to_vec(),
+            dict! {
+                Ident::from("username") => RelationalExpr::new(
+                    Ident::from("username"), Lit::new_str("sayan"), RelationalExpr::OP_EQ
+                ),
+            },
+        );
+        assert_eq!(r, e);
+    }
+    #[test]
+    fn select_aggregates_bad() {
+        for query in [
+            // mixed with fields
+            &b"select username, count(*) from twitter.users"[..],
+            // only count can use the wildcard
+            b"select sum(*) from twitter.users",
+            // unknown aggregate
+            b"select median(followers) from twitter.users",
+            // unclosed
+            b"select count(followers from twitter.users",
+        ] {
+            let tok = lex_insecure(query).unwrap();
+            assert_eq!(
+                parse_ast_node_full::<SelectStatement>(&tok[1..]).unwrap_err(),
+                QueryError::QLInvalidSyntax
+            );
+        }
+    }
 }
 mod expression_tests {
     use {
@@ -613,6 +910,20 @@ mod expression_tests {
         );
     }
     #[test]
+    fn expr_assign_path() {
+        let src = lex_insecure(b"profile.address.city = 'Kolkata'").unwrap();
+        let r = parse_ast_node_full::<AssignmentExpression>(&src).unwrap();
+        assert_eq!(
+            r,
+            AssignmentExpression::new_with_path(
+                Ident::from("profile"),
+                vec![Ident::from("address"), Ident::from("city")],
+                Lit::new_str("Kolkata"),
+                AssignmentOperator::Assign
+            )
+        );
+    }
+    #[test]
     fn expr_add_assign() {
         let src = lex_insecure(b"followers += 100").unwrap();
         let r = parse_ast_node_full::<AssignmentExpression>(&src).unwrap();
@@ -664,18 +975,71 @@ mod expression_tests {
             )
         );
     }
+    #[test]
+    fn expr_assign_index() {
+        let src = lex_insecure(b"tags[-1] += 'x'").unwrap();
+        let r = parse_ast_node_full::<AssignmentExpression>(&src).unwrap();
+        assert_eq!(
+            r,
+            AssignmentExpression::new_with_index(
+                Ident::from("tags"),
+                -1,
+                Lit::new_str("x"),
+                AssignmentOperator::AddAssign
+            )
+        );
+    }
+    #[test]
+    fn expr_remove_index() {
+        let src = lex_insecure(b"remove tags[2]").unwrap();
+        let r = parse_ast_node_full::<AssignmentExpression>(&src).unwrap();
+        assert_eq!(
+            r,
+            AssignmentExpression::new(
+                Ident::from("tags"),
+                Lit::new_uint(2),
+                AssignmentOperator::Remove
+            )
+        );
+    }
+    #[test]
+    fn expr_truncate() {
+        let src = lex_insecure(b"truncate tags to 5").unwrap();
+        let r = parse_ast_node_full::<AssignmentExpression>(&src).unwrap();
+        assert_eq!(
+            r,
+            AssignmentExpression::new(
+                Ident::from("tags"),
+                Lit::new_uint(5),
+                AssignmentOperator::Truncate
+            )
+        );
+    }
+    #[test]
+    fn expr_bad_list_operations() {
+        for src in [
+            b"tags['a'] = 'x'".as_slice(),
+            b"tags[1 = 'x'",
+            b"remove tags",
+            b"remove tags[1",
+            b"truncate tags 5",
+        ] {
+            let src = lex_insecure(src).unwrap();
+            assert!(parse_ast_node_full::<AssignmentExpression>(&src).is_err());
+        }
+    }
 }
 mod update_statement {
     use {
         super::*,
         crate::engine::{
-            core::query_meta::AssignmentOperator,
+            core::{model::Durability, query_meta::AssignmentOperator},
             data::lit::Lit,
             ql::{
                 ast::{parse_ast_node_full, parse_ast_node_full_with_space},
                 dml::{
                     upd::{AssignmentExpression, UpdateStatement},
-                    RelationalExpr, WhereClause,
+                    RelationalExpr, ReturningClause, WhereClause,
                 },
                 lex::Ident,
             },
@@ -746,15 +1110,114 @@ mod update_statement {
         );
         assert_eq!(r, e);
     }
+    #[test]
+    fn update_conditional() {
+        let tok = lex_insecure(
+            br#"
+                update jotsy.app set notes += "note", version += 1 where username = "sayan" if version = 1
+            "#,
+        )
+        .unwrap();
+        let r = parse_ast_node_full::<UpdateStatement>(&tok[1..]).unwrap();
+        let e = UpdateStatement::new(
+            ("jotsy", "app").into(),
+            vec![
+                AssignmentExpression::new(
+                    Ident::from("notes"),
+                    Lit::new_str("note"),
+                    AssignmentOperator::AddAssign,
+                ),
+                AssignmentExpression::new(
+                    Ident::from("version"),
+                    Lit::new_uint(1),
+                    AssignmentOperator::AddAssign,
+                ),
+            ],
+            WhereClause::new(dict! {
+                Ident::from("username") => RelationalExpr::new(
+                    Ident::from("username"),
+                    Lit::new_str("sayan"),
+                    RelationalExpr::OP_EQ
+                ),
+                Ident::from("version") => RelationalExpr::new(
+                    Ident::from("version"),
+                    Lit::new_uint(1),
+                    RelationalExpr::OP_EQ
+                ),
+            }),
+        );
+        assert_eq!(r, e);
+    }
+    #[test]
+    fn update_returning() {
+        let tok = lex_insecure(
+            br#"
+                update jotsy.app set version += 1 where username = "sayan" returning version
+            "#,
+        )
+        .unwrap();
+        let r = parse_ast_node_full::<UpdateStatement>(&tok[1..]).unwrap();
+        let e = UpdateStatement::new(
+            ("jotsy", "app").into(),
+            vec![AssignmentExpression::new(
+                Ident::from("version"),
+                Lit::new_uint(1),
+                AssignmentOperator::AddAssign,
+            )],
+            WhereClause::new(dict! {
+                Ident::from("username") => RelationalExpr::new(
+                    Ident::from("username"),
+                    Lit::new_str("sayan"),
+                    RelationalExpr::OP_EQ
+                ),
+            }),
+        )
+        .with_returning(ReturningClause::new(vec![Ident::from("version")], false));
+        assert_eq!(r, e);
+    }
+    #[test]
+    fn update_with_durability() {
+        let tok = lex_insecure(
+            br#"
+                update bank.accounts set balance -= 100 where id = "a1" with durability "sync" returning balance
+            "#,
+        )
+        .unwrap();
+        let r = parse_ast_node_full::<UpdateStatement>(&tok[1..]).unwrap();
+        let e = UpdateStatement::new(
+            ("bank", "accounts").into(),
+            vec![AssignmentExpression::new(
+                Ident::from("balance"),
+                Lit::new_uint(100),
+                AssignmentOperator::SubAssign,
+            )],
+            WhereClause::new(dict! {
+                Ident::from("id") => RelationalExpr::new(
+                    Ident::from("id"),
+                    Lit::new_str("a1"),
+                    RelationalExpr::OP_EQ
+                ),
+            }),
+        )
+        .with_durability(Durability::Sync)
+        .with_returning(ReturningClause::new(vec![Ident::from("balance")], false));
+        assert_eq!(r, e);
+        // only the durability can be set
+        let tok =
+            lex_insecure(br#"update bank.accounts set balance -= 100 where id = "a1" with ttl 60"#)
+                .unwrap();
+        assert!(parse_ast_node_full::<UpdateStatement>(&tok[1..]).is_err());
+    }
 }
 mod delete_stmt {
     use {
         super::*,
         crate::engine::{
+            core::model::Durability,
             data::lit::Lit,
             ql::{
                 ast::{parse_ast_node_full, parse_ast_node_full_with_space},
-                dml::{del::DeleteStatement, RelationalExpr},
+                dml::{del::DeleteStatement, RelationalExpr, ReturningClause},
                 lex::Ident,
             },
         },
@@ -806,6 +1269,97 @@ mod delete_stmt {
             e
         );
     }
+    #[test]
+    fn delete_conditional() {
+        let tok = lex_insecure(
+            br#"
+                delete from twitter.users where username = "sayan" if followers >= 10 and verified = false
+            "#,
+        )
+        .unwrap();
+        let e = DeleteStatement::new_test(
+            ("twitter", "users").into(),
+            dict! {
+                Ident::from("username") => RelationalExpr::new(
+                    Ident::from("username"),
+                    Lit::new_str("sayan"),
+                    RelationalExpr::OP_EQ
+                ),
+                Ident::from("followers") => RelationalExpr::new(
+                    Ident::from("followers"),
+                    Lit::new_uint(10),
+                    RelationalExpr::OP_GE
+                ),
+                Ident::from("verified") => RelationalExpr::new(
+                    Ident::from("verified"),
+                    Lit::new_bool(false),
+                    RelationalExpr::OP_EQ
+                ),
+            },
+        );
+        assert_eq!(
+            parse_ast_node_full::<DeleteStatement>(&tok[1..]).unwrap(),
+            e
+        );
+    }
+    #[test]
+    fn delete_conditional_without_conditions() {
+        let tok = lex_insecure(b"delete from twitter.users where username = 'sayan' if").unwrap();
+        assert!(parse_ast_node_full::<DeleteStatement>(&tok[1..]).is_err());
+    }
+    #[test]
+    fn delete_returning() {
+        let tok = lex_insecure(
+            br#"
+                delete from twitter.users where username = "sayan" if verified = false returning *
+            "#,
+        )
+        .unwrap();
+        let e = DeleteStatement::new_test(
+            ("twitter", "users").into(),
+            dict! {
+                Ident::from("username") => RelationalExpr::new(
+                    Ident::from("username"),
+                    Lit::new_str("sayan"),
+                    RelationalExpr::OP_EQ
+                ),
+                Ident::from("verified") => RelationalExpr::new(
+                    Ident::from("verified"),
+                    Lit::new_bool(false),
+                    RelationalExpr::OP_EQ
+                ),
+            },
+        )
+        .with_returning(ReturningClause::new(vec![], true));
+        assert_eq!(
+            parse_ast_node_full::<DeleteStatement>(&tok[1..]).unwrap(),
+            e
+        );
+    }
+    #[test]
+    fn delete_with_durability() {
+        let tok = lex_insecure(
+            br#"
+                delete from bank.payments where id = "p1" with durability "group-commit"
+            "#,
+        )
+        .unwrap();
+        let e = DeleteStatement::new_test(
+            ("bank", "payments").into(),
+            dict! {
+                Ident::from("id") => RelationalExpr::new(
+                    Ident::from("id"),
+                    Lit::new_str("p1"),
+                    RelationalExpr::OP_EQ
+                )
+            },
+        )
+        .with_durability(Durability::GroupCommit);
+        assert_eq!(
+            parse_ast_node_full::<DeleteStatement>(&tok[1..]).unwrap(),
+            e
+        );
+    }
 }
 mod relational_expr {
     use {
@@ -894,6 +1448,19 @@ mod relational_expr {
             )
         );
     }
+    #[test]
+    fn expr_contains() {
+        let expr = lex_insecure(b"tags contains 'rust'").unwrap();
+        let r = parse_ast_node_full::<RelationalExpr>(&expr).unwrap();
+        assert_eq!(
+            r,
+            RelationalExpr::new(
+                Ident::from("tags"),
+                Lit::new_str("rust"),
+                RelationalExpr::OP_CONTAINS
+            )
+        );
+    }
 }
 mod where_clause {
     use {
@@ -956,6 +1523,43 @@ mod where_clause {
         .unwrap();
         assert!(parse_ast_node_full::<WhereClause>(&tok).is_err());
     }
+    #[test]
+    fn where_range() {
+        let tok = lex_insecure(
+            br#"
+                ts >= 100 and ts < 200
+            "#,
+        )
+        .unwrap();
+        let expected = WhereClause::new_with_ranges(
+            dict! {
+                Ident::from("ts") => RelationalExpr::new(
+                    Ident::from("ts"),
+                    Lit::new_uint(100),
+                    RelationalExpr::OP_GE
+                )
+            },
+            dict! {
+                Ident::from("ts") => RelationalExpr::new(
+                    Ident::from("ts"),
+                    Lit::new_uint(200),
+                    RelationalExpr::OP_LT
+                )
+            },
+        );
+        assert_eq!(expected, parse_ast_node_full::<WhereClause>(&tok).unwrap());
+    }
+    #[test]
+    fn where_bad_range() {
+        for query in [
+            "ts > 100 and ts >= 200",
+            "ts < 100 and ts != 200",
+            "ts > 100 and ts < 200 and ts <= 300",
+        ] {
+            let tok = lex_insecure(query.as_bytes()).unwrap();
+            assert!(parse_ast_node_full::<WhereClause>(&tok).is_err());
+        }
+    }
 }
 
 mod select_all {
@@ -963,7 +1567,11 @@ mod select_all {
         super::lex_insecure,
         crate::engine::{
             error::QueryError,
-            ql::{ast::parse_ast_node_full_with_space, dml::sel::SelectAllStatement},
+            ql::{
+                ast::parse_ast_node_full_with_space,
+                dml::sel::{OrderBy, SelectAllStatement},
+                lex::Ident,
+            },
         },
     };
 
@@ -1003,4 +1611,234 @@ mod select_all {
             QueryError::QLUnexpectedEndOfStatement
         );
     }
+
+    #[test]
+    fn select_all_after() {
+        let tok = lex_insecure(b"select all * from mymodel after \r5\ncurs1 limit 100").unwrap();
+        assert_eq!(
+            parse_ast_node_full_with_space::<SelectAllStatement>(&tok[2..], "myspace").unwrap(),
+            SelectAllStatement::test_new_after(
+                ("myspace", "mymodel").into(),
+                vec![],
+                true,
+                100,
+                b"curs1"
+            )
+        );
+        let tok = lex_insecure(b"select all username from mymodel after \r0\n limit 10").unwrap();
+        assert_eq!(
+            parse_ast_node_full_with_space::<SelectAllStatement>(&tok[2..], "myspace").unwrap(),
+            SelectAllStatement::test_new_after(
+                ("myspace", "mymodel").into(),
+                into_vec!["username"],
+                false,
+                10,
+                b""
+            )
+        );
+        // `after` isn't a keyword, so it can still be used as a name
+        let tok = lex_insecure(b"select all after from mymodel after \r0\n limit 10").unwrap();
+        assert_eq!(
+            parse_ast_node_full_with_space::<SelectAllStatement>(&tok[2..], "myspace").unwrap(),
+            SelectAllStatement::test_new_after(
+                ("myspace", "mymodel").into(),
+                into_vec!["after"],
+                false,
+                10,
+                b""
+            )
+        );
+    }
+
+    #[test]
+    fn select_all_bad_after() {
+        for query in [
+            // the cursor must be a binary blob
+            &b"select all * from mymodel after 'curs1' limit 100"[..],
+            // missing cursor
+            b"select all * from mymodel after limit 100",
+        ] {
+            let tok = lex_insecure(query).unwrap();
+            assert_eq!(
+                parse_ast_node_full_with_space::<SelectAllStatement>(&tok[2..], "myspace")
+                    .unwrap_err(),
+                QueryError::QLInvalidSyntax
+            );
+        }
+    }
+
+    #[test]
+    fn select_all_order_by() {
+        let tok = lex_insecure(b"select all * from mymodel order by followers limit 10").unwrap();
+        assert_eq!(
+            parse_ast_node_full_with_space::<SelectAllStatement>(&tok[2..], "myspace").unwrap(),
+            SelectAllStatement::test_new_ordered(
+                ("myspace", "mymodel").into(),
+                vec![],
+                true,
+                10,
+                OrderBy::new(Ident::from("followers"), false)
+            )
+        );
+        let tok = lex_insecure(b"select all username from mymodel order by followers asc limit 10")
+            .unwrap();
+        assert_eq!(
+            parse_ast_node_full_with_space::<SelectAllStatement>(&tok[2..], "myspace").unwrap(),
+            SelectAllStatement::test_new_ordered(
+                ("myspace", "mymodel").into(),
+                into_vec!["username"],
+                false,
+                10,
+                OrderBy::new(Ident::from("followers"), false)
+            )
+        );
+        let tok =
+            lex_insecure(b"select all * from mymodel order by followers desc limit 10").unwrap();
+        assert_eq!(
+            parse_ast_node_full_with_space::<SelectAllStatement>(&tok[2..], "myspace").unwrap(),
+            SelectAllStatement::test_new_ordered(
+                ("myspace", "mymodel").into(),
+                vec![],
+                true,
+                10,
+                OrderBy::new(Ident::from("followers"), true)
+            )
+        );
+    }
+
+    #[test]
+    fn select_all_bad_order_by() {
+        for query in [
+            // missing by
+            &b"select all * from mymodel order followers limit 10"[..],
+            // missing field
+            b"select all * from mymodel order by limit 10",
+            // can't be paginated
+            b"select all * from mymodel after \r0\n order by followers limit 10",
+            // missing limit
+            b"select all * from mymodel order by followers desc",
+        ] {
+            let tok = lex_insecure(query).unwrap();
+            assert_eq!(
+                parse_ast_node_full_with_space::<SelectAllStatement>(&tok[2..], "myspace")
+                    .unwrap_err(),
+                QueryError::QLInvalidSyntax
+            );
+        }
+    }
+}
+
+mod exists_stmt {
+    use {
+        super::lex_insecure,
+        crate::engine::{
+            data::lit::Lit,
+            error::QueryError,
+            ql::{ast::parse_ast_node_full_with_space, dml::sel::ExistsStatement},
+        },
+    };
+
+    #[test]
+    fn exists_single() {
+        let tok = lex_insecure(b"exists from mymodel where username = 'sayan'").unwrap();
+        assert_eq!(
+            parse_ast_node_full_with_space::<ExistsStatement>(&tok[1..], "myspace").unwrap(),
+            ExistsStatement::test_new(
+                ("myspace", "mymodel").into(),
+                "username".into(),
+                vec![Lit::new_str("sayan")],
+                false
+            )
+        );
+    }
+
+    #[test]
+    fn exists_multi() {
+        let tok =
+            lex_insecure(b"exists from mymodel where username in ('sayan', 'robot')").unwrap();
+        assert_eq!(
+            parse_ast_node_full_with_space::<ExistsStatement>(&tok[1..], "myspace").unwrap(),
+            ExistsStatement::test_new(
+                ("myspace", "mymodel").into(),
+                "username".into(),
+                vec![Lit::new_str("sayan"), Lit::new_str("robot")],
+                true
+            )
+        );
+    }
+
+    #[test]
+    fn exists_bad_syntax() {
+        for query in [
+            &b"exists from mymodel where username in ()"[..],
+            b"exists from mymodel where username in ('sayan',)",
+            b"exists from mymodel where username > 'sayan'",
+            b"exists from mymodel when username = 'sayan'",
+            b"exists from mymodel where username = 'sayan' and password = 'pass123'",
+            b"exists from mymodel where username in ('sayan') and username in ('robot')",
+        ] {
+            let tok = lex_insecure(query).unwrap();
+            assert_eq!(
+                parse_ast_node_full_with_space::<ExistsStatement>(&tok[1..], "myspace")
+                    .unwrap_err(),
+                QueryError::QLInvalidSyntax
+            );
+        }
+    }
+}
+
+mod subscribe_stmt {
+    use {
+        super::lex_insecure,
+        crate::engine::{
+            error::QueryError,
+            ql::{
+                ast::{parse_ast_node_full, parse_ast_node_full_with_space},
+                dml::sub::SubscribeStatement,
+            },
+        },
+    };
+
+    #[test]
+    fn subscribe() {
+        let tok = lex_insecure(b"subscribe myspace.mymodel").unwrap();
+        assert_eq!(
+            parse_ast_node_full::<SubscribeStatement>(&tok[1..]).unwrap(),
+            SubscribeStatement::new(("myspace", "mymodel").into(), None)
+        );
+    }
+
+    #[test]
+    fn subscribe_with_space() {
+        let tok = lex_insecure(b"subscribe mymodel").unwrap();
+        assert_eq!(
+            parse_ast_node_full_with_space::<SubscribeStatement>(&tok[1..], "myspace").unwrap(),
+            SubscribeStatement::new(("myspace", "mymodel").into(), None)
+        );
+    }
+
+    #[test]
+    fn subscribe_from() {
+        let tok = lex_insecure(b"subscribe myspace.mymodel from 42").unwrap();
+        assert_eq!(
+            parse_ast_node_full::<SubscribeStatement>(&tok[1..]).unwrap(),
+            SubscribeStatement::new(("myspace", "mymodel").into(), Some(42))
+        );
+    }
+
+    #[test]
+    fn subscribe_bad_syntax() {
+        for query in [
+            &b"subscribe myspace.mymodel from"[..],
+            b"subscribe myspace.mymodel from -1",
+            b"subscribe myspace.mymodel from 'sayan'",
+            b"subscribe myspace.mymodel since 42",
+        ] {
+            let tok = lex_insecure(query).unwrap();
+            assert_eq!(
+                parse_ast_node_full::<SubscribeStatement>(&tok[1..]).unwrap_err(),
+                QueryError::QLInvalidSyntax
+            );
+        }
+    }
 }
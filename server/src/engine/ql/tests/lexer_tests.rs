@@ -171,6 +171,40 @@ fn lex_unsafe_literal_pro() {
     assert_eq!(Token::Lit(Lit::new_bin(b"abcdefghi123456789")), usl[0]);
 }
 
+#[test]
+fn lex_typed_literals() {
+    let typed = lex_insecure(
+        br#"timestamp'2023-01-08T14:23:59.5Z' uuid"4593264b-0231-43e9-b0aa-50784f14e204""#,
+    )
+    .unwrap();
+    assert_eq!(
+        typed,
+        vec![
+            Token::Lit(Lit::new_timestamp(1673187839_500_000_000)),
+            Token::Lit(Lit::new_boxed_uuid(0x4593264b_0231_43e9_b0aa_50784f14e204)),
+        ]
+    );
+    // not a literal, just an ident
+    assert_eq!(
+        lex_insecure(b"timestamp uuid").unwrap(),
+        vec![
+            Token::Ident(Ident::from("timestamp")),
+            Token::Ident(Ident::from("uuid"))
+        ]
+    );
+}
+#[test]
+fn lex_typed_literals_bad() {
+    for bad in [
+        b"timestamp'yesterday'".as_slice(),
+        b"timestamp'1969-12-31T23:59:59Z'",
+        b"uuid'4593264b'",
+        b"uuid'4593264b-0231-43e9-b0aa-50784f14e204",
+    ] {
+        assert_eq!(lex_insecure(bad).unwrap_err(), QueryError::LexInvalidInput);
+    }
+}
+
 /*
     safe query tests
 */
@@ -248,6 +282,9 @@ const SFQ_SINT: &[u8] = b"\x03-9223372036854775808\n";
 const SFQ_FLOAT: &[u8] = b"\x043.141592654\n";
 const SFQ_BINARY: &[u8] = "\x0546\ncringe😃😄😁😆😅😂🤣😊😸😺".as_bytes();
 const SFQ_STRING: &[u8] = "\x0646\ncringe😃😄😁😆😅😂🤣😊😸😺".as_bytes();
+const SFQ_TIMESTAMP: &[u8] = b"\x071673187839000000000\n";
+const SFQ_UUID: &[u8] = b"\x08\x45\x93\x26\x4b\x02\x31\x43\xe9\xb0\xaa\x50\x78\x4f\x14\xe2\x04";
+const SFQ_UUID_VALUE: u128 = 0x4593264b_0231_43e9_b0aa_50784f14e204;
 
 #[test]
 fn safe_query_null() {
@@ -316,6 +353,29 @@ fn safe_query_string() {
     }
 }
 
+#[test]
+fn safe_query_timestamp() {
+    let (query, query_window) = make_safe_query(b"?", SFQ_TIMESTAMP);
+    let ts = lex_secure(&query, query_window).unwrap();
+    assert_eq!(
+        ts,
+        vec![Token::Lit(Lit::new_timestamp(1673187839_000_000_000))]
+    );
+}
+
+#[test]
+fn safe_query_uuid() {
+    let (query, query_window) = make_safe_query(b"?", SFQ_UUID);
+    let uuid = lex_secure(&query, query_window).unwrap();
+    assert_eq!(uuid, vec![Token::Lit(Lit::new_boxed_uuid(SFQ_UUID_VALUE))]);
+    // not enough bytes
+    let (query, query_window) = make_safe_query(b"?", &SFQ_UUID[..16]);
+    assert_eq!(
+        lex_secure(&query, query_window).unwrap_err(),
+        QueryError::LexInvalidInput
+    );
+}
+
 #[test]
 fn safe_params_shuffled() {
     let expected = [
@@ -335,6 +395,11 @@ fn safe_params_shuffled() {
                 "cringe😃😄😁😆😅😂🤣😊😸😺".to_owned().into(),
             )),
         ),
+        (
+            SFQ_TIMESTAMP,
+            Token::Lit(Lit::new_timestamp(1673187839_000_000_000)),
+        ),
+        (SFQ_UUID, Token::Lit(Lit::new_boxed_uuid(SFQ_UUID_VALUE))),
     ];
     let mut rng = crate::util::test_utils::randomizer();
     for _ in 0..expected.len().pow(2) {
@@ -345,7 +410,7 @@ fn safe_params_shuffled() {
             .map(|(raw, _)| raw.to_vec())
             .flatten()
             .collect();
-        let (query, query_window) = make_safe_query(b"? ? ? ? ? ? ? ?", &param_segment);
+        let (query, query_window) = make_safe_query(b"? ? ? ? ? ? ? ? ? ?", &param_segment);
         let ret = lex_secure(&query, query_window).unwrap();
         assert_eq!(
             ret,
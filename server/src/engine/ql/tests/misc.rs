@@ -27,7 +27,7 @@
 use super::*;
 use crate::engine::ql::{
     ast::State,
-    ddl::{Inspect, Use},
+    ddl::{Describe, Inspect, Use},
 };
 
 /*
@@ -113,3 +113,27 @@ fn inspect_model() {
         Inspect::Model(("myspace", "mymodel").into())
     );
 }
+
+/*
+    describe
+*/
+
+#[test]
+fn describe_space() {
+    let t = lex_insecure(b"describe space myspace").unwrap();
+    let mut state = State::new_inplace(&t[1..]);
+    assert_eq!(
+        Describe::test_parse_from_state(&mut state).unwrap(),
+        Describe::Space("myspace".into())
+    );
+}
+
+#[test]
+fn describe_model() {
+    let t = lex_insecure(b"describe model myspace.mymodel").unwrap();
+    let mut state = State::new_inplace(&t[1..]);
+    assert_eq!(
+        Describe::test_parse_from_state(&mut state).unwrap(),
+        Describe::Model(("myspace", "mymodel").into())
+    );
+}
@@ -26,7 +26,7 @@
 
 use {
     super::{super::lex::Ident, lex_insecure, *},
-    crate::engine::data::lit::Lit,
+    crate::engine::data::{cell::Datacell, lit::Lit},
 };
 
 mod alter_space {
@@ -92,6 +92,31 @@ mod tymeta {
         );
     }
     #[test]
+    fn tymeta_list() {
+        let tok = lex_insecure(br#"{oneof: ["a", "b"], empty: [], maxlen: 2 }"#).unwrap();
+        let tymeta = parse_ast_node_full::<DictTypeMeta>(&tok).unwrap();
+        assert_eq!(
+            tymeta,
+            null_dict! {
+                "oneof" => Datacell::new_list(vec![Datacell::from("a"), Datacell::from("b")]),
+                "empty" => Datacell::new_list(vec![]),
+                "maxlen" => Lit::new_uint(2)
+            }
+        );
+    }
+    #[test]
+    fn tymeta_default() {
+        let tok = lex_insecure(br#"{default: 0, created: @timesec() }"#).unwrap();
+        let tymeta = parse_ast_node_full::<DictTypeMeta>(&tok).unwrap();
+        assert_eq!(
+            tymeta,
+            null_dict! {
+                "default" => Lit::new_uint(0),
+                "created" => crate::engine::data::DictEntryGeneric::Producer("timesec".into())
+            }
+        );
+    }
+    #[test]
     fn tymeta_pro() {
         // list { maxlen: 100, type: string, unique: true }
         //        ^^^^^^^^^^^^^^^^^^ cursor should be at string
@@ -1089,7 +1114,11 @@ mod ddl_other_query_tests {
         super::*,
         crate::engine::ql::{
             ast::{parse_ast_node_full, parse_ast_node_full_with_space},
-            ddl::drop::{DropModel, DropSpace},
+            ddl::{
+                crt::CreateIndex,
+                drop::{DropIndex, DropModel, DropSpace},
+                TruncateModel,
+            },
         },
     };
     #[test]
@@ -1144,4 +1173,77 @@ mod ddl_other_query_tests {
             DropModel::new(("apps", "mymodel").into(), true, true)
         );
     }
+    #[test]
+    fn create_index() {
+        let src = lex_insecure(br"create index by_email on myspace.users(email)").unwrap();
+        assert_eq!(
+            parse_ast_node_full::<CreateIndex>(&src[1..]).unwrap(),
+            CreateIndex::new(
+                Ident::from("by_email"),
+                ("myspace", "users").into(),
+                Ident::from("email"),
+                false,
+                false
+            )
+        );
+        let src =
+            lex_insecure(br"create unique index if not exists by_email on myspace.users(email)")
+                .unwrap();
+        assert_eq!(
+            parse_ast_node_full::<CreateIndex>(&src[1..]).unwrap(),
+            CreateIndex::new(
+                Ident::from("by_email"),
+                ("myspace", "users").into(),
+                Ident::from("email"),
+                true,
+                true
+            )
+        );
+    }
+    #[test]
+    fn create_index_bad() {
+        for src in [
+            &b"create index by_email on myspace.users"[..],
+            b"create index by_email myspace.users(email)",
+            b"create unique by_email on myspace.users(email)",
+            b"create index by_email on myspace.users(email, username)",
+        ] {
+            let src = lex_insecure(src).unwrap();
+            assert!(parse_ast_node_full::<CreateIndex>(&src[1..]).is_err());
+        }
+    }
+    #[test]
+    fn drop_index() {
+        let src = lex_insecure(br"drop index by_email on myspace.users").unwrap();
+        assert_eq!(
+            parse_ast_node_full::<DropIndex>(&src[2..]).unwrap(),
+            DropIndex::new(Ident::from("by_email"), ("myspace", "users").into(), false)
+        );
+        let src = lex_insecure(br"drop index if exists by_email on myspace.users").unwrap();
+        assert_eq!(
+            parse_ast_node_full::<DropIndex>(&src[2..]).unwrap(),
+            DropIndex::new(Ident::from("by_email"), ("myspace", "users").into(), true)
+        );
+    }
+    #[test]
+    fn truncate_model() {
+        let src = lex_insecure(br"truncate model myspace.mymodel").unwrap();
+        assert_eq!(
+            parse_ast_node_full::<TruncateModel>(&src[2..]).unwrap(),
+            TruncateModel::new(("myspace", "mymodel").into())
+        );
+        let src = lex_insecure(br"truncate model mymodel").unwrap();
+        assert_eq!(
+            parse_ast_node_full_with_space::<TruncateModel>(&src[2..], "apps").unwrap(),
+            TruncateModel::new(("apps", "mymodel").into())
+        );
+        for src in [
+            br"truncate model".as_slice(),
+            br"truncate model myspace.mymodel allow not empty",
+            br"truncate model myspace.",
+        ] {
+            let src = lex_insecure(src).unwrap();
+            assert!(parse_ast_node_full::<TruncateModel>(&src[2..]).is_err());
+        }
+    }
 }
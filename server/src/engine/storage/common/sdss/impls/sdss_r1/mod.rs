@@ -412,6 +412,10 @@ pub trait FileSpecV1 {
     }
     /// write metadata
     fn write_metadata(f: &mut impl FileWrite, args: Self::EncodeArgs) -> IoResult<Self::Metadata>;
+    /// Returns true if the file was written with an older (but still supported) revision of this spec
+    fn is_outdated(_: &Self::Metadata) -> bool {
+        false
+    }
     fn metadata_to_block(args: Self::EncodeArgs) -> RuntimeResult<Vec<u8>> {
         let mut v = Vec::new();
         Self::write_metadata(&mut v, args)?;
@@ -476,4 +480,7 @@ impl<Sfs: SimpleFileSpecV1> FileSpecV1 for Sfs {
         );
         f.fwrite_all(&block).map(|_| md)
     }
+    fn is_outdated(md: &Self::Metadata) -> bool {
+        md.file_specifier_version() != Self::FILE_SPECFIER_VERSION
+    }
 }
@@ -59,6 +59,9 @@ impl<S: FileSpecV1, F> SdssFile<S, F> {
     fn new(file: F, meta: S::Metadata) -> Self {
         Self { file, meta }
     }
+    pub fn get_md(&self) -> &S::Metadata {
+        &self.meta
+    }
 }
 
 impl<S: FileSpecV1> SdssFile<S> {
@@ -192,6 +195,9 @@ impl<'a, S: FileSpecV1> TrackedReaderContext<'a, S> {
     pub fn remaining(&self) -> u64 {
         self.tr.remaining()
     }
+    pub fn get_md(&self) -> &S::Metadata {
+        self.tr.f.get_md()
+    }
 }
 
 impl<S: FileSpecV1> TrackedReader<S> {
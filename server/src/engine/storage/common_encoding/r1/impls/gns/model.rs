@@ -29,6 +29,7 @@ use {
     crate::{
         engine::{
             core::{
+                index::SecondaryIndex,
                 model::{Field, Model, ModelData},
                 space::Space,
                 EntityID, EntityIDRef, GNSData,
@@ -41,8 +42,8 @@ use {
             storage::common_encoding::r1::{self, map, obj, PersistObject},
             txn::{
                 gns::model::{
-                    AlterModelAddTxn, AlterModelRemoveTxn, AlterModelUpdateTxn, CreateModelTxn,
-                    DropModelTxn,
+                    AlterModelAddTxn, AlterModelRemoveTxn, AlterModelUpdateTxn, CreateIndexTxn,
+                    CreateModelTxn, DropIndexTxn, DropModelTxn,
                 },
                 ModelIDRef,
             },
@@ -603,7 +604,180 @@ impl<'a> GNSEvent for DropModelTxn<'a> {
             if removed_model.data().get_uuid() != model_uuid {
                 return Err(TransactionError::OnRestoreDataConflictMismatch.into());
             }
+            gns.sys_db()
+                .__raw_purge_grants(&space_id.name, Some(&model_name));
             Ok(())
         })
     }
 }
+
+/*
+    create index
+*/
+
+pub struct CreateIndexTxnMD {
+    model_id_md: ModelIDMD,
+    index_name_l: u64,
+    field_l: u64,
+    unique: u8,
+}
+#[derive(Debug, PartialEq)]
+pub struct CreateIndexTxnRestorePL {
+    pub(super) model_id: ModelIDRes,
+    pub(super) index_name: Box<str>,
+    pub(super) field: Box<str>,
+    pub(super) unique: bool,
+}
+
+impl<'a> PersistObject for CreateIndexTxn<'a> {
+    const METADATA_SIZE: usize = <ModelID as PersistObject>::METADATA_SIZE + sizeof!(u64, 2) + 1;
+    type InputType = CreateIndexTxn<'a>;
+    type OutputType = CreateIndexTxnRestorePL;
+    type Metadata = CreateIndexTxnMD;
+    fn pretest_can_dec_object(scanner: &BufferedScanner, md: &Self::Metadata) -> bool {
+        scanner.has_left(
+            (md.model_id_md.space_id.space_name_l
+                + md.model_id_md.model_name_l
+                + md.index_name_l
+                + md.field_l) as usize,
+        )
+    }
+    fn meta_enc(buf: &mut Vec<u8>, data: Self::InputType) {
+        // [model id][index name l][field l][unique]
+        <ModelID as PersistObject>::meta_enc(buf, data.model_id());
+        buf.extend(data.index().name().len().u64_bytes_le());
+        buf.extend(data.index().field().len().u64_bytes_le());
+        buf.push(data.index().is_unique() as u8);
+    }
+    unsafe fn meta_dec(scanner: &mut BufferedScanner) -> RuntimeResult<Self::Metadata> {
+        let model_id_md = <ModelID as PersistObject>::meta_dec(scanner)?;
+        let index_name_l = scanner.next_u64_le();
+        let field_l = scanner.next_u64_le();
+        let unique = scanner.next_byte();
+        if unique > 1 {
+            return Err(StorageError::InternalDecodeStructureIllegalData.into());
+        }
+        Ok(CreateIndexTxnMD {
+            model_id_md,
+            index_name_l,
+            field_l,
+            unique,
+        })
+    }
+    fn obj_enc(buf: &mut Vec<u8>, data: Self::InputType) {
+        <ModelID as PersistObject>::obj_enc(buf, data.model_id());
+        buf.extend(data.index().name().as_bytes());
+        buf.extend(data.index().field().as_bytes());
+    }
+    unsafe fn obj_dec(
+        s: &mut BufferedScanner,
+        md: Self::Metadata,
+    ) -> RuntimeResult<Self::OutputType> {
+        let model_id = <ModelID as PersistObject>::obj_dec(s, md.model_id_md)?;
+        let index_name =
+            r1::dec::utils::decode_string(s, md.index_name_l as usize)?.into_boxed_str();
+        let field = r1::dec::utils::decode_string(s, md.field_l as usize)?.into_boxed_str();
+        Ok(CreateIndexTxnRestorePL {
+            model_id,
+            index_name,
+            field,
+            unique: md.unique == 1,
+        })
+    }
+}
+
+impl<'a> GNSEvent for CreateIndexTxn<'a> {
+    type CommitType = CreateIndexTxn<'a>;
+    type RestoreType = CreateIndexTxnRestorePL;
+    fn update_global_state(
+        CreateIndexTxnRestorePL {
+            model_id,
+            index_name,
+            field,
+            unique,
+        }: Self::RestoreType,
+        gns: &GNSData,
+    ) -> RuntimeResult<()> {
+        with_model_mut(gns, &model_id.space_id, &model_id, |model| {
+            // the index is populated once the model's data has been loaded
+            if model.restore_secondary_index(SecondaryIndex::new_empty(index_name, field, unique)) {
+                Ok(())
+            } else {
+                Err(TransactionError::OnRestoreDataConflictMismatch.into())
+            }
+        })
+    }
+}
+
+/*
+    drop index
+*/
+
+pub struct DropIndexTxnMD {
+    model_id_md: ModelIDMD,
+    index_name_l: u64,
+}
+#[derive(Debug, PartialEq)]
+pub struct DropIndexTxnRestorePL {
+    pub(super) model_id: ModelIDRes,
+    pub(super) index_name: Box<str>,
+}
+
+impl<'a> PersistObject for DropIndexTxn<'a> {
+    const METADATA_SIZE: usize = <ModelID as PersistObject>::METADATA_SIZE + sizeof!(u64);
+    type InputType = DropIndexTxn<'a>;
+    type OutputType = DropIndexTxnRestorePL;
+    type Metadata = DropIndexTxnMD;
+    fn pretest_can_dec_object(scanner: &BufferedScanner, md: &Self::Metadata) -> bool {
+        scanner.has_left(
+            (md.model_id_md.space_id.space_name_l + md.model_id_md.model_name_l + md.index_name_l)
+                as usize,
+        )
+    }
+    fn meta_enc(buf: &mut Vec<u8>, data: Self::InputType) {
+        <ModelID as PersistObject>::meta_enc(buf, data.model_id());
+        buf.extend(data.index_name().len().u64_bytes_le());
+    }
+    unsafe fn meta_dec(scanner: &mut BufferedScanner) -> RuntimeResult<Self::Metadata> {
+        Ok(DropIndexTxnMD {
+            model_id_md: <ModelID as PersistObject>::meta_dec(scanner)?,
+            index_name_l: scanner.next_u64_le(),
+        })
+    }
+    fn obj_enc(buf: &mut Vec<u8>, data: Self::InputType) {
+        <ModelID as PersistObject>::obj_enc(buf, data.model_id());
+        buf.extend(data.index_name().as_bytes());
+    }
+    unsafe fn obj_dec(
+        s: &mut BufferedScanner,
+        md: Self::Metadata,
+    ) -> RuntimeResult<Self::OutputType> {
+        let model_id = <ModelID as PersistObject>::obj_dec(s, md.model_id_md)?;
+        let index_name =
+            r1::dec::utils::decode_string(s, md.index_name_l as usize)?.into_boxed_str();
+        Ok(DropIndexTxnRestorePL {
+            model_id,
+            index_name,
+        })
+    }
+}
+
+impl<'a> GNSEvent for DropIndexTxn<'a> {
+    type CommitType = DropIndexTxn<'a>;
+    type RestoreType = DropIndexTxnRestorePL;
+    fn update_global_state(
+        DropIndexTxnRestorePL {
+            model_id,
+            index_name,
+        }: Self::RestoreType,
+        gns: &GNSData,
+    ) -> RuntimeResult<()> {
+        with_model_mut(gns, &model_id.space_id, &model_id, |model| {
+            if model.restore_drop_secondary_index(&index_name) {
+                Ok(())
+            } else {
+                Err(TransactionError::OnRestoreDataMissing.into())
+            }
+        })
+    }
+}
@@ -243,7 +243,8 @@ impl<'a> GNSEvent for DropSpaceTxn<'a> {
                         };
                         let _ = wmodel.st_delete(&id);
                     }
-                    oe.remove_entry();
+                    let (name, _) = oe.remove_entry();
+                    gns.sys_db().__raw_purge_grants(&name, None);
                     Ok(())
                 } else {
                     return Err(TransactionError::OnRestoreDataConflictMismatch.into());
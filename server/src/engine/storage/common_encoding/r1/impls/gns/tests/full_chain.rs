@@ -26,10 +26,12 @@
 
 use crate::engine::{
     core::{
+        dml,
+        index::PrimaryIndexKind,
         model::{Field, Layer, ModelData},
         space::Space,
     },
-    data::{cell::Datacell, tag::TagSelector, uuid::Uuid, DictEntryGeneric},
+    data::{cell::Datacell, lit::Lit, tag::TagSelector, uuid::Uuid, DictEntryGeneric},
     error::QueryError,
     fractal::{test_utils::TestGlobal, GlobalInstanceLike},
     idx::STIndex,
@@ -197,7 +199,53 @@ fn create_model() {
                             into_dict! {
                                 "username" => Field::new([Layer::str()].into(), false),
                                 "password" => Field::new([Layer::bin()].into(), false),
-                            }
+                            },
+                            PrimaryIndexKind::Hashed,
+                        )
+                    );
+                    Ok(())
+                })
+                .unwrap();
+        })
+    })
+}
+
+#[test]
+fn create_ordered_model() {
+    with_variable("create_ordered_model_test.global.db-tlog", |log_name| {
+        let uuid_model;
+        {
+            let global = TestGlobal::new_with_driver_id(log_name);
+            init_space(&global, "myspace", "{}");
+            let stmt = lex_insecure(
+                b"create model myspace.mymodel(ts: uint64, reading: float64) with { index: \"ordered\" }",
+            )
+            .unwrap();
+            let stmt = parse_ast_node_full::<CreateModel>(&stmt[2..]).unwrap();
+            ModelData::transactional_exec_create(&global, stmt).unwrap();
+            uuid_model = global
+                .state()
+                .namespace()
+                .with_model(("myspace", "mymodel").into(), |model| Ok(model.get_uuid()))
+                .unwrap();
+        }
+        multirun(|| {
+            let global = TestGlobal::new_with_driver_id(log_name);
+            global
+                .state()
+                .namespace()
+                .with_model(("myspace", "mymodel").into(), |model| {
+                    assert_eq!(
+                        model,
+                        &ModelData::new_restore(
+                            uuid_model,
+                            "ts".into(),
+                            TagSelector::UInt64.into_full(),
+                            into_dict! {
+                                "ts" => Field::new([Layer::uint64()].into(), false),
+                                "reading" => Field::new([Layer::float64()].into(), false),
+                            },
+                            PrimaryIndexKind::Ordered,
                         )
                     );
                     Ok(())
@@ -331,3 +379,80 @@ fn drop_model() {
         })
     })
 }
+
+#[test]
+fn create_index() {
+    with_variable("create_index_test.global.db-tlog", |log_name| {
+        {
+            let global = TestGlobal::new_with_driver_id_instant_update(log_name);
+            init_space(&global, "myspace", "{}");
+            init_model(
+                &global,
+                "myspace",
+                "mymodel",
+                "username: string, email: string",
+            );
+            for (username, email) in [
+                ("sayan", "sayan@example.com"),
+                ("elena", "elena@example.com"),
+            ] {
+                let insert = format!("insert into myspace.mymodel('{username}', '{email}')");
+                let stmt = lex_insecure(insert.as_bytes()).unwrap();
+                dml::insert(&global, parse_ast_node_full(&stmt[1..]).unwrap()).unwrap();
+            }
+            let stmt =
+                lex_insecure(b"create unique index by_email on myspace.mymodel(email)").unwrap();
+            let stmt = parse_ast_node_full(&stmt[1..]).unwrap();
+            ModelData::transactional_exec_create_index(&global, stmt).unwrap();
+        }
+        multirun(|| {
+            let global = TestGlobal::new_with_driver_id(log_name);
+            global
+                .state()
+                .namespace()
+                .with_model(("myspace", "mymodel").into(), |model| {
+                    // the index is restored from the GNS and its contents are rebuilt from the data
+                    let index = model.secondary_indexes().get("by_email").unwrap();
+                    assert_eq!(index.field(), "email");
+                    assert!(index.is_unique());
+                    assert_eq!(index.count(), 2);
+                    assert_eq!(index.lookup(Lit::new_str("elena@example.com")).len(), 1);
+                    Ok(())
+                })
+                .unwrap();
+        })
+    })
+}
+
+#[test]
+fn drop_index() {
+    with_variable("drop_index_test.global.db-tlog", |log_name| {
+        {
+            let global = TestGlobal::new_with_driver_id(log_name);
+            init_space(&global, "myspace", "{}");
+            init_model(
+                &global,
+                "myspace",
+                "mymodel",
+                "username: string, email: string",
+            );
+            let stmt = lex_insecure(b"create index by_email on myspace.mymodel(email)").unwrap();
+            let stmt = parse_ast_node_full(&stmt[1..]).unwrap();
+            ModelData::transactional_exec_create_index(&global, stmt).unwrap();
+            let stmt = lex_insecure(b"drop index by_email on myspace.mymodel").unwrap();
+            let stmt = parse_ast_node_full(&stmt[2..]).unwrap();
+            ModelData::transactional_exec_drop_index(&global, stmt).unwrap();
+        }
+        multirun(|| {
+            let global = TestGlobal::new_with_driver_id(log_name);
+            global
+                .state()
+                .namespace()
+                .with_model(("myspace", "mymodel").into(), |model| {
+                    assert!(model.secondary_indexes().is_empty());
+                    Ok(())
+                })
+                .unwrap();
+        })
+    })
+}
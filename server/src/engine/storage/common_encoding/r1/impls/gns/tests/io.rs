@@ -93,16 +93,20 @@ mod model_tests {
         super::{
             model::{
                 AlterModelAddTxnRestorePL, AlterModelRemoveTxnRestorePL,
-                AlterModelUpdateTxnRestorePL, CreateModelTxnRestorePL,
+                AlterModelUpdateTxnRestorePL, CreateIndexTxnRestorePL, CreateModelTxnRestorePL,
+                DropIndexTxnRestorePL,
             },
             ModelData, Space,
         },
         crate::engine::{
-            core::model::{Field, Layer},
+            core::{
+                index::{PrimaryIndexKind, SecondaryIndex},
+                model::{Field, Layer},
+            },
             data::{tag::TagSelector, uuid::Uuid},
             txn::gns::model::{
-                AlterModelAddTxn, AlterModelRemoveTxn, AlterModelUpdateTxn, CreateModelTxn,
-                DropModelTxn,
+                AlterModelAddTxn, AlterModelRemoveTxn, AlterModelUpdateTxn, CreateIndexTxn,
+                CreateModelTxn, DropIndexTxn, DropModelTxn,
             },
         },
     };
@@ -116,6 +120,7 @@ mod model_tests {
                 "password" => Field::new([Layer::bin()].into(), false),
                 "profile_pic" => Field::new([Layer::bin()].into(), true),
             ),
+            PrimaryIndexKind::Hashed,
         );
         (space, model)
     }
@@ -250,4 +255,61 @@ mod model_tests {
             decoded
         );
     }
+    #[test]
+    fn create_index() {
+        let (space, model) = default_space_model();
+        let index = SecondaryIndex::new_empty("by_password".into(), "password".into(), true);
+        let txn = CreateIndexTxn::new(
+            super::ModelIDRef::new(
+                super::SpaceIDRef::new("myspace", &space),
+                "mymodel",
+                model.get_uuid(),
+                model.delta_state().schema_current_version().value_u64(),
+            ),
+            &index,
+        );
+        let encoded = super::enc::full_self(txn);
+        let decoded = super::dec::full::<CreateIndexTxn>(&encoded).unwrap();
+        assert_eq!(
+            CreateIndexTxnRestorePL {
+                model_id: super::ModelIDRes::new(
+                    super::SpaceIDRes::new(space.get_uuid(), "myspace".into()),
+                    "mymodel".into(),
+                    model.get_uuid(),
+                    model.delta_state().schema_current_version().value_u64()
+                ),
+                index_name: "by_password".into(),
+                field: "password".into(),
+                unique: true,
+            },
+            decoded
+        );
+    }
+    #[test]
+    fn drop_index() {
+        let (space, model) = default_space_model();
+        let txn = DropIndexTxn::new(
+            super::ModelIDRef::new(
+                super::SpaceIDRef::new("myspace", &space),
+                "mymodel",
+                model.get_uuid(),
+                model.delta_state().schema_current_version().value_u64(),
+            ),
+            "by_password",
+        );
+        let encoded = super::enc::full_self(txn);
+        let decoded = super::dec::full::<DropIndexTxn>(&encoded).unwrap();
+        assert_eq!(
+            DropIndexTxnRestorePL {
+                model_id: super::ModelIDRes::new(
+                    super::SpaceIDRes::new(space.get_uuid(), "myspace".into()),
+                    "mymodel".into(),
+                    model.get_uuid(),
+                    model.delta_state().schema_current_version().value_u64()
+                ),
+                index_name: "by_password".into(),
+            },
+            decoded
+        );
+    }
 }
@@ -184,6 +184,12 @@ impl MapStorageSpec for GenericDictSpec {
                 buf.extend(key.as_bytes());
                 cell::encode_cell(buf, dc);
             }
+            DictEntryGeneric::Producer(name) => {
+                buf.push(StorageCellTypeID::Producer.value_u8());
+                buf.extend(key.as_bytes());
+                buf.extend(name.len().u64_bytes_le());
+                buf.extend(name.as_bytes());
+            }
         }
     }
     fn encode_entry_key(_: &mut VecU8, _: &Self::InMemoryKey) {
@@ -221,6 +227,16 @@ impl MapStorageSpec for GenericDictSpec {
         scanner: &mut BufferedScanner,
         md: &Self::EntryMetadata,
     ) -> Option<Self::RestoredVal> {
+        if md.dscr == StorageCellTypeID::Producer.value_u8() {
+            // UNSAFE(@ohsayan): +pretest (the length)
+            let len = scanner.next_u64_le() as usize;
+            if !scanner.has_left(len) {
+                return None;
+            }
+            return super::dec::utils::decode_string(scanner, len)
+                .map(|name| DictEntryGeneric::Producer(name.into_boxed_str()))
+                .ok();
+        }
         Some(
             match cell::decode_element::<CanYieldDict, BufferedScanner>(
                 scanner,
@@ -326,7 +342,7 @@ impl<FM: FieldMapAny> MapStorageSpec for FieldMapSpec<FM> {
     }
     fn encode_entry_meta(buf: &mut VecU8, key: &Self::InMemoryKey, val: &Self::InMemoryVal) {
         buf.extend(key.len().u64_bytes_le());
-        buf.extend(0u64.to_le_bytes()); // TODO(@ohsayan): props
+        buf.extend(val.props().len().u64_bytes_le());
         buf.extend(val.layers().len().u64_bytes_le());
         buf.push(val.is_nullable() as u8);
     }
@@ -337,9 +353,7 @@ impl<FM: FieldMapAny> MapStorageSpec for FieldMapSpec<FM> {
         buf.extend(key.as_bytes());
     }
     fn encode_entry_val(buf: &mut VecU8, val: &Self::InMemoryVal) {
-        for layer in val.layers() {
-            super::obj::LayerRef::default_full_enc(buf, super::obj::LayerRef(layer))
-        }
+        super::obj::FieldRef::obj_enc(buf, val)
     }
     fn decode_pretest_for_entry_meta(scanner: &mut BufferedScanner) -> bool {
         scanner.has_left(sizeof!(u64, 3) + 1)
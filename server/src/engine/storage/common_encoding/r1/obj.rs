@@ -33,7 +33,7 @@ use {
                 space::Space,
             },
             data::{
-                tag::{DataTag, TagClass, TagSelector},
+                tag::{DataTag, TagSelector},
                 uuid::Uuid,
                 DictGeneric,
             },
@@ -56,6 +56,7 @@ pub mod cell {
         crate::{
             engine::data::{
                 cell::Datacell,
+                dict::DataDict,
                 tag::{DataTag, TagClass, TagSelector},
             },
             util::{compiler::TaggedEnum, EndianQW},
@@ -92,6 +93,12 @@ pub mod cell {
         Str = 0x0D,
         List = 0x0E,
         Dict = 0x0F,
+        Timestamp = 0x10,
+        Uuid = 0x11,
+        /// a `dict` cell (unlike [`Self::Dict`] which is a nested map in a generic dict)
+        DataDict = 0x12,
+        /// a producer function in a generic dict (`[name len][name]`)
+        Producer = 0x13,
     }
     impl StorageCellTypeID {
         #[inline(always)]
@@ -100,10 +107,19 @@ pub mod cell {
         }
         unsafe fn into_selector(self) -> TagSelector {
             debug_assert!(self.value_u8() != Self::Null.value_u8());
-            TagSelector::from_raw(self.value_u8() - 1)
+            debug_assert!(self.value_u8() != Self::Dict.value_u8());
+            match self {
+                Self::Timestamp => TagSelector::Timestamp,
+                Self::Uuid => TagSelector::Uuid,
+                Self::DataDict => TagSelector::Dict,
+                _ => TagSelector::from_raw(self.value_u8() - 1),
+            }
         }
         #[inline(always)]
         pub fn expect_atleast(d: u8) -> usize {
+            if d == Self::Uuid.value_u8() {
+                return sizeof!(u128);
+            }
             [0u8, 1, 8, 8][d.min(3) as usize] as usize
         }
     }
@@ -112,7 +128,14 @@ pub mod cell {
         encode_cell(buf, dc)
     }
     pub fn encode_tag(dc: &Datacell) -> u8 {
-        (dc.tag().tag_selector().value_u8() + 1) * (dc.is_init() as u8)
+        let dscr = match dc.tag().tag_selector() {
+            // the IDs following the selector were already taken by the dict
+            TagSelector::Timestamp => StorageCellTypeID::Timestamp.value_u8(),
+            TagSelector::Uuid => StorageCellTypeID::Uuid.value_u8(),
+            TagSelector::Dict => StorageCellTypeID::DataDict.value_u8(),
+            selector => selector.value_u8() + 1,
+        };
+        dscr * (dc.is_init() as u8)
     }
     pub fn encode_cell(buf: &mut VecU8, dc: &Datacell) {
         if dc.is_null() {
@@ -123,7 +146,10 @@ pub mod cell {
             match dc.tag().tag_class() {
                 Bool if dc.is_init() => buf.push(dc.read_bool() as u8),
                 Bool => {}
-                UnsignedInt | SignedInt | Float => buf.extend(dc.read_uint().to_le_bytes()),
+                UnsignedInt | SignedInt | Float | Timestamp => {
+                    buf.extend(dc.read_uint().to_le_bytes())
+                }
+                Uuid => buf.extend(dc.read_uuid().to_le_bytes()),
                 Str | Bin => {
                     let slc = dc.read_bin();
                     buf.extend(slc.len().u64_bytes_le());
@@ -136,6 +162,15 @@ pub mod cell {
                         encode(buf, item);
                     }
                 }
+                Dict => {
+                    let dict = dc.read_dict().read();
+                    buf.extend(dict.len().u64_bytes_le());
+                    for (key, value) in dict.iter() {
+                        buf.extend(key.len().u64_bytes_le());
+                        buf.extend(key.as_bytes());
+                        encode(buf, value);
+                    }
+                }
             }
         }
     }
@@ -196,6 +231,10 @@ pub mod cell {
         if dscr == StorageCellTypeID::Null {
             return Ok(EY::yield_data(Datacell::null())?);
         }
+        if dscr == StorageCellTypeID::Producer {
+            // only a generic dict can have a producer and it decodes the producer itself
+            return Ok(EY::error()?);
+        }
         let tag = dscr.into_selector().into_full();
         let d = match tag.tag_class() {
             TagClass::Bool => {
@@ -205,10 +244,11 @@ pub mod cell {
                 }
                 Datacell::new_bool(nx == 1)
             }
-            TagClass::UnsignedInt | TagClass::SignedInt | TagClass::Float => {
+            TagClass::UnsignedInt | TagClass::SignedInt | TagClass::Float | TagClass::Timestamp => {
                 let nx = s.read_next_u64_le()?;
                 Datacell::new_qw(nx, tag)
             }
+            TagClass::Uuid => Datacell::new_uuid(u128::from_le_bytes(s.read_next_block()?)),
             TagClass::Bin | TagClass::Str => {
                 let len = s.read_next_u64_le()? as usize;
                 let block = s.read_next_variable_block(len)?;
@@ -239,6 +279,37 @@ pub mod cell {
                 }
                 Datacell::new_list(l)
             }
+            TagClass::Dict => {
+                let len = s.read_next_u64_le()? as usize;
+                let mut d = DataDict::new();
+                while (d.len() != len) & s.has_remaining(sizeof!(u64)) {
+                    // [key len][key][value]
+                    let key_len = s.read_next_u64_le()? as usize;
+                    if !s.has_remaining(key_len) {
+                        return Ok(EY::error()?);
+                    }
+                    let Ok(key) = String::from_utf8(s.read_next_variable_block(key_len)?) else {
+                        return Ok(EY::error()?);
+                    };
+                    if !s.has_remaining(1) {
+                        return Ok(EY::error()?);
+                    }
+                    let Some(dscr) = StorageCellTypeID::try_from_raw(s.read_next_byte()?) else {
+                        return Ok(EY::error()?);
+                    };
+                    if !s.has_remaining(StorageCellTypeID::expect_atleast(dscr.value_u8())) {
+                        return Ok(EY::error()?);
+                    }
+                    let value = self::decode_element::<Datacell, DS>(s, dscr)?;
+                    if d.insert(key.into_boxed_str(), value).is_some() {
+                        return Ok(EY::error()?);
+                    }
+                }
+                if d.len() != len {
+                    return Ok(EY::error()?);
+                }
+                Datacell::new_dict(d)
+            }
         };
         Ok(EY::yield_data(d)?)
     }
@@ -280,22 +351,50 @@ impl<'a> PersistObject for LayerRef<'a> {
     }
     fn meta_enc(buf: &mut VecU8, LayerRef(layer): Self::InputType) {
         buf.extend(layer.tag().tag_selector().value_qword().to_le_bytes());
-        buf.extend(0u64.to_le_bytes());
+        buf.extend(
+            layer
+                .props()
+                .map_or(0, |props| props.count())
+                .u64_bytes_le(),
+        );
     }
     unsafe fn meta_dec(scanner: &mut BufferedScanner) -> RuntimeResult<Self::Metadata> {
         Ok(LayerMD::new(scanner.next_u64_le(), scanner.next_u64_le()))
     }
-    fn obj_enc(_: &mut VecU8, _: Self::InputType) {}
+    fn obj_enc(buf: &mut VecU8, LayerRef(layer): Self::InputType) {
+        if let Some(props) = layer.props() {
+            <super::map::PersistMapImpl<super::map::GenericDictSpec> as PersistObject>::obj_enc(
+                buf,
+                &props.to_dict(),
+            )
+        }
+    }
     unsafe fn obj_dec(
-        _: &mut BufferedScanner,
+        scanner: &mut BufferedScanner,
         md: Self::Metadata,
     ) -> RuntimeResult<Self::OutputType> {
-        if (md.type_selector > TagSelector::List.value_qword()) | (md.prop_set_arity != 0) {
+        if md.type_selector > TagSelector::MAX_DSCR as u64 {
             return Err(StorageError::InternalDecodeStructureCorruptedPayload.into());
         }
-        Ok(Layer::new_empty_props(
-            TagSelector::from_raw(md.type_selector as u8).into_full(),
-        ))
+        let layer =
+            Layer::new_empty_props(TagSelector::from_raw(md.type_selector as u8).into_full());
+        if md.prop_set_arity == 0 {
+            return Ok(layer);
+        }
+        let props =
+            <super::map::PersistMapImpl<super::map::GenericDictSpec> as PersistObject>::obj_dec(
+                scanner,
+                super::map::MapIndexSizeMD(md.prop_set_arity as usize),
+            )?;
+        // the props must be exactly what we encoded and must still be valid for the layer
+        match layer.with_props(props) {
+            Some(layer)
+                if layer.props().map_or(0, |props| props.count()) as u64 == md.prop_set_arity =>
+            {
+                Ok(layer)
+            }
+            _ => Err(StorageError::InternalDecodeStructureCorruptedPayload.into()),
+        }
     }
 }
 
@@ -335,7 +434,7 @@ impl<'a> PersistObject for FieldRef<'a> {
     }
     fn meta_enc(buf: &mut VecU8, slf: Self::InputType) {
         // [prop_c][layer_c][null]
-        buf.extend(0u64.to_le_bytes());
+        buf.extend(slf.props().len().u64_bytes_le());
         buf.extend(slf.layers().len().u64_bytes_le());
         buf.push(slf.is_nullable() as u8);
     }
@@ -350,6 +449,13 @@ impl<'a> PersistObject for FieldRef<'a> {
         for layer in slf.layers() {
             LayerRef::default_full_enc(buf, LayerRef(layer));
         }
+        // props follow the layers since they are validated against them
+        let props = slf.props();
+        if !props.is_empty() {
+            <super::map::PersistMapImpl<super::map::GenericDictSpec> as PersistObject>::obj_enc(
+                buf, &props,
+            )
+        }
     }
     unsafe fn obj_dec(
         scanner: &mut BufferedScanner,
@@ -367,15 +473,24 @@ impl<'a> PersistObject for FieldRef<'a> {
                 LayerRef::meta_dec(scanner)?
             };
             let l = LayerRef::obj_dec(scanner, layer_md)?;
-            fin = l.tag().tag_class() != TagClass::List;
+            fin = l.is_final(layers.len() as u64 + 1 < md.layer_c);
             layers.push(l);
         }
         let field = Field::new(layers, md.null == 1);
-        if (field.layers().len() as u64 == md.layer_c) & (md.null <= 1) & (md.prop_c == 0) & fin {
-            Ok(field)
-        } else {
-            Err(StorageError::InternalDecodeStructureCorrupted.into())
+        if !((field.layers().len() as u64 == md.layer_c) & (md.null <= 1) & fin) {
+            return Err(StorageError::InternalDecodeStructureCorrupted.into());
         }
+        if md.prop_c == 0 {
+            return Ok(field);
+        }
+        let props =
+            <super::map::PersistMapImpl<super::map::GenericDictSpec> as PersistObject>::obj_dec(
+                scanner,
+                super::map::MapIndexSizeMD(md.prop_c as usize),
+            )?;
+        field
+            .with_props(props)
+            .ok_or_else(|| StorageError::InternalDecodeStructureCorruptedPayload.into())
     }
 }
 
@@ -406,6 +521,17 @@ impl ModelLayoutMD {
     }
 }
 
+/*
+    NOTE(@ohsayan): the primary key's tag only needs a byte, so the upper bytes of its qword hold the version of the
+    layout. Version 0 (models created before model properties existed) is just the key and the fields, while version 1
+    is followed by the model's property map: `[prop_c][props]`
+*/
+
+/// the layout version is stored above the primary key's tag
+const MODEL_LAYOUT_VERSION_SHIFT: u32 = 8;
+/// the current layout version (with a property map)
+const MODEL_LAYOUT_VERSION: u64 = 1;
+
 #[derive(Clone, Copy)]
 pub struct ModelLayoutRef<'a>(pub(super) &'a ModelData);
 impl<'a> From<&'a ModelData> for ModelLayoutRef<'a> {
@@ -424,7 +550,11 @@ impl<'a> PersistObject for ModelLayoutRef<'a> {
     fn meta_enc(buf: &mut VecU8, ModelLayoutRef(model_def): Self::InputType) {
         buf.extend(model_def.get_uuid().to_le_bytes());
         buf.extend(model_def.p_key().len().u64_bytes_le());
-        buf.extend(model_def.p_tag().tag_selector().value_qword().to_le_bytes());
+        buf.extend(
+            (model_def.p_tag().tag_selector().value_qword()
+                | (MODEL_LAYOUT_VERSION << MODEL_LAYOUT_VERSION_SHIFT))
+                .to_le_bytes(),
+        );
         buf.extend(model_def.fields().len().u64_bytes_le());
     }
     unsafe fn meta_dec(scanner: &mut BufferedScanner) -> RuntimeResult<Self::Metadata> {
@@ -440,6 +570,11 @@ impl<'a> PersistObject for ModelLayoutRef<'a> {
         <super::map::PersistMapImpl<super::map::FieldMapSpec<_>> as PersistObject>::obj_enc(
             buf,
             model_definition.fields(),
+        );
+        let props = model_definition.props();
+        buf.extend(props.len().u64_bytes_le());
+        <super::map::PersistMapImpl<super::map::GenericDictSpec> as PersistObject>::obj_enc(
+            buf, &props,
         )
     }
     unsafe fn obj_dec(
@@ -452,17 +587,31 @@ impl<'a> PersistObject for ModelLayoutRef<'a> {
         > as PersistObject>::obj_dec(
             scanner, super::map::MapIndexSizeMD(md.field_c as usize)
         )?;
-        let ptag = if md.p_key_tag > TagSelector::MAX_DSCR as u64 {
+        let ptag = md.p_key_tag & !(u64::MAX << MODEL_LAYOUT_VERSION_SHIFT);
+        let ptag = if ptag > TagSelector::MAX_DSCR as u64 {
             return Err(StorageError::InternalDecodeStructureCorruptedPayload.into());
         } else {
-            TagSelector::from_raw(md.p_key_tag as u8)
+            TagSelector::from_raw(ptag as u8)
+        };
+        let props = match md.p_key_tag >> MODEL_LAYOUT_VERSION_SHIFT {
+            0 => DictGeneric::new(),
+            MODEL_LAYOUT_VERSION if scanner.has_left(sizeof!(u64)) => {
+                let prop_c = scanner.next_u64_le();
+                <super::map::PersistMapImpl<super::map::GenericDictSpec> as PersistObject>::obj_dec(
+                    scanner,
+                    super::map::MapIndexSizeMD(prop_c as usize),
+                )?
+            }
+            _ => return Err(StorageError::InternalDecodeStructureCorruptedPayload.into()),
         };
-        Ok(ModelData::new_restore(
+        ModelData::new_restore_with_props(
             md.model_uuid,
             key.into_boxed_str(),
             ptag.into_full(),
             fieldmap,
-        ))
+            props,
+        )
+        .ok_or_else(|| StorageError::InternalDecodeStructureCorruptedPayload.into())
     }
 }
 
@@ -29,12 +29,13 @@ use {
     crate::{
         engine::{
             core::{
-                model::{Field, Layer, ModelData},
+                index::PrimaryIndexKind,
+                model::{Durability, Field, Layer, ModelData},
                 space::Space,
             },
             data::{
                 cell::Datacell,
-                dict::{DictEntryGeneric, DictGeneric},
+                dict::{DataDict, DictEntryGeneric, DictGeneric},
                 tag::{FloatSpec, SIntSpec, TagSelector, UIntSpec},
                 uuid::Uuid,
             },
@@ -69,6 +70,29 @@ fn layer() {
     assert_eq!(layer, dec);
 }
 
+#[test]
+fn layer_timestamp_uuid() {
+    for layer in [Layer::timestamp(), Layer::uuid()] {
+        let encoded = super::enc::full::<obj::LayerRef>(obj::LayerRef(&layer));
+        let dec = super::dec::full::<obj::LayerRef>(&encoded).unwrap();
+        assert_eq!(layer, dec);
+    }
+}
+
+#[test]
+fn layer_with_props() {
+    let layer = Layer::str()
+        .with_props(into_dict! {
+            "maxlen" => Datacell::new_uint_default(64),
+            "oneof" => Datacell::new_list(vec![Datacell::from("a"), Datacell::from("b")]),
+            "pattern" => Datacell::from("[a-z]"),
+        })
+        .unwrap();
+    let encoded = super::enc::full::<obj::LayerRef>(obj::LayerRef(&layer));
+    let dec = super::dec::full::<obj::LayerRef>(&encoded).unwrap();
+    assert_eq!(layer, dec);
+}
+
 #[test]
 fn field() {
     let field = Field::new([Layer::list(), Layer::uint64()].into(), true);
@@ -77,6 +101,34 @@ fn field() {
     assert_eq!(field, dec);
 }
 
+#[test]
+fn field_dict() {
+    for field in [
+        Field::new([Layer::dict()].into(), false),
+        Field::new([Layer::dict(), Layer::str()].into(), false),
+        Field::new([Layer::list(), Layer::dict(), Layer::uint64()].into(), true),
+    ] {
+        let encoded = super::enc::full::<obj::FieldRef>((&field).into());
+        let dec = super::dec::full::<obj::FieldRef>(&encoded).unwrap();
+        assert_eq!(field, dec);
+    }
+}
+
+#[test]
+fn field_with_default() {
+    for default in [
+        DictEntryGeneric::Data(Datacell::new_uint_default(100)),
+        DictEntryGeneric::Producer("timesec".into()),
+    ] {
+        let field = Field::new([Layer::uint64()].into(), false)
+            .with_default(default)
+            .unwrap();
+        let encoded = super::enc::full::<obj::FieldRef>((&field).into());
+        let dec = super::dec::full::<obj::FieldRef>(&encoded).unwrap();
+        assert_eq!(field, dec);
+    }
+}
+
 #[test]
 fn fieldmap() {
     let mut fields = IndexSTSeqCns::<Box<str>, Field>::idx_init();
@@ -100,19 +152,68 @@ fn fieldmap() {
 
 #[test]
 fn model() {
-    let uuid = Uuid::new();
+    for index in [PrimaryIndexKind::Hashed, PrimaryIndexKind::Ordered] {
+        let uuid = Uuid::new();
+        let model = ModelData::new_restore(
+            uuid,
+            "username".into(),
+            TagSelector::String.into_full(),
+            into_dict! {
+                "password" => Field::new([Layer::bin()].into(), false),
+                "profile_pic" => Field::new([Layer::bin()].into(), true),
+            },
+            index,
+        );
+        let enc = super::enc::full::<obj::ModelLayoutRef>(obj::ModelLayoutRef(&model));
+        let dec = super::dec::full::<obj::ModelLayoutRef>(&enc).unwrap();
+        assert_eq!(model, dec);
+        assert_eq!(dec.primary_index().kind(), index);
+    }
+}
+
+#[test]
+fn model_durability() {
+    for durability in [Durability::Async, Durability::GroupCommit, Durability::Sync] {
+        let model = ModelData::new_restore(
+            Uuid::new(),
+            "username".into(),
+            TagSelector::String.into_full(),
+            into_dict! {
+                "password" => Field::new([Layer::bin()].into(), false),
+            },
+            PrimaryIndexKind::Ordered,
+        )
+        .with_ttl(Some(3600))
+        .with_durability(durability);
+        let enc = super::enc::full::<obj::ModelLayoutRef>(obj::ModelLayoutRef(&model));
+        let dec = super::dec::full::<obj::ModelLayoutRef>(&enc).unwrap();
+        assert_eq!(model, dec);
+        assert_eq!(dec.durability(), durability);
+        assert_eq!(dec.ttl(), Some(3600));
+        assert_eq!(dec.primary_index().kind(), PrimaryIndexKind::Ordered);
+    }
+}
+
+#[test]
+fn model_layout_v0() {
+    // models created before model properties existed have neither the layout version nor the property map
     let model = ModelData::new_restore(
-        uuid,
+        Uuid::new(),
         "username".into(),
         TagSelector::String.into_full(),
         into_dict! {
             "password" => Field::new([Layer::bin()].into(), false),
-            "profile_pic" => Field::new([Layer::bin()].into(), true),
         },
+        PrimaryIndexKind::Hashed,
     );
-    let enc = super::enc::full::<obj::ModelLayoutRef>(obj::ModelLayoutRef(&model));
+    let mut enc = super::enc::full::<obj::ModelLayoutRef>(obj::ModelLayoutRef(&model));
+    // [uuid][p_key_len][p_key_tag] ... [prop_c = 0]
+    enc[sizeof!(u128) + sizeof!(u64) + 1] = 0;
+    enc.truncate(enc.len() - sizeof!(u64));
     let dec = super::dec::full::<obj::ModelLayoutRef>(&enc).unwrap();
     assert_eq!(model, dec);
+    assert_eq!(dec.ttl(), None);
+    assert_eq!(dec.durability(), Durability::Async);
 }
 
 #[test]
@@ -228,8 +329,30 @@ fn dc_encode_decode() {
         // str
         Datacell::new_str("".to_owned().into_boxed_str()),
         Datacell::new_str("abcdefghijkl".to_owned().into_boxed_str()),
+        // timestamp
+        Datacell::new_timestamp(u64::MIN),
+        Datacell::new_timestamp(u64::MAX),
+        // uuid
+        Datacell::new_uuid(u128::MIN),
+        Datacell::new_uuid(u128::MAX),
+        Datacell::new_uuid(0x4593264b_0231_43e9_b0aa_50784f14e204),
         // list
         Datacell::new_list(vec![]),
+        Datacell::new_list(vec![
+            Datacell::new_timestamp(1673187839_000_000_000),
+            Datacell::new_uuid(u128::MAX),
+        ]),
+        // dict
+        Datacell::new_dict(DataDict::new()),
+        Datacell::new_dict(into_dict! {
+            "name" => Datacell::from("sayan"),
+            "visits" => Datacell::new_uint_default(100),
+            "last_seen" => Datacell::null(),
+            "tags" => Datacell::new_list(vec![Datacell::from("a"), Datacell::from("b")]),
+            "address" => Datacell::new_dict(into_dict! {
+                "city" => Datacell::from("Kolkata"),
+            }),
+        }),
     ];
     for value in dc_tests {
         enc_dec(&value)
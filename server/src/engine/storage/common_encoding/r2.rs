@@ -32,13 +32,19 @@ use {
     super::r1::{dec, impls::gns::GNSEvent, PersistObject},
     crate::{
         engine::{
-            core::GNSData,
+            core::{
+                system_db::{Grantee, Privilege},
+                EntityIDRef, GNSData,
+            },
             error::{StorageError, TransactionError},
             mem::BufferedScanner,
-            txn::gns::sysctl::{AlterUserTxn, CreateUserTxn, DropUserTxn},
+            txn::gns::sysctl::{
+                AlterUserTxn, CreateRoleTxn, CreateUserTxn, DropRoleTxn, DropUserTxn,
+                GrantPrivilegeTxn, GrantRoleTxn, RevokePrivilegeTxn, RevokeRoleTxn,
+            },
             RuntimeResult,
         },
-        util::EndianQW,
+        util::{compiler::TaggedEnum, EndianQW},
     },
 };
 
@@ -235,3 +241,291 @@ impl<'a> PersistObject for DropUserTxn<'a> {
         Ok(DropUserPayload(username.into_boxed_str()))
     }
 }
+
+/*
+    grant/revoke privilege txn
+*/
+
+pub struct PrivilegePayload {
+    role: bool,
+    grantee: Box<str>,
+    space: Box<str>,
+    model: Option<Box<str>>,
+    privilege: Privilege,
+}
+
+impl PrivilegePayload {
+    fn grantee(&self) -> Grantee<'_> {
+        if self.role {
+            Grantee::Role(&self.grantee)
+        } else {
+            Grantee::User(&self.grantee)
+        }
+    }
+}
+
+pub struct PrivilegeMetadata {
+    role: bool,
+    grantee_l: u64,
+    space_l: u64,
+    model_l: u64,
+    privilege: u8,
+}
+
+impl<'a> GNSEvent for GrantPrivilegeTxn<'a> {
+    type CommitType = Self;
+    type RestoreType = PrivilegePayload;
+    fn update_global_state(payload: Self::RestoreType, gns: &GNSData) -> RuntimeResult<()> {
+        /*
+            an earlier version could commit a grant right after its space or model was dropped. such a grant
+            doesn't apply to anything (and must not apply to a new object with the same name), so skip it
+        */
+        let exists = match payload.model.as_deref() {
+            Some(model) => gns
+                .idx_models()
+                .read()
+                .contains_key(&EntityIDRef::new(&payload.space, model)),
+            None => gns.idx().read().contains_key(payload.space.as_ref()),
+        };
+        if !exists {
+            return Ok(());
+        }
+        if gns.sys_db().__raw_grant(
+            payload.grantee(),
+            &payload.space,
+            payload.model.as_deref(),
+            payload.privilege,
+        ) {
+            Ok(())
+        } else {
+            Err(TransactionError::OnRestoreDataConflictMismatch.into())
+        }
+    }
+}
+
+impl<'a> GNSEvent for RevokePrivilegeTxn<'a> {
+    type CommitType = Self;
+    type RestoreType = PrivilegePayload;
+    fn update_global_state(payload: Self::RestoreType, gns: &GNSData) -> RuntimeResult<()> {
+        if gns.sys_db().__raw_revoke(
+            payload.grantee(),
+            &payload.space,
+            payload.model.as_deref(),
+            payload.privilege,
+        ) {
+            Ok(())
+        } else {
+            Err(TransactionError::OnRestoreDataConflictMismatch.into())
+        }
+    }
+}
+
+macro_rules! impl_privilege_txn_persist {
+    ($($ty:ident),*) => {$(
+        impl<'a> PersistObject for $ty<'a> {
+            const METADATA_SIZE: usize = 1 + sizeof!(u64, 3) + 1;
+            type InputType = Self;
+            type OutputType = PrivilegePayload;
+            type Metadata = PrivilegeMetadata;
+            fn pretest_can_dec_object(scanner: &BufferedScanner, md: &Self::Metadata) -> bool {
+                scanner.has_left((md.grantee_l + md.space_l + md.model_l) as usize)
+            }
+            fn meta_enc(buf: &mut Vec<u8>, data: Self::InputType) {
+                // [grantee is a role: 1B][grantee length: 8B][space length: 8B][model length (0 if space-wide): 8B][privilege: 1B]
+                buf.push(data.grantee().is_role() as u8);
+                buf.extend(data.grantee().name().len().u64_bytes_le());
+                buf.extend(data.space().len().u64_bytes_le());
+                buf.extend(data.model().map_or(0, str::len).u64_bytes_le());
+                buf.push(data.privilege().dscr());
+            }
+            unsafe fn meta_dec(scanner: &mut BufferedScanner) -> RuntimeResult<Self::Metadata> {
+                let role = match scanner.next_byte() {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(StorageError::InternalDecodeStructureIllegalData.into()),
+                };
+                Ok(PrivilegeMetadata {
+                    role,
+                    grantee_l: scanner.next_u64_le(),
+                    space_l: scanner.next_u64_le(),
+                    model_l: scanner.next_u64_le(),
+                    privilege: scanner.next_byte(),
+                })
+            }
+            fn obj_enc(buf: &mut Vec<u8>, data: Self::InputType) {
+                buf.extend(data.grantee().name().as_bytes());
+                buf.extend(data.space().as_bytes());
+                buf.extend(data.model().unwrap_or_default().as_bytes());
+            }
+            unsafe fn obj_dec(
+                s: &mut BufferedScanner,
+                md: Self::Metadata,
+            ) -> RuntimeResult<Self::OutputType> {
+                let grantee = dec::utils::decode_string(s, md.grantee_l as _)?;
+                let space = dec::utils::decode_string(s, md.space_l as _)?;
+                let model = match md.model_l {
+                    0 => None,
+                    l => Some(dec::utils::decode_string(s, l as _)?.into_boxed_str()),
+                };
+                let privilege = Privilege::try_from_raw(md.privilege)
+                    .ok_or(StorageError::InternalDecodeStructureIllegalData)?;
+                Ok(PrivilegePayload {
+                    role: md.role,
+                    grantee: grantee.into_boxed_str(),
+                    space: space.into_boxed_str(),
+                    model,
+                    privilege,
+                })
+            }
+        }
+    )*};
+}
+
+impl_privilege_txn_persist!(GrantPrivilegeTxn, RevokePrivilegeTxn);
+
+/*
+    create/drop role txn
+*/
+
+pub struct RolePayload(Box<str>);
+
+impl<'a> GNSEvent for CreateRoleTxn<'a> {
+    type CommitType = Self;
+    type RestoreType = RolePayload;
+    fn update_global_state(
+        RolePayload(role): Self::RestoreType,
+        gns: &GNSData,
+    ) -> RuntimeResult<()> {
+        if gns.sys_db().__raw_create_role(role) {
+            Ok(())
+        } else {
+            Err(TransactionError::OnRestoreDataConflictAlreadyExists.into())
+        }
+    }
+}
+
+impl<'a> GNSEvent for DropRoleTxn<'a> {
+    type CommitType = Self;
+    type RestoreType = RolePayload;
+    fn update_global_state(
+        RolePayload(role): Self::RestoreType,
+        gns: &GNSData,
+    ) -> RuntimeResult<()> {
+        if gns.sys_db().__raw_drop_role(&role) {
+            Ok(())
+        } else {
+            Err(TransactionError::OnRestoreDataConflictMismatch.into())
+        }
+    }
+}
+
+macro_rules! impl_role_txn_persist {
+    ($($ty:ident),*) => {$(
+        impl<'a> PersistObject for $ty<'a> {
+            const METADATA_SIZE: usize = sizeof!(u64);
+            type InputType = Self;
+            type OutputType = RolePayload;
+            type Metadata = u64;
+            fn pretest_can_dec_object(scanner: &BufferedScanner, md: &Self::Metadata) -> bool {
+                scanner.has_left(*md as usize)
+            }
+            fn meta_enc(buf: &mut Vec<u8>, data: Self::InputType) {
+                // [role length: 8B]
+                buf.extend(data.role().len().u64_bytes_le())
+            }
+            unsafe fn meta_dec(scanner: &mut BufferedScanner) -> RuntimeResult<Self::Metadata> {
+                Ok(scanner.next_u64_le())
+            }
+            fn obj_enc(buf: &mut Vec<u8>, data: Self::InputType) {
+                buf.extend(data.role().as_bytes());
+            }
+            unsafe fn obj_dec(
+                s: &mut BufferedScanner,
+                md: Self::Metadata,
+            ) -> RuntimeResult<Self::OutputType> {
+                let role = dec::utils::decode_string(s, md as usize)?;
+                Ok(RolePayload(role.into_boxed_str()))
+            }
+        }
+    )*};
+}
+
+impl_role_txn_persist!(CreateRoleTxn, DropRoleTxn);
+
+/*
+    grant/revoke role txn
+*/
+
+pub struct RoleMembershipPayload {
+    username: Box<str>,
+    role: Box<str>,
+}
+
+impl<'a> GNSEvent for GrantRoleTxn<'a> {
+    type CommitType = Self;
+    type RestoreType = RoleMembershipPayload;
+    fn update_global_state(
+        RoleMembershipPayload { username, role }: Self::RestoreType,
+        gns: &GNSData,
+    ) -> RuntimeResult<()> {
+        if gns.sys_db().__raw_grant_role(&username, &role) {
+            Ok(())
+        } else {
+            Err(TransactionError::OnRestoreDataConflictMismatch.into())
+        }
+    }
+}
+
+impl<'a> GNSEvent for RevokeRoleTxn<'a> {
+    type CommitType = Self;
+    type RestoreType = RoleMembershipPayload;
+    fn update_global_state(
+        RoleMembershipPayload { username, role }: Self::RestoreType,
+        gns: &GNSData,
+    ) -> RuntimeResult<()> {
+        if gns.sys_db().__raw_revoke_role(&username, &role) {
+            Ok(())
+        } else {
+            Err(TransactionError::OnRestoreDataConflictMismatch.into())
+        }
+    }
+}
+
+macro_rules! impl_role_membership_txn_persist {
+    ($($ty:ident),*) => {$(
+        impl<'a> PersistObject for $ty<'a> {
+            const METADATA_SIZE: usize = sizeof!(u64, 2);
+            type InputType = Self;
+            type OutputType = RoleMembershipPayload;
+            type Metadata = (u64, u64);
+            fn pretest_can_dec_object(scanner: &BufferedScanner, (uname_l, role_l): &Self::Metadata) -> bool {
+                scanner.has_left((uname_l + role_l) as usize)
+            }
+            fn meta_enc(buf: &mut Vec<u8>, data: Self::InputType) {
+                // [username length: 8B][role length: 8B]
+                buf.extend(data.username().len().u64_bytes_le());
+                buf.extend(data.role().len().u64_bytes_le());
+            }
+            unsafe fn meta_dec(scanner: &mut BufferedScanner) -> RuntimeResult<Self::Metadata> {
+                Ok((scanner.next_u64_le(), scanner.next_u64_le()))
+            }
+            fn obj_enc(buf: &mut Vec<u8>, data: Self::InputType) {
+                buf.extend(data.username().as_bytes());
+                buf.extend(data.role().as_bytes());
+            }
+            unsafe fn obj_dec(
+                s: &mut BufferedScanner,
+                (uname_l, role_l): Self::Metadata,
+            ) -> RuntimeResult<Self::OutputType> {
+                let username = dec::utils::decode_string(s, uname_l as usize)?;
+                let role = dec::utils::decode_string(s, role_l as usize)?;
+                Ok(RoleMembershipPayload {
+                    username: username.into_boxed_str(),
+                    role: role.into_boxed_str(),
+                })
+            }
+        }
+    )*};
+}
+
+impl_role_membership_txn_persist!(GrantRoleTxn, RevokeRoleTxn);
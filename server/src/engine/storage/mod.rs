@@ -40,7 +40,10 @@ pub mod v2;
 pub mod safe_interfaces {
     pub use super::{
         common::{interface::fs::FileSystem, paths_v1},
-        v2::impls::mdl_journal::StdModelBatch,
+        v2::impls::mdl_journal::{
+            ModelChangeLog, RecordedChange, RecordedChanges, StdModelBatch, TruncateModelBatch,
+            TxnModelBatch,
+        },
     };
 }
 
@@ -54,7 +57,7 @@ pub use v2::{
         gns_log::GNSDriver,
         mdl_journal::{BatchStats, ModelDriver},
     },
-    repair, restore,
+    online_backup, repair, restore,
 };
 
 pub struct SELoaded {
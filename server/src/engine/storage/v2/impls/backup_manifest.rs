@@ -37,7 +37,10 @@ use {
                     versions::FileSpecifierVersion,
                 },
                 common_encoding::r1::{dec, enc, PersistObject},
-                v2::raw::spec::{FileClass, FileSpecifier, HeaderImplV2},
+                v2::raw::{
+                    journal::JournalCheckpoint,
+                    spec::{FileClass, FileSpecifier, HeaderImplV2},
+                },
             },
         },
         util::{compiler::TaggedEnum, os},
@@ -51,9 +54,24 @@ impl sdss::sdss_r1::SimpleFileSpecV1 for BackupManifestV1 {
     type HeaderSpec = HeaderImplV2;
     const FILE_CLASS: FileClass = FileClass::BackupMetadata;
     const FILE_SPECIFIER: FileSpecifier = FileSpecifier::BackupManifest;
-    const FILE_SPECFIER_VERSION: FileSpecifierVersion = FileSpecifierVersion::__new(0);
+    const FILE_SPECFIER_VERSION: FileSpecifierVersion = FileSpecifierVersion::__new(2);
+    fn check_if_file_specifier_revision_is_compatible(
+        v: FileSpecifierVersion,
+    ) -> RuntimeResult<()> {
+        // older manifests don't have the journals (v0) or the snapshot point (v1), but they can still be restored
+        if v == Self::FILE_SPECFIER_VERSION || v == BACKUP_MANIFEST_V0 || v == BACKUP_MANIFEST_V1 {
+            Ok(())
+        } else {
+            Err(StorageError::FileDecodeHeaderVersionMismatch.into())
+        }
+    }
 }
 
+/// The first version of the manifest (without the base backup or the journals)
+const BACKUP_MANIFEST_V0: FileSpecifierVersion = FileSpecifierVersion::__new(0);
+/// The second version of the manifest (without the snapshot point)
+const BACKUP_MANIFEST_V1: FileSpecifierVersion = FileSpecifierVersion::__new(1);
+
 pub struct BackupManifestMetadata {
     context_md: u64,
     hostname_l: u64,
@@ -133,16 +151,139 @@ impl<'a> PersistObject for BackupManifestStorage<'a> {
             hostname,
             date,
             description: if dscr.is_empty() { None } else { Some(dscr) },
+            base: None,
+            journals: vec![],
+            snapshot_at: None,
         })
     }
 }
 
+pub struct BackupJournalsMetadata {
+    base_l: u64,
+    journal_count: u64,
+}
+
+impl BackupJournalsMetadata {
+    fn new(base_l: u64, journal_count: u64) -> Self {
+        Self {
+            base_l,
+            journal_count,
+        }
+    }
+}
+
+/// The part of the manifest that was added in v1 (the base backup and the journals)
+struct BackupJournalsStorage<'a>(PhantomData<&'a [BackupJournal]>);
+
+impl<'a> PersistObject for BackupJournalsStorage<'a> {
+    const METADATA_SIZE: usize = sizeof!(u64, 2);
+    type InputType = (&'a Option<String>, &'a [BackupJournal]);
+    type OutputType = (Option<String>, Vec<BackupJournal>);
+    type Metadata = BackupJournalsMetadata;
+    fn pretest_can_dec_object(scanner: &BufferedScanner, md: &Self::Metadata) -> bool {
+        // every journal has atleast its fixed size fields
+        (md.journal_count as usize)
+            .checked_mul(BackupJournal::FIXED_SIZE)
+            .and_then(|size| size.checked_add(md.base_l as usize))
+            .map_or(false, |size| scanner.has_left(size))
+    }
+    fn meta_enc(buf: &mut Vec<u8>, (base, journals): Self::InputType) {
+        buf.extend((base.as_ref().map_or(0, String::len) as u64).to_le_bytes());
+        buf.extend((journals.len() as u64).to_le_bytes());
+    }
+    unsafe fn meta_dec(scanner: &mut BufferedScanner) -> RuntimeResult<Self::Metadata> {
+        Ok(BackupJournalsMetadata::new(
+            scanner.next_u64_le(),
+            scanner.next_u64_le(),
+        ))
+    }
+    fn obj_enc(buf: &mut Vec<u8>, (base, journals): Self::InputType) {
+        if let Some(base) = base {
+            buf.extend(base.as_bytes());
+        }
+        for journal in journals {
+            // a journal's offset is never zero, so that means that there's no checkpoint
+            let (offset, txn_id, checksum) = journal
+                .checkpoint
+                .map_or((0, 0, 0), |cp| (cp.offset(), cp.txn_id(), cp.checksum()));
+            buf.extend((journal.path.len() as u64).to_le_bytes());
+            buf.extend(journal.copied_from.to_le_bytes());
+            buf.extend(offset.to_le_bytes());
+            buf.extend(txn_id.to_le_bytes());
+            buf.extend(checksum.to_le_bytes());
+            buf.extend(journal.path.as_bytes());
+        }
+    }
+    unsafe fn obj_dec(
+        s: &mut BufferedScanner,
+        md: Self::Metadata,
+    ) -> RuntimeResult<Self::OutputType> {
+        let base = dec::utils::decode_string(s, md.base_l as usize)?;
+        let mut journals = Vec::with_capacity(md.journal_count as usize);
+        for _ in 0..md.journal_count {
+            if !s.has_left(BackupJournal::FIXED_SIZE) {
+                return Err(StorageError::InternalDecodeStructureCorruptedPayload.into());
+            }
+            let path_l = s.next_u64_le();
+            let copied_from = s.next_u64_le();
+            let (offset, txn_id, checksum) = (s.next_u64_le(), s.next_u64_le(), s.next_u64_le());
+            if !s.has_left(path_l as usize) {
+                return Err(StorageError::InternalDecodeStructureCorruptedPayload.into());
+            }
+            journals.push(BackupJournal::new(
+                dec::utils::decode_string(s, path_l as usize)?,
+                copied_from,
+                if offset == 0 {
+                    None
+                } else {
+                    Some(JournalCheckpoint::new(offset, txn_id, checksum))
+                },
+            ));
+        }
+        Ok((if base.is_empty() { None } else { Some(base) }, journals))
+    }
+}
+
+/// A journal in a backup
+#[derive(Debug, PartialEq, Clone)]
+pub struct BackupJournal {
+    /// the path of the journal (relative to the working directory)
+    path: String,
+    /// where the copy of the journal starts. this is zero if the whole journal was copied and otherwise the backup
+    /// only has what was appended to the journal since the base backup
+    copied_from: u64,
+    /// the checkpoint of the journal when it was backed up (if it was closed cleanly)
+    checkpoint: Option<JournalCheckpoint>,
+}
+
+impl BackupJournal {
+    /// path length, copy offset and checkpoint
+    const FIXED_SIZE: usize = sizeof!(u64, 5);
+    pub fn new(path: String, copied_from: u64, checkpoint: Option<JournalCheckpoint>) -> Self {
+        Self {
+            path,
+            copied_from,
+            checkpoint,
+        }
+    }
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    pub fn copied_from(&self) -> u64 {
+        self.copied_from
+    }
+    pub fn checkpoint(&self) -> Option<JournalCheckpoint> {
+        self.checkpoint
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Copy, sky_macros::TaggedEnum, sky_macros::EnumMethods)]
 #[repr(u8)]
 pub enum BackupContext {
     BeforeRepair = 0,
     BeforeCompaction = 1,
     Manual = 2,
+    Online = 3,
 }
 
 impl BackupContext {
@@ -151,6 +292,7 @@ impl BackupContext {
             Self::BeforeRepair => "before a repair operation",
             Self::BeforeCompaction => "before a compaction operation",
             Self::Manual => "manually",
+            Self::Online => "while the server was running",
         }
     }
 }
@@ -161,6 +303,13 @@ pub struct BackupManifest {
     hostname: String,
     date: NaiveDateTime,
     description: Option<String>,
+    /// the full path of the directory of the backup that this (incremental) backup was taken on top of
+    base: Option<String>,
+    /// all the journals in the backup
+    journals: Vec<BackupJournal>,
+    /// when all the journals were snapshotted (in milliseconds since the epoch), if they were all copied as of the
+    /// same point
+    snapshot_at: Option<u64>,
 }
 
 impl BackupManifest {
@@ -168,9 +317,12 @@ impl BackupManifest {
         path: impl AsRef<Path>,
         context: BackupContext,
         description: Option<String>,
+        base: Option<String>,
+        journals: &[BackupJournal],
+        snapshot_at: Option<u64>,
     ) -> RuntimeResult<()> {
         let mut file = SdssFile::<BackupManifestV1>::create(path)?;
-        let backup_manifest = Self::generate(context, description);
+        let backup_manifest = Self::generate(context, description, base, journals, snapshot_at);
         file.write_buffer(&backup_manifest)?;
         file.fsync_all()?;
         Ok(())
@@ -181,12 +333,37 @@ impl BackupManifest {
         let mut f = SdssFile::<BackupManifestV1>::open_rw(path)?;
         let manifest_payload = f.read_full()?;
         let meta = f.into_meta();
-        dec::full::<BackupManifestStorage>(&manifest_payload).map(|bm| (bm, meta))
+        let mut scanner = BufferedScanner::new(&manifest_payload);
+        let mut manifest = dec::full_from_scanner::<BackupManifestStorage>(&mut scanner)?;
+        if meta.file_specifier_version() != BACKUP_MANIFEST_V0 {
+            (manifest.base, manifest.journals) =
+                dec::full_from_scanner::<BackupJournalsStorage>(&mut scanner)?;
+        }
+        if meta.file_specifier_version() > BACKUP_MANIFEST_V1 {
+            let snapshot_at = scanner
+                .try_next_variable_block(sizeof!(u64))
+                .ok_or(StorageError::InternalDecodeStructureCorruptedPayload)?;
+            // zero means that the journals weren't all copied as of the same point
+            manifest.snapshot_at = match u64::from_le_bytes(snapshot_at.try_into().unwrap()) {
+                0 => None,
+                snapshot_at => Some(snapshot_at),
+            };
+        }
+        Ok((manifest, meta))
     }
-    fn generate(context: BackupContext, description: Option<String>) -> Vec<u8> {
+    fn generate(
+        context: BackupContext,
+        description: Option<String>,
+        base: Option<String>,
+        journals: &[BackupJournal],
+        snapshot_at: Option<u64>,
+    ) -> Vec<u8> {
         let date = Utc::now().format("%Y%m%d%H%M%S").to_string();
         let hostname = os::get_hostname().as_str().to_string();
-        enc::full::<BackupManifestStorage>(&(context, hostname, date, description))
+        let mut buf = enc::full::<BackupManifestStorage>(&(context, hostname, date, description));
+        enc::full_into_buffer::<BackupJournalsStorage>(&mut buf, (&base, journals));
+        buf.extend(snapshot_at.unwrap_or(0).to_le_bytes());
+        buf
     }
     pub fn context(&self) -> BackupContext {
         self.context
@@ -200,4 +377,17 @@ impl BackupManifest {
     pub fn description(&self) -> Option<&str> {
         self.description.as_deref()
     }
+    pub fn base(&self) -> Option<&str> {
+        self.base.as_deref()
+    }
+    pub fn journals(&self) -> &[BackupJournal] {
+        &self.journals
+    }
+    pub fn snapshot_at(&self) -> Option<u64> {
+        self.snapshot_at
+    }
+    /// Returns the journal at the given path, if it's in the backup
+    pub fn journal(&self, path: &str) -> Option<&BackupJournal> {
+        self.journals.iter().find(|journal| journal.path == path)
+    }
 }
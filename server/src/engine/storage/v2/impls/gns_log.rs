@@ -31,7 +31,7 @@ use {
     },
     crate::{
         engine::{
-            core::{model::Model, EntityID, GNSData},
+            core::{model::Model, system_db::Grantee, EntityID, GNSData},
             fractal::context,
             storage::{
                 common::{interface::fs::FileSystem, paths_v1},
@@ -45,14 +45,17 @@ use {
             txn::{
                 gns::{
                     model::{
-                        AlterModelAddTxn, AlterModelRemoveTxn, AlterModelUpdateTxn, CreateModelTxn,
-                        DropModelTxn,
+                        AlterModelAddTxn, AlterModelRemoveTxn, AlterModelUpdateTxn, CreateIndexTxn,
+                        CreateModelTxn, DropIndexTxn, DropModelTxn,
                     },
                     space::{AlterSpaceTxn, CreateSpaceTxn, DropSpaceTxn},
-                    sysctl::{AlterUserTxn, CreateUserTxn, DropUserTxn},
+                    sysctl::{
+                        AlterUserTxn, CreateRoleTxn, CreateUserTxn, DropRoleTxn, DropUserTxn,
+                        GrantPrivilegeTxn, GrantRoleTxn, RevokePrivilegeTxn, RevokeRoleTxn,
+                    },
                     GNSTransaction, GNSTransactionCode,
                 },
-                SpaceIDRef,
+                ModelIDRef, SpaceIDRef,
             },
             RuntimeResult,
         },
@@ -139,10 +142,19 @@ pub fn reinit_full<const INIT_DIRS: bool>(
         }
         gns_driver.commit_event(CreateSpaceTxn::new(space.props(), &space_name, space))?;
     }
+    // create all roles
+    context::set_dmsg("creating all roles");
+    for (role, _) in gns.sys_db().roles().read().iter() {
+        gns_driver.commit_event(CreateRoleTxn::new(role))?;
+    }
     // create all users
     context::set_dmsg("creating all users");
     for (user_name, user) in gns.sys_db().users().read().iter() {
         gns_driver.commit_event(CreateUserTxn::new(&user_name, user.hash()))?;
+        // and their roles
+        for role in user.roles() {
+            gns_driver.commit_event(GrantRoleTxn::new(user_name, role))?;
+        }
     }
     // create all models
     context::set_dmsg("creating all models");
@@ -155,6 +167,43 @@ pub fn reinit_full<const INIT_DIRS: bool>(
             model_id.entity(),
             model_data,
         ))?;
+        // and its secondary indexes
+        for index in model_data.secondary_indexes().indexes() {
+            gns_driver.commit_event(CreateIndexTxn::new(
+                ModelIDRef::new(
+                    SpaceIDRef::with_uuid(model_id.space(), space_uuid),
+                    model_id.entity(),
+                    model_data.get_uuid(),
+                    model_data
+                        .delta_state()
+                        .schema_current_version()
+                        .value_u64(),
+                ),
+                index,
+            ))?;
+        }
+    }
+    // grant all privileges (after the models, since a grant on an object that doesn't exist is skipped on restore)
+    context::set_dmsg("granting all privileges");
+    for (role, grants) in gns.sys_db().roles().read().iter() {
+        for (space, model, privilege) in grants.iter() {
+            gns_driver.commit_event(GrantPrivilegeTxn::new(
+                Grantee::Role(role),
+                space,
+                model,
+                privilege,
+            ))?;
+        }
+    }
+    for (user_name, user) in gns.sys_db().users().read().iter() {
+        for (space, model, privilege) in user.grants().iter() {
+            gns_driver.commit_event(GrantPrivilegeTxn::new(
+                Grantee::User(user_name),
+                space,
+                model,
+                privilege,
+            ))?;
+        }
     }
     Ok(())
 }
@@ -178,6 +227,14 @@ impl EventLogSpec for GNSEventLog {
         CreateUserTxn => |_| {},
         AlterUserTxn => |h| h.report_new_redundant_record(),
         DropUserTxn => |h| h.report_new_redundant_record(),
+        CreateIndexTxn => |_| {},
+        DropIndexTxn => |h| h.report_new_redundant_record(),
+        GrantPrivilegeTxn => |_| {},
+        RevokePrivilegeTxn => |h| h.report_new_redundant_record(),
+        CreateRoleTxn => |_| {},
+        DropRoleTxn => |h| h.report_new_redundant_record(),
+        GrantRoleTxn => |_| {},
+        RevokeRoleTxn => |h| h.report_new_redundant_record(),
     ];
     fn rewrite_log<'a>(writer: &mut GNSDriver, ctx: Self::FullSyncCtx<'a>) -> RuntimeResult<()> {
         reinit_full::<false>(writer, ctx, |_, _| Ok(()))
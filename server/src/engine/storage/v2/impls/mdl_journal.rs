@@ -38,7 +38,8 @@ use {
                 cell::Datacell,
                 tag::{DataTag, TagUnique},
             },
-            error::StorageError,
+            error::{StorageError, TransactionError},
+            fractal::context,
             idx::{MTIndex, STIndex, STIndexSeq},
             storage::{
                 common::sdss::sdss_r1::rw::{TrackedReaderContext, TrackedWriter},
@@ -48,18 +49,19 @@ use {
                         self, BatchAdapter, BatchAdapterSpec, BatchDriver, JournalAdapterEvent,
                         JournalHeuristics, JournalSettings, JournalStats, RawJournalAdapter,
                     },
-                    spec::ModelDataBatchAofV1,
+                    spec::{ModelDataBatchAofV1, JOURNAL_V0},
                 },
             },
             RuntimeResult,
         },
-        util::{compiler::TaggedEnum, EndianQW},
+        util::{compiler::TaggedEnum, os, EndianQW},
     },
     crossbeam_epoch::{pin, Guard},
     sky_macros::TaggedEnum,
     std::{
-        cell::RefCell,
+        cell::{Cell, RefCell},
         collections::{hash_map::Entry as HMEntry, HashMap},
+        marker::PhantomData,
         rc::Rc,
     },
 };
@@ -90,12 +92,23 @@ impl ModelDriver {
         model_data_file_path: &str,
         settings: JournalSettings,
     ) -> RuntimeResult<(Self, JournalStats)> {
-        journal::open_journal(model_data_file_path, mdl, settings)
+        let ret = journal::open_journal(model_data_file_path, mdl, settings)?;
+        // secondary indexes are not journaled; they are rebuilt from the restored data
+        if mdl.rebuild_secondary_indexes() {
+            Ok(ret)
+        } else {
+            Err(TransactionError::OnRestoreDataConflictMismatch.into())
+        }
     }
     /// Create a new event log
     pub fn create_model_driver(model_data_file_path: &str) -> RuntimeResult<Self> {
         journal::create_journal(model_data_file_path)
     }
+    /// Open a model's journal to read back its changes. The journal is still open for writing, so the caller must make
+    /// sure that nothing is written to it while it is being opened (but only what was written until then is read)
+    pub fn open_changes(model_data_file_path: &str) -> RuntimeResult<ModelChangeLog> {
+        journal::LiveJournalReader::open(model_data_file_path).map(ModelChangeLog)
+    }
 }
 
 /// The model data adapter (abstract journal adapter impl)
@@ -108,6 +121,23 @@ pub struct ModelDataAdapter;
 pub enum BatchType {
     /// a standard batch (with n <= m events; n = Δdata, m = cardinality)
     Standard = 0,
+    /// a standard batch where every row that is written is followed by its expiry time
+    Expiring = 1,
+    /// an empty batch that removes all the rows that were restored so far
+    Truncate = 2,
+    /// an empty batch that follows a compacted model, with the version that the model was compacted at
+    Checkpoint = 3,
+}
+
+impl BatchType {
+    /// Returns the kind of batch to be used for the model's rows
+    fn for_model(model: &ModelData) -> Self {
+        if model.primary_index().expires_rows() {
+            Self::Expiring
+        } else {
+            Self::Standard
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Copy, TaggedEnum)]
@@ -133,6 +163,8 @@ pub enum EventType {
 
 struct RowWriter<'b> {
     f: &'b mut TrackedWriter<<BatchAdapter<ModelDataAdapter> as RawJournalAdapter>::Spec>,
+    /// if set, row data is preceded by the row's expiry time
+    expiring: bool,
 }
 
 impl<'b> RowWriter<'b> {
@@ -157,10 +189,12 @@ impl<'b> RowWriter<'b> {
     /// write row metadata:
     /// - change type
     /// - txn id
+    /// - commit time (zero if unknown)
     fn write_row_metadata(
         &mut self,
         change: DataDeltaKind,
         txn_id: DeltaVersion,
+        committed_at: u64,
     ) -> RuntimeResult<()> {
         if cfg!(debug_assertions) {
             let event_kind = EventType::try_from_raw(change.value_u8()).unwrap();
@@ -177,6 +211,7 @@ impl<'b> RowWriter<'b> {
         self.f.dtrack_write(&change_type)?;
         let txn_id = txn_id.value_u64().u64_bytes_le();
         self.f.dtrack_write(&txn_id)?;
+        self.f.dtrack_write(&committed_at.u64_bytes_le())?;
         Ok(())
     }
     /// encode the primary key only. this means NO TAG is encoded.
@@ -213,8 +248,11 @@ impl<'b> RowWriter<'b> {
         self.f.dtrack_write(&buf)?;
         Ok(())
     }
-    /// Encode row data
+    /// Encode row data (and the expiry time, if the batch has it)
     fn write_row_data(&mut self, model: &ModelData, row_data: &RowData) -> RuntimeResult<()> {
+        if self.expiring {
+            self.f.dtrack_write(&row_data.expires_at().u64_bytes_le())?;
+        }
         for field_name in model.fields().stseq_ord_key() {
             match row_data.fields().get(field_name) {
                 Some(cell) => {
@@ -241,6 +279,7 @@ impl<'a, 'b> BatchWriter<'a, 'b> {
         g: &'a Guard,
         expected: usize,
         f: &'b mut TrackedWriter<<BatchAdapter<ModelDataAdapter> as RawJournalAdapter>::Spec>,
+        batch_type: BatchType,
         batch_stat: &mut BatchStats,
     ) -> RuntimeResult<usize> {
         /*
@@ -254,10 +293,13 @@ impl<'a, 'b> BatchWriter<'a, 'b> {
 
             -- @ohsayan
         */
-        let mut me = Self::new(model, g, f)?;
+        let mut me = Self::new(model, g, f, batch_type)?;
         let mut i = 0;
         while i < expected {
-            let delta = me.model.delta_state().__data_delta_dequeue(me.g).unwrap();
+            // the deltas might have been written by someone else (see `StdModelBatch::drain`)
+            let Some(delta) = me.model.delta_state().__data_delta_dequeue(me.g) else {
+                break;
+            };
             match me.step(&delta) {
                 Ok(()) => i += 1,
                 Err(e) => {
@@ -274,8 +316,12 @@ impl<'a, 'b> BatchWriter<'a, 'b> {
         model: &'a ModelData,
         g: &'a Guard,
         f: &'b mut TrackedWriter<<BatchAdapter<ModelDataAdapter> as RawJournalAdapter>::Spec>,
+        batch_type: BatchType,
     ) -> RuntimeResult<Self> {
-        let mut row_writer = RowWriter { f };
+        let mut row_writer = RowWriter {
+            f,
+            expiring: batch_type == BatchType::Expiring,
+        };
         row_writer.write_row_global_metadata(model)?;
         Ok(Self {
             model,
@@ -285,25 +331,52 @@ impl<'a, 'b> BatchWriter<'a, 'b> {
         })
     }
     fn step(&mut self, delta: &DataDelta) -> RuntimeResult<()> {
+        self.step_keep_if(delta, |_| false)
+    }
+    /// Write a delta. If the row has since been revised, the delta is skipped (since the delta that revised it is
+    /// written later) unless `keep` returns true for the version that revised it, in which case the row is written
+    /// as of that version instead
+    fn step_keep_if(
+        &mut self,
+        delta: &DataDelta,
+        keep: impl Fn(DeltaVersion) -> bool,
+    ) -> RuntimeResult<()> {
         match delta.change() {
             DataDeltaKind::Delete => {
-                self.row_writer
-                    .write_row_metadata(delta.change(), delta.data_version())?;
+                self.row_writer.write_row_metadata(
+                    delta.change(),
+                    delta.data_version(),
+                    delta.committed_at(),
+                )?;
                 self.row_writer.write_row_pk(delta.row().d_key())?;
             }
             DataDeltaKind::Insert | DataDeltaKind::Update | DataDeltaKind::Upsert => {
                 // resolve deltas (this is yet another opportunity for us to reclaim memory from deleted items)
-                let row_data = delta
-                    .row()
-                    .resolve_schema_deltas_and_freeze_if(self.model.delta_state(), |row| {
-                        row.get_txn_revised() <= delta.data_version()
-                    });
-                if row_data.get_txn_revised() > delta.data_version() {
-                    // inconsistent read. there should already be another revised delta somewhere
-                    return Ok(());
+                let row_data = delta.row().resolve_schema_deltas_and_freeze_if(
+                    self.model.delta_state(),
+                    |row| {
+                        (row.get_txn_revised() <= delta.data_version())
+                            || keep(row.get_txn_revised())
+                    },
+                );
+                let mut version = delta.data_version();
+                if row_data.get_txn_revised() > version {
+                    if !keep(row_data.get_txn_revised()) {
+                        // inconsistent read. there should already be another revised delta somewhere
+                        return Ok(());
+                    }
+                    version = row_data.get_txn_revised();
                 }
-                self.row_writer
-                    .write_row_metadata(delta.change(), delta.data_version())?;
+                /*
+                    NB: a row that is written as of a later version keeps this delta's commit time (since the row can't
+                    be written as of this delta anymore), so rewinding to a point between the two commits restores the
+                    row as of the later version
+                */
+                self.row_writer.write_row_metadata(
+                    delta.change(),
+                    version,
+                    delta.committed_at(),
+                )?;
                 // encode data
                 self.row_writer.write_row_pk(delta.row().d_key())?;
                 self.row_writer.write_row_data(self.model, &row_data)?;
@@ -316,17 +389,22 @@ impl<'a, 'b> BatchWriter<'a, 'b> {
 }
 
 /// A standard model batch where atmost the given number of keys are flushed
-pub struct StdModelBatch<'a>(&'a ModelData, usize);
+pub struct StdModelBatch<'a>(&'a ModelData, usize, BatchType);
 
 impl<'a> StdModelBatch<'a> {
     pub fn new(model: &'a ModelData, observed_len: usize) -> Self {
-        Self(model, observed_len)
+        Self(model, observed_len, BatchType::for_model(model))
+    }
+    /// A batch with all the deltas in the model's queue, including those that the fractal manager has already taken a
+    /// count of (it then finds fewer deltas than it expected once it gets to write its batch)
+    pub fn drain(model: &'a ModelData) -> Self {
+        Self::new(model, usize::MAX)
     }
 }
 
 impl<'a> JournalAdapterEvent<BatchAdapter<ModelDataAdapter>> for StdModelBatch<'a> {
     fn md(&self) -> u64 {
-        BatchType::Standard.dscr_u64()
+        self.2.dscr_u64()
     }
     fn write_direct(
         self,
@@ -337,7 +415,7 @@ impl<'a> JournalAdapterEvent<BatchAdapter<ModelDataAdapter>> for StdModelBatch<'
         writer.dtrack_write(&self.1.u64_bytes_le())?;
         let g = pin();
         let actual_commit =
-            BatchWriter::write_batch(self.0, &g, self.1, writer, &mut ctx.borrow_mut())?;
+            BatchWriter::write_batch(self.0, &g, self.1, writer, self.2, &mut ctx.borrow_mut())?;
         if actual_commit != self.1 {
             // early exit
             writer.dtrack_write(&[EventType::EarlyExit.dscr()])?;
@@ -346,18 +424,132 @@ impl<'a> JournalAdapterEvent<BatchAdapter<ModelDataAdapter>> for StdModelBatch<'
     }
 }
 
-pub struct FullModel<'a>(&'a ModelData);
+/// A batch of deltas that were staged by a transaction (and never published to the delta state). All the deltas
+/// are written as a single event so that the transaction is either fully restored or not at all
+pub struct TxnModelBatch<'a>(&'a ModelData, &'a [DataDelta], BatchType);
+
+impl<'a> TxnModelBatch<'a> {
+    pub fn new(model: &'a ModelData, deltas: &'a [DataDelta]) -> Self {
+        Self(model, deltas, BatchType::for_model(model))
+    }
+}
+
+impl<'a> JournalAdapterEvent<BatchAdapter<ModelDataAdapter>> for TxnModelBatch<'a> {
+    fn md(&self) -> u64 {
+        self.2.dscr_u64()
+    }
+    fn write_direct(
+        self,
+        writer: &mut TrackedWriter<<BatchAdapter<ModelDataAdapter> as RawJournalAdapter>::Spec>,
+        _: Rc<RefCell<BatchStats>>,
+    ) -> RuntimeResult<()> {
+        // [expected commit]
+        writer.dtrack_write(&self.1.len().u64_bytes_le())?;
+        let g = pin();
+        let mut me = BatchWriter::new(self.0, &g, writer, self.2)?;
+        /*
+            the writes in this transaction are acknowledged as durable once it is written, but a row might already have
+            been revised by an (async) write outside it (once the row's lock was released). that write's delta is only
+            written later (if at all before a crash), so we write the row as of that version right away
+        */
+        let mut versions: Vec<_> = self.1.iter().map(DataDelta::data_version).collect();
+        versions.sort_unstable();
+        for delta in self.1 {
+            // a failure here is handled by the caller, which discards the whole transaction
+            me.step_keep_if(delta, |revised| versions.binary_search(&revised).is_err())?;
+        }
+        let actual_commit = me.sync_count;
+        if actual_commit != self.1.len() {
+            // some deltas were superseded by later deltas in the same transaction
+            me.row_writer
+                .f
+                .dtrack_write(&[EventType::EarlyExit.dscr()])?;
+        }
+        e!(me.row_writer.f.dtrack_write(&actual_commit.u64_bytes_le()))
+    }
+}
+
+/// A truncation record. This is an empty batch (with just the batch metadata, followed by the version that the
+/// model was truncated in and when) and on restore, all rows that were restored before it are removed
+///
+/// NB: any deltas that are still pending for the truncated rows must be made stale (by revising the rows) before
+/// the rows are removed, so that they're never written after this record
+pub struct TruncateModelBatch<'a>(&'a ModelData, DeltaVersion);
+
+impl<'a> TruncateModelBatch<'a> {
+    pub fn new(model: &'a ModelData, truncated_at: DeltaVersion) -> Self {
+        Self(model, truncated_at)
+    }
+}
+
+impl<'a> JournalAdapterEvent<BatchAdapter<ModelDataAdapter>> for TruncateModelBatch<'a> {
+    fn md(&self) -> u64 {
+        BatchType::Truncate.dscr_u64()
+    }
+    fn write_direct(
+        self,
+        writer: &mut TrackedWriter<<BatchAdapter<ModelDataAdapter> as RawJournalAdapter>::Spec>,
+        _: Rc<RefCell<BatchStats>>,
+    ) -> RuntimeResult<()> {
+        write_empty_batch(writer, self.0, self.1)
+    }
+}
+
+/// A checkpoint record, written right after a compacted model (whose rows are written with the versions that they
+/// were last revised in). This is an empty batch (like a truncation record) with the version that the model was
+/// compacted at: every change from this version on is in the journal, but the changes before it are lost (and the
+/// compacted rows don't record when they were committed, so the model can't be rewound to before the checkpoint)
+struct ModelCheckpoint<'a>(&'a ModelData, DeltaVersion);
+
+impl<'a> JournalAdapterEvent<BatchAdapter<ModelDataAdapter>> for ModelCheckpoint<'a> {
+    fn md(&self) -> u64 {
+        BatchType::Checkpoint.dscr_u64()
+    }
+    fn write_direct(
+        self,
+        writer: &mut TrackedWriter<<BatchAdapter<ModelDataAdapter> as RawJournalAdapter>::Spec>,
+        _: Rc<RefCell<BatchStats>>,
+    ) -> RuntimeResult<()> {
+        write_empty_batch(writer, self.0, self.1)
+    }
+}
+
+/// Write an empty batch with just the batch metadata, followed by the given version and the time it was written at
+fn write_empty_batch(
+    writer: &mut TrackedWriter<<BatchAdapter<ModelDataAdapter> as RawJournalAdapter>::Spec>,
+    model: &ModelData,
+    version: DeltaVersion,
+) -> RuntimeResult<()> {
+    // [expected commit]
+    writer.dtrack_write(&0u64.u64_bytes_le())?;
+    // [pk tag][schema version][column cnt]
+    RowWriter {
+        f: writer,
+        expiring: false,
+    }
+    .write_row_global_metadata(model)?;
+    // [truncation/checkpoint version][commit time]
+    writer.dtrack_write(&version.value_u64().u64_bytes_le())?;
+    writer.dtrack_write(&os::get_commit_time().u64_bytes_le())?;
+    // [actual commit]
+    e!(writer.dtrack_write(&0u64.u64_bytes_le()))
+}
+
+pub struct FullModel<'a>(&'a ModelData, BatchType);
 
 impl<'a> FullModel<'a> {
     pub fn new(model: &'a ModelData) -> Self {
-        Self(model)
+        Self(model, BatchType::for_model(model))
     }
-    fn write<const ZERO: bool>(
+    fn write(
         self,
         f: &mut TrackedWriter<ModelDataBatchAofV1>,
     ) -> Result<(), crate::engine::fractal::error::Error> {
         let g = pin();
-        let mut row_writer: RowWriter<'_> = RowWriter { f };
+        let mut row_writer: RowWriter<'_> = RowWriter {
+            f,
+            expiring: self.1 == BatchType::Expiring,
+        };
         let index = self.0.primary_index().__raw_index();
         let current_row_count = index.mt_len();
         // expect commit == current row count
@@ -368,14 +560,8 @@ impl<'a> FullModel<'a> {
         row_writer.write_row_global_metadata(self.0)?;
         for (key, row_data) in index.mt_iter_kv(&g) {
             let row_data = row_data.read();
-            row_writer.write_row_metadata(
-                DataDeltaKind::Insert,
-                if ZERO {
-                    DeltaVersion::genesis()
-                } else {
-                    row_data.get_txn_revised()
-                },
-            )?;
+            // the time at which the row was last revised isn't known (so this is never rewound; see `ModelCheckpoint`)
+            row_writer.write_row_metadata(DataDeltaKind::Insert, row_data.get_txn_revised(), 0)?;
             row_writer.write_row_pk(key)?;
             row_writer.write_row_data(self.0, &row_data)?;
         }
@@ -389,14 +575,14 @@ impl<'a> FullModel<'a> {
 
 impl<'a> JournalAdapterEvent<BatchAdapter<ModelDataAdapter>> for FullModel<'a> {
     fn md(&self) -> u64 {
-        BatchType::Standard.dscr_u64()
+        self.1.dscr_u64()
     }
     fn write_direct(
         self,
         f: &mut TrackedWriter<<BatchAdapter<ModelDataAdapter> as RawJournalAdapter>::Spec>,
         _: Rc<RefCell<BatchStats>>,
     ) -> RuntimeResult<()> {
-        self.write::<false>(f)
+        self.write(f)
     }
 }
 
@@ -415,14 +601,24 @@ pub struct BatchMetadata {
     pk_tag: TagUnique,
     schema_version: u64,
     column_count: u64,
+    /// rows are preceded by their expiry time
+    expiring: bool,
+    /// the version that the model was truncated in, if this batch truncates the model
+    truncated_at: Option<DeltaVersion>,
+    /// the version that the model was compacted at, if this batch is a checkpoint
+    checkpoint: Option<DeltaVersion>,
+    /// every change (and truncation or checkpoint) records when it was committed (not in v0 journals)
+    timed: bool,
+    /// when the truncation or checkpoint was committed (zero for any other batch)
+    committed_at: u64,
 }
 
 #[derive(Debug)]
 enum DecodedBatchEventKind {
     Delete,
-    Insert(Vec<Datacell>),
-    Update(Vec<Datacell>),
-    Upsert(Vec<Datacell>),
+    Insert(DecodedRow),
+    Update(DecodedRow),
+    Upsert(DecodedRow),
 }
 
 /// State handling for any pending queries
@@ -435,16 +631,99 @@ struct DecodedBatchEvent {
     txn_id: DeltaVersion,
     pk: PrimaryIndexKey,
     kind: DecodedBatchEventKind,
+    /// when the change was committed (zero if unknown)
+    committed_at: u64,
 }
 
 impl DecodedBatchEvent {
-    fn new(txn_id: u64, pk: PrimaryIndexKey, kind: DecodedBatchEventKind) -> Self {
+    fn new(
+        txn_id: u64,
+        pk: PrimaryIndexKey,
+        kind: DecodedBatchEventKind,
+        committed_at: u64,
+    ) -> Self {
         Self {
             txn_id: DeltaVersion::__new(txn_id),
             pk,
             kind,
+            committed_at,
         }
     }
+    /// Decode an event (that isn't an early exit) in a batch
+    fn decode(
+        f: &mut TrackedReaderContext<ModelDataBatchAofV1>,
+        batch_info: &BatchMetadata,
+        event_type: EventType,
+    ) -> RuntimeResult<Self> {
+        // get txn id
+        let txn_id = u64::from_le_bytes(f.read_block()?);
+        // get commit time
+        let committed_at = if batch_info.timed {
+            u64::from_le_bytes(f.read_block()?)
+        } else {
+            0
+        };
+        // get pk
+        let pk = restore_impls::decode_primary_key::<ModelDataBatchAofV1>(f, batch_info.pk_tag)?;
+        let kind = match event_type {
+            EventType::Delete => DecodedBatchEventKind::Delete,
+            EventType::Insert | EventType::Update | EventType::Upsert => {
+                // insert or update
+                // prepare row
+                let row = restore_impls::decode_row_data(batch_info, f)?;
+                match event_type {
+                    EventType::Insert => DecodedBatchEventKind::Insert(row),
+                    EventType::Upsert => DecodedBatchEventKind::Upsert(row),
+                    _ => DecodedBatchEventKind::Update(row),
+                }
+            }
+            EventType::EarlyExit => unreachable!(),
+        };
+        Ok(Self::new(txn_id, pk, kind, committed_at))
+    }
+}
+
+/// Decode the metadata at the start of a batch
+fn decode_batch_metadata(
+    f: &mut TrackedReaderContext<ModelDataBatchAofV1>,
+    batch_type: BatchType,
+) -> RuntimeResult<BatchMetadata> {
+    // [pk tag][schema version][column cnt]
+    let pk_tag = TagUnique::try_from_raw(f.read_block().map(|[b]| b)?)
+        .ok_or(StorageError::InternalDecodeStructureIllegalData)?;
+    let schema_version = u64::from_le_bytes(f.read_block()?);
+    let column_count = u64::from_le_bytes(f.read_block()?);
+    let timed = f.get_md().file_specifier_version() != JOURNAL_V0;
+    // [truncation/checkpoint version][commit time]
+    let (version, committed_at) = match batch_type {
+        BatchType::Standard | BatchType::Expiring => (None, 0),
+        BatchType::Truncate | BatchType::Checkpoint => {
+            let version = DeltaVersion::__new(u64::from_le_bytes(f.read_block()?));
+            let committed_at = if timed {
+                u64::from_le_bytes(f.read_block()?)
+            } else {
+                0
+            };
+            (Some(version), committed_at)
+        }
+    };
+    Ok(BatchMetadata {
+        pk_tag,
+        schema_version,
+        column_count,
+        expiring: batch_type == BatchType::Expiring,
+        truncated_at: version.filter(|_| batch_type == BatchType::Truncate),
+        checkpoint: version.filter(|_| batch_type == BatchType::Checkpoint),
+        timed,
+        committed_at,
+    })
+}
+
+/// A decoded row along with its expiry time (zero if it never expires)
+#[derive(Debug)]
+struct DecodedRow {
+    data: Vec<Datacell>,
+    expires_at: u64,
 }
 
 pub struct BatchStats {
@@ -466,17 +745,17 @@ impl BatchStats {
     }
 }
 
-struct ModelConslidation<'a>(&'a ModelData);
+struct ModelConslidation<'a>(FullModel<'a>);
 impl<'a> JournalAdapterEvent<BatchAdapter<ModelDataAdapter>> for ModelConslidation<'a> {
     fn md(&self) -> u64 {
-        BatchType::Standard.dscr_u64()
+        self.0 .1.dscr_u64()
     }
     fn write_direct(
         self,
         f: &mut TrackedWriter<<BatchAdapter<ModelDataAdapter> as RawJournalAdapter>::Spec>,
         _: <BatchAdapter<ModelDataAdapter> as RawJournalAdapter>::CommitContext,
     ) -> RuntimeResult<()> {
-        FullModel(self.0).write::<true>(f)
+        self.0.write(f)
     }
 }
 
@@ -494,13 +773,14 @@ impl BatchAdapterSpec for ModelDataAdapter {
         ctx: Self::FullSyncCtx<'a>,
     ) -> RuntimeResult<()> {
         /*
-            a batch consolidation is our opportunity to fully reset version counters to genesis (+1 because of fetch add).
-            basically after the compaction "all events already happened" and the next event to happen will have ID 1
+            the rows are written with the versions that they were last revised in, and the checkpoint records the version
+            that the model was compacted at so that versions are never reused (a deleted row's version is gone with it)
         */
-        writer.commit_with_ctx(ModelConslidation(ctx), BatchStats::new())?;
-        ctx.delta_state()
-            .__set_delta_version(DeltaVersion::__new(1));
-        Ok(())
+        writer.commit_with_ctx(ModelConslidation(FullModel::new(ctx)), BatchStats::new())?;
+        writer.commit_with_ctx(
+            ModelCheckpoint(ctx, ctx.delta_state().data_current_version()),
+            BatchStats::new(),
+        )
     }
     fn is_early_exit(event_type: &Self::EventType) -> bool {
         EventType::EarlyExit.eq(event_type)
@@ -513,19 +793,7 @@ impl BatchAdapterSpec for ModelDataAdapter {
         f: &mut TrackedReaderContext<Self::Spec>,
         batch_type: Self::BatchType,
     ) -> RuntimeResult<Self::BatchMetadata> {
-        // [pk tag][schema version][column cnt]
-        match batch_type {
-            BatchType::Standard => {}
-        }
-        let pk_tag = TagUnique::try_from_raw(f.read_block().map(|[b]| b)?)
-            .ok_or(StorageError::InternalDecodeStructureIllegalData)?;
-        let schema_version = u64::from_le_bytes(f.read_block()?);
-        let column_count = u64::from_le_bytes(f.read_block()?);
-        Ok(BatchMetadata {
-            pk_tag,
-            schema_version,
-            column_count,
-        })
+        self::decode_batch_metadata(f, batch_type)
     }
     fn update_state_for_new_event(
         _: &Self::GlobalState,
@@ -536,46 +804,8 @@ impl BatchAdapterSpec for ModelDataAdapter {
         h: &mut JournalHeuristics,
     ) -> RuntimeResult<()> {
         h.increment_server_event_count();
-        // get txn id
-        let txn_id = u64::from_le_bytes(f.read_block()?);
-        // get pk
-        let pk = restore_impls::decode_primary_key::<Self::Spec>(f, batch_info.pk_tag)?;
-        match event_type {
-            EventType::Delete => {
-                bs.events.push(DecodedBatchEvent::new(
-                    txn_id,
-                    pk,
-                    DecodedBatchEventKind::Delete,
-                ));
-            }
-            EventType::Insert | EventType::Update | EventType::Upsert => {
-                // insert or update
-                // prepare row
-                let row = restore_impls::decode_row_data(batch_info, f)?;
-                if event_type == EventType::Insert {
-                    bs.events.push(DecodedBatchEvent::new(
-                        txn_id,
-                        pk,
-                        DecodedBatchEventKind::Insert(row),
-                    ));
-                } else {
-                    if event_type == EventType::Upsert {
-                        bs.events.push(DecodedBatchEvent::new(
-                            txn_id,
-                            pk,
-                            DecodedBatchEventKind::Upsert(row),
-                        ));
-                    } else {
-                        bs.events.push(DecodedBatchEvent::new(
-                            txn_id,
-                            pk,
-                            DecodedBatchEventKind::Update(row),
-                        ));
-                    }
-                }
-            }
-            EventType::EarlyExit => unreachable!(),
-        }
+        bs.events
+            .push(DecodedBatchEvent::decode(f, batch_info, event_type)?);
         Ok(())
     }
     fn finish(
@@ -591,11 +821,43 @@ impl BatchAdapterSpec for ModelDataAdapter {
         let mut pending_delete = HashMap::new();
         let p_index = gs.primary_index().__raw_index();
         let m = gs;
+        if let Some(truncated_at) = batch_md.truncated_at {
+            // every record so far is now redundant
+            let truncated = p_index.mt_len();
+            p_index.mt_clear(&g);
+            // +1 since it is a fetch add!
+            m.delta_state()
+                .__raise_delta_version(DeltaVersion::__new(truncated_at.value_u64() + 1));
+            heuristics.report_additional_redundant_records(truncated);
+            #[cfg(test)]
+            {
+                local_mut!(BATCH_INFO, |info| info.push(BatchInfo {
+                    items_count: 0,
+                    redundant_count: truncated
+                }))
+            }
+            return Ok(());
+        }
+        if let Some(checkpoint) = batch_md.checkpoint {
+            // the version that the next change was going to get when the model was compacted
+            m.delta_state().__raise_delta_version(checkpoint);
+            #[cfg(test)]
+            {
+                local_mut!(BATCH_INFO, |info| info.push(BatchInfo {
+                    items_count: 0,
+                    redundant_count: 0
+                }))
+            }
+            return Ok(());
+        }
         let mut real_last_txn_id = DeltaVersion::genesis();
         let mut redundant_records = 0;
         #[cfg(test)]
         let ev_count = batch_state.events.len();
-        for DecodedBatchEvent { txn_id, pk, kind } in batch_state.events {
+        for DecodedBatchEvent {
+            txn_id, pk, kind, ..
+        } in batch_state.events
+        {
             match kind {
                 DecodedBatchEventKind::Insert(new_row)
                 | DecodedBatchEventKind::Update(new_row)
@@ -625,9 +887,10 @@ impl BatchAdapterSpec for ModelDataAdapter {
                             g.flush();
                             continue;
                         } else {
+                            // a row written ahead of its delta (see `TxnModelBatch`) is written again with the same version
                             assert!(
                                 popped_row_txn_revised.value_u64() == 0
-                                    || popped_row_txn_revised < txn_id,
+                                    || popped_row_txn_revised <= txn_id,
                                 "revised ID is {} but our row has version {}",
                                 popped_row.d_data().read().get_txn_revised().value_u64(),
                                 txn_id.value_u64()
@@ -642,7 +905,7 @@ impl BatchAdapterSpec for ModelDataAdapter {
                         .fields()
                         .stseq_ord_key()
                         .filter(|key| key.as_str() != m.p_key())
-                        .zip(new_row)
+                        .zip(new_row.data)
                     {
                         data.st_insert(
                             unsafe {
@@ -652,11 +915,15 @@ impl BatchAdapterSpec for ModelDataAdapter {
                             new_data,
                         );
                     }
-                    let row = Row::new_restored(
+                    if new_row.expires_at != 0 {
+                        m.primary_index().set_expires_rows();
+                    }
+                    let row = Row::new_expiring(
                         pk,
                         data,
                         DeltaVersion::__new(batch_md.schema_version),
                         txn_id,
+                        new_row.expires_at,
                     );
                     // resolve any deltas
                     let _ = row.resolve_schema_deltas_and_freeze(m.delta_state());
@@ -719,7 +986,7 @@ impl BatchAdapterSpec for ModelDataAdapter {
         g.flush();
         // +1 since it is a fetch add!
         m.delta_state()
-            .__set_delta_version(DeltaVersion::__new(real_last_txn_id.value_u64() + 1));
+            .__raise_delta_version(DeltaVersion::__new(real_last_txn_id.value_u64() + 1));
         heuristics.report_additional_redundant_records(redundant_records);
         #[cfg(test)]
         {
@@ -732,9 +999,254 @@ impl BatchAdapterSpec for ModelDataAdapter {
     }
 }
 
+/*
+    change reader
+    ---
+    the journal has every change that made it to disk (along with its version), so it can be read back to catch up
+    a subscriber of the model's change feed. the batches are decoded just like they are on restore, but the changes
+    are only collected (and never applied). a compacted model only has the latest version of every row, so only the
+    changes after the last checkpoint are kept
+*/
+
+/// A change read back from a model journal
+#[derive(Debug)]
+pub enum RecordedChange {
+    /// a row was changed in this version. unless it was deleted, this has the row's data as it was when the change
+    /// was written (the fields other than the primary key, in the model's field order)
+    Row {
+        txn_id: DeltaVersion,
+        kind: DataDeltaKind,
+        pk: PrimaryIndexKey,
+        data: Option<Vec<Datacell>>,
+    },
+    /// the model was truncated in this version
+    Truncate(DeltaVersion),
+}
+
+impl RecordedChange {
+    pub fn txn_id(&self) -> DeltaVersion {
+        match self {
+            Self::Row { txn_id, .. } | Self::Truncate(txn_id) => *txn_id,
+        }
+    }
+    /// Returns the primary key of the changed row (if this isn't a truncation)
+    pub fn pk(&self) -> Option<&PrimaryIndexKey> {
+        match self {
+            Self::Row { pk, .. } => Some(pk),
+            Self::Truncate(_) => None,
+        }
+    }
+}
+
+/// The changes read back from a model journal
+#[derive(Debug)]
+pub struct RecordedChanges {
+    /// the journal has every change from this version on (the changes before it were compacted)
+    pub since: DeltaVersion,
+    /// the changes, in the order in which they were written
+    pub changes: Vec<RecordedChange>,
+}
+
+/// A model journal that was opened to read back its changes (see [`ModelDriver::open_changes`])
+pub struct ModelChangeLog(journal::LiveJournalReader<BatchAdapter<ModelChangeReader>>);
+
+impl ModelChangeLog {
+    /// Read back the changes in the journal (as of when it was opened)
+    pub fn read(self) -> RuntimeResult<RecordedChanges> {
+        let changes = RefCell::new(RecordedChanges {
+            since: DeltaVersion::genesis(),
+            changes: Vec::new(),
+        });
+        self.0.read(&changes, JournalSettings::default())?;
+        Ok(changes.into_inner())
+    }
+}
+
+/// The adapter used to read back the changes in a model journal
+#[derive(Debug)]
+pub struct ModelChangeReader;
+
+impl BatchAdapterSpec for ModelChangeReader {
+    type Spec = ModelDataBatchAofV1;
+    type GlobalState = RefCell<RecordedChanges>;
+    type BatchType = BatchType;
+    type EventType = EventType;
+    type BatchMetadata = BatchMetadata;
+    type BatchState = Vec<RecordedChange>;
+    type CommitContext = ();
+    type FullSyncCtx<'a> = ();
+    fn consolidate_batch<'a>(
+        _: &mut BatchDriver<Self>,
+        _: Self::FullSyncCtx<'a>,
+    ) -> RuntimeResult<()> {
+        unreachable!("the change reader is only used to read journals")
+    }
+    fn is_early_exit(event_type: &Self::EventType) -> bool {
+        EventType::EarlyExit.eq(event_type)
+    }
+    fn initialize_batch_state(_: &Self::GlobalState) -> Self::BatchState {
+        Vec::new()
+    }
+    fn decode_batch_metadata(
+        _: &Self::GlobalState,
+        f: &mut TrackedReaderContext<Self::Spec>,
+        batch_type: Self::BatchType,
+    ) -> RuntimeResult<Self::BatchMetadata> {
+        self::decode_batch_metadata(f, batch_type)
+    }
+    fn update_state_for_new_event(
+        _: &Self::GlobalState,
+        bs: &mut Self::BatchState,
+        f: &mut TrackedReaderContext<Self::Spec>,
+        batch_info: &Self::BatchMetadata,
+        event_type: Self::EventType,
+        _: &mut JournalHeuristics,
+    ) -> RuntimeResult<()> {
+        let DecodedBatchEvent {
+            txn_id, pk, kind, ..
+        } = DecodedBatchEvent::decode(f, batch_info, event_type)?;
+        let (kind, data) = match kind {
+            DecodedBatchEventKind::Delete => (DataDeltaKind::Delete, None),
+            DecodedBatchEventKind::Insert(row) => (DataDeltaKind::Insert, Some(row.data)),
+            DecodedBatchEventKind::Update(row) => (DataDeltaKind::Update, Some(row.data)),
+            DecodedBatchEventKind::Upsert(row) => (DataDeltaKind::Upsert, Some(row.data)),
+        };
+        bs.push(RecordedChange::Row {
+            txn_id,
+            kind,
+            pk,
+            data,
+        });
+        Ok(())
+    }
+    fn finish(
+        batch_state: Self::BatchState,
+        batch_md: Self::BatchMetadata,
+        gs: &Self::GlobalState,
+        _: &mut JournalHeuristics,
+    ) -> RuntimeResult<()> {
+        let mut changes = gs.borrow_mut();
+        match (batch_md.truncated_at, batch_md.checkpoint) {
+            (Some(truncated_at), _) => changes.changes.push(RecordedChange::Truncate(truncated_at)),
+            (None, Some(checkpoint)) => {
+                // the changes so far were either compacted or are the compacted model itself
+                changes.changes.clear();
+                changes.since = checkpoint;
+            }
+            (None, None) => changes.changes.extend(batch_state),
+        }
+        Ok(())
+    }
+}
+
+/*
+    rewind
+    ---
+    every change in a model journal records when it was committed, but the changes are written in batches that can be
+    written well after (and not in the order in which) the changes in them were committed. so a model is rewound by
+    restoring only the changes that were committed by the restore point, and the journal is then rewritten with the
+    restored model (just like it is on compaction)
+*/
+
+/// A model that is being restored with only the changes that were committed by the given time
+pub struct ModelRewind<'a> {
+    model: &'a ModelData,
+    /// the restore point (a commit time; see [`os::get_commit_time`])
+    until: u64,
+    /// the number of changes (and truncations) committed after the restore point
+    discarded: Cell<usize>,
+}
+
+impl<'a> ModelRewind<'a> {
+    pub fn new(model: &'a ModelData, until: u64) -> Self {
+        Self {
+            model,
+            until,
+            discarded: Cell::new(0),
+        }
+    }
+    pub fn discarded(&self) -> usize {
+        self.discarded.get()
+    }
+    fn discard(&self) {
+        self.discarded.set(self.discarded.get() + 1)
+    }
+}
+
+/// The adapter used to restore a model as of a point in time (see [`ModelRewind`])
+pub struct ModelRewinder<'a>(PhantomData<ModelRewind<'a>>);
+
+impl<'a> BatchAdapterSpec for ModelRewinder<'a> {
+    type Spec = ModelDataBatchAofV1;
+    type GlobalState = ModelRewind<'a>;
+    type BatchType = BatchType;
+    type EventType = EventType;
+    type BatchMetadata = BatchMetadata;
+    type BatchState = BatchRestoreState;
+    type CommitContext = ();
+    type FullSyncCtx<'b> = ();
+    fn consolidate_batch<'b>(
+        _: &mut BatchDriver<Self>,
+        _: Self::FullSyncCtx<'b>,
+    ) -> RuntimeResult<()> {
+        unreachable!("the rewinder is only used to read journals")
+    }
+    fn is_early_exit(event_type: &Self::EventType) -> bool {
+        EventType::EarlyExit.eq(event_type)
+    }
+    fn initialize_batch_state(_: &Self::GlobalState) -> Self::BatchState {
+        BatchRestoreState { events: Vec::new() }
+    }
+    fn decode_batch_metadata(
+        gs: &Self::GlobalState,
+        f: &mut TrackedReaderContext<Self::Spec>,
+        batch_type: Self::BatchType,
+    ) -> RuntimeResult<Self::BatchMetadata> {
+        let batch_md = self::decode_batch_metadata(f, batch_type)?;
+        if !batch_md.timed {
+            context::set_dmsg("the model journal doesn't record when changes were committed");
+            return Err(StorageError::RuntimeRestoreValidationFailure.into());
+        }
+        if batch_md.checkpoint.is_some() && batch_md.committed_at > gs.until {
+            context::set_dmsg("the model was compacted after the restore point");
+            return Err(StorageError::RuntimeRestoreValidationFailure.into());
+        }
+        Ok(batch_md)
+    }
+    fn update_state_for_new_event(
+        gs: &Self::GlobalState,
+        bs: &mut Self::BatchState,
+        f: &mut TrackedReaderContext<Self::Spec>,
+        batch_info: &Self::BatchMetadata,
+        event_type: Self::EventType,
+        h: &mut JournalHeuristics,
+    ) -> RuntimeResult<()> {
+        let event = DecodedBatchEvent::decode(f, batch_info, event_type)?;
+        if event.committed_at > gs.until {
+            gs.discard();
+        } else {
+            h.increment_server_event_count();
+            bs.events.push(event);
+        }
+        Ok(())
+    }
+    fn finish(
+        batch_state: Self::BatchState,
+        batch_md: Self::BatchMetadata,
+        gs: &Self::GlobalState,
+        heuristics: &mut JournalHeuristics,
+    ) -> RuntimeResult<()> {
+        if batch_md.truncated_at.is_some() && batch_md.committed_at > gs.until {
+            gs.discard();
+            return Ok(());
+        }
+        ModelDataAdapter::finish(batch_state, batch_md, gs.model, heuristics)
+    }
+}
+
 mod restore_impls {
     use {
-        super::BatchMetadata,
+        super::{BatchMetadata, DecodedRow},
         crate::{
             engine::{
                 core::index::PrimaryIndexKey,
@@ -793,7 +1305,12 @@ mod restore_impls {
     pub fn decode_row_data(
         batch_info: &BatchMetadata,
         f: &mut TrackedReaderContext<ModelDataBatchAofV1>,
-    ) -> Result<Vec<Datacell>, crate::engine::fractal::error::Error> {
+    ) -> Result<DecodedRow, crate::engine::fractal::error::Error> {
+        let expires_at = if batch_info.expiring {
+            u64::from_le_bytes(f.read_block()?)
+        } else {
+            0
+        };
         let mut row = vec![];
         let mut this_col_cnt = batch_info.column_count;
         while this_col_cnt != 0 {
@@ -804,7 +1321,10 @@ mod restore_impls {
             row.push(cell);
             this_col_cnt -= 1;
         }
-        Ok(row)
+        Ok(DecodedRow {
+            data: row,
+            expires_at,
+        })
     }
 
     /*
@@ -27,7 +27,13 @@
 use std::thread;
 
 use crate::engine::{
-    core::{dcl, model::ModelData, space::Space, system_db::VerifyUser, EntityIDRef},
+    core::{
+        dcl,
+        model::ModelData,
+        space::Space,
+        system_db::{Grantee, Privilege, VerifyUser},
+        EntityIDRef,
+    },
     fractal::{test_utils::TestGlobal, GlobalInstanceLike},
     idx::STIndex,
     net::protocol::ClientLocalState,
@@ -145,3 +151,143 @@ fn compaction_test() {
         .unwrap()
         .is_nullable());
 }
+
+#[test]
+fn grants_compaction_test() {
+    FileSystem::set_context(FSContext::Local);
+    let mut fs = FileSystem::instance();
+    fs.mark_file_for_removal("grants_compaction_test_gns");
+    let global = TestGlobal::new_with_driver_id("grants_compaction_test_gns");
+    super::exec(
+        &global,
+        "create space myspace",
+        Space::transactional_exec_create,
+    )
+    .unwrap();
+    super::exec(
+        &global,
+        "create model myspace.mymodel(username: string, password: string)",
+        ModelData::transactional_exec_create,
+    )
+    .unwrap();
+    for query in [
+        "sysctl create user sayan with { password: 'mypassword12345678' }",
+        "sysctl grant select on myspace.mymodel to sayan",
+        "sysctl grant insert on myspace to sayan",
+        "sysctl revoke insert on myspace from sayan",
+        "sysctl create role cleaners",
+        "sysctl grant delete on myspace to role cleaners",
+        "sysctl grant role cleaners to sayan",
+        "sysctl create role temp",
+        "sysctl drop role temp",
+    ] {
+        super::exec_step(&global, query, 1, |g, n| {
+            dcl::exec_ref(g, &ClientLocalState::test_new("root", true), n)
+        })
+        .unwrap();
+    }
+    assert_eq!(gns_log::get_executed_event_count(), 11);
+    {
+        let (gns_data, old_driver) = global.finish_into_driver();
+        let mut new_jrnl = journal::compact_journal::<true, _>(
+            "grants_compaction_test_gns",
+            old_driver,
+            &gns_data,
+        )
+        .unwrap();
+        assert_eq!(
+            gns_log::get_executed_event_count(),
+            // create space, create role, create user, grant role, create model, grant delete, grant select
+            7,
+        );
+        GNSDriver::close_driver(&mut new_jrnl).unwrap();
+    }
+    let tg = thread::spawn(|| {
+        FileSystem::set_context(FSContext::Local);
+        let tg = TestGlobal::new_with_driver_id("grants_compaction_test_gns");
+        assert_eq!(
+            gns_log::get_tracing(),
+            ReadEventTracing {
+                total: 7,
+                repeat: 0,
+            }
+        );
+        tg
+    })
+    .join()
+    .unwrap();
+    let sys_db = tg.state().namespace().sys_db();
+    assert!(sys_db.has_privilege("sayan", "myspace", Some("mymodel"), Privilege::Select));
+    assert!(!sys_db.has_privilege("sayan", "myspace", Some("mymodel"), Privilege::Insert));
+    assert!(!sys_db.has_privilege("sayan", "myspace", None, Privilege::Select));
+    // through the role
+    assert!(sys_db.has_privilege("sayan", "myspace", None, Privilege::Delete));
+    assert!(!sys_db.roles().read().contains_key("temp"));
+}
+
+#[test]
+fn late_grant_on_dropped_model() {
+    FileSystem::set_context(FSContext::Local);
+    let mut fs = FileSystem::instance();
+    fs.mark_file_for_removal("late_grant_on_dropped_model_gns");
+    let global = TestGlobal::new_with_driver_id("late_grant_on_dropped_model_gns");
+    super::exec(
+        &global,
+        "create space myspace",
+        Space::transactional_exec_create,
+    )
+    .unwrap();
+    super::exec(
+        &global,
+        "create model myspace.mymodel(username: string, password: string)",
+        ModelData::transactional_exec_create,
+    )
+    .unwrap();
+    super::exec_step(
+        &global,
+        "sysctl create user sayan with { password: 'mypassword12345678' }",
+        1,
+        |g, n| dcl::exec_ref(g, &ClientLocalState::test_new("root", true), n),
+    )
+    .unwrap();
+    super::exec(
+        &global,
+        "drop model myspace.mymodel",
+        ModelData::transactional_exec_drop,
+    )
+    .unwrap();
+    // a grant that raced with the drop (and was committed after it), as an earlier version could write it
+    global
+        .state()
+        .namespace()
+        .sys_db()
+        .grant(
+            &global,
+            Grantee::User("sayan"),
+            "myspace",
+            Some("mymodel"),
+            Privilege::Select,
+        )
+        .unwrap();
+    // now recreate the model
+    super::exec(
+        &global,
+        "create model myspace.mymodel(username: string, password: string)",
+        ModelData::transactional_exec_create,
+    )
+    .unwrap();
+    drop(global);
+    let tg = thread::spawn(|| {
+        FileSystem::set_context(FSContext::Local);
+        TestGlobal::new_with_driver_id("late_grant_on_dropped_model_gns")
+    })
+    .join()
+    .unwrap();
+    // the grant doesn't apply to the new model
+    assert!(!tg.state().namespace().sys_db().has_privilege(
+        "sayan",
+        "myspace",
+        Some("mymodel"),
+        Privilege::Select
+    ));
+}
@@ -0,0 +1,279 @@
+/*
+ * Created on Sun Oct 18 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+use crate::engine::{
+    config::RestorePoint,
+    core::{EntityIDRef, GNSData},
+    fractal::{test_utils::TestGlobal, GlobalInstanceLike},
+    storage::{
+        common::{
+            interface::fs::{FSContext, File, FileExt, FileSystem, FileWriteExt},
+            paths_v1,
+        },
+        v2::{
+            self,
+            impls::{
+                backup_manifest::{BackupContext, BackupManifest},
+                gns_log::GNSDriver,
+                mdl_journal::ModelDriver,
+            },
+            raw::journal::{self, JournalCheckpoint},
+        },
+    },
+};
+
+const BACKUP_DIR: &str = "online_backup_test_bkp";
+
+#[test]
+fn online_backup() {
+    // file locks are always taken on the local filesystem
+    FileSystem::set_context(FSContext::Local);
+    let mut fs = FileSystem::instance();
+    fs.mark_file_for_removal("online_backup_test");
+    {
+        // the deltas are not written until the backup (or shutdown)
+        let global = TestGlobal::new_with_driver_id("online_backup_test");
+        super::create_model_and_space(
+            &global,
+            "create model online_backup_test.users(username: string, password: string)",
+        )
+        .unwrap();
+        for (k, v) in super::create_test_kv_strings(10) {
+            super::run_insert(
+                &global,
+                &format!("insert into online_backup_test.users('{k}', '{v}')"),
+            )
+            .unwrap();
+        }
+        v2::_online_backup(
+            &global,
+            "online_backup_test",
+            BACKUP_DIR,
+            Some("nightly".into()),
+        )
+        .unwrap();
+        // these are made after the backup
+        for (k, v) in (11..=20).map(|i| super::create_test_kv(i, 10)) {
+            super::run_insert(
+                &global,
+                &format!("insert into online_backup_test.users('{k}', '{v}')"),
+            )
+            .unwrap();
+        }
+    }
+    // the backup should open like any cleanly closed instance
+    let (manifest, _) =
+        BackupManifest::open(format!("{BACKUP_DIR}/{}", v2::BACKUP_MANIFEST_FILE)).unwrap();
+    assert_eq!(manifest.context(), BackupContext::Online);
+    assert_eq!(manifest.description(), Some("nightly"));
+    assert!(manifest.snapshot_at().is_some());
+    // the GNS and the model journal, both closed cleanly
+    assert_eq!(manifest.journals().len(), 2);
+    assert!(manifest
+        .journals()
+        .iter()
+        .all(|journal| journal.copied_from() == 0 && journal.checkpoint().is_some()));
+    let gns = GNSData::empty();
+    let (mut gns_driver, _) = GNSDriver::open_gns_with_name(
+        &format!("{BACKUP_DIR}/online_backup_test"),
+        &gns,
+        Default::default(),
+    )
+    .unwrap();
+    {
+        let space_uuid = gns
+            .idx()
+            .read()
+            .get("online_backup_test")
+            .unwrap()
+            .get_uuid();
+        let models = gns.idx_models().read();
+        let model = models
+            .get(&EntityIDRef::new("online_backup_test", "users"))
+            .unwrap();
+        let (mut model_driver, _) = ModelDriver::open_model_driver(
+            model.data(),
+            &format!(
+                "{BACKUP_DIR}/{}",
+                paths_v1::model_path(
+                    "online_backup_test",
+                    space_uuid,
+                    "users",
+                    model.data().get_uuid()
+                )
+            ),
+            Default::default(),
+        )
+        .unwrap();
+        assert_eq!(model.data().primary_index().count(), 10);
+        ModelDriver::close_driver(&mut model_driver).unwrap();
+    }
+    GNSDriver::close_driver(&mut gns_driver).unwrap();
+    FileSystem::remove_dir_all(BACKUP_DIR).unwrap();
+}
+
+#[test]
+fn incremental_journal_segment() {
+    let model_path = {
+        let global = TestGlobal::new_with_driver_id("incremental_backup_test");
+        super::create_model_and_space(
+            &global,
+            "create model incremental_backup_test.users(username: string, password: string)",
+        )
+        .unwrap();
+        for (k, v) in super::create_test_kv_strings(10) {
+            super::run_insert(
+                &global,
+                &format!("insert into incremental_backup_test.users('{k}', '{v}')"),
+            )
+            .unwrap();
+        }
+        let spaces = global.state().namespace().idx().read();
+        let models = global.state().namespace().idx_models().read();
+        let model = models
+            .get(&EntityIDRef::new("incremental_backup_test", "users"))
+            .unwrap();
+        paths_v1::model_path(
+            "incremental_backup_test",
+            spaces.get("incremental_backup_test").unwrap().get_uuid(),
+            "users",
+            model.data().get_uuid(),
+        )
+    };
+    // the base backup
+    let checkpoint = JournalCheckpoint::of_journal(&model_path).unwrap().unwrap();
+    FileSystem::copy(&model_path, "incremental_backup_test_restored.db").unwrap();
+    {
+        let global = TestGlobal::new_with_driver_id("incremental_backup_test");
+        for (k, v) in (11..=20).map(|i| super::create_test_kv(i, 10)) {
+            super::run_insert(
+                &global,
+                &format!("insert into incremental_backup_test.users('{k}', '{v}')"),
+            )
+            .unwrap();
+        }
+    }
+    // the journal was only appended to, so the segment after the checkpoint brings the base backup up to date
+    assert!(checkpoint.holds_for(&model_path).unwrap());
+    let mut restored = File::open_rw("incremental_backup_test_restored.db").unwrap();
+    restored.f_seek_start(checkpoint.offset()).unwrap();
+    v2::append_segment(&model_path, checkpoint.offset(), &mut restored).unwrap();
+    drop(restored);
+    assert_eq!(
+        FileSystem::read(&model_path).unwrap(),
+        FileSystem::read("incremental_backup_test_restored.db").unwrap()
+    );
+    // but not once it's been rewritten
+    let mut rewritten = File::open_rw(&model_path).unwrap();
+    rewritten.f_truncate(checkpoint.offset() - 1).unwrap();
+    drop(rewritten);
+    assert!(!checkpoint.holds_for(&model_path).unwrap());
+}
+
+#[test]
+fn point_in_time_restore() {
+    const BACKUP_DIR: &str = "pitr_test_bkp";
+    FileSystem::set_context(FSContext::Local);
+    let mut fs = FileSystem::instance();
+    fs.mark_file_for_removal("pitr_test");
+    journal::debug_use_commit_clock();
+    let late_space_dir = {
+        let global = TestGlobal::new_with_driver_id("pitr_test");
+        // txn 0 (create space) and txn 1 (create model)
+        super::create_model_and_space(
+            &global,
+            "create model pitr_test.users(username: string, password: string)",
+        )
+        .unwrap();
+        // these are committed right before (and most likely in the same millisecond as) the restore point
+        for (k, v) in super::create_test_kv_strings(10) {
+            super::run_insert(
+                &global,
+                &format!("insert into pitr_test.users('{k}', '{v}')"),
+            )
+            .unwrap();
+        }
+        // txn 2 (the restore point) and txn 3
+        super::create_model_and_space(
+            &global,
+            "create model pitr_test_late.events(id: string, payload: string)",
+        )
+        .unwrap();
+        for (k, v) in (11..=20).map(|i| super::create_test_kv(i, 10)) {
+            super::run_insert(
+                &global,
+                &format!("insert into pitr_test.users('{k}', '{v}')"),
+            )
+            .unwrap();
+            super::run_insert(
+                &global,
+                &format!("insert into pitr_test_late.events('{k}', '{v}')"),
+            )
+            .unwrap();
+        }
+        v2::_online_backup(&global, "pitr_test", BACKUP_DIR, None).unwrap();
+        paths_v1::space_dir(
+            "pitr_test_late",
+            global
+                .state()
+                .namespace()
+                .idx()
+                .read()
+                .get("pitr_test_late")
+                .unwrap()
+                .get_uuid(),
+        )
+    };
+    journal::debug_set_event_time(0);
+    let gns_path = format!("{BACKUP_DIR}/{}", v2::GNS_PATH);
+    FileSystem::rename(&format!("{BACKUP_DIR}/pitr_test"), &gns_path).unwrap();
+    v2::rewind(Some(BACKUP_DIR), RestorePoint::Txn(2)).unwrap();
+    // the space (and model) created after the restore point are gone, along with their data
+    assert!(!std::path::Path::new(&format!("{BACKUP_DIR}/{late_space_dir}")).exists());
+    let gns = GNSData::empty();
+    let (mut gns_driver, _) =
+        GNSDriver::open_gns_with_name(&gns_path, &gns, Default::default()).unwrap();
+    assert!(!gns.idx().read().contains_key("pitr_test_late"));
+    {
+        let space_uuid = gns.idx().read().get("pitr_test").unwrap().get_uuid();
+        let models = gns.idx_models().read();
+        let model = models.get(&EntityIDRef::new("pitr_test", "users")).unwrap();
+        let (mut model_driver, _) = ModelDriver::open_model_driver(
+            model.data(),
+            &format!(
+                "{BACKUP_DIR}/{}",
+                paths_v1::model_path("pitr_test", space_uuid, "users", model.data().get_uuid())
+            ),
+            Default::default(),
+        )
+        .unwrap();
+        // every change that was committed before the restore point, and none after
+        assert_eq!(model.data().primary_index().count(), 10);
+        ModelDriver::close_driver(&mut model_driver).unwrap();
+    }
+    GNSDriver::close_driver(&mut gns_driver).unwrap();
+    FileSystem::remove_dir_all(BACKUP_DIR).unwrap();
+}
@@ -137,6 +137,10 @@ fn compaction_test() {
                         items_count: 1000,
                         redundant_count: 0
                     }, // we consolidated this one
+                    BatchInfo {
+                        items_count: 0,
+                        redundant_count: 0
+                    }, // the checkpoint after the consolidation
                     BatchInfo {
                         items_count: 1,
                         redundant_count: 0
@@ -164,7 +168,7 @@ fn compaction_test() {
     /*
         verify the pre compaction rows
     */
-    for (key, _) in super::create_test_kv_strings(1000) {
+    for (i, (key, _)) in super::create_test_kv_strings(1000).into_iter().enumerate() {
         let row = mdl
             .data()
             .primary_index()
@@ -181,8 +185,8 @@ fn compaction_test() {
                 .unwrap(),
             "password"
         );
-        // alll pre compaction rows are set to txn id 0
-        assert_eq!(row.get_txn_revised().value_u64(), 0);
+        // pre compaction rows keep the txn id of their update
+        assert_eq!(row.get_txn_revised().value_u64(), 2 * i as u64 + 1);
     }
     /*
         verify the post compaction rows
@@ -206,7 +210,7 @@ fn compaction_test() {
             .unwrap(),
         kv_1001.1
     );
-    assert_eq!(row_1001.get_txn_revised().value_u64(), 1);
+    assert_eq!(row_1001.get_txn_revised().value_u64(), 2000);
     let row_1002 = mdl
         .data()
         .primary_index()
@@ -224,9 +228,25 @@ fn compaction_test() {
             .unwrap(),
         kv_1002.1
     );
-    assert_eq!(row_1002.get_txn_revised().value_u64(), 2);
+    assert_eq!(row_1002.get_txn_revised().value_u64(), 2001);
     assert_eq!(
         mdl.data().delta_state().data_current_version().value_u64(),
-        3
-    ); // this is important! the next row must get (compaction:id0, insert(row1001):id1, insert(row1002):id2) id3
+        2002
+    ); // this is important! versions carry on after the compaction (insert(row1001):id2000, insert(row1002):id2001) so the next row must get id2002
+       /*
+           the changes before the compaction are gone, but every change after it is in the journal
+       */
+    let changes = mdl_journal::ModelDriver::open_changes(&driver_path)
+        .unwrap()
+        .read()
+        .unwrap();
+    assert_eq!(changes.since.value_u64(), 2000);
+    assert_eq!(
+        changes
+            .changes
+            .iter()
+            .map(|change| change.txn_id().value_u64())
+            .collect::<Vec<_>>(),
+        [2000, 2001]
+    );
 }
@@ -36,9 +36,11 @@ use crate::engine::{
     },
 };
 
+mod backup;
 mod compaction_test;
 mod generic;
 mod skew;
+mod truncate;
 
 /*
     utils
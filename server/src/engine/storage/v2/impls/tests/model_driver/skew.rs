@@ -32,9 +32,9 @@ use {
     crate::{
         engine::{
             core::{
-                index::{PrimaryIndexKey, Row},
+                index::{PrimaryIndexKey, PrimaryIndexKind, Row},
                 model::{
-                    delta::{DataDeltaKind, DeltaState, DeltaVersion},
+                    delta::{DataDelta, DataDeltaKind, DeltaState, DeltaVersion},
                     Field, Layer, Model, ModelData,
                 },
             },
@@ -50,7 +50,10 @@ use {
             mem::RawStr,
             storage::{
                 common::interface::fs::{FSContext, FileSystem},
-                v2::{impls::mdl_journal::StdModelBatch, raw::journal::JournalSettings},
+                v2::{
+                    impls::mdl_journal::{StdModelBatch, TxnModelBatch},
+                    raw::journal::JournalSettings,
+                },
                 BatchStats, ModelDriver,
             },
         },
@@ -66,7 +69,13 @@ fn initialize_or_reopen_model_driver(name: &str, mdl_uuid: Uuid) -> Model {
     mdl_fields.st_insert("username".into(), Field::new([Layer::str()].into(), false));
     mdl_fields.st_insert("password".into(), Field::new([Layer::str()].into(), false));
     let mdl = Model::new(
-        ModelData::new_restore(mdl_uuid, "username".into(), FullTag::STR, mdl_fields),
+        ModelData::new_restore(
+            mdl_uuid,
+            "username".into(),
+            FullTag::STR,
+            mdl_fields,
+            PrimaryIndexKind::Hashed,
+        ),
         FractalModelDriver::uninitialized(),
     );
     let mdl_driver = match ModelDriver::create_model_driver(name) {
@@ -337,3 +346,76 @@ fn skewed_upsert() {
         },
     )
 }
+
+#[test]
+fn skewed_durable_write() {
+    FileSystem::set_context(FSContext::Local);
+    let mut fs = FileSystem::instance();
+    fs.mark_file_for_removal("skewed_durable_write");
+    // the async write's delta is either never written (a crash) or written after the durable write
+    for write_async in [false, true] {
+        let g = pin();
+        let mdl_uuid = Uuid::new();
+        let mut model = initialize_or_reopen_model_driver("skewed_durable_write", mdl_uuid);
+        let field_id_ptr = unsafe { model.data_mut().model_mutator().allocate("password") };
+        // a durable insert (t=0)
+        let row = Row::new(
+            PrimaryIndexKey::try_from_dc(Datacell::new_str("sayan".into())).unwrap(),
+            into_dict!(field_id_ptr => Datacell::new_str("pwd1".into())),
+            DeltaVersion::genesis(),
+            DeltaVersion::__new(0),
+        );
+        let durable = [DataDelta::new(
+            DeltaVersion::__new(0),
+            row.clone(),
+            DataDeltaKind::Insert,
+        )];
+        // an async update (t=1) revises the row before the insert is written
+        {
+            let mut row_data = row.d_data().write();
+            *row_data.fields_mut().st_get_mut("password").unwrap() =
+                Datacell::new_str("pwd2".into());
+            row_data.set_txn_revised(DeltaVersion::__new(1));
+        }
+        model.data().delta_state().append_new_data_delta_with(
+            DataDeltaKind::Update,
+            row.clone(),
+            DeltaVersion::__new(1),
+            &g,
+        );
+        {
+            let mut model_driver = model.driver().batch_driver().lock();
+            let model_driver = model_driver.as_mut().unwrap();
+            model_driver
+                .commit_with_ctx(
+                    TxnModelBatch::new(model.data(), &durable),
+                    BatchStats::new(),
+                )
+                .unwrap();
+            if write_async {
+                model_driver
+                    .commit_with_ctx(StdModelBatch::new(model.data(), 1), BatchStats::new())
+                    .unwrap();
+            }
+            ModelDriver::close_driver(model_driver).unwrap();
+        }
+        drop(model);
+        // the insert is never lost, and is restored as of the update
+        let mdl = initialize_or_reopen_model_driver("skewed_durable_write", mdl_uuid);
+        let row = mdl
+            .data()
+            .primary_index()
+            .__raw_index()
+            .mt_get_element(&Lit::new_str("sayan"), &g)
+            .unwrap();
+        let row_data = row.d_data().read();
+        assert_eq!(row_data.get_txn_revised(), DeltaVersion::__new(1));
+        assert_eq!(
+            *row_data.fields().st_get("password").unwrap(),
+            Datacell::new_str("pwd2".into())
+        );
+        drop(row_data);
+        drop(mdl);
+        FileSystem::remove_file("skewed_durable_write").unwrap();
+    }
+}
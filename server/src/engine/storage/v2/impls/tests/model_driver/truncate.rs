@@ -0,0 +1,109 @@
+/*
+ * Created on Sun Oct 18 2026
+ *
+ * This file is a part of Skytable
+ * Skytable (formerly known as TerrabaseDB or Skybase) is a free and open-source
+ * NoSQL database written by Sayan Nandan ("the Author") with the
+ * vision to provide flexibility in data modelling without compromising
+ * on performance, queryability or scalability.
+ *
+ * Copyright (c) 2026, Sayan Nandan <ohsayan@outlook.com>
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ *
+*/
+
+use {
+    crate::engine::{
+        core::{model::ModelData, EntityIDRef},
+        data::lit::Lit,
+        error::QueryResult,
+        fractal::{test_utils::TestGlobal, GlobalInstanceLike},
+        ql::{ast, ddl::TruncateModel, tests::lex_insecure},
+    },
+    crossbeam_epoch::pin,
+};
+
+fn run_truncate(global: &TestGlobal, truncate: &str) -> QueryResult<()> {
+    let tokens = lex_insecure(truncate.as_bytes()).unwrap();
+    let truncate: TruncateModel = ast::parse_ast_node_full(&tokens[2..]).unwrap();
+    ModelData::transactional_exec_truncate(global, truncate)
+}
+
+#[test]
+fn truncate_and_restore() {
+    {
+        let mut global = TestGlobal::new_with_driver_id_instant_update("truncate_and_restore");
+        super::create_model_and_space(
+            &global,
+            "create model truncate_test.users(username: string, password: string)",
+        )
+        .unwrap();
+        // these are synced before the truncation
+        for (k, v) in super::create_test_kv_strings(10) {
+            super::run_insert(
+                &global,
+                &format!("insert into truncate_test.users('{k}', '{v}')"),
+            )
+            .unwrap();
+        }
+        // while these (and the update) are still pending when we truncate
+        global.set_max_data_pressure(usize::MAX);
+        for (k, v) in (11..=20).map(|i| super::create_test_kv(i, 10)) {
+            super::run_insert(
+                &global,
+                &format!("insert into truncate_test.users('{k}', '{v}')"),
+            )
+            .unwrap();
+        }
+        super::run_update(
+            &global,
+            "update truncate_test.users set password = 'new' where username = 'user-0000000001'",
+        )
+        .unwrap();
+        run_truncate(&global, "truncate model truncate_test.users").unwrap();
+        // the schema is intact and the keys can be reused
+        for k in ["user-0000000001", "user-0000000020", "sayan"] {
+            super::run_insert(
+                &global,
+                &format!("insert into truncate_test.users('{k}', 'after')"),
+            )
+            .unwrap();
+        }
+        assert_eq!(
+            run_truncate(&global, "truncate model truncate_test.nope").unwrap_err(),
+            crate::engine::error::QueryError::QExecObjectNotFound
+        );
+    }
+    // the stale deltas must never resurrect the truncated rows
+    let global = TestGlobal::new_with_driver_id("truncate_and_restore");
+    global
+        .state()
+        .namespace()
+        .with_model(EntityIDRef::new("truncate_test", "users"), |model| {
+            let g = pin();
+            assert_eq!(model.primary_index().count(), 3);
+            for k in ["user-0000000001", "user-0000000020", "sayan"] {
+                let row = model
+                    .primary_index()
+                    .select(Lit::new_str(k), &g)
+                    .unwrap()
+                    .d_data()
+                    .read();
+                assert_eq!(row.fields().get("password").unwrap().str(), "after");
+            }
+            Ok(())
+        })
+        .unwrap();
+}
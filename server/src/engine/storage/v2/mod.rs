@@ -28,25 +28,31 @@ use {
     self::{
         impls::{
             gns_log::{self, GNSEventLog},
-            mdl_journal::{BatchStats, FullModel, ModelDataAdapter},
+            mdl_journal::{BatchStats, FullModel, ModelDataAdapter, ModelRewind, ModelRewinder},
+        },
+        raw::journal::{
+            BatchAdapter, EventLogAdapter, JournalSettings, JournalStop, RepairResult, RewindResult,
         },
-        raw::journal::{BatchAdapter, EventLogAdapter, JournalSettings, RepairResult},
     },
     crate::{
         engine::{
-            config::{BackupSettings, BackupType, Configuration, RestoreSettings},
+            config::{BackupSettings, BackupType, Configuration, RestorePoint, RestoreSettings},
             core::{
                 system_db::{SystemDatabase, VerifyUser},
                 EntityIDRef, GNSData, GlobalNS,
             },
-            error::StorageError,
-            fractal::{context, FractalGNSDriver},
+            error::{QueryResult, StorageError},
+            fractal::{context, FractalGNSDriver, GlobalInstanceLike, ModelUniqueIDRef},
             storage::{
-                common::{interface::fs::FileSystem, paths_v1, sdss::sdss_r1::rw::SdssFile},
+                common::{
+                    interface::fs::{File, FileExt, FileRead, FileSystem, FileWrite, FileWriteExt},
+                    paths_v1,
+                    sdss::sdss_r1::rw::SdssFile,
+                },
                 v1,
                 v2::{
                     impls::{gns_log::GNSAdapter, mdl_journal::ModelAdapter},
-                    raw::journal::{self, JournalRepairMode},
+                    raw::journal::{self, JournalCheckpoint, JournalRepairMode},
                 },
                 SELoaded,
             },
@@ -55,16 +61,16 @@ use {
         },
         util::{
             self,
-            os::{self, FileLocks},
+            os::{self, EntryKind, FileLocks},
         },
     },
     impls::{
-        backup_manifest::{BackupContext, BackupManifest},
+        backup_manifest::{BackupContext, BackupJournal, BackupManifest},
         gns_log::GNSDriver,
         mdl_journal::ModelDriver,
     },
     raw::spec::SystemDatabaseV1,
-    std::path::Path,
+    std::{cmp, path::Path},
 };
 
 pub(super) mod impls;
@@ -297,11 +303,19 @@ fn _full_backup(
         context::set_dmsg("creating backup directory");
         FileSystem::create_dir_all(&backup_dir)?;
     }
+    context::set_dmsg("checkpointing journals");
+    let journals: Vec<_> = checkpoint_journals()?
+        .into_iter()
+        .map(|(path, checkpoint)| BackupJournal::new(path, 0, checkpoint))
+        .collect();
     context::set_dmsg("creating backup manifest");
     BackupManifest::create(
         pathbuf!(&backup_dir, BACKUP_MANIFEST_FILE),
         context,
         description,
+        None,
+        &journals,
+        None,
     )?;
     context::set_dmsg("backing up GNS");
     FileSystem::copy(GNS_PATH, pathbuf!(&backup_dir, GNS_PATH))?;
@@ -311,6 +325,99 @@ fn _full_backup(
     Ok(())
 }
 
+/// Take a backup of only what was appended to each journal since the backup with the manifest at `base_manifest`.
+/// Journals that are new or have been rewritten (for example, by a compaction) since then are copied in full
+fn _incremental_backup(
+    backup_dir: &str,
+    base_manifest: &str,
+    description: Option<String>,
+) -> RuntimeResult<()> {
+    context::set_dmsg("opening base backup manifest");
+    let (base, _) = BackupManifest::open(base_manifest)?;
+    if base.journals().is_empty() {
+        context::set_dmsg(
+            "the base backup has no journal checkpoints (it was created by an older version)",
+        );
+        return Err(StorageError::RuntimeBackupValidationFailure.into());
+    }
+    // restore may not run from this directory, so the base is recorded with its full path
+    context::set_dmsg("resolving base backup directory");
+    let base_dir = Path::new(base_manifest)
+        .canonicalize()?
+        .parent()
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_owned();
+    context::set_dmsg("checkpointing journals");
+    let mut journals = vec![];
+    for (path, checkpoint) in checkpoint_journals()? {
+        let copied_from = match base.journal(&path).and_then(BackupJournal::checkpoint) {
+            Some(base_checkpoint) if base_checkpoint.holds_for(&path)? => base_checkpoint.offset(),
+            _ => 0,
+        };
+        journals.push(BackupJournal::new(path, copied_from, checkpoint));
+    }
+    context::set_dmsg("creating backup manifest");
+    BackupManifest::create(
+        pathbuf!(backup_dir, BACKUP_MANIFEST_FILE),
+        BackupContext::Manual,
+        description,
+        Some(base_dir.clone()),
+        &journals,
+        None,
+    )?;
+    for journal in journals.iter() {
+        context::set_dmsg(format!("backing up {}", journal.path()));
+        let backup_path = pathbuf!(backup_dir, journal.path());
+        if let Some(parent) = backup_path.parent() {
+            FileSystem::create_dir_all(parent)?;
+        }
+        let mut backup_file = File::create(&backup_path)?;
+        append_segment(journal.path(), journal.copied_from(), &mut backup_file)?;
+    }
+    info!("All changes since the backup in {base_dir} backed up in {backup_dir}");
+    Ok(())
+}
+
+/// Returns the paths of all the journals in the working directory (the GNS and the model journals) along with their
+/// checkpoints
+fn checkpoint_journals() -> RuntimeResult<Vec<(String, Option<JournalCheckpoint>)>> {
+    let mut journals = vec![GNS_PATH.to_owned()];
+    journals.extend(
+        os::rlistdir(DATA_DIR)?
+            .into_iter()
+            .filter_map(|entry| match entry {
+                EntryKind::File(path) => Some(path),
+                EntryKind::Directory(_) => None,
+            }),
+    );
+    journals
+        .into_iter()
+        .map(|path| {
+            let checkpoint = JournalCheckpoint::of_journal(&path)?;
+            Ok((path, checkpoint))
+        })
+        .collect()
+}
+
+/// Append everything in the file at `src` from `offset` onwards to `dst` (at its current position)
+fn append_segment(src: impl AsRef<Path>, offset: u64, dst: &mut File) -> RuntimeResult<()> {
+    const BLOCK_SIZE: usize = 64 * 1024;
+    let mut src = File::open_with_options(src, true, false)?;
+    let mut remaining = src.f_len()? - offset;
+    src.f_seek_start(offset)?;
+    let mut block = vec![0; BLOCK_SIZE];
+    while remaining != 0 {
+        let block_size = cmp::min(remaining, BLOCK_SIZE as u64) as usize;
+        src.fread_exact(&mut block[..block_size])?;
+        dst.fwrite_all(&block[..block_size])?;
+        remaining -= block_size as u64;
+    }
+    dst.fsync_all()?;
+    Ok(())
+}
+
 fn print_repair_info(result: RepairResult, id: &str) {
     match result {
         RepairResult::NoErrors => info!("repair: no errors detected in {id}"),
@@ -371,9 +478,6 @@ pub fn compact() -> RuntimeResult<()> {
 const BACKUP_MANIFEST_FILE: &str = "backup.manifest";
 
 pub fn backup(settings: BackupSettings) -> RuntimeResult<()> {
-    match settings.kind {
-        BackupType::Direct => {}
-    }
     // first lock directory
     let mut locks = FileLocks::new();
     if settings.allow_dirty {
@@ -392,18 +496,129 @@ pub fn backup(settings: BackupSettings) -> RuntimeResult<()> {
     context::set_dmsg("locking backup directory");
     locks.lock(pathbuf!(&settings.to, crate::SKY_PID_FILE))?;
     // initiate backup
-    _full_backup(
-        &settings.to,
-        false,
-        BackupContext::Manual,
-        settings.description,
-    )?;
+    match settings.kind {
+        BackupType::Direct => _full_backup(
+            &settings.to,
+            false,
+            BackupContext::Manual,
+            settings.description,
+        )?,
+        BackupType::Incremental => _incremental_backup(
+            &settings.to,
+            // the config verifies that incremental backups have a base
+            settings.base.as_deref().unwrap(),
+            settings.description,
+        )?,
+    }
     // release locks
     context::set_dmsg("releasing directory locks");
     locks.release()?;
     Ok(())
 }
 
+/// Take a backup of the running instance into `backup_dir`, without stopping writes. The GNS driver is held
+/// throughout so that no DDL (or DCL) transactions are committed while we're at it. Every journal is snapshotted at the
+/// same point (writers to each model only wait until every model's pending changes have been written) and then copied
+/// as of that point, without holding any model. Hence, the backup is the instance as it was at that point
+pub fn online_backup(
+    global: &impl GlobalInstanceLike,
+    backup_dir: &str,
+    description: Option<String>,
+) -> QueryResult<()> {
+    _online_backup(global, GNS_PATH, backup_dir, description)
+}
+
+fn _online_backup(
+    global: &impl GlobalInstanceLike,
+    gns_path: &str,
+    backup_dir: &str,
+    description: Option<String>,
+) -> QueryResult<()> {
+    // NB: DDL holds the namespace while it waits for the GNS driver, so get the namespace first
+    let spaces = global.state().namespace().idx().read();
+    let models = global.state().namespace().idx_models().read();
+    global
+        .state()
+        .gns_driver()
+        .frozen_context(move |gns_driver| {
+            FileSystem::create_dir_all(backup_dir)?;
+            let mut locks = FileLocks::new();
+            locks.lock(pathbuf!(backup_dir, crate::SKY_PID_FILE))?;
+            FileSystem::create_dir_all(pathbuf!(backup_dir, DATA_DIR))?;
+            for (space_name, space) in spaces.iter() {
+                FileSystem::create_dir_all(pathbuf!(
+                    backup_dir,
+                    paths_v1::space_dir(space_name, space.get_uuid())
+                ))?;
+            }
+            // keep writers out of every model, so that every journal is snapshotted at the same point
+            let feeds: Vec<_> = models
+                .values()
+                .map(|model| model.data().change_feed().freeze())
+                .collect();
+            let snapshot_at = (crate::util::os::get_epoch_time() / 1_000_000) as u64;
+            let gns_snapshot = gns_driver.snapshot()?;
+            let mut model_snapshots = Vec::with_capacity(models.len());
+            for (id, model) in models.iter() {
+                let model_data = model.data();
+                let space_uuid = spaces.get(id.space()).unwrap().get_uuid();
+                FileSystem::create_dir_all(pathbuf!(
+                    backup_dir,
+                    paths_v1::model_dir(id.space(), space_uuid, id.entity(), model_data.get_uuid())
+                ))?;
+                let snapshot = model.driver().flushed_driver_context(
+                    global,
+                    ModelUniqueIDRef::new(id.space(), id.entity(), model_data.get_uuid()),
+                    model_data,
+                    ModelDriver::snapshot,
+                )?;
+                model_snapshots.push((
+                    paths_v1::model_path(
+                        id.space(),
+                        space_uuid,
+                        id.entity(),
+                        model_data.get_uuid(),
+                    ),
+                    snapshot,
+                ));
+            }
+            // everything that's been written after this isn't part of the backup, so the models can be written to while
+            // the journals are copied
+            drop(feeds);
+            drop(models);
+            drop(spaces);
+            let mut journals = vec![];
+            let mut backup_journal = |path: String| {
+                let checkpoint =
+                    JournalCheckpoint::of_journal(pathbuf!(backup_dir, &path).to_str().unwrap())?;
+                journals.push(BackupJournal::new(path, 0, checkpoint));
+                RuntimeResult::Ok(())
+            };
+            gns_snapshot.copy_to(gns_path, pathbuf!(backup_dir, gns_path).to_str().unwrap())?;
+            backup_journal(gns_path.to_owned())?;
+            for (model_data_file_path, snapshot) in model_snapshots {
+                snapshot.copy_to(
+                    &model_data_file_path,
+                    pathbuf!(backup_dir, &model_data_file_path)
+                        .to_str()
+                        .unwrap(),
+                )?;
+                backup_journal(model_data_file_path)?;
+            }
+            BackupManifest::create(
+                pathbuf!(backup_dir, BACKUP_MANIFEST_FILE),
+                BackupContext::Online,
+                description,
+                None,
+                &journals,
+                Some(snapshot_at),
+            )?;
+            locks.release()?;
+            info!("All data backed up in {backup_dir}");
+            Ok(())
+        })
+}
+
 /*
     restore
 */
@@ -482,12 +697,38 @@ pub fn restore(settings: RestoreSettings) -> RuntimeResult<()> {
         backup_info_fmt.push_str(&format!("{description:?}"));
     }
     backup_info_fmt.push_str(&format!(" on {} (UTC)", backup_manifest.date()));
+    if let Some(snapshot_at) = backup_manifest
+        .snapshot_at()
+        .and_then(|time| chrono::DateTime::from_timestamp_millis(time as i64))
+    {
+        backup_info_fmt.push_str(&format!(" as of {}", snapshot_at.to_rfc3339()));
+    }
     info!("{backup_info_fmt}");
+    // find the full backup that this backup is based on (if this is an incremental backup)
+    let mut chain = vec![(settings.from.clone(), backup_manifest)];
+    while let Some(base_dir) = chain.last().unwrap().1.base().map(str::to_owned) {
+        if chain.iter().any(|(backup_dir, _)| *backup_dir == base_dir) {
+            context::set_dmsg(format!("backup {base_dir} is its own base"));
+            return Err(StorageError::RuntimeRestoreValidationFailure.into());
+        }
+        context::set_dmsg(format!("opening manifest of base backup {base_dir}"));
+        let (base_manifest, _) =
+            BackupManifest::open(&format!("{base_dir}/{BACKUP_MANIFEST_FILE}"))?;
+        chain.push((base_dir, base_manifest));
+    }
+    chain.reverse();
+    let full_backup_dir = &chain[0].0;
+    if chain.len() > 1 {
+        info!(
+            "this backup is based on {} earlier backup(s) and the full backup is in {full_backup_dir}",
+            chain.len() - 1
+        );
+    }
     // now restore the files
     // restore gns
     context::set_dmsg("restoring GNS");
     FileSystem::copy(
-        pathbuf!(&settings.from, GNS_PATH).to_str().unwrap(),
+        pathbuf!(full_backup_dir, GNS_PATH).to_str().unwrap(),
         if let Some(to) = settings.to.as_deref() {
             pathbuf!(to, GNS_PATH)
         } else {
@@ -506,9 +747,52 @@ pub fn restore(settings: RestoreSettings) -> RuntimeResult<()> {
     FileSystem::create_dir(&data_dir_path)?;
     context::set_dmsg("restoring data directory");
     FileSystem::copy_directory(
-        pathbuf!(&settings.from, DATA_DIR).to_str().unwrap(),
+        pathbuf!(full_backup_dir, DATA_DIR).to_str().unwrap(),
         data_dir_path.to_str().unwrap(),
     )?;
+    // apply the incremental backups, oldest first
+    for backups in chain.windows(2) {
+        let ((_, base), (backup_dir, backup)) = (&backups[0], &backups[1]);
+        for journal in backup.journals() {
+            context::set_dmsg(format!(
+                "restoring {} from incremental backup {backup_dir}",
+                journal.path()
+            ));
+            let backup_path = pathbuf!(backup_dir, journal.path());
+            let restore_path = if let Some(to) = settings.to.as_deref() {
+                pathbuf!(to, journal.path())
+            } else {
+                pathbuf!(journal.path())
+            };
+            if journal.copied_from() == 0 {
+                // this journal was copied in full
+                if let Some(parent) = restore_path.parent() {
+                    FileSystem::create_dir_all(parent)?;
+                }
+                FileSystem::copy(&backup_path, &restore_path)?;
+                continue;
+            }
+            // the restored journal must be exactly what was backed up in the base backup
+            let base_checkpoint = base
+                .journal(journal.path())
+                .and_then(BackupJournal::checkpoint);
+            if base_checkpoint.map(|checkpoint| checkpoint.offset()) != Some(journal.copied_from())
+                || JournalCheckpoint::of_journal(restore_path.to_str().unwrap())? != base_checkpoint
+            {
+                context::set_dmsg(format!(
+                    "{} does not match the base of incremental backup {backup_dir}",
+                    journal.path()
+                ));
+                return Err(StorageError::RuntimeRestoreValidationFailure.into());
+            }
+            let mut restore_file = File::open_rw(&restore_path)?;
+            restore_file.f_seek_start(journal.copied_from())?;
+            append_segment(&backup_path, 0, &mut restore_file)?;
+        }
+    }
+    if let Some(until) = settings.until {
+        rewind(settings.to.as_deref(), until)?;
+    }
     if settings.flag_delete_on_restore_completion {
         context::set_dmsg("removing backup directory that was recently restored");
         FileSystem::remove_dir_all(&settings.from)?;
@@ -517,3 +801,142 @@ pub fn restore(settings: RestoreSettings) -> RuntimeResult<()> {
     info!("restore completed successfully");
     Ok(())
 }
+
+/// Rewind the restored data (in `root`) to the given point. The GNS journal is truncated at the restore point (its
+/// transactions are committed one at a time, in order). The model journals are written in batches (so a change can be
+/// written well after it was committed) but every change records when it was committed, using the same (strictly
+/// increasing) commit clock as the GNS transactions. So each model is restored with only the changes that were
+/// committed by the restore point (or before the GNS transaction, when rewinding to a transaction) and its journal is
+/// then rewritten. The spaces and models that were created after the restore point are removed
+fn rewind(root: Option<&str>, until: RestorePoint) -> RuntimeResult<()> {
+    let restored_path = |path: &str| {
+        let path = if let Some(root) = root {
+            pathbuf!(root, path)
+        } else {
+            pathbuf!(path)
+        };
+        path.to_str().unwrap().to_owned()
+    };
+    let gns_stop = match until {
+        RestorePoint::Txn(txn_id) => JournalStop::AtTxn(txn_id),
+        RestorePoint::Time(time) => JournalStop::AfterTime(time),
+    };
+    // first find the restore point (and the models as of it) without touching the GNS, so that a failed model rewind
+    // doesn't leave the GNS rewound
+    let gns = GNSData::empty();
+    context::set_dmsg("reading GNS");
+    let gns_rewind = journal::find_journal_stop::<GNSAdapter>(
+        &restored_path(GNS_PATH),
+        &gns,
+        JournalSettings::with_stop(gns_stop),
+    )?;
+    let until = match (until, gns_rewind) {
+        (RestorePoint::Time(time), _) => time,
+        (RestorePoint::Txn(_), RewindResult::Truncated { time, .. }) if time != 0 => time - 1,
+        (RestorePoint::Txn(txn_id), RewindResult::Truncated { .. }) => {
+            context::set_dmsg(format!(
+                "GNS transaction {txn_id} has no commit time (it was written by an earlier version)"
+            ));
+            return Err(StorageError::RuntimeRestoreValidationFailure.into());
+        }
+        (RestorePoint::Txn(txn_id), RewindResult::Unchanged) => {
+            context::set_dmsg(format!("GNS transaction {txn_id} was not found"));
+            return Err(StorageError::RuntimeRestoreValidationFailure.into());
+        }
+    };
+    let models = gns.idx_models().read();
+    let spaces = gns.idx().read();
+    for (space_id, space) in spaces.iter() {
+        for model_id in space.models().iter() {
+            let model = models.get(&EntityIDRef::new(&space_id, &model_id)).unwrap();
+            let model_data_file_path = restored_path(&paths_v1::model_path(
+                &space_id,
+                space.get_uuid(),
+                &model_id,
+                model.data().get_uuid(),
+            ));
+            context::set_dmsg(format!("rewinding {model_data_file_path}"));
+            let model_rewind = ModelRewind::new(model.data(), until);
+            journal::read_journal::<BatchAdapter<ModelRewinder>>(
+                &model_data_file_path,
+                &model_rewind,
+                JournalSettings::default(),
+            )?;
+            journal::compact_journal_direct::<false, ModelAdapter, _>(
+                &model_data_file_path,
+                None,
+                model.data(),
+                true,
+                |_| Ok(()),
+            )?;
+            info!(
+                "rewind: removed {} changes from {model_data_file_path}",
+                model_rewind.discarded()
+            );
+        }
+    }
+    // remove the spaces and models that were created after the restore point (by UUID, since one might have been
+    // dropped and created again with the same name)
+    context::set_dmsg("reading GNS");
+    let latest_gns = GNSData::empty();
+    journal::read_journal::<GNSAdapter>(
+        &restored_path(GNS_PATH),
+        &latest_gns,
+        JournalSettings::default(),
+    )?;
+    let latest_models = latest_gns.idx_models().read();
+    for (space_id, space) in latest_gns.idx().read().iter() {
+        if spaces
+            .get(space_id)
+            .filter(|old_space| old_space.get_uuid() == space.get_uuid())
+            .is_none()
+        {
+            let space_dir = restored_path(&paths_v1::space_dir(&space_id, space.get_uuid()));
+            context::set_dmsg(format!("removing {space_dir}"));
+            FileSystem::remove_dir_all(&space_dir)?;
+            info!("rewind: removed space {space_id} since it was created after the restore point");
+            continue;
+        }
+        for model_id in space.models().iter() {
+            let entity = EntityIDRef::new(&space_id, &model_id);
+            let model_uuid = latest_models.get(&entity).unwrap().data().get_uuid();
+            if models
+                .get(&entity)
+                .filter(|old_model| old_model.data().get_uuid() == model_uuid)
+                .is_none()
+            {
+                let model_dir = restored_path(&paths_v1::model_dir(
+                    &space_id,
+                    space.get_uuid(),
+                    &model_id,
+                    model_uuid,
+                ));
+                context::set_dmsg(format!("removing {model_dir}"));
+                FileSystem::remove_dir_all(&model_dir)?;
+                info!("rewind: removed model {space_id}.{model_id} since it was created after the restore point");
+            }
+        }
+    }
+    context::set_dmsg("rewinding GNS");
+    let gns_rewind = journal::rewind_journal::<GNSAdapter>(
+        &restored_path(GNS_PATH),
+        &GNSData::empty(),
+        JournalSettings::with_stop(gns_stop),
+    )?;
+    print_rewind_info(&gns_rewind, "GNS");
+    Ok(())
+}
+
+fn print_rewind_info(result: &RewindResult, id: &str) {
+    match result {
+        RewindResult::Unchanged => info!("rewind: no events were removed from {id}"),
+        RewindResult::Truncated { txn_id, time } => {
+            let committed_at = i64::try_from(*time)
+                .ok()
+                .map(chrono::DateTime::from_timestamp_nanos)
+                .filter(|_| *time != 0)
+                .map_or_else(|| "an unknown time".to_owned(), |time| time.to_rfc3339());
+            info!("rewind: removed all events in {id} from event {txn_id} (committed at {committed_at})")
+        }
+    }
+}
@@ -47,10 +47,13 @@ mod raw;
 #[cfg(test)]
 mod tests;
 pub use raw::{
-    compact_journal, compact_journal_direct, create_journal, open_journal, read_journal,
-    repair_journal, JournalHeuristics, JournalRepairMode, JournalSettings, JournalStats,
-    RawJournalAdapter, RawJournalAdapterEvent as JournalAdapterEvent, RepairResult,
+    compact_journal, compact_journal_direct, create_journal, find_journal_stop, open_journal,
+    read_journal, repair_journal, rewind_journal, JournalCheckpoint, JournalHeuristics,
+    JournalRepairMode, JournalSettings, JournalStats, JournalStop, LiveJournalReader,
+    RawJournalAdapter, RawJournalAdapterEvent as JournalAdapterEvent, RepairResult, RewindResult,
 };
+#[cfg(test)]
+pub use raw::{debug_set_event_time, debug_use_commit_clock};
 
 /*
     implementation of a blanket event log
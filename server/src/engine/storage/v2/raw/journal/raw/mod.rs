@@ -77,6 +77,86 @@ where
     RawJournalReader::<J>::scroll(log, gs, settings).map(|x| x.1)
 }
 
+/// A journal that is still open for writing (and hence doesn't end with a close event), opened for reading. Only the
+/// events that were in the journal when it was opened are read, so the caller must only make sure that nothing is
+/// written to the journal while it is being opened
+pub struct LiveJournalReader<J: RawJournalAdapter>(TrackedReader<J::Spec>);
+
+impl<J: RawJournalAdapter> LiveJournalReader<J>
+where
+    J::Spec: FileSpecV1<DecodeArgs = ()>,
+{
+    pub fn open(log_path: &str) -> RuntimeResult<Self> {
+        let log = SdssFile::<J::Spec>::open(log_path, true, false)?;
+        Ok(Self(TrackedReader::with_cursor(
+            log,
+            <J::Spec as FileSpecV1>::SIZE as u64,
+        )?))
+    }
+    pub fn read(
+        self,
+        gs: &J::GlobalState,
+        settings: JournalSettings,
+    ) -> RuntimeResult<JournalStats> {
+        RawJournalReader::<J>::scroll_live(self.0, gs, settings)
+    }
+}
+
+/// The close event at the end of a journal that was closed cleanly. A journal is only ever appended to (until it's
+/// compacted) so if a journal still has the same close event at the same offset, it hasn't changed up to there
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct JournalCheckpoint {
+    /// the offset right after the close event (the length of the journal)
+    offset: u64,
+    /// the id of the close event
+    txn_id: u64,
+    /// the checksum of the journal up to the close event
+    checksum: u64,
+}
+
+impl JournalCheckpoint {
+    pub const fn new(offset: u64, txn_id: u64, checksum: u64) -> Self {
+        Self {
+            offset,
+            txn_id,
+            checksum,
+        }
+    }
+    /// Returns the checkpoint of the journal at `log_path`, if it was closed cleanly
+    pub fn of_journal(log_path: &str) -> RuntimeResult<Option<Self>> {
+        let mut f = File::open_with_options(log_path, true, false)?;
+        let len = f.f_len()?;
+        Self::read_at(&mut f, len)
+    }
+    /// Returns true if the journal at `log_path` still has this checkpoint (and hence has only been appended to since)
+    pub fn holds_for(&self, log_path: &str) -> RuntimeResult<bool> {
+        let mut f = File::open_with_options(log_path, true, false)?;
+        if f.f_len()? < self.offset {
+            return Ok(false);
+        }
+        Ok(Self::read_at(&mut f, self.offset)? == Some(*self))
+    }
+    fn read_at(f: &mut File, len: u64) -> RuntimeResult<Option<Self>> {
+        if len < DriverEvent::FULL_EVENT_SIZE as u64 {
+            return Ok(None);
+        }
+        f.f_seek_start(len - DriverEvent::FULL_EVENT_SIZE as u64)?;
+        let block = f.fread_exact_block::<{ DriverEvent::FULL_EVENT_SIZE }>()?;
+        Ok(DriverEvent::decode(block)
+            .filter(|ev| ev.event == DriverEventKind::Closed)
+            .map(|ev| Self::new(len, ev.txn_id as u64, ev.last_checksum)))
+    }
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+    pub fn txn_id(&self) -> u64 {
+        self.txn_id
+    }
+    pub fn checksum(&self) -> u64 {
+        self.checksum
+    }
+}
+
 /// Open an existing journal
 pub fn open_journal<J: RawJournalAdapter>(
     log_path: &str,
@@ -119,6 +199,46 @@ where
     RawJournalReader::<J>::repair(log, gs, settings, repair_mode).map(|(lost, ..)| lost)
 }
 
+#[derive(Debug, PartialEq)]
+/// The result of a journal rewind operation
+pub enum RewindResult {
+    /// The journal ends before the stop point
+    Unchanged,
+    /// The journal was (or would be) truncated at (and without) the event with this ID, that was committed at this time
+    Truncated { txn_id: u64, time: u64 },
+}
+
+/**
+    Replays the given journal up to the stop point in the settings and truncates it there (closing it), **in-place**
+
+    **WARNING**: Backup before calling this
+*/
+pub fn rewind_journal<J: RawJournalAdapter>(
+    log_path: &str,
+    gs: &J::GlobalState,
+    settings: JournalSettings,
+) -> RuntimeResult<RewindResult>
+where
+    J::Spec: FileSpecV1<DecodeArgs = ()>,
+{
+    let log = SdssFile::<J::Spec>::open_rw(log_path)?;
+    RawJournalReader::<J>::rewind(log, gs, settings, true)
+}
+
+/// Replays the given journal up to the stop point in the settings and returns where it would be rewound to, without
+/// modifying it
+pub fn find_journal_stop<J: RawJournalAdapter>(
+    log_path: &str,
+    gs: &J::GlobalState,
+    settings: JournalSettings,
+) -> RuntimeResult<RewindResult>
+where
+    J::Spec: FileSpecV1<DecodeArgs = ()>,
+{
+    let log = SdssFile::<J::Spec>::open(log_path, true, false)?;
+    RawJournalReader::<J>::rewind(log, gs, settings, false)
+}
+
 pub fn compact_journal<'a, const LOG: bool, J: RawJournalAdapter>(
     original_journal_path: &str,
     original_journal: RawJournalWriter<J>,
@@ -279,6 +399,17 @@ pub fn debug_get_first_meta_triplet() -> Option<(u64, u64, u64)> {
     local_mut!(FIRST_TRIPLET, |tr| core::mem::take(tr))
 }
 
+#[cfg(test)]
+pub fn debug_set_event_time(time: u64) {
+    local_mut!(EVENT_TIME, |time_| *time_ = Some(time))
+}
+
+#[cfg(test)]
+/// Use the commit clock for new events (on this thread), like release builds do
+pub fn debug_use_commit_clock() {
+    local_mut!(EVENT_TIME, |time| *time = None)
+}
+
 #[derive(Debug, PartialEq)]
 #[cfg(test)]
 pub enum JournalTraceEvent {
@@ -347,6 +478,7 @@ local! {
     static OFFSETS: std::collections::BTreeMap<u64, u64> = Default::default();
     static TRACE_OFFSETS: bool = false;
     static FIRST_TRIPLET: Option<(u64, u64, u64)> = None;
+    static EVENT_TIME: Option<u64> = Some(0);
 }
 
 macro_rules! jtrace_event_offset {
@@ -461,6 +593,40 @@ pub trait RawJournalAdapter: Sized {
     ) -> RuntimeResult<()>;
 }
 
+/*
+    event IDs
+    ---
+    Every event is identified by a 128-bit ID. The lower 64 bits are the sequential ID of the event in the journal and
+    the upper 64 bits are the time at which the event was committed (from the commit clock that model changes use too,
+    so the times order GNS transactions and model changes; see `os::get_commit_time`). The time is zero
+    in v0 journals, which didn't record it (the upper bits were always zero, so both are read the same way)
+*/
+
+const fn event_id(txn_id: u64, time: u64) -> u128 {
+    ((time as u128) << u64::BITS) | txn_id as u128
+}
+
+const fn event_id_txn_id(event_id: u128) -> u64 {
+    event_id as u64
+}
+
+const fn event_id_time(event_id: u128) -> u64 {
+    (event_id >> u64::BITS) as u64
+}
+
+/// Returns the commit time for a new event. Tests use a fixed time (see `debug_set_event_time`) so that the
+/// journals they create are reproducible
+fn event_time() -> u64 {
+    #[cfg(test)]
+    {
+        local_ref!(EVENT_TIME, |time| *time).unwrap_or_else(crate::util::os::get_commit_time)
+    }
+    #[cfg(not(test))]
+    {
+        crate::util::os::get_commit_time()
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum CommitPreference {
     #[allow(unused)]
@@ -875,7 +1041,7 @@ impl<J: RawJournalAdapter> RawJournalWriter<J> {
             })
         }
         let id = self.txn_id;
-        let ret = f(self, id as u128);
+        let ret = f(self, event_id(id, event_time()));
         if ret.is_ok() {
             jtrace_event_offset!(id, self.log_file.cursor());
             self.known_txn_id = id;
@@ -920,6 +1086,56 @@ impl<J: RawJournalAdapter> RawJournalWriter<J> {
         Self::_commit_driver_event(me, DriverEventKind::Reopened)?;
         Ok(())
     }
+    /// Take a snapshot of this journal, that it can be copied as of (with [`JournalSnapshot::copy_to`]) even after
+    /// more has been written to it. This journal is not affected and stays open
+    ///
+    /// NB: the journal is appended to in place, so it has to be copied (a hard link would also see later writes)
+    pub fn snapshot(&mut self) -> RuntimeResult<JournalSnapshot<J>>
+    where
+        <<J as RawJournalAdapter>::Spec as FileSpecV1>::Metadata: Clone,
+    {
+        // make sure that everything we've written is in the file
+        self.log_file.flush_sync()?;
+        self.log_file.verify_cursor()?;
+        Ok(JournalSnapshot(JournalWriterStateBackup::new(
+            self.log_file.get_md().clone(),
+            self.log_file.cursor(),
+            self.log_file.checksum_state(),
+            self.txn_id,
+            self.known_txn_id,
+            self.known_txn_offset,
+        )))
+    }
+}
+
+/// The state of a journal at some point, which the journal can be copied as of
+pub struct JournalSnapshot<J: RawJournalAdapter>(JournalWriterStateBackup<J>);
+
+impl<J: RawJournalAdapter> JournalSnapshot<J> {
+    /// Copy the journal at `journal_path` to `backup_path` as it was when this snapshot was taken, and close the copy so
+    /// that it can be opened like any other journal
+    pub fn copy_to(self, journal_path: &str, backup_path: &str) -> RuntimeResult<()>
+    where
+        <J as RawJournalAdapter>::Spec: FileSpecV1<DecodeArgs = ()>,
+    {
+        let Self(state) = self;
+        FileSystem::copy(journal_path, backup_path)?;
+        // drop anything that was written after the snapshot
+        File::open_rw(backup_path)?.f_truncate(state.log_file_cursor)?;
+        // the copy is in the same state as the journal was, so pick up from there and close it
+        let mut backup = RawJournalWriter::load_using_backup(
+            J::initialize(&JournalInitializer::new(
+                state.log_file_cursor,
+                state.log_file_checksum.clone(),
+                state.adapter_txn_id,
+                state.adapter_known_txn_offset,
+            )),
+            backup_path,
+            state,
+        )?;
+        RawJournalWriter::close_driver(&mut backup)?;
+        Ok(backup.log_file.flush_sync()?)
+    }
 }
 
 pub struct RawJournalReader<J: RawJournalAdapter> {
@@ -929,7 +1145,7 @@ pub struct RawJournalReader<J: RawJournalAdapter> {
     last_txn_offset: u64,
     last_txn_checksum: u64,
     stats: JournalStats,
-    _settings: JournalSettings,
+    settings: JournalSettings,
     state: JournalState,
 }
 
@@ -939,6 +1155,8 @@ enum JournalState {
     AwaitingServerEvent,
     AwaitingClose,
     AwaitingReopen,
+    /// reached the stop point at the event with this ID
+    Stopped(u128),
 }
 
 impl Default for JournalState {
@@ -948,7 +1166,9 @@ impl Default for JournalState {
 }
 
 #[derive(Debug)]
-pub struct JournalSettings {}
+pub struct JournalSettings {
+    stop: Option<JournalStop>,
+}
 
 impl Default for JournalSettings {
     fn default() -> Self {
@@ -958,7 +1178,30 @@ impl Default for JournalSettings {
 
 impl JournalSettings {
     pub fn new() -> Self {
-        Self {}
+        Self { stop: None }
+    }
+    /// Stop reading the journal at the given point (only used when rewinding a journal)
+    pub fn with_stop(stop: JournalStop) -> Self {
+        Self { stop: Some(stop) }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+/// The point at which a journal is rewound to
+pub enum JournalStop {
+    /// stop at the event with this ID (so that it isn't replayed)
+    AtTxn(u64),
+    /// stop at the first event committed after this commit time (see `os::get_commit_time`). this is only exact for
+    /// journals whose events are committed in the order that they are written (like the GNS journal)
+    AfterTime(u64),
+}
+
+impl JournalStop {
+    fn is_reached(&self, event_id: u128) -> bool {
+        match *self {
+            Self::AtTxn(txn_id) => event_id_txn_id(event_id) >= txn_id,
+            Self::AfterTime(time) => event_id_time(event_id) > time,
+        }
     }
 }
 
@@ -968,6 +1211,8 @@ pub struct JournalStats {
     driver_events: usize,
     heuristics: JournalHeuristics,
     file_size: usize,
+    /// the journal was written with an older revision of its spec
+    outdated: bool,
 }
 
 #[derive(Debug)]
@@ -999,17 +1244,23 @@ pub enum Recommendation {
     NoActionNeeded,
     CompactDrvHighRatio,
     CompactRedHighRatio,
+    /// the journal is compacted so that it's rewritten with the current revision of its spec
+    CompactOutdated,
 }
 
 impl Recommendation {
     pub const fn needs_compaction(&self) -> bool {
-        matches!(self, Self::CompactDrvHighRatio | Self::CompactRedHighRatio)
+        matches!(
+            self,
+            Self::CompactDrvHighRatio | Self::CompactRedHighRatio | Self::CompactOutdated
+        )
     }
     pub const fn reason_str(&self) -> &'static str {
         match self {
             Self::NoActionNeeded => "no action needed",
             Self::CompactDrvHighRatio => "drv_high_ratio",
             Self::CompactRedHighRatio => "srv_high_redundancy",
+            Self::CompactOutdated => "outdated_format",
         }
     }
 }
@@ -1032,6 +1283,9 @@ impl JournalStats {
             (self.heuristics.redundant_records as f64 / self.heuristics.server_events as f64)
                 * 100.00
         };
+        if self.outdated {
+            return Recommendation::CompactOutdated;
+        }
         if self.file_size >= minimum_file_size_compaction_trigger {
             if driver_event_percentage >= server_event_percentage {
                 return Recommendation::CompactDrvHighRatio;
@@ -1051,11 +1305,15 @@ impl JournalStats {
             },
             file_size: 0,
             header: <<J as RawJournalAdapter>::Spec as FileSpecV1>::SIZE,
+            outdated: false,
         }
     }
     fn set_file_size(&mut self, size: usize) {
         self.file_size = size;
     }
+    fn set_outdated(&mut self, outdated: bool) {
+        self.outdated = outdated;
+    }
 }
 
 impl<J: RawJournalAdapter> RawJournalReader<J> {
@@ -1064,6 +1322,7 @@ impl<J: RawJournalAdapter> RawJournalReader<J> {
         gs: &J::GlobalState,
         settings: JournalSettings,
     ) -> RuntimeResult<(JournalInitializer, JournalStats, SdssFile<J::Spec>)> {
+        let outdated = <J::Spec as FileSpecV1>::is_outdated(file.get_md());
         let reader = TrackedReader::with_cursor(
             file,
             <<J as RawJournalAdapter>::Spec as FileSpecV1>::SIZE as u64,
@@ -1071,9 +1330,33 @@ impl<J: RawJournalAdapter> RawJournalReader<J> {
         jtrace_reader!(Initialized);
         let mut me = Self::new(reader, 0, 0, 0, 0, settings);
         me.stats.set_file_size(me.tr.cached_size() as usize);
+        me.stats.set_outdated(outdated);
         me._scroll(gs)
             .map(|jinit| (jinit, me.stats, me.tr.into_inner()))
     }
+    fn scroll_live(
+        reader: TrackedReader<<J as RawJournalAdapter>::Spec>,
+        gs: &J::GlobalState,
+        settings: JournalSettings,
+    ) -> RuntimeResult<JournalStats> {
+        jtrace_reader!(Initialized);
+        let mut me = Self::new(reader, 0, 0, 0, 0, settings);
+        me.stats.set_file_size(me.tr.cached_size() as usize);
+        loop {
+            // a live journal ends right after its last event
+            if me.tr.is_eof() {
+                jtrace_reader!(Completed);
+                return Ok(me.stats);
+            }
+            jtrace_reader!(LookingForEvent);
+            if me._apply_next_event_and_stop(gs)? {
+                // the journal was closed after all (and hasn't been reopened)
+                jtrace_reader!(Completed);
+                return Ok(me.stats);
+            }
+            me.state = JournalState::AwaitingEvent;
+        }
+    }
     fn _scroll(&mut self, gs: &J::GlobalState) -> RuntimeResult<JournalInitializer> {
         loop {
             jtrace_reader!(LookingForEvent);
@@ -1109,7 +1392,7 @@ impl<J: RawJournalAdapter> RawJournalReader<J> {
             last_txn_offset,
             last_txn_checksum,
             stats: JournalStats::new::<J>(),
-            _settings: settings,
+            settings,
             state: JournalState::AwaitingEvent,
         }
     }
@@ -1177,6 +1460,7 @@ impl<J: RawJournalAdapter> RawJournalReader<J> {
                 | StorageError::RawJournalRuntimeDirty
                 | StorageError::RuntimeEngineLoadError
                 | StorageError::RuntimeRestoreValidationFailure
+                | StorageError::RuntimeBackupValidationFailure
                 | StorageError::FileDecodeHeaderVersionMismatch         // should be caught earlier
                 | StorageError::FileDecodeHeaderCorrupted               // should be caught earlier
                 | StorageError::V1JournalDecodeLogEntryCorrupted        // v1 errors can't be raised here
@@ -1211,6 +1495,36 @@ impl<J: RawJournalAdapter> RawJournalReader<J> {
             ErrorKind::Other(_) => unreachable!(),
             ErrorKind::Config(_) => unreachable!(),
         }
+        let (initializer, base_log) = self.revert_to_last_txn()?;
+        Ok((repair_result, initializer, base_log))
+    }
+    fn rewind(
+        file: SdssFile<<J as RawJournalAdapter>::Spec>,
+        gs: &J::GlobalState,
+        settings: JournalSettings,
+        truncate: bool,
+    ) -> RuntimeResult<RewindResult> {
+        let reader = TrackedReader::with_cursor(
+            file,
+            <<J as RawJournalAdapter>::Spec as FileSpecV1>::SIZE as u64,
+        )?;
+        jtrace_reader!(Initialized);
+        let mut me = Self::new(reader, 0, 0, 0, 0, settings);
+        me._scroll(gs)?;
+        let JournalState::Stopped(event_id) = me.state else {
+            return Ok(RewindResult::Unchanged);
+        };
+        if truncate {
+            // everything from the stop point is discarded, just like a trailing corruption would be
+            me.revert_to_last_txn()?;
+        }
+        Ok(RewindResult::Truncated {
+            txn_id: event_id_txn_id(event_id),
+            time: event_id_time(event_id),
+        })
+    }
+    /// Truncate the journal right after the last event that we read and close it (if it wasn't already closed there)
+    fn revert_to_last_txn(self) -> RuntimeResult<(JournalInitializer, SdssFile<J::Spec>)> {
         /*
             revert log. record previous signatures.
         */
@@ -1229,7 +1543,8 @@ impl<J: RawJournalAdapter> RawJournalReader<J> {
         match self.state {
             JournalState::AwaitingEvent
             | JournalState::AwaitingServerEvent
-            | JournalState::AwaitingClose => {
+            | JournalState::AwaitingClose
+            | JournalState::Stopped(_) => {
                 /*
                     no matter what the last event was (and definitely not a close since if we are expecting a close the log was not already closed),
                     the log is in a dirty state that can only be resolved by closing it
@@ -1260,7 +1575,6 @@ impl<J: RawJournalAdapter> RawJournalReader<J> {
         let jinit_event_id = known_event_id + 2; // since we already used +1
         let jinit_checksum = last_logged_checksum;
         Ok((
-            repair_result,
             JournalInitializer::new(
                 jinit_cursor,
                 jinit_checksum,
@@ -1276,13 +1590,19 @@ impl<J: RawJournalAdapter> RawJournalReader<J> {
     fn _apply_next_event_and_stop(&mut self, gs: &J::GlobalState) -> RuntimeResult<bool> {
         let txn_id = u128::from_le_bytes(self.tr.read_block()?);
         let meta = u64::from_le_bytes(self.tr.read_block()?);
-        if txn_id != self.txn_id as u128 {
+        if event_id_txn_id(txn_id) != self.txn_id {
             jtrace_reader!(ErrTxnIdMismatch {
                 expected: self.txn_id,
                 current: txn_id as u64
             });
             return Err(StorageError::RawJournalDecodeEventCorruptedMetadata.into());
         }
+        if let Some(stop) = self.settings.stop {
+            if stop.is_reached(txn_id) {
+                self.state = JournalState::Stopped(txn_id);
+                return Ok(true);
+            }
+        }
         jtrace_reader!(AttemptingEvent(txn_id as u64));
         // check for a server event
         // is this a server event?
@@ -1387,7 +1707,7 @@ impl<J: RawJournalAdapter> RawJournalReader<J> {
             self.last_txn_checksum == reopen_event.last_checksum,
             self.last_txn_id == reopen_event.last_txn_id,
             self.last_txn_offset == reopen_event.last_offset,
-            self.txn_id as u128 == reopen_event.txn_id,
+            self.txn_id == event_id_txn_id(reopen_event.txn_id),
         };
         if valid_meta {
             // valid meta, update all
@@ -27,12 +27,13 @@
 use {
     super::{
         super::{
-            create_journal, debug_get_trace, open_journal, DriverEventKind,
-            JournalReaderTraceEvent, JournalSettings, JournalWriterTraceEvent, RawJournalWriter,
+            create_journal, debug_get_trace, debug_set_event_time, open_journal, rewind_journal,
+            DriverEventKind, JournalReaderTraceEvent, JournalSettings, JournalStop,
+            JournalWriterTraceEvent, RawJournalWriter, RewindResult,
         },
         SimpleDB, SimpleDBJournal,
     },
-    crate::engine::fractal::error::ErrorContext,
+    crate::engine::{fractal::error::ErrorContext, storage::common::interface::fs::FileSystem},
 };
 
 #[test]
@@ -330,3 +331,81 @@ fn multi_boot() {
         RawJournalWriter::close_driver(&mut j).unwrap();
     }
 }
+
+#[test]
+fn rewind() {
+    {
+        debug_set_event_time(1000);
+        let mut j = create_journal::<SimpleDBJournal>("rewind").unwrap();
+        let mut db = SimpleDB::new();
+        db.push(&mut j, "key_a").unwrap();
+        db.push(&mut j, "key_b").unwrap();
+        RawJournalWriter::close_driver(&mut j).unwrap();
+    }
+    {
+        debug_set_event_time(2000);
+        let mut db = SimpleDB::new();
+        let (mut j, _) =
+            open_journal::<SimpleDBJournal>("rewind", &db, JournalSettings::default()).unwrap();
+        db.push(&mut j, "key_c").unwrap();
+        debug_set_event_time(3000);
+        db.push(&mut j, "key_d").unwrap();
+        RawJournalWriter::close_driver(&mut j).unwrap();
+    }
+    debug_set_event_time(0);
+    let _ = debug_get_trace();
+    // rewinding to a time drops everything that was committed after it
+    FileSystem::copy("rewind", "rewind_time").unwrap();
+    assert_eq!(
+        rewind_journal::<SimpleDBJournal>(
+            "rewind_time",
+            &SimpleDB::new(),
+            JournalSettings::with_stop(JournalStop::AfterTime(2000))
+        )
+        .unwrap(),
+        RewindResult::Truncated {
+            txn_id: 5,
+            time: 3000
+        }
+    );
+    {
+        let db = SimpleDB::new();
+        let (mut j, _) =
+            open_journal::<SimpleDBJournal>("rewind_time", &db, JournalSettings::default())
+                .unwrap();
+        assert_eq!(db.data().as_ref(), ["key_a", "key_b", "key_c"]);
+        RawJournalWriter::close_driver(&mut j).unwrap();
+    }
+    // rewinding to a transaction drops it and everything after it
+    FileSystem::copy("rewind", "rewind_txn").unwrap();
+    assert_eq!(
+        rewind_journal::<SimpleDBJournal>(
+            "rewind_txn",
+            &SimpleDB::new(),
+            JournalSettings::with_stop(JournalStop::AtTxn(1))
+        )
+        .unwrap(),
+        RewindResult::Truncated {
+            txn_id: 1,
+            time: 1000
+        }
+    );
+    {
+        let db = SimpleDB::new();
+        let (mut j, _) =
+            open_journal::<SimpleDBJournal>("rewind_txn", &db, JournalSettings::default()).unwrap();
+        assert_eq!(db.data().as_ref(), ["key_a"]);
+        RawJournalWriter::close_driver(&mut j).unwrap();
+    }
+    // nothing is dropped if the journal ends before the stop point
+    assert_eq!(
+        rewind_journal::<SimpleDBJournal>(
+            "rewind",
+            &SimpleDB::new(),
+            JournalSettings::with_stop(JournalStop::AfterTime(3000))
+        )
+        .unwrap(),
+        RewindResult::Unchanged
+    );
+    let _ = debug_get_trace();
+}
@@ -24,9 +24,12 @@
  *
 */
 
-use crate::engine::storage::common::{
-    sdss::{self, sdss_r1::HeaderV1},
-    versions::{self, DriverVersion, FileSpecifierVersion, ServerVersion},
+use crate::engine::{
+    error::{RuntimeResult, StorageError},
+    storage::common::{
+        sdss::{self, sdss_r1::HeaderV1},
+        versions::{self, DriverVersion, FileSpecifierVersion, ServerVersion},
+    },
 };
 
 #[allow(unused)]
@@ -78,12 +81,25 @@ impl sdss::sdss_r1::HeaderV1Spec for HeaderImplV2 {
     const CURRENT_DRIVER_VERSION: DriverVersion = versions::v2::V2_DRIVER_VERSION;
 }
 
+/// The first version of the journals, where events (and the changes in model batches) don't record when they were
+/// committed. These are still read, and are compacted into the current version when they're loaded
+pub const JOURNAL_V0: FileSpecifierVersion = FileSpecifierVersion::__new(0);
+
 pub struct SystemDatabaseV1;
 impl sdss::sdss_r1::SimpleFileSpecV1 for SystemDatabaseV1 {
     type HeaderSpec = HeaderImplV2;
     const FILE_CLASS: FileClass = FileClass::EventLog;
     const FILE_SPECIFIER: FileSpecifier = FileSpecifier::GlobalNS;
-    const FILE_SPECFIER_VERSION: FileSpecifierVersion = FileSpecifierVersion::__new(0);
+    const FILE_SPECFIER_VERSION: FileSpecifierVersion = FileSpecifierVersion::__new(1);
+    fn check_if_file_specifier_revision_is_compatible(
+        v: FileSpecifierVersion,
+    ) -> RuntimeResult<()> {
+        if v == Self::FILE_SPECFIER_VERSION || v == JOURNAL_V0 {
+            Ok(())
+        } else {
+            Err(StorageError::FileDecodeHeaderVersionMismatch.into())
+        }
+    }
 }
 
 pub struct ModelDataBatchAofV1;
@@ -91,5 +107,14 @@ impl sdss::sdss_r1::SimpleFileSpecV1 for ModelDataBatchAofV1 {
     type HeaderSpec = HeaderImplV2;
     const FILE_CLASS: FileClass = FileClass::Batch;
     const FILE_SPECIFIER: FileSpecifier = FileSpecifier::ModelData;
-    const FILE_SPECFIER_VERSION: FileSpecifierVersion = FileSpecifierVersion::__new(0);
+    const FILE_SPECFIER_VERSION: FileSpecifierVersion = FileSpecifierVersion::__new(1);
+    fn check_if_file_specifier_revision_is_compatible(
+        v: FileSpecifierVersion,
+    ) -> RuntimeResult<()> {
+        if v == Self::FILE_SPECFIER_VERSION || v == JOURNAL_V0 {
+            Ok(())
+        } else {
+            Err(StorageError::FileDecodeHeaderVersionMismatch.into())
+        }
+    }
 }
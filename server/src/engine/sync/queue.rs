@@ -113,6 +113,14 @@ impl<T> Queue<T> {
             }
         }
     }
+    /// Returns true if the queue has no elements (at this point in time)
+    pub fn is_empty(&self, g: &Guard) -> bool {
+        let head = unsafe {
+            // UNSAFE(@ohsayan): the head always points to a (sentinel) node
+            self.head.load(Ordering::Acquire, g).deref()
+        };
+        head.next.load(Ordering::Acquire, g).is_null()
+    }
     pub fn blocking_try_dequeue(&self, g: &Guard) -> Option<T> {
         loop {
             // get current head
@@ -72,3 +72,21 @@ fn ensure_sysctl_drop_user() {
         Error::ServerError(INVALID_SYNTAX_ERR)
     );
 }
+
+#[dbtest]
+fn ensure_sysctl_grant_revoke() {
+    let mut db = db!();
+    for stmt in [
+        "sysctl grant select on myspace to myuser blah",
+        "sysctl grant everything on myspace to myuser",
+        "sysctl revoke select on myspace to myuser",
+        "sysctl grant select on myspace to role myrole blah",
+        "sysctl grant role myrole to myuser blah",
+        "sysctl create role myrole blah",
+    ] {
+        assert_err_eq!(
+            db.query_parse::<()>(&query!(stmt)),
+            Error::ServerError(INVALID_SYNTAX_ERR)
+        );
+    }
+}
@@ -48,6 +48,14 @@ pub enum GNSTransactionCode {
     CreateUser = 8,
     AlterUser = 9,
     DropUser = 10,
+    CreateIndex = 11,
+    DropIndex = 12,
+    GrantPrivilege = 13,
+    RevokePrivilege = 14,
+    CreateRole = 15,
+    DropRole = 16,
+    GrantRole = 17,
+    RevokeRole = 18,
 }
 
 pub trait GNSTransaction {
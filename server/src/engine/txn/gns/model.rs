@@ -25,7 +25,10 @@
 */
 
 use crate::engine::{
-    core::model::{Field, ModelData},
+    core::{
+        index::SecondaryIndex,
+        model::{Field, ModelData},
+    },
     idx::{IndexST, IndexSTSeqCns},
     ql::lex::Ident,
     txn::{ModelIDRef, SpaceIDRef},
@@ -36,7 +39,9 @@ impl_gns_event!(
     AlterModelAddTxn<'_> = AlterModelAdd,
     AlterModelRemoveTxn<'_> = AlterModelRemove,
     AlterModelUpdateTxn<'_> = AlterModelUpdate,
-    DropModelTxn<'_> = DropModel
+    DropModelTxn<'_> = DropModel,
+    CreateIndexTxn<'_> = CreateIndex,
+    DropIndexTxn<'_> = DropIndex
 );
 
 #[derive(Debug, Clone, Copy)]
@@ -153,3 +158,44 @@ impl<'a> DropModelTxn<'a> {
         self.model_id
     }
 }
+
+#[derive(Debug, Clone, Copy)]
+/// Transaction commit payload for a `create index ...` query
+pub struct CreateIndexTxn<'a> {
+    model_id: ModelIDRef<'a>,
+    index: &'a SecondaryIndex,
+}
+
+impl<'a> CreateIndexTxn<'a> {
+    pub const fn new(model_id: ModelIDRef<'a>, index: &'a SecondaryIndex) -> Self {
+        Self { model_id, index }
+    }
+    pub fn model_id(&self) -> ModelIDRef<'_> {
+        self.model_id
+    }
+    pub fn index(&self) -> &SecondaryIndex {
+        self.index
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+/// Transaction commit payload for a `drop index ...` query
+pub struct DropIndexTxn<'a> {
+    model_id: ModelIDRef<'a>,
+    index_name: &'a str,
+}
+
+impl<'a> DropIndexTxn<'a> {
+    pub const fn new(model_id: ModelIDRef<'a>, index_name: &'a str) -> Self {
+        Self {
+            model_id,
+            index_name,
+        }
+    }
+    pub fn model_id(&self) -> ModelIDRef<'_> {
+        self.model_id
+    }
+    pub fn index_name(&self) -> &str {
+        self.index_name
+    }
+}
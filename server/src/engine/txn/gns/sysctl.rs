@@ -24,6 +24,8 @@
  *
 */
 
+use crate::engine::core::system_db::{Grantee, Privilege};
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct CreateUserTxn<'a> {
     username: &'a str,
@@ -80,4 +82,150 @@ impl<'a> DropUserTxn<'a> {
     }
 }
 
-impl_gns_event!(CreateUserTxn<'_> = CreateUser, AlterUserTxn<'_> = AlterUser, DropUserTxn<'_> = DropUser);
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct GrantPrivilegeTxn<'a> {
+    grantee: Grantee<'a>,
+    space: &'a str,
+    model: Option<&'a str>,
+    privilege: Privilege,
+}
+
+impl<'a> GrantPrivilegeTxn<'a> {
+    pub fn new(
+        grantee: Grantee<'a>,
+        space: &'a str,
+        model: Option<&'a str>,
+        privilege: Privilege,
+    ) -> Self {
+        Self {
+            grantee,
+            space,
+            model,
+            privilege,
+        }
+    }
+    pub fn grantee(&self) -> Grantee<'_> {
+        self.grantee
+    }
+    pub fn space(&self) -> &str {
+        self.space
+    }
+    pub fn model(&self) -> Option<&str> {
+        self.model
+    }
+    pub fn privilege(&self) -> Privilege {
+        self.privilege
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct RevokePrivilegeTxn<'a> {
+    grantee: Grantee<'a>,
+    space: &'a str,
+    model: Option<&'a str>,
+    privilege: Privilege,
+}
+
+impl<'a> RevokePrivilegeTxn<'a> {
+    pub fn new(
+        grantee: Grantee<'a>,
+        space: &'a str,
+        model: Option<&'a str>,
+        privilege: Privilege,
+    ) -> Self {
+        Self {
+            grantee,
+            space,
+            model,
+            privilege,
+        }
+    }
+    pub fn grantee(&self) -> Grantee<'_> {
+        self.grantee
+    }
+    pub fn space(&self) -> &str {
+        self.space
+    }
+    pub fn model(&self) -> Option<&str> {
+        self.model
+    }
+    pub fn privilege(&self) -> Privilege {
+        self.privilege
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct CreateRoleTxn<'a> {
+    role: &'a str,
+}
+
+impl<'a> CreateRoleTxn<'a> {
+    pub fn new(role: &'a str) -> Self {
+        Self { role }
+    }
+    pub fn role(&self) -> &str {
+        self.role
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DropRoleTxn<'a> {
+    role: &'a str,
+}
+
+impl<'a> DropRoleTxn<'a> {
+    pub fn new(role: &'a str) -> Self {
+        Self { role }
+    }
+    pub fn role(&self) -> &str {
+        self.role
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct GrantRoleTxn<'a> {
+    username: &'a str,
+    role: &'a str,
+}
+
+impl<'a> GrantRoleTxn<'a> {
+    pub fn new(username: &'a str, role: &'a str) -> Self {
+        Self { username, role }
+    }
+    pub fn username(&self) -> &str {
+        self.username
+    }
+    pub fn role(&self) -> &str {
+        self.role
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct RevokeRoleTxn<'a> {
+    username: &'a str,
+    role: &'a str,
+}
+
+impl<'a> RevokeRoleTxn<'a> {
+    pub fn new(username: &'a str, role: &'a str) -> Self {
+        Self { username, role }
+    }
+    pub fn username(&self) -> &str {
+        self.username
+    }
+    pub fn role(&self) -> &str {
+        self.role
+    }
+}
+
+impl_gns_event!(
+    CreateUserTxn<'_> = CreateUser,
+    AlterUserTxn<'_> = AlterUser,
+    DropUserTxn<'_> = DropUser,
+    GrantPrivilegeTxn<'_> = GrantPrivilege,
+    RevokePrivilegeTxn<'_> = RevokePrivilege,
+    CreateRoleTxn<'_> = CreateRole,
+    DropRoleTxn<'_> = DropRole,
+    GrantRoleTxn<'_> = GrantRole,
+    RevokeRoleTxn<'_> = RevokeRole
+);
@@ -34,9 +34,11 @@ mod free_memory;
 use {
     crate::IoResult,
     std::{
+        cmp,
         ffi::OsStr,
         fmt, fs,
         path::Path,
+        sync::atomic::{AtomicU64, Ordering},
         time::{SystemTime, UNIX_EPOCH},
     },
 };
@@ -323,6 +325,19 @@ pub fn get_epoch_time() -> u128 {
         .as_nanos()
 }
 
+/// Returns the time at which a change (or a journal event) is committed, in nanoseconds since the epoch. Commit times
+/// are strictly increasing (for every journal, in this process), so they order commits even if the clock doesn't
+pub fn get_commit_time() -> u64 {
+    static LAST_COMMIT_TIME: AtomicU64 = AtomicU64::new(0);
+    let now = get_epoch_time() as u64;
+    let last = LAST_COMMIT_TIME
+        .fetch_update(Ordering::AcqRel, Ordering::Acquire, |last| {
+            Some(cmp::max(now, last + 1))
+        })
+        .unwrap();
+    cmp::max(now, last + 1)
+}
+
 /// Returns the hostname
 pub fn get_hostname() -> hostname_impl::Hostname {
     hostname_impl::Hostname::get()